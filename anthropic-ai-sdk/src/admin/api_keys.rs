@@ -0,0 +1,200 @@
+//! API Key Management
+//!
+//! This module contains the implementations for the API key endpoints of the Admin API.
+
+use async_trait::async_trait;
+use futures_util::Stream;
+
+use crate::client::AnthropicClient;
+use crate::types::admin::AdminError;
+use crate::types::admin::api_keys::{
+    ApiKeyClient, ApiKeyInfo, ListApiKeysParams, ListApiKeysResponse, UpdateApiKeyParams,
+};
+use crate::types::pagination::Paginator;
+
+#[async_trait]
+impl ApiKeyClient for AnthropicClient {
+    /// List API keys
+    ///
+    /// Retrieves a page of the API keys of the organization.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional pagination and filter parameters
+    ///
+    /// # Returns
+    ///
+    /// Returns a page of API key metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::api_keys::{ApiKeyClient, ApiKeyStatus, ListApiKeysParams};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let params = ListApiKeysParams::new()
+    ///     .workspace_id("workspace_id")
+    ///     .status(ApiKeyStatus::Active);
+    /// let page = client.list_api_keys(Some(&params)).await?;
+    /// for key in page.data {
+    ///     println!("{} ({:?})", key.name, key.partial_key_hint);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn list_api_keys<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+    ) -> Result<ListApiKeysResponse, AdminError> {
+        self.send_admin_request::<_, _, ()>(
+            reqwest::Method::GET,
+            "/organizations/api_keys",
+            params,
+            None,
+        )
+        .await
+    }
+
+    /// List API keys one by one
+    ///
+    /// Starts at the page selected by `params` and follows the pagination
+    /// cursors until there are no more results. Paging goes backwards when
+    /// `before_id` is set and forwards otherwise.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of API key metadata; it ends after the last key or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::api_keys::ApiKeyClient;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let keys: Vec<_> = client.list_api_keys_stream(None).try_collect().await?;
+    /// println!("{} API keys", keys.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_api_keys_stream<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+    ) -> impl Stream<Item = Result<ApiKeyInfo, AdminError>> + Send + 'a {
+        Paginator::new(
+            params.cloned().unwrap_or_default(),
+            move |params| async move { self.list_api_keys(Some(&params)).await },
+        )
+        .items()
+    }
+
+    /// Get an API key
+    ///
+    /// Retrieves the metadata of an API key by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key_id` - ID of the API key
+    ///
+    /// # Returns
+    ///
+    /// Returns the API key metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The API key does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::api_keys::ApiKeyClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let key = client.get_api_key_info("api_key_id").await?;
+    /// println!("{}: {:?}", key.name, key.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_api_key_info<'a>(&'a self, api_key_id: &'a str) -> Result<ApiKeyInfo, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::GET,
+            &format!("/organizations/api_keys/{}", api_key_id),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Update an API key
+    ///
+    /// Renames an API key or changes its status, e.g. to disable a leaked key.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key_id` - ID of the API key
+    /// * `params` - The fields to change
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated API key metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The API key does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::api_keys::{ApiKeyClient, ApiKeyStatus, UpdateApiKeyParams};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let params = UpdateApiKeyParams::new().status(ApiKeyStatus::Inactive);
+    /// let key = client.update_api_key("api_key_id", &params).await?;
+    /// println!("{} is now {:?}", key.name, key.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn update_api_key<'a>(
+        &'a self,
+        api_key_id: &'a str,
+        params: &'a UpdateApiKeyParams,
+    ) -> Result<ApiKeyInfo, AdminError> {
+        self.send_admin_request::<_, (), _>(
+            reqwest::Method::POST,
+            &format!("/organizations/api_keys/{}", api_key_id),
+            None,
+            Some(params),
+        )
+        .await
+    }
+}
@@ -0,0 +1,153 @@
+//! Cost Report
+//!
+//! This module contains the implementations for the cost report endpoint of the Admin API.
+
+use async_trait::async_trait;
+use futures_util::{Stream, TryStreamExt};
+use time::OffsetDateTime;
+
+use crate::client::AnthropicClient;
+use crate::types::admin::AdminError;
+use crate::types::admin::cost::{
+    CostBucket, CostGroupBy, CostReport, CostReportClient, CostReportParams, WorkspaceCost,
+    sum_by_workspace,
+};
+use crate::types::admin::usage::BucketWidth;
+
+#[async_trait]
+impl CostReportClient for AnthropicClient {
+    /// Get the cost report
+    ///
+    /// Retrieves a page of daily costs of the organization, optionally grouped
+    /// by workspace and cost description.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Time range, grouping and pagination of the report
+    ///
+    /// # Returns
+    ///
+    /// Returns a page of cost buckets
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::cost::{CostReportClient, CostReportParams};
+    /// use time::OffsetDateTime;
+    /// use time::format_description::well_known::Rfc3339;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let params = CostReportParams::new(OffsetDateTime::parse("2025-01-01T00:00:00Z", &Rfc3339)?);
+    /// let report = client.get_cost_report(&params).await?;
+    /// for bucket in report.data {
+    ///     for result in bucket.results {
+    ///         println!("{}: {} {}", bucket.starting_at, result.amount, result.currency.as_str());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_cost_report<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+    ) -> Result<CostReport, AdminError> {
+        self.send_admin_request::<_, _, ()>(
+            reqwest::Method::GET,
+            "/organizations/cost_report",
+            Some(&params.to_query()),
+            None,
+        )
+        .await
+    }
+
+    /// Get the cost report bucket by bucket
+    ///
+    /// Starts at the page selected by `params` and follows `next_page` until
+    /// there are no more results.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of cost buckets; it ends after the last bucket or the first error.
+    fn cost_report_stream<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+    ) -> impl Stream<Item = Result<CostBucket, AdminError>> + Send + 'a {
+        let pages =
+            futures_util::stream::try_unfold(Some(params.clone()), move |params| async move {
+                let Some(mut params) = params else {
+                    return Ok::<_, AdminError>(None);
+                };
+                let report = self.get_cost_report(&params).await?;
+                let next = if report.has_more {
+                    report.next_page.clone().map(|page| {
+                        params.page = Some(page);
+                        params
+                    })
+                } else {
+                    None
+                };
+                Ok(Some((report, next)))
+            });
+        pages
+            .map_ok(|report| futures_util::stream::iter(report.data.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Get the total cost of each workspace
+    ///
+    /// Fetches the daily costs between `starting_at` and `ending_at`, grouped by
+    /// workspace, and sums them into one exact total per workspace and currency,
+    /// e.g. for chargeback exports.
+    ///
+    /// # Returns
+    ///
+    /// Returns the totals sorted by workspace, with the default workspace first
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::cost::CostReportClient;
+    /// use time::OffsetDateTime;
+    /// use time::format_description::well_known::Rfc3339;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let totals = client
+    ///     .get_workspace_costs(
+    ///         OffsetDateTime::parse("2025-01-01T00:00:00Z", &Rfc3339)?,
+    ///         OffsetDateTime::parse("2025-02-01T00:00:00Z", &Rfc3339)?,
+    ///     )
+    ///     .await?;
+    /// for total in totals {
+    ///     let workspace = total.workspace_id.as_deref().unwrap_or("default");
+    ///     println!("{workspace},{},{}", total.currency.as_str(), total.amount.div_pow10(2));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_workspace_costs(
+        &self,
+        starting_at: OffsetDateTime,
+        ending_at: OffsetDateTime,
+    ) -> Result<Vec<WorkspaceCost>, AdminError> {
+        let params = CostReportParams::new(starting_at)
+            .ending_at(ending_at)
+            .bucket_width(BucketWidth::Day)
+            .group_by(CostGroupBy::WorkspaceId);
+        let buckets: Vec<CostBucket> = self.cost_report_stream(&params).try_collect().await?;
+        Ok(sum_by_workspace(&buckets))
+    }
+}
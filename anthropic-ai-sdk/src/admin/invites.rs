@@ -0,0 +1,373 @@
+//! Organization Invites
+//!
+//! This module contains the implementations for the invite endpoints of the Admin API.
+
+use async_trait::async_trait;
+use futures_util::{Stream, TryStreamExt};
+
+use crate::client::AnthropicClient;
+use crate::types::admin::AdminError;
+use crate::types::admin::invites::{
+    CreateInviteParams, Invite, InviteClient, InviteDeleted, InviteStatus, ListInvitesParams,
+    ListInvitesResponse,
+};
+use crate::types::admin::users::OrganizationRole;
+use crate::types::pagination::Paginator;
+
+#[async_trait]
+impl InviteClient for AnthropicClient {
+    /// List invites
+    ///
+    /// Retrieves a page of the invites of the organization.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional pagination parameters
+    ///
+    /// # Returns
+    ///
+    /// Returns a page of invites
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::invites::InviteClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let page = client.list_invites(None).await?;
+    /// for invite in page.data {
+    ///     println!("{}: {:?}", invite.email, invite.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn list_invites<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+    ) -> Result<ListInvitesResponse, AdminError> {
+        self.send_admin_request::<_, _, ()>(
+            reqwest::Method::GET,
+            "/organizations/invites",
+            params,
+            None,
+        )
+        .await
+    }
+
+    /// List invites one by one
+    ///
+    /// Starts at the page selected by `params` and follows the pagination
+    /// cursors until there are no more results. Paging goes backwards when
+    /// `before_id` is set and forwards otherwise.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of invites; it ends after the last invite or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::invites::InviteClient;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let invites: Vec<_> = client.list_invites_stream(None).try_collect().await?;
+    /// println!("{} invites", invites.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_invites_stream<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+    ) -> impl Stream<Item = Result<Invite, AdminError>> + Send + 'a {
+        Paginator::new(
+            params.cloned().unwrap_or_default(),
+            move |params| async move { self.list_invites(Some(&params)).await },
+        )
+        .items()
+    }
+
+    /// Get an invite
+    ///
+    /// # Arguments
+    ///
+    /// * `invite_id` - ID of the invite
+    ///
+    /// # Returns
+    ///
+    /// Returns the invite
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The invite does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::invites::InviteClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let invite = client.get_invite("invite_id").await?;
+    /// println!("Expires at {}", invite.expires_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_invite<'a>(&'a self, invite_id: &'a str) -> Result<Invite, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::GET,
+            &format!("/organizations/invites/{}", invite_id),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Create an invite
+    ///
+    /// Invites a user to the organization by email.
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - Email of the user to invite
+    /// * `role` - Role the user gets in the organization
+    ///
+    /// # Returns
+    ///
+    /// Returns the created invite
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The user is already invited or a member (`AdminError::Conflict`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::invites::InviteClient;
+    /// use anthropic_ai_sdk::types::admin::users::OrganizationRole;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let invite = client
+    ///     .create_invite("user@example.com", OrganizationRole::Developer)
+    ///     .await?;
+    /// println!("Invited: {}", invite.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn create_invite<'a>(
+        &'a self,
+        email: &'a str,
+        role: OrganizationRole,
+    ) -> Result<Invite, AdminError> {
+        self.send_admin_request::<_, (), _>(
+            reqwest::Method::POST,
+            "/organizations/invites",
+            None,
+            Some(&CreateInviteParams { email, role }),
+        )
+        .await
+    }
+
+    /// Create an invite unless one is already pending
+    ///
+    /// Like [`create_invite`](InviteClient::create_invite), but when the API
+    /// reports a conflict, the invites for `email` are looked up: a pending
+    /// invite is returned as is, and an expired one is deleted and replaced
+    /// by a new invite.
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - Email of the user to invite
+    /// * `role` - Role the user gets if a new invite is created
+    ///
+    /// # Returns
+    ///
+    /// Returns the new or pending invite
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The conflict is not caused by an invite, e.g. the user is already a member (`AdminError::Conflict`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::invites::InviteClient;
+    /// use anthropic_ai_sdk::types::admin::users::OrganizationRole;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let interaction = |method: &str, path: &str, status, body: String| Interaction {
+    ///     request: RecordedRequest {
+    ///         method: method.to_string(),
+    ///         path: path.to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response: RecordedResponse::json(status, body),
+    /// };
+    /// let invite = |id: &str, email: &str, status: &str, expires_at: &str| {
+    ///     serde_json::json!({"type": "invite", "id": id, "email": email, "role": "user",
+    ///         "invited_at": "2025-01-01T00:00:00Z", "expires_at": expires_at, "status": status})
+    /// };
+    /// let page = |invites: Vec<serde_json::Value>| {
+    ///     serde_json::json!({"data": invites, "has_more": false, "first_id": null, "last_id": null})
+    ///         .to_string()
+    /// };
+    /// let conflict = || {
+    ///     r#"{"type": "error", "error": {"type": "invalid_request_error",
+    ///         "message": "User already invited or a member"}}"#
+    ///         .to_string()
+    /// };
+    /// let pending = invite("invite_01", "a@example.com", "pending", "2099-01-01T00:00:00Z");
+    /// let expired = invite("invite_02", "b@example.com", "expired", "2025-01-22T00:00:00Z");
+    /// let renewed = invite("invite_03", "b@example.com", "pending", "2099-01-01T00:00:00Z");
+    /// let invites = "/organizations/invites";
+    /// let path = std::env::temp_dir().join(format!("ensure-invite-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         // a@example.com is already invited
+    ///         interaction("POST", invites, 409, conflict()),
+    ///         interaction("GET", invites, 200, page(vec![pending])),
+    ///         // b@example.com has an expired invite, which is replaced
+    ///         interaction("POST", invites, 409, conflict()),
+    ///         interaction("GET", invites, 200, page(vec![expired])),
+    ///         interaction("DELETE", "/organizations/invites/invite_02", 200,
+    ///             r#"{"id": "invite_02", "type": "invite_deleted"}"#.to_string()),
+    ///         interaction("POST", invites, 200, renewed.to_string()),
+    ///         // c@example.com is already a member
+    ///         interaction("POST", invites, 409, conflict()),
+    ///         interaction("GET", invites, 200, page(Vec::new())),
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path)
+    ///     .mode(VcrMode::Replay)
+    ///     .matcher(RequestMatcher::new().body(false))
+    ///     .start()
+    ///     .await?;
+    /// let client = AnthropicClient::builder("sk-ant-admin-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<AdminError>()?;
+    ///
+    /// let invite = client.ensure_invite("a@example.com", OrganizationRole::User).await?;
+    /// assert_eq!(invite.id, "invite_01");
+    /// let invite = client.ensure_invite("b@example.com", OrganizationRole::User).await?;
+    /// assert_eq!(invite.id, "invite_03");
+    /// match client.ensure_invite("c@example.com", OrganizationRole::User).await {
+    ///     Err(AdminError::Conflict(message)) => assert_eq!(message, "User already invited or a member"),
+    ///     other => panic!("expected a conflict, got {:?}", other),
+    /// }
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn ensure_invite<'a>(
+        &'a self,
+        email: &'a str,
+        role: OrganizationRole,
+    ) -> Result<Invite, AdminError> {
+        let message = match self.create_invite(email, role.clone()).await {
+            Err(AdminError::Conflict(message)) => message,
+            result => return result,
+        };
+        let invites: Vec<Invite> = self
+            .list_invites_stream(None)
+            .try_filter(|invite| std::future::ready(invite.email.eq_ignore_ascii_case(email)))
+            .try_collect()
+            .await?;
+        if let Some(pending) = invites
+            .iter()
+            .find(|invite| invite.status == InviteStatus::Pending && !invite.is_expired())
+        {
+            return Ok(pending.clone());
+        }
+        if let Some(expired) = invites.iter().find(|invite| invite.is_expired()) {
+            self.delete_invite(&expired.id).await?;
+            return self.create_invite(email, role).await;
+        }
+        Err(AdminError::Conflict(message))
+    }
+
+    /// Delete an invite
+    ///
+    /// # Arguments
+    ///
+    /// * `invite_id` - ID of the invite
+    ///
+    /// # Returns
+    ///
+    /// Returns the deletion confirmation
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The invite does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::invites::InviteClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let deleted = client.delete_invite("invite_id").await?;
+    /// println!("Deleted: {}", deleted.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn delete_invite<'a>(&'a self, invite_id: &'a str) -> Result<InviteDeleted, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::DELETE,
+            &format!("/organizations/invites/{}", invite_id),
+            None,
+            None,
+        )
+        .await
+    }
+}
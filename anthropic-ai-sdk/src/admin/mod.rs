@@ -0,0 +1,11 @@
+//! Admin API
+//!
+//! This module contains the implementations for the Anthropic Admin API endpoints.
+
+pub mod api_keys;
+pub mod cost;
+pub mod invites;
+pub mod usage;
+pub mod users;
+pub mod workspace_members;
+pub mod workspaces;
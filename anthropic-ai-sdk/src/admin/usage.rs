@@ -0,0 +1,124 @@
+//! Usage Report
+//!
+//! This module contains the implementations for the usage report endpoint of the Admin API.
+
+use async_trait::async_trait;
+use futures_util::{Stream, TryStreamExt};
+
+use crate::client::AnthropicClient;
+use crate::types::admin::AdminError;
+use crate::types::admin::usage::{UsageBucket, UsageReport, UsageReportClient, UsageReportParams};
+
+#[async_trait]
+impl UsageReportClient for AnthropicClient {
+    /// Get the messages usage report
+    ///
+    /// Retrieves a page of token usage of the organization, bucketed by time
+    /// and optionally grouped by API key, workspace, model or service tier.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Time range, bucketing, grouping and filters of the report
+    ///
+    /// # Returns
+    ///
+    /// Returns a page of usage buckets
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::usage::{BucketWidth, UsageReportClient, UsageReportParams};
+    /// use time::OffsetDateTime;
+    /// use time::format_description::well_known::Rfc3339;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let params = UsageReportParams::new(OffsetDateTime::parse("2025-01-01T00:00:00Z", &Rfc3339)?).bucket_width(BucketWidth::Day);
+    /// let report = client.get_messages_usage_report(&params).await?;
+    /// for bucket in report.data {
+    ///     println!("{}: {} results", bucket.starting_at, bucket.results.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_messages_usage_report<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+    ) -> Result<UsageReport, AdminError> {
+        self.send_admin_request::<_, _, ()>(
+            reqwest::Method::GET,
+            "/organizations/usage_report/messages",
+            Some(&params.to_query()),
+            None,
+        )
+        .await
+    }
+
+    /// Get the messages usage report bucket by bucket
+    ///
+    /// Starts at the page selected by `params` and follows `next_page` until
+    /// there are no more results.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of usage buckets; it ends after the last bucket or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::usage::{UsageGroupBy, UsageReportClient, UsageReportParams};
+    /// use futures_util::TryStreamExt;
+    /// use time::OffsetDateTime;
+    /// use time::format_description::well_known::Rfc3339;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let params = UsageReportParams::new(OffsetDateTime::parse("2025-01-01T00:00:00Z", &Rfc3339)?)
+    ///     .ending_at(OffsetDateTime::parse("2025-01-08T00:00:00Z", &Rfc3339)?)
+    ///     .group_by(UsageGroupBy::WorkspaceId);
+    /// let output_tokens: u64 = client
+    ///     .messages_usage_report_stream(&params)
+    ///     .map_ok(|bucket| bucket.results.iter().map(|r| r.output_tokens).sum::<u64>())
+    ///     .try_fold(0, |total, tokens| async move { Ok(total + tokens) })
+    ///     .await?;
+    /// println!("{output_tokens} output tokens");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn messages_usage_report_stream<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+    ) -> impl Stream<Item = Result<UsageBucket, AdminError>> + Send + 'a {
+        let pages =
+            futures_util::stream::try_unfold(Some(params.clone()), move |params| async move {
+                let Some(mut params) = params else {
+                    return Ok::<_, AdminError>(None);
+                };
+                let report = self.get_messages_usage_report(&params).await?;
+                let next = if report.has_more {
+                    report.next_page.clone().map(|page| {
+                        params.page = Some(page);
+                        params
+                    })
+                } else {
+                    None
+                };
+                Ok(Some((report, next)))
+            });
+        pages
+            .map_ok(|report| futures_util::stream::iter(report.data.into_iter().map(Ok)))
+            .try_flatten()
+    }
+}
@@ -0,0 +1,256 @@
+//! Organization Member Management
+//!
+//! This module contains the implementations for the organization member endpoints of the Admin API.
+
+use async_trait::async_trait;
+use futures_util::Stream;
+
+use crate::client::AnthropicClient;
+use crate::types::admin::AdminError;
+use crate::types::admin::users::{
+    ListOrganizationMembersParams, ListOrganizationMembersResponse, OrganizationMember,
+    OrganizationMemberClient, OrganizationMemberDeleted, OrganizationRole,
+    UpdateOrganizationMemberParams,
+};
+use crate::types::pagination::Paginator;
+
+#[async_trait]
+impl OrganizationMemberClient for AnthropicClient {
+    /// List organization members
+    ///
+    /// Retrieves a page of the members of the organization.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional pagination and filter parameters
+    ///
+    /// # Returns
+    ///
+    /// Returns a page of organization members
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::users::{
+    ///     ListOrganizationMembersParams, OrganizationMemberClient,
+    /// };
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_admin_key("sk-ant-REDACTED")
+    ///     .build::<AdminError>()?;
+    /// let params = ListOrganizationMembersParams::new().limit(20);
+    /// let page = client.list_organization_members(Some(&params)).await?;
+    /// for member in page.data {
+    ///     println!("{} ({:?})", member.email, member.role);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn list_organization_members<'a>(
+        &'a self,
+        params: Option<&'a ListOrganizationMembersParams>,
+    ) -> Result<ListOrganizationMembersResponse, AdminError> {
+        self.send_admin_request::<_, _, ()>(
+            reqwest::Method::GET,
+            "/organizations/users",
+            params,
+            None,
+        )
+        .await
+    }
+
+    /// List organization members one by one
+    ///
+    /// Starts at the page selected by `params` and follows the pagination
+    /// cursors until there are no more results. Paging goes backwards when
+    /// `before_id` is set and forwards otherwise.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of organization members; it ends after the last member or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::users::OrganizationMemberClient;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let members: Vec<_> = client.list_organization_members_stream(None).try_collect().await?;
+    /// println!("{} members", members.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_organization_members_stream<'a>(
+        &'a self,
+        params: Option<&'a ListOrganizationMembersParams>,
+    ) -> impl Stream<Item = Result<OrganizationMember, AdminError>> + Send + 'a {
+        Paginator::new(
+            params.cloned().unwrap_or_default(),
+            move |params| async move { self.list_organization_members(Some(&params)).await },
+        )
+        .items()
+    }
+
+    /// Get an organization member
+    ///
+    /// Retrieves a member of the organization by user ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - ID of the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the organization member
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The user does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::users::OrganizationMemberClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let member = client.get_organization_member("user_id").await?;
+    /// println!("{} joined at {}", member.email, member.added_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_organization_member<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> Result<OrganizationMember, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::GET,
+            &format!("/organizations/users/{}", user_id),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Update an organization member
+    ///
+    /// Changes the role of a member of the organization.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - ID of the user
+    /// * `role` - New role of the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated organization member
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The user does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::users::{OrganizationMemberClient, OrganizationRole};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let member = client
+    ///     .update_organization_member("user_id", OrganizationRole::Developer)
+    ///     .await?;
+    /// println!("{} is now {:?}", member.email, member.role);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn update_organization_member<'a>(
+        &'a self,
+        user_id: &'a str,
+        role: OrganizationRole,
+    ) -> Result<OrganizationMember, AdminError> {
+        self.send_admin_request::<_, (), _>(
+            reqwest::Method::POST,
+            &format!("/organizations/users/{}", user_id),
+            None,
+            Some(&UpdateOrganizationMemberParams { role }),
+        )
+        .await
+    }
+
+    /// Remove an organization member
+    ///
+    /// Removes a user from the organization.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - ID of the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the removal confirmation
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The user does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::users::OrganizationMemberClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let removed = client.remove_organization_member("user_id").await?;
+    /// println!("Removed: {}", removed.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn remove_organization_member<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> Result<OrganizationMemberDeleted, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::DELETE,
+            &format!("/organizations/users/{}", user_id),
+            None,
+            None,
+        )
+        .await
+    }
+}
@@ -0,0 +1,426 @@
+//! Workspace Member Management
+//!
+//! This module contains the implementations for the workspace member endpoints of the Admin API.
+
+use async_trait::async_trait;
+use futures_util::Stream;
+
+use crate::client::AnthropicClient;
+use crate::types::admin::AdminError;
+use crate::types::admin::workspace_members::{
+    AddWorkspaceMemberParams, ListWorkspaceMembersParams, ListWorkspaceMembersResponse,
+    UpdateWorkspaceMemberParams, WorkspaceMember, WorkspaceMemberClient, WorkspaceMemberDeleted,
+    WorkspaceRole,
+};
+use crate::types::pagination::Paginator;
+
+#[async_trait]
+impl WorkspaceMemberClient for AnthropicClient {
+    /// List workspace members
+    ///
+    /// Retrieves a page of the members of a workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    /// * `params` - Optional pagination parameters
+    ///
+    /// # Returns
+    ///
+    /// Returns a page of workspace members
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The workspace does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::WorkspaceMemberClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let page = client.list_workspace_members("workspace_id", None).await?;
+    /// for member in page.data {
+    ///     println!("{}: {:?}", member.user_id, member.workspace_role);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn list_workspace_members<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+    ) -> Result<ListWorkspaceMembersResponse, AdminError> {
+        self.send_admin_request::<_, _, ()>(
+            reqwest::Method::GET,
+            &format!("/organizations/workspaces/{}/members", workspace_id),
+            params,
+            None,
+        )
+        .await
+    }
+
+    /// List workspace members one by one
+    ///
+    /// Starts at the page selected by `params` and follows the pagination
+    /// cursors until there are no more results. Paging goes backwards when
+    /// `before_id` is set and forwards otherwise.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of workspace members; it ends after the last member or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::WorkspaceMemberClient;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let members: Vec<_> = client
+    ///     .list_workspace_members_stream("workspace_id", None)
+    ///     .try_collect()
+    ///     .await?;
+    /// println!("{} members", members.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_workspace_members_stream<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+    ) -> impl Stream<Item = Result<WorkspaceMember, AdminError>> + Send + 'a {
+        Paginator::new(
+            params.cloned().unwrap_or_default(),
+            move |params| async move {
+                self.list_workspace_members(workspace_id, Some(&params))
+                    .await
+            },
+        )
+        .items()
+    }
+
+    /// Get a workspace member
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    /// * `user_id` - ID of the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the workspace member
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The user is not a member of the workspace (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::WorkspaceMemberClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let member = client.get_workspace_member("workspace_id", "user_id").await?;
+    /// println!("{:?}", member.workspace_role);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+    ) -> Result<WorkspaceMember, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::GET,
+            &format!(
+                "/organizations/workspaces/{}/members/{}",
+                workspace_id, user_id
+            ),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Add a workspace member
+    ///
+    /// Adds a user of the organization to a workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    /// * `user_id` - ID of the user
+    /// * `role` - Role of the user in the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the new workspace member
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The user is already a member of the workspace (`AdminError::Conflict`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::{WorkspaceMemberClient, WorkspaceRole};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let member = client
+    ///     .add_workspace_member("workspace_id", "user_id", WorkspaceRole::WorkspaceDeveloper)
+    ///     .await?;
+    /// println!("Added {}", member.user_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn add_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError> {
+        self.send_admin_request::<_, (), _>(
+            reqwest::Method::POST,
+            &format!("/organizations/workspaces/{}/members", workspace_id),
+            None,
+            Some(&AddWorkspaceMemberParams {
+                user_id,
+                workspace_role: role,
+            }),
+        )
+        .await
+    }
+
+    /// Add a workspace member if they are not a member yet
+    ///
+    /// Like [`add_workspace_member`](WorkspaceMemberClient::add_workspace_member),
+    /// but a user who is already a member is not an error: the existing
+    /// membership is returned unchanged, even if its role differs from `role`.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    /// * `user_id` - ID of the user
+    /// * `role` - Role of the user if they are added
+    ///
+    /// # Returns
+    ///
+    /// Returns the new or existing workspace member
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::{WorkspaceMemberClient, WorkspaceRole};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let interaction = |method: &str, path: &str, response| Interaction {
+    ///     request: RecordedRequest {
+    ///         method: method.to_string(),
+    ///         path: path.to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response,
+    /// };
+    /// let member = r#"{"type": "workspace_member", "user_id": "user_01",
+    ///     "workspace_id": "wrkspc_01", "workspace_role": "workspace_admin"}"#;
+    /// let conflict = r#"{"type": "error", "error": {"type": "invalid_request_error",
+    ///     "message": "User is already a member of the workspace"}}"#;
+    /// let members = "/organizations/workspaces/wrkspc_01/members";
+    /// let path = std::env::temp_dir().join(format!("workspace-member-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         interaction("POST", members, RecordedResponse::json(409, conflict)),
+    ///         interaction("POST", members, RecordedResponse::json(409, conflict)),
+    ///         interaction("GET", &format!("{members}/user_01"), RecordedResponse::json(200, member)),
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path)
+    ///     .mode(VcrMode::Replay)
+    ///     .matcher(RequestMatcher::new().body(false))
+    ///     .start()
+    ///     .await?;
+    /// let client = AnthropicClient::builder("sk-ant-admin-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<AdminError>()?;
+    ///
+    /// let result = client
+    ///     .add_workspace_member("wrkspc_01", "user_01", WorkspaceRole::WorkspaceUser)
+    ///     .await;
+    /// assert!(matches!(result, Err(AdminError::Conflict(_))));
+    ///
+    /// // The existing membership is returned, with its own role
+    /// let member = client
+    ///     .ensure_workspace_member("wrkspc_01", "user_01", WorkspaceRole::WorkspaceUser)
+    ///     .await?;
+    /// assert_eq!(member.workspace_role, WorkspaceRole::WorkspaceAdmin);
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn ensure_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError> {
+        match self.add_workspace_member(workspace_id, user_id, role).await {
+            Err(AdminError::Conflict(_)) => self.get_workspace_member(workspace_id, user_id).await,
+            result => result,
+        }
+    }
+
+    /// Update a workspace member
+    ///
+    /// Changes the role of a member of a workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    /// * `user_id` - ID of the user
+    /// * `role` - New role of the user in the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated workspace member
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The user is not a member of the workspace (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::{WorkspaceMemberClient, WorkspaceRole};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let member = client
+    ///     .update_workspace_member("workspace_id", "user_id", WorkspaceRole::WorkspaceAdmin)
+    ///     .await?;
+    /// println!("{:?}", member.workspace_role);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn update_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError> {
+        self.send_admin_request::<_, (), _>(
+            reqwest::Method::POST,
+            &format!(
+                "/organizations/workspaces/{}/members/{}",
+                workspace_id, user_id
+            ),
+            None,
+            Some(&UpdateWorkspaceMemberParams {
+                workspace_role: role,
+            }),
+        )
+        .await
+    }
+
+    /// Remove a workspace member
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    /// * `user_id` - ID of the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the removal confirmation
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The user is not a member of the workspace (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::WorkspaceMemberClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let removed = client.remove_workspace_member("workspace_id", "user_id").await?;
+    /// println!("Removed {}", removed.user_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn remove_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+    ) -> Result<WorkspaceMemberDeleted, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::DELETE,
+            &format!(
+                "/organizations/workspaces/{}/members/{}",
+                workspace_id, user_id
+            ),
+            None,
+            None,
+        )
+        .await
+    }
+}
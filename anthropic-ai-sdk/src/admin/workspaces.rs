@@ -0,0 +1,289 @@
+//! Workspace Management
+//!
+//! This module contains the implementations for the workspace endpoints of the Admin API.
+
+use async_trait::async_trait;
+use futures_util::Stream;
+
+use crate::client::AnthropicClient;
+use crate::types::admin::AdminError;
+use crate::types::admin::workspaces::{
+    ListWorkspacesParams, ListWorkspacesResponse, Workspace, WorkspaceClient, WorkspaceNameParams,
+};
+use crate::types::pagination::Paginator;
+
+#[async_trait]
+impl WorkspaceClient for AnthropicClient {
+    /// List workspaces
+    ///
+    /// Retrieves a page of the workspaces of the organization. Archived
+    /// workspaces are only included when `include_archived` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional pagination and filter parameters
+    ///
+    /// # Returns
+    ///
+    /// Returns a page of workspaces
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::{ListWorkspacesParams, WorkspaceClient};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let params = ListWorkspacesParams::new().include_archived(true);
+    /// let page = client.list_workspaces(Some(&params)).await?;
+    /// for workspace in page.data.iter().filter(|w| w.is_archived()) {
+    ///     println!("Archived: {}", workspace.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn list_workspaces<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+    ) -> Result<ListWorkspacesResponse, AdminError> {
+        self.send_admin_request::<_, _, ()>(
+            reqwest::Method::GET,
+            "/organizations/workspaces",
+            params,
+            None,
+        )
+        .await
+    }
+
+    /// List workspaces one by one
+    ///
+    /// Starts at the page selected by `params` and follows the pagination
+    /// cursors until there are no more results. Paging goes backwards when
+    /// `before_id` is set and forwards otherwise.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of workspaces; it ends after the last workspace or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::WorkspaceClient;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let workspaces: Vec<_> = client.list_workspaces_stream(None).try_collect().await?;
+    /// println!("{} active workspaces", workspaces.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_workspaces_stream<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+    ) -> impl Stream<Item = Result<Workspace, AdminError>> + Send + 'a {
+        Paginator::new(
+            params.cloned().unwrap_or_default(),
+            move |params| async move { self.list_workspaces(Some(&params)).await },
+        )
+        .items()
+    }
+
+    /// Get a workspace
+    ///
+    /// Retrieves a workspace by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the workspace
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The workspace does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::WorkspaceClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let workspace = client.get_workspace("workspace_id").await?;
+    /// println!("{} created at {}", workspace.name, workspace.created_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_workspace<'a>(&'a self, workspace_id: &'a str) -> Result<Workspace, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::GET,
+            &format!("/organizations/workspaces/{}", workspace_id),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Create a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the created workspace
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::WorkspaceClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let workspace = client.create_workspace("Customer A").await?;
+    /// println!("Created: {}", workspace.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn create_workspace<'a>(&'a self, name: &'a str) -> Result<Workspace, AdminError> {
+        self.send_admin_request::<_, (), _>(
+            reqwest::Method::POST,
+            "/organizations/workspaces",
+            None,
+            Some(&WorkspaceNameParams { name }),
+        )
+        .await
+    }
+
+    /// Update a workspace
+    ///
+    /// Renames a workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    /// * `name` - New name of the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated workspace
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The workspace does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::WorkspaceClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let workspace = client.update_workspace("workspace_id", "Customer A (EU)").await?;
+    /// println!("Renamed to {}", workspace.name);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn update_workspace<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        name: &'a str,
+    ) -> Result<Workspace, AdminError> {
+        self.send_admin_request::<_, (), _>(
+            reqwest::Method::POST,
+            &format!("/organizations/workspaces/{}", workspace_id),
+            None,
+            Some(&WorkspaceNameParams { name }),
+        )
+        .await
+    }
+
+    /// Archive a workspace
+    ///
+    /// Archived workspaces can no longer be used, and their API keys are
+    /// deactivated.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - ID of the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the archived workspace
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - No admin API key is configured
+    /// - The workspace does not exist (`AdminError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::WorkspaceClient;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<AdminError>("sk-ant-REDACTED", "2023-06-01")?;
+    /// let workspace = client.archive_workspace("workspace_id").await?;
+    /// println!("Archived at {:?}", workspace.archived_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn archive_workspace<'a>(
+        &'a self,
+        workspace_id: &'a str,
+    ) -> Result<Workspace, AdminError> {
+        self.send_admin_request::<_, (), ()>(
+            reqwest::Method::POST,
+            &format!("/organizations/workspaces/{}/archive", workspace_id),
+            None,
+            None,
+        )
+        .await
+    }
+}
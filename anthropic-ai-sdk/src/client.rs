@@ -7,7 +7,17 @@ use reqwest::Client as ReqwestClient;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use std::error::Error as StdError;
-
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OwnedSemaphorePermit;
+
+use crate::error::{ApiErrorResponse, retry_after};
+use crate::models::ModelCache;
+use crate::types::admin::AdminError;
+use crate::types::concurrent::ConcurrencyLimit;
+use crate::types::message::{MaxTokens, RoleMerge};
+use crate::types::rate_limit::RateLimiter;
+use crate::types::response_cache::ResponseCache;
 
 /// Anthropic API client
 ///
@@ -40,16 +50,55 @@ use std::error::Error as StdError;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct AnthropicClient {
     /// The underlying HTTP client for making requests
     client: ReqwestClient,
     /// The API key used for authentication with Anthropic's services
     api_key: String,
+    /// The admin API key used for the Admin API
+    admin_key: Option<String>,
     /// The API version used for authentication with Anthropic's services
     api_version: String,
     /// The base URL for the Anthropic API
     api_base_url: String,
+    /// Cache of the Models API list, shared by clones of the client
+    model_cache: Option<Arc<ModelCache>>,
+    /// Rate limiter of message requests, shared by clones of the client
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Cap on the requests in flight, shared by clones of the client
+    concurrency_limit: Option<Arc<ConcurrencyLimit>>,
+    /// Cache of message responses, shared by clones of the client
+    response_cache: Option<Arc<ResponseCache>>,
+    /// How `max_tokens` is filled in for message requests that leave it at 0
+    default_max_tokens: Option<MaxTokens>,
+    /// Whether consecutive messages with the same role are merged before sending
+    normalize_roles: bool,
+    /// Called with the merges of each request whose roles were normalized
+    role_merge_hook: Option<RoleMergeHook>,
+}
+
+/// Hook called with the merges of a request whose roles were normalized
+pub type RoleMergeHook = Arc<dyn Fn(&[RoleMerge]) + Send + Sync>;
+
+impl std::fmt::Debug for AnthropicClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print key material
+        f.debug_struct("AnthropicClient")
+            .field("client", &self.client)
+            .field("api_key", &"<redacted>")
+            .field("admin_key", &self.admin_key.as_ref().map(|_| "<redacted>"))
+            .field("api_version", &self.api_version)
+            .field("api_base_url", &self.api_base_url)
+            .field("model_cache", &self.model_cache)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("concurrency_limit", &self.concurrency_limit)
+            .field("response_cache", &self.response_cache)
+            .field("default_max_tokens", &self.default_max_tokens)
+            .field("normalize_roles", &self.normalize_roles)
+            .field("role_merge_hook", &self.role_merge_hook.is_some())
+            .finish()
+    }
 }
 
 /// Builder for AnthropicClient
@@ -57,9 +106,17 @@ pub struct AnthropicClient {
 /// Provides a flexible way to configure and create an AnthropicClient.
 pub struct AnthropicClientBuilder {
     api_key: String,
+    admin_key: Option<String>,
     api_version: String,
     api_base_url: String,
     client: Option<ReqwestClient>,
+    model_cache_ttl: Option<Duration>,
+    rate_limiter: Option<RateLimiter>,
+    max_concurrent_requests: Option<usize>,
+    response_cache: Option<ResponseCache>,
+    default_max_tokens: Option<MaxTokens>,
+    normalize_roles: bool,
+    role_merge_hook: Option<RoleMergeHook>,
 }
 
 impl AnthropicClientBuilder {
@@ -67,9 +124,17 @@ impl AnthropicClientBuilder {
     pub fn new(api_key: impl Into<String>, api_version: impl Into<String>) -> Self {
         Self {
             api_key: api_key.into(),
+            admin_key: None,
             api_version: api_version.into(),
             api_base_url: AnthropicClient::DEFAULT_API_BASE_URL.to_string(),
             client: None,
+            model_cache_ttl: None,
+            rate_limiter: None,
+            max_concurrent_requests: None,
+            response_cache: None,
+            default_max_tokens: None,
+            normalize_roles: false,
+            role_merge_hook: None,
         }
     }
 
@@ -85,6 +150,269 @@ impl AnthropicClientBuilder {
         self
     }
 
+    /// Sets the admin API key (`sk-ant-admin...`) used for the Admin API
+    pub fn with_admin_key(mut self, admin_key: impl Into<String>) -> Self {
+        self.admin_key = Some(admin_key.into());
+        self
+    }
+
+    /// Caches the Models API list for [`AnthropicClient::DEFAULT_MODEL_CACHE_TTL`]
+    ///
+    /// The cached list is used by
+    /// [`ModelClient::cached_models`](crate::types::model::ModelClient::cached_models),
+    /// alias resolution and the `max_tokens` validation of message requests.
+    pub fn with_model_cache(self) -> Self {
+        self.with_model_cache_ttl(AnthropicClient::DEFAULT_MODEL_CACHE_TTL)
+    }
+
+    /// Caches the Models API list for the given time
+    pub fn with_model_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.model_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Paces message requests with `rate_limiter`
+    ///
+    /// Clones of the built client share the limiter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
+    /// };
+    /// use anthropic_ai_sdk::types::rate_limit::{RateLimiter, RateLimits};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let path = std::env::temp_dir().join(format!("rate-limit-{}.json", std::process::id()));
+    /// let body = r#"{"content": [{"type": "text", "text": "Hi"}], "id": "msg_01",
+    ///     "model": "claude-3-7-sonnet-20250219", "role": "assistant", "type": "message",
+    ///     "stop_reason": "end_turn", "usage": {"input_tokens": 900, "output_tokens": 2}}"#;
+    /// Cassette {
+    ///     interactions: vec![Interaction {
+    ///         request: RecordedRequest {
+    ///             method: "POST".to_string(),
+    ///             path: "/messages".to_string(),
+    ///             headers: Vec::new(),
+    ///             body: String::new(),
+    ///         },
+    ///         response: RecordedResponse::json(200, body),
+    ///     }],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path)
+    ///     .mode(VcrMode::Replay)
+    ///     .matcher(RequestMatcher::new().body(false))
+    ///     .start()
+    ///     .await?;
+    ///
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .with_rate_limiter(RateLimiter::new(RateLimits::new().input_tokens_per_minute(1000)))
+    ///     .build::<MessageError>()?;
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-7-sonnet-20250219".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hi")],
+    ///     max_tokens: 1024,
+    /// });
+    /// client.create_message(Some(&params)).await?;
+    ///
+    /// // The input tokens reported by the response count against the clone as well
+    /// let clone = client.clone();
+    /// let limiter = clone.rate_limiter().unwrap();
+    /// assert!(limiter.delay("claude-3-7-sonnet-20250219", 50).is_zero());
+    /// assert!(!limiter.delay("claude-3-7-sonnet-20250219", 500).is_zero());
+    /// # server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Allows at most `max` requests in flight at a time
+    ///
+    /// Requests beyond the limit wait for a permit before they are sent, see
+    /// [`ConcurrencyLimit`]. Clones of the built client share the limit.
+    pub fn with_max_concurrent_requests(mut self, max: usize) -> Self {
+        self.max_concurrent_requests = Some(max);
+        self
+    }
+
+    /// Answers repeated identical message requests from `response_cache`
+    ///
+    /// Clones of the built client share the cache.
+    pub fn with_response_cache(mut self, response_cache: ResponseCache) -> Self {
+        self.response_cache = Some(response_cache);
+        self
+    }
+
+    /// Fills in `max_tokens` of message requests that leave it at 0
+    ///
+    /// The value is chosen from the model limits, from the Models API when
+    /// its cache is enabled and from the built-in table otherwise, as with
+    /// [`CreateMessageParams::with_max_tokens_auto`](crate::types::message::CreateMessageParams::with_max_tokens_auto).
+    /// Unknown models fall back to
+    /// [`DEFAULT_MAX_TOKENS`](crate::types::message::DEFAULT_MAX_TOKENS).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, MaxTokens, Message, MessageClient, MessageError,
+    ///     RequiredMessageParams, Role,
+    /// };
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-haiku-latest".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hello")],
+    ///     max_tokens: 0,
+    /// });
+    ///
+    /// // The request is only answered if it asks for the model maximum
+    /// let path = std::env::temp_dir().join(format!("max-tokens-{}.json", std::process::id()));
+    /// let expected = CreateMessageParams { max_tokens: 8_192, ..params.clone() };
+    /// Cassette {
+    ///     interactions: vec![Interaction {
+    ///         request: RecordedRequest {
+    ///             method: "POST".to_string(),
+    ///             path: "/messages".to_string(),
+    ///             headers: Vec::new(),
+    ///             body: serde_json::to_string(&expected)?,
+    ///         },
+    ///         response: RecordedResponse::json(
+    ///             200,
+    ///             r#"{"content": [{"type": "text", "text": "Hi"}], "id": "msg_01",
+    ///                 "model": "claude-3-5-haiku-20241022", "role": "assistant",
+    ///                 "type": "message", "stop_reason": "end_turn",
+    ///                 "usage": {"input_tokens": 8, "output_tokens": 1}}"#,
+    ///         ),
+    ///     }],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+    ///
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .with_default_max_tokens(MaxTokens::ModelMaximum)
+    ///     .build::<MessageError>()?;
+    /// let response = client.create_message(Some(&params)).await?;
+    /// assert_eq!(response.text(), "Hi");
+    /// # server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_default_max_tokens(mut self, default_max_tokens: MaxTokens) -> Self {
+        self.default_max_tokens = Some(default_max_tokens);
+        self
+    }
+
+    /// Merges consecutive messages with the same role before sending message requests
+    ///
+    /// Instead of being rejected, histories with adjacent messages of the
+    /// same role are sent with their content blocks concatenated in order,
+    /// see [`normalize_messages_with_report`](crate::types::message::normalize_messages_with_report).
+    /// The history of the caller is left unchanged. Each merge is logged as a
+    /// `tracing` warning and passed to the hook set with
+    /// [`with_role_merge_hook`](Self::with_role_merge_hook).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
+    ///     RoleMerge,
+    /// };
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-5".to_string(),
+    ///     messages: vec![
+    ///         Message::new_text(Role::User, "Hello"),
+    ///         Message::new_text(Role::User, "Are you there?"),
+    ///     ],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_role_validation(true);
+    ///
+    /// // The request is only answered if the messages were merged
+    /// let path = std::env::temp_dir().join(format!("normalize-roles-{}.json", std::process::id()));
+    /// let mut merged = params.clone();
+    /// merged.messages = anthropic_ai_sdk::types::message::normalize_messages(merged.messages);
+    /// Cassette {
+    ///     interactions: vec![Interaction {
+    ///         request: RecordedRequest {
+    ///             method: "POST".to_string(),
+    ///             path: "/messages".to_string(),
+    ///             headers: Vec::new(),
+    ///             body: serde_json::to_string(&merged)?,
+    ///         },
+    ///         response: RecordedResponse::json(
+    ///             200,
+    ///             r#"{"content": [{"type": "text", "text": "Yes"}], "id": "msg_01",
+    ///                 "model": "claude-sonnet-4-5", "role": "assistant",
+    ///                 "type": "message", "stop_reason": "end_turn",
+    ///                 "usage": {"input_tokens": 12, "output_tokens": 1}}"#,
+    ///         ),
+    ///     }],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+    ///
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let hook_seen = seen.clone();
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .with_normalize_roles(true)
+    ///     .with_role_merge_hook(move |merges| {
+    ///         hook_seen.lock().unwrap().extend_from_slice(merges)
+    ///     })
+    ///     .build::<MessageError>()?;
+    /// let response = client.create_message(Some(&params)).await?;
+    /// assert_eq!(response.text(), "Yes");
+    /// assert_eq!(*seen.lock().unwrap(), [RoleMerge { role: Role::User, messages: 0..2 }]);
+    /// # server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_normalize_roles(mut self, enabled: bool) -> Self {
+        self.normalize_roles = enabled;
+        self
+    }
+
+    /// Calls `hook` with the merges of each request whose roles were normalized
+    ///
+    /// Only called when [`with_normalize_roles`](Self::with_normalize_roles)
+    /// is enabled and a request had messages to merge.
+    pub fn with_role_merge_hook(
+        mut self,
+        hook: impl Fn(&[RoleMerge]) + Send + Sync + 'static,
+    ) -> Self {
+        self.role_merge_hook = Some(Arc::new(hook));
+        self
+    }
+
     /// Set the API version
     pub fn with_api_version(mut self, api_version: impl Into<String>) -> Self {
         self.api_version = api_version.into();
@@ -117,8 +445,20 @@ impl AnthropicClientBuilder {
         Ok(AnthropicClient {
             client,
             api_key: self.api_key,
+            admin_key: self.admin_key,
             api_version: self.api_version,
             api_base_url: self.api_base_url,
+            model_cache: self
+                .model_cache_ttl
+                .map(|ttl| Arc::new(ModelCache::new(ttl))),
+            rate_limiter: self.rate_limiter.map(Arc::new),
+            concurrency_limit: self
+                .max_concurrent_requests
+                .map(|max| Arc::new(ConcurrencyLimit::new(max))),
+            response_cache: self.response_cache.map(Arc::new),
+            default_max_tokens: self.default_max_tokens,
+            normalize_roles: self.normalize_roles,
+            role_merge_hook: self.role_merge_hook,
         })
     }
 }
@@ -136,6 +476,11 @@ impl AnthropicClient {
     pub const DEFAULT_USER_AGENT: &'static str =
         concat!(env!("CARGO_PKG_NAME"), "-", env!("CARGO_PKG_VERSION"));
 
+    /// Prefix of admin API keys
+    pub const ADMIN_KEY_PREFIX: &str = "sk-ant-admin";
+
+    /// Default time the Models API list is cached, when caching is enabled
+    pub const DEFAULT_MODEL_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
 
     pub fn get_client(&self) -> &ReqwestClient {
         &self.client
@@ -153,8 +498,67 @@ impl AnthropicClient {
         &self.api_base_url
     }
 
+    /// The model list cache, if enabled
+    pub(crate) fn model_cache(&self) -> Option<&ModelCache> {
+        self.model_cache.as_deref()
+    }
+
+    /// The rate limiter of message requests, if enabled
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    /// The cap on requests in flight, if enabled
+    pub fn concurrency_limit(&self) -> Option<&ConcurrencyLimit> {
+        self.concurrency_limit.as_deref()
+    }
+
+    /// The cache of message responses, if enabled
+    pub fn response_cache(&self) -> Option<&ResponseCache> {
+        self.response_cache.as_deref()
+    }
+
+    /// How `max_tokens` is filled in for message requests that leave it at 0, if set
+    pub fn default_max_tokens(&self) -> Option<MaxTokens> {
+        self.default_max_tokens
+    }
+
+    /// Whether consecutive messages with the same role are merged before sending
+    pub fn normalize_roles(&self) -> bool {
+        self.normalize_roles
+    }
+
+    /// Report the merges of a request whose roles were normalized
+    pub(crate) fn report_role_merges(&self, merges: &[RoleMerge]) {
+        if merges.is_empty() {
+            return;
+        }
+        for merge in merges {
+            tracing::warn!(
+                "Merged messages {}..{} with the {:?} role before sending",
+                merge.messages.start,
+                merge.messages.end,
+                merge.role
+            );
+        }
+        if let Some(hook) = &self.role_merge_hook {
+            hook(merges);
+        }
+    }
+
+    /// Wait for a permit to send a request, if the requests in flight are capped
+    pub(crate) async fn acquire_request_permit(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.concurrency_limit {
+            Some(limit) => Some(limit.acquire().await),
+            None => None,
+        }
+    }
+
     /// Creates a new AnthropicClient builder
-    pub fn builder(api_key: impl Into<String>, api_version: impl Into<String>) -> AnthropicClientBuilder {
+    pub fn builder(
+        api_key: impl Into<String>,
+        api_version: impl Into<String>,
+    ) -> AnthropicClientBuilder {
         AnthropicClientBuilder::new(api_key, api_version)
     }
 
@@ -203,12 +607,13 @@ impl AnthropicClient {
     /// * `path` - The API endpoint path (will be appended to the base URL)
     /// * `query` - Optional query parameters to include in the URL
     /// * `body` - Optional request body to send
+    /// * `betas` - Beta feature flags to send in the `anthropic-beta` header
     ///
     /// # Returns
     ///
     /// Returns the deserialized response on success, or an error if:
     /// - The request fails to send
-    /// - The response indicates an error (non-2xx status)
+    /// - The response indicates an error (non-2xx status), passed to `E` as an [`ApiErrorResponse`]
     /// - The response body cannot be parsed
     pub(crate) async fn send_request<T, Q, B, E>(
         &self,
@@ -216,21 +621,81 @@ impl AnthropicClient {
         path: &str,
         query: Option<&Q>,
         body: Option<&B>,
+        betas: &[&str],
     ) -> Result<T, E>
     where
         T: DeserializeOwned,
         Q: Serialize + ?Sized,
         B: Serialize + ?Sized,
-        E: StdError + From<String>,
+        E: StdError + From<String> + From<ApiErrorResponse>,
+    {
+        self.send_request_with_key(&self.api_key, method, path, query, body, betas)
+            .await
+    }
+
+    /// Sends a request to the Admin API, authenticated with the admin key
+    ///
+    /// The admin key set with [`AnthropicClientBuilder::with_admin_key`] is
+    /// used, or else the API key if it is an admin key. Regular API keys are
+    /// never sent to admin endpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method to use for the request
+    /// * `path` - The API endpoint path
+    /// * `query` - Optional query parameters
+    /// * `body` - Optional request body
+    pub(crate) async fn send_admin_request<T, Q, B>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        query: Option<&Q>,
+        body: Option<&B>,
+    ) -> Result<T, AdminError>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+        B: Serialize + ?Sized,
+    {
+        let admin_key = match &self.admin_key {
+            Some(key) if key.starts_with(Self::ADMIN_KEY_PREFIX) => key,
+            Some(_) => return Err(AdminError::NotAnAdminKey),
+            None if self.api_key.starts_with(Self::ADMIN_KEY_PREFIX) => &self.api_key,
+            None => return Err(AdminError::MissingAdminKey),
+        };
+        self.send_request_with_key(admin_key, method, path, query, body, &[])
+            .await
+    }
+
+    /// Sends a request authenticated with the given key
+    async fn send_request_with_key<T, Q, B, E>(
+        &self,
+        api_key: &str,
+        method: reqwest::Method,
+        path: &str,
+        query: Option<&Q>,
+        body: Option<&B>,
+        betas: &[&str],
+    ) -> Result<T, E>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+        B: Serialize + ?Sized,
+        E: StdError + From<String> + From<ApiErrorResponse>,
     {
         let url = format!("{}{}", self.api_base_url, path);
 
         let mut request = self
             .client
             .request(method, &url)
-            .header("x-api-key", &self.api_key)
+            .header("x-api-key", api_key)
             .header("anthropic-version", &self.api_version);
 
+        // Add beta feature flags if any are required
+        if !betas.is_empty() {
+            request = request.header("anthropic-beta", betas.join(","));
+        }
+
         // Add query parameters if provided
         if let Some(q) = query {
             request = request.query(q);
@@ -238,21 +703,98 @@ impl AnthropicClient {
 
         // Add request body if provided
         if let Some(b) = body {
-            let _json = serde_json::to_string_pretty(b)
-                .map_err(|e| E::from(format!("Failed to serialize body: {}", e)))?;
             request = request.json(b);
         }
 
+        let _permit = self.acquire_request_permit().await;
         let response = request.send().await.map_err(|e| E::from(e.to_string()))?;
 
+        Self::parse_response(response).await
+    }
+
+    /// Sends a request with a prebuilt body, e.g. a multipart form
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The expected response type
+    /// * `E` - The error type
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method to use for the request
+    /// * `path` - The API endpoint path
+    /// * `body` - The request body
+    /// * `content_type` - Value of the `content-type` header
+    /// * `betas` - Beta feature flags for the `anthropic-beta` header
+    pub(crate) async fn send_body<T, E>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: reqwest::Body,
+        content_type: &str,
+        betas: &[&str],
+    ) -> Result<T, E>
+    where
+        T: DeserializeOwned,
+        E: StdError + From<String> + From<ApiErrorResponse>,
+    {
+        let (response, _permit) = self
+            .send_body_response(method, path, body, content_type, betas)
+            .await
+            .map_err(|e| E::from(e.to_string()))?;
+
+        Self::parse_response(response).await
+    }
+
+    /// Sends a request with a prebuilt body and returns the response unread
+    ///
+    /// Takes the same arguments as [`send_body`](Self::send_body). Hold the
+    /// returned permit of the concurrency limit until the response is read.
+    pub(crate) async fn send_body_response(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: reqwest::Body,
+        content_type: &str,
+        betas: &[&str],
+    ) -> Result<(reqwest::Response, Option<OwnedSemaphorePermit>), reqwest::Error> {
+        let url = format!("{}{}", self.api_base_url, path);
+
+        let mut request = self
+            .client
+            .request(method, &url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", &self.api_version)
+            .header("content-type", content_type)
+            .body(body);
+
+        if !betas.is_empty() {
+            request = request.header("anthropic-beta", betas.join(","));
+        }
+
+        let permit = self.acquire_request_permit().await;
+        Ok((request.send().await?, permit))
+    }
+
+    /// Reads a response and parses its JSON body
+    ///
+    /// Non-2xx responses are passed to `E` as an [`ApiErrorResponse`].
+    pub(crate) async fn parse_response<T, E>(response: reqwest::Response) -> Result<T, E>
+    where
+        T: DeserializeOwned,
+        E: StdError + From<String> + From<ApiErrorResponse>,
+    {
         let status = response.status();
+        let retry_after = retry_after(response.headers());
         let body = response
             .text()
             .await
             .map_err(|e| E::from(format!("Failed to get response body: {}", e)))?;
 
         if !status.is_success() {
-            return Err(E::from(body));
+            return Err(E::from(
+                ApiErrorResponse::new(status.as_u16(), body).with_retry_after(retry_after),
+            ));
         }
 
         // Parse the JSON response
@@ -280,12 +822,108 @@ impl AnthropicClient {
     where
         T: DeserializeOwned,
         Q: Serialize + ?Sized,
-        E: StdError + From<String>,
+        E: StdError + From<String> + From<ApiErrorResponse>,
     {
-        self.send_request::<T, Q, (), E>(reqwest::Method::GET, path, query, None)
+        self.send_request::<T, Q, (), E>(reqwest::Method::GET, path, query, None, &[])
             .await
     }
 
+    /// Sends a GET request with the given beta feature flags enabled
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The expected response type
+    /// * `Q` - The query parameters type
+    /// * `E` - The error type
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The API endpoint path
+    /// * `query` - Optional query parameters
+    /// * `betas` - Beta feature flags for the `anthropic-beta` header
+    pub(crate) async fn get_with_betas<T, Q, E>(
+        &self,
+        path: &str,
+        query: Option<&Q>,
+        betas: &[&str],
+    ) -> Result<T, E>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+        E: StdError + From<String> + From<ApiErrorResponse>,
+    {
+        self.send_request::<T, Q, (), E>(reqwest::Method::GET, path, query, None, betas)
+            .await
+    }
+
+    /// Sends a GET request and returns the successful response without reading the body
+    ///
+    /// Used for endpoints whose body is streamed instead of parsed as a whole.
+    /// Hold the returned permit until the body has been read.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `E` - The error type
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The API endpoint path
+    /// * `betas` - Beta feature flags for the `anthropic-beta` header
+    pub(crate) async fn get_response<E>(
+        &self,
+        path: &str,
+        betas: &[&str],
+    ) -> Result<(reqwest::Response, Option<OwnedSemaphorePermit>), E>
+    where
+        E: StdError + From<String> + From<ApiErrorResponse>,
+    {
+        self.get_response_with_headers(path, betas, &[]).await
+    }
+
+    /// Like [`get_response`](Self::get_response), sending additional `headers`
+    pub(crate) async fn get_response_with_headers<E>(
+        &self,
+        path: &str,
+        betas: &[&str],
+        headers: &[(&str, String)],
+    ) -> Result<(reqwest::Response, Option<OwnedSemaphorePermit>), E>
+    where
+        E: StdError + From<String> + From<ApiErrorResponse>,
+    {
+        let url = format!("{}{}", self.api_base_url, path);
+
+        let mut request = self
+            .client
+            .get(&url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", &self.api_version);
+
+        if !betas.is_empty() {
+            request = request.header("anthropic-beta", betas.join(","));
+        }
+
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+
+        let permit = self.acquire_request_permit().await;
+        let response = request.send().await.map_err(|e| E::from(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = retry_after(response.headers());
+            let body = response
+                .text()
+                .await
+                .map_err(|e| E::from(format!("Failed to get response body: {}", e)))?;
+            return Err(E::from(
+                ApiErrorResponse::new(status.as_u16(), body).with_retry_after(retry_after),
+            ));
+        }
+
+        Ok((response, permit))
+    }
+
     /// Sends a POST request to the specified endpoint
     ///
     /// # Type Parameters
@@ -302,9 +940,37 @@ impl AnthropicClient {
     where
         T: DeserializeOwned,
         B: Serialize + ?Sized,
-        E: StdError + From<String>,
+        E: StdError + From<String> + From<ApiErrorResponse>,
+    {
+        self.send_request::<T, (), B, E>(reqwest::Method::POST, path, None, body, &[])
+            .await
+    }
+
+    /// Sends a POST request with the given beta feature flags enabled
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The expected response type
+    /// * `B` - The request body type
+    /// * `E` - The error type
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The API endpoint path
+    /// * `body` - Optional request body
+    /// * `betas` - Beta feature flags for the `anthropic-beta` header
+    pub(crate) async fn post_with_betas<T, B, E>(
+        &self,
+        path: &str,
+        body: Option<&B>,
+        betas: &[&str],
+    ) -> Result<T, E>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+        E: StdError + From<String> + From<ApiErrorResponse>,
     {
-        self.send_request::<T, (), B, E>(reqwest::Method::POST, path, None, body)
+        self.send_request::<T, (), B, E>(reqwest::Method::POST, path, None, body, betas)
             .await
     }
 
@@ -324,9 +990,29 @@ impl AnthropicClient {
     where
         T: DeserializeOwned,
         Q: Serialize + ?Sized,
-        E: StdError + From<String>,
+        E: StdError + From<String> + From<ApiErrorResponse>,
+    {
+        self.send_request::<T, Q, (), E>(reqwest::Method::DELETE, path, query, None, &[])
+            .await
+    }
+
+    /// Sends a DELETE request with the given beta feature flags enabled
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The expected response type
+    /// * `E` - The error type
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The API endpoint path
+    /// * `betas` - Beta feature flags for the `anthropic-beta` header
+    pub(crate) async fn delete_with_betas<T, E>(&self, path: &str, betas: &[&str]) -> Result<T, E>
+    where
+        T: DeserializeOwned,
+        E: StdError + From<String> + From<ApiErrorResponse>,
     {
-        self.send_request::<T, Q, (), E>(reqwest::Method::DELETE, path, query, None)
+        self.send_request::<T, (), (), E>(reqwest::Method::DELETE, path, None, None, betas)
             .await
     }
 }
@@ -0,0 +1,138 @@
+//! API error responses
+//!
+//! This module contains the structured form of error responses returned by the API.
+//!
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use serde::{Deserialize, Serialize};
+
+/// Error response returned by the API for a non-2xx status
+///
+/// The `Display` output is the raw response body.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::error::ApiErrorResponse;
+///
+/// let body = r#"{"type":"error","error":{"type":"not_found_error","message":"model: claude-x"}}"#;
+/// let error = ApiErrorResponse::new(404, body);
+/// assert!(error.is_not_found());
+/// assert_eq!(error.error_type.as_deref(), Some("not_found_error"));
+/// assert_eq!(error.message.as_deref(), Some("model: claude-x"));
+/// assert_eq!(error.to_string(), body);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiErrorResponse {
+    /// HTTP status code of the response
+    pub status: u16,
+    /// Error type reported by the API (e.g. "not_found_error")
+    pub error_type: Option<String>,
+    /// Human-readable error message reported by the API
+    pub message: Option<String>,
+    /// Raw response body
+    pub body: String,
+    /// How long to wait before retrying, from the `retry-after` header
+    pub retry_after: Option<Duration>,
+}
+
+/// Error object in the API's error format
+///
+/// Serialized as `{"type": "error", "error": {"type": ..., "message": ...}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiErrorBody {
+    /// The error details
+    pub error: ApiErrorDetail,
+}
+
+/// Type and message of an API error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiErrorDetail {
+    /// Error type (e.g. "invalid_request_error")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Human-readable error message
+    pub message: String,
+}
+
+impl ApiErrorDetail {
+    /// Whether the error is transient, so the same request may succeed when retried
+    ///
+    /// Overloaded, rate limit, timeout and internal API errors are retryable;
+    /// errors caused by the request itself are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::error::ApiErrorDetail;
+    ///
+    /// let error = |type_: &str| ApiErrorDetail {
+    ///     type_: type_.to_string(),
+    ///     message: String::new(),
+    /// };
+    /// assert!(error("overloaded_error").is_retryable());
+    /// assert!(!error("invalid_request_error").is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.type_.as_str(),
+            "overloaded_error" | "rate_limit_error" | "api_error" | "timeout_error"
+        )
+    }
+}
+
+impl ApiErrorResponse {
+    /// Parse an error response from its status code and body
+    ///
+    /// Bodies that are not in the API's error format keep `error_type` and
+    /// `message` unset.
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        let body = body.into();
+        let detail = serde_json::from_str::<ApiErrorBody>(&body)
+            .ok()
+            .map(|envelope| envelope.error);
+        Self {
+            status,
+            error_type: detail.as_ref().map(|d| d.type_.clone()),
+            message: detail.map(|d| d.message),
+            body,
+            retry_after: None,
+        }
+    }
+
+    /// Set the retry delay requested by the API
+    pub fn with_retry_after(mut self, retry_after: Option<Duration>) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Whether the requested resource does not exist
+    pub fn is_not_found(&self) -> bool {
+        self.status == 404
+    }
+
+    /// Whether the request conflicts with the current state of the resource
+    pub fn is_conflict(&self) -> bool {
+        self.status == 409
+    }
+
+    /// Whether the request was rejected by rate limiting
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == 429
+    }
+}
+
+impl std::fmt::Display for ApiErrorResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.body)
+    }
+}
+
+impl std::error::Error for ApiErrorResponse {}
+
+/// Parse the `retry-after` header, given in seconds
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+    Duration::try_from_secs_f64(value.trim().parse().ok()?).ok()
+}
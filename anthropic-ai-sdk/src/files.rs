@@ -0,0 +1,646 @@
+//! Files API
+//!
+//! This module contains the implementations for the Anthropic Files API endpoints.
+//! It provides functionality for uploading files that can be referenced from messages.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use futures_util::{Stream, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::sync::OwnedSemaphorePermit;
+use tokio_util::io::ReaderStream;
+
+use crate::client::AnthropicClient;
+use crate::types::beta::Beta;
+use crate::types::files::{
+    FileClient, FileContentInfo, FileDeleted, FileDownload, FileError, FileObject, ListFilesParams,
+    ListFilesResponse, MAX_FILE_SIZE,
+};
+use crate::types::pagination::Paginator;
+
+#[async_trait]
+impl FileClient for AnthropicClient {
+    /// Upload a file
+    ///
+    /// Uploads a file held in memory as multipart/form-data.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - Name of the file as stored by the API
+    /// * `content` - The file content
+    /// * `mime_type` - MIME type of the content (e.g. "application/pdf")
+    ///
+    /// # Returns
+    ///
+    /// Returns the metadata of the uploaded file
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The file is larger than [`MAX_FILE_SIZE`] (`FileError::FileTooLarge`)
+    /// - The API rejects the request size (`FileError::RequestTooLarge`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::files::{FileClient, FileError};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let uploaded = r#"{"type": "file", "id": "file_01", "filename": "report.pdf",
+    ///     "mime_type": "application/pdf", "size_bytes": 8, "created_at": "2025-01-01T00:00:00Z"}"#;
+    /// let too_large = r#"{"type": "error", "error": {"type": "request_too_large",
+    ///     "message": "Request exceeds the maximum size"}}"#;
+    /// let upload = |response| Interaction {
+    ///     request: RecordedRequest {
+    ///         method: "POST".to_string(),
+    ///         path: "/files".to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response,
+    /// };
+    /// let path = std::env::temp_dir().join(format!("files-upload-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         upload(RecordedResponse::json(200, uploaded)),
+    ///         upload(RecordedResponse::json(413, too_large)),
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// // Multipart boundaries differ between runs, so bodies are not compared
+    /// let server = Vcr::new(&path)
+    ///     .mode(VcrMode::Replay)
+    ///     .matcher(RequestMatcher::new().body(false))
+    ///     .start()
+    ///     .await?;
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<FileError>()?;
+    ///
+    /// let content = b"%PDF-1.7".to_vec();
+    /// let file = client
+    ///     .upload_file("report.pdf", content.clone(), "application/pdf")
+    ///     .await?;
+    /// assert_eq!(file.id, "file_01");
+    /// assert_eq!(file.size_bytes, 8);
+    ///
+    /// match client.upload_file("report.pdf", content, "application/pdf").await {
+    ///     Err(FileError::RequestTooLarge(message)) => {
+    ///         assert_eq!(message, "Request exceeds the maximum size")
+    ///     }
+    ///     other => panic!("expected a size error, got {:?}", other),
+    /// }
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn upload_file<'a>(
+        &'a self,
+        filename: &'a str,
+        content: Vec<u8>,
+        mime_type: &'a str,
+    ) -> Result<FileObject, FileError> {
+        let size = content.len() as u64;
+        if size > MAX_FILE_SIZE {
+            return Err(FileError::FileTooLarge {
+                size,
+                limit: MAX_FILE_SIZE,
+            });
+        }
+
+        let form = MultipartFile::new(filename, mime_type);
+        let mut body = form.head();
+        body.extend_from_slice(&content);
+        body.extend_from_slice(&form.tail());
+
+        self.send_body(
+            reqwest::Method::POST,
+            "/files",
+            body.into(),
+            &form.content_type(),
+            &[Beta::FilesApi.as_str()],
+        )
+        .await
+    }
+
+    /// Upload a file from a reader
+    ///
+    /// Streams the content of `reader` as multipart/form-data, so large files
+    /// are not buffered in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - Name of the file as stored by the API
+    /// * `reader` - Source of the file content
+    /// * `mime_type` - MIME type of the content (e.g. "application/pdf")
+    ///
+    /// # Returns
+    ///
+    /// Returns the metadata of the uploaded file
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The API rejects the request size (`FileError::RequestTooLarge`)
+    /// - The request fails to send or the reader fails
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::files::{FileClient, FileError};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<FileError>("your-api-key", "2023-06-01")?;
+    /// let reader = tokio::fs::File::open("dataset.csv").await?;
+    /// let file = client
+    ///     .upload_file_stream("dataset.csv", reader, "text/csv")
+    ///     .await?;
+    /// println!("Uploaded: {} ({} bytes)", file.id, file.size_bytes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn upload_file_stream<'a, R>(
+        &'a self,
+        filename: &'a str,
+        reader: R,
+        mime_type: &'a str,
+    ) -> Result<FileObject, FileError>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let form = MultipartFile::new(filename, mime_type);
+        let head = form.head();
+        let tail = form.tail();
+        let chunks = futures_util::stream::once(async move { Ok(head.into()) })
+            .chain(ReaderStream::new(reader))
+            .chain(futures_util::stream::once(async move { Ok(tail.into()) }));
+
+        self.send_body(
+            reqwest::Method::POST,
+            "/files",
+            reqwest::Body::wrap_stream(chunks),
+            &form.content_type(),
+            &[Beta::FilesApi.as_str()],
+        )
+        .await
+    }
+
+    /// List files
+    ///
+    /// Retrieves a page of uploaded files, most recently created first.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional pagination parameters
+    ///
+    /// # Returns
+    ///
+    /// Returns a page of file metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::files::{FileClient, FileError, ListFilesParams};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<FileError>("your-api-key", "2023-06-01")?;
+    /// let page = client.list_files(Some(&ListFilesParams::new().limit(20))).await?;
+    /// for file in page.data {
+    ///     println!("{}: {}", file.id, file.filename);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn list_files<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> Result<ListFilesResponse, FileError> {
+        self.get_with_betas("/files", params, &[Beta::FilesApi.as_str()])
+            .await
+    }
+
+    /// List files one by one
+    ///
+    /// Starts at the page selected by `params` and follows the pagination
+    /// cursors until there are no more results. Paging goes backwards when
+    /// `before_id` is set and forwards otherwise.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of file metadata; it ends after the last file or the first error.
+    ///
+    /// # Examples
+    ///
+    /// Deleting files older than 30 days, against a replay of two pages:
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::files::{FileClient, FileError};
+    /// use futures_util::TryStreamExt;
+    /// use time::{Duration, OffsetDateTime};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let interaction = |method: &str, path: &str, body: String| Interaction {
+    ///     request: RecordedRequest {
+    ///         method: method.to_string(),
+    ///         path: path.to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response: RecordedResponse::json(200, body),
+    /// };
+    /// let file = |id: &str, created_at: &str| {
+    ///     serde_json::json!({"type": "file", "id": id, "filename": format!("{id}.txt"),
+    ///         "mime_type": "text/plain", "size_bytes": 1, "created_at": created_at})
+    /// };
+    /// let page = |files: Vec<serde_json::Value>, has_more: bool| {
+    ///     let last_id = files.last().map(|file| file["id"].clone());
+    ///     serde_json::json!({"data": files, "has_more": has_more,
+    ///         "first_id": null, "last_id": last_id})
+    ///     .to_string()
+    /// };
+    /// let recent = OffsetDateTime::now_utc().format(&time::format_description::well_known::Rfc3339)?;
+    /// let path = std::env::temp_dir().join(format!("files-cleanup-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         interaction("GET", "/files", page(vec![file("file_01", &recent)], true)),
+    ///         interaction(
+    ///             "GET",
+    ///             "/files?after_id=file_01",
+    ///             page(vec![file("file_02", "2025-01-01T00:00:00Z")], false),
+    ///         ),
+    ///         interaction(
+    ///             "DELETE",
+    ///             "/files/file_02",
+    ///             r#"{"id": "file_02", "type": "file_deleted"}"#.to_string(),
+    ///         ),
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<FileError>()?;
+    ///
+    /// let cutoff = OffsetDateTime::now_utc() - Duration::days(30);
+    /// let old: Vec<_> = client
+    ///     .list_files_stream(None)
+    ///     .try_filter(|file| std::future::ready(file.created_at < cutoff))
+    ///     .try_collect()
+    ///     .await?;
+    /// assert_eq!(old.len(), 1);
+    /// for file in old {
+    ///     let deleted = client.delete_file(&file.id).await?;
+    ///     assert_eq!(deleted.id, "file_02");
+    /// }
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_files_stream<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> impl Stream<Item = Result<FileObject, FileError>> + Send + 'a {
+        Paginator::new(
+            params.cloned().unwrap_or_default(),
+            move |params| async move { self.list_files(Some(&params)).await },
+        )
+        .items()
+    }
+
+    /// Get file metadata
+    ///
+    /// Retrieves the metadata of an uploaded file by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - ID of the file
+    ///
+    /// # Returns
+    ///
+    /// Returns the metadata of the file
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The file does not exist (`FileError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::files::{FileClient, FileError};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<FileError>("your-api-key", "2023-06-01")?;
+    /// let file = client.get_file_metadata("file_id").await?;
+    /// println!("{} was created at {}", file.filename, file.created_at);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_file_metadata<'a>(&'a self, file_id: &'a str) -> Result<FileObject, FileError> {
+        self.get_with_betas::<FileObject, (), FileError>(
+            &format!("/files/{}", file_id),
+            None,
+            &[Beta::FilesApi.as_str()],
+        )
+        .await
+    }
+
+    /// Delete a file
+    ///
+    /// Deletes an uploaded file by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - ID of the file
+    ///
+    /// # Returns
+    ///
+    /// Returns the deletion confirmation
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The file does not exist (`FileError::NotFound`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::files::{FileClient, FileError};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<FileError>("your-api-key", "2023-06-01")?;
+    /// let deleted = client.delete_file("file_id").await?;
+    /// println!("Deleted: {}", deleted.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn delete_file<'a>(&'a self, file_id: &'a str) -> Result<FileDeleted, FileError> {
+        self.delete_with_betas(&format!("/files/{}", file_id), &[Beta::FilesApi.as_str()])
+            .await
+    }
+
+    /// Download a file
+    ///
+    /// Downloads the content of a file, such as one created by the code
+    /// execution tool, into memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - ID of the file
+    ///
+    /// # Returns
+    ///
+    /// Returns the file content with its content type and name
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The file does not exist (`FileError::NotFound`)
+    /// - The file cannot be downloaded (`FileError::NotDownloadable`)
+    /// - The request fails to send
+    /// - The API returns an error response
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::files::{FileClient, FileError};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let interaction = |path: &str, response| Interaction {
+    ///     request: RecordedRequest {
+    ///         method: "GET".to_string(),
+    ///         path: path.to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response,
+    /// };
+    /// let content = RecordedResponse {
+    ///     status: 200,
+    ///     headers: vec![
+    ///         ("content-type".to_string(), "text/csv".to_string()),
+    ///         ("content-disposition".to_string(), r#"attachment; filename="data.csv""#.to_string()),
+    ///     ],
+    ///     body: "a,b\n1,2\n".to_string(),
+    ///     events: Vec::new(),
+    /// };
+    /// let rejected = r#"{"type": "error", "error": {"type": "invalid_request_error",
+    ///     "message": "File cannot be downloaded"}}"#;
+    /// let uploaded = r#"{"type": "file", "id": "file_02", "filename": "report.pdf",
+    ///     "mime_type": "application/pdf", "size_bytes": 8, "created_at": "2025-01-01T00:00:00Z",
+    ///     "downloadable": false}"#;
+    /// let not_found = r#"{"type": "error", "error": {"type": "not_found_error",
+    ///     "message": "File not found"}}"#;
+    /// let path = std::env::temp_dir().join(format!("files-download-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         interaction("/files/file_01/content", content),
+    ///         // Uploaded files are rejected, and their metadata tells why
+    ///         interaction("/files/file_02/content", RecordedResponse::json(400, rejected)),
+    ///         interaction("/files/file_02", RecordedResponse::json(200, uploaded)),
+    ///         interaction("/files/file_03/content", RecordedResponse::json(404, not_found)),
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<FileError>()?;
+    ///
+    /// let download = client.download_file("file_01").await?;
+    /// assert_eq!(download.content, b"a,b\n1,2\n");
+    /// assert_eq!(download.info.content_type.as_deref(), Some("text/csv"));
+    /// assert_eq!(download.info.filename.as_deref(), Some("data.csv"));
+    ///
+    /// match client.download_file("file_02").await {
+    ///     Err(FileError::NotDownloadable(id)) => assert_eq!(id, "file_02"),
+    ///     other => panic!("expected a download error, got {:?}", other),
+    /// }
+    /// assert!(matches!(
+    ///     client.download_file("file_03").await,
+    ///     Err(FileError::NotFound(_))
+    /// ));
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn download_file<'a>(&'a self, file_id: &'a str) -> Result<FileDownload, FileError> {
+        let (response, _permit) = self.file_content_response(file_id).await?;
+        let info = FileContentInfo::from_headers(response.headers());
+        let content = response
+            .bytes()
+            .await
+            .map_err(|e| FileError::RequestFailed(e.to_string()))?;
+        Ok(FileDownload {
+            info,
+            content: content.to_vec(),
+        })
+    }
+
+    /// Download a file to a writer
+    ///
+    /// Streams the content of a file into `writer` as it arrives.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - ID of the file
+    /// * `writer` - Destination of the file content
+    ///
+    /// # Returns
+    ///
+    /// Returns the content type and name of the file
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The file does not exist (`FileError::NotFound`)
+    /// - The file cannot be downloaded (`FileError::NotDownloadable`)
+    /// - The request fails to send or the download is interrupted
+    /// - Writing fails (`FileError::Io`)
+    /// - The API returns an error response
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::files::{FileClient, FileError};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<FileError>("your-api-key", "2023-06-01")?;
+    /// let mut output = tokio::fs::File::create("chart.png").await?;
+    /// let info = client.download_file_to("file_id", &mut output).await?;
+    /// println!("Saved {:?} ({:?})", info.filename, info.content_type);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn download_file_to<'a, W>(
+        &'a self,
+        file_id: &'a str,
+        writer: &'a mut W,
+    ) -> Result<FileContentInfo, FileError>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
+        let (response, _permit) = self.file_content_response(file_id).await?;
+        let info = FileContentInfo::from_headers(response.headers());
+        let mut chunks = response.bytes_stream();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk.map_err(|e| FileError::RequestFailed(e.to_string()))?;
+            writer.write_all(&chunk).await?;
+        }
+        writer.flush().await?;
+        Ok(info)
+    }
+}
+
+impl AnthropicClient {
+    /// Request the content of a file
+    ///
+    /// If the API rejects the download, the file metadata is checked to
+    /// report files that are not downloadable with a specific error.
+    async fn file_content_response(
+        &self,
+        file_id: &str,
+    ) -> Result<(reqwest::Response, Option<OwnedSemaphorePermit>), FileError> {
+        let result = self
+            .get_response::<FileError>(
+                &format!("/files/{}/content", file_id),
+                &[Beta::FilesApi.as_str()],
+            )
+            .await;
+        match result {
+            Err(FileError::ApiError(body)) => match self.get_file_metadata(file_id).await {
+                Ok(file) if !file.downloadable => Err(FileError::NotDownloadable(file.id)),
+                _ => Err(FileError::ApiError(body)),
+            },
+            result => result,
+        }
+    }
+}
+
+/// A multipart/form-data body with a single `file` part
+struct MultipartFile {
+    boundary: String,
+    filename: String,
+    mime_type: String,
+}
+
+impl MultipartFile {
+    fn new(filename: &str, mime_type: &str) -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        Self {
+            boundary: format!("anthropic-sdk-boundary-{:016x}{:08x}", nanos, count),
+            // Quotes and line breaks would end the header value early
+            filename: filename
+                .replace('"', "%22")
+                .replace('\r', "%0D")
+                .replace('\n', "%0A"),
+            mime_type: mime_type.replace(['\r', '\n'], ""),
+        }
+    }
+
+    fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// The part headers, sent before the file content
+    fn head(&self) -> Vec<u8> {
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            self.boundary, self.filename, self.mime_type
+        )
+        .into_bytes()
+    }
+
+    /// The closing boundary, sent after the file content
+    fn tail(&self) -> Vec<u8> {
+        format!("\r\n--{}--\r\n", self.boundary).into_bytes()
+    }
+}
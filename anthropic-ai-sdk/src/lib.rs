@@ -1,5 +1,22 @@
+pub mod admin;
 pub mod client;
+pub mod error;
+pub mod files;
+mod macros;
 pub mod message_batches;
 pub mod messages;
 pub mod models;
+pub mod testing;
 pub mod types;
+pub mod util;
+
+#[cfg(feature = "macros")]
+pub use anthropic_sdk_macros::anthropic_tool;
+
+#[doc(hidden)]
+#[cfg(feature = "macros")]
+pub mod __private {
+    pub use async_trait::async_trait;
+    pub use serde;
+    pub use serde_json;
+}
@@ -0,0 +1,80 @@
+//! Macros for building messages
+
+/// Build a [`Message`](crate::types::message::Message) from a role and its content
+///
+/// The role is `user` or `assistant`. The content is anything that converts
+/// into [`MessageContent`](crate::types::message::MessageContent): a string,
+/// or an array or `Vec` of content blocks.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::msg;
+/// use anthropic_ai_sdk::types::message::{ContentBlock, Message, Role};
+///
+/// let name = "Claude";
+/// assert_eq!(msg!(user: format!("Hi, {}", name)), Message::new_text(Role::User, "Hi, Claude"));
+/// assert_eq!(
+///     msg!(assistant: [ContentBlock::text("Hello")]),
+///     Message::new_blocks(Role::Assistant, vec![ContentBlock::text("Hello")])
+/// );
+/// ```
+///
+/// Other roles are rejected at compile time:
+///
+/// ```compile_fail
+/// let message = anthropic_ai_sdk::msg!(system: "Be brief");
+/// ```
+#[macro_export]
+macro_rules! msg {
+    (user: $content:expr) => {
+        $crate::types::message::Message::new($crate::types::message::Role::User, $content)
+    };
+    (assistant: $content:expr) => {
+        $crate::types::message::Message::new($crate::types::message::Role::Assistant, $content)
+    };
+    ($role:ident: $content:expr) => {
+        compile_error!(concat!(
+            "invalid message role `",
+            stringify!($role),
+            "`, expected `user` or `assistant`"
+        ))
+    };
+}
+
+/// Build a `Vec` of [`Message`](crate::types::message::Message)s from roles and their content
+///
+/// Each entry is written as for [`msg!`].
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::messages;
+/// use anthropic_ai_sdk::types::message::{ContentBlock, Message, Role};
+///
+/// let history = messages![
+///     user: "hi",
+///     assistant: "hello",
+///     user: [
+///         ContentBlock::text("look"),
+///         ContentBlock::image_url("https://example.com/cat.png"),
+///     ],
+/// ];
+/// assert_eq!(history.len(), 3);
+/// assert_eq!(history[1], Message::new_text(Role::Assistant, "hello"));
+/// assert_eq!(messages![], Vec::<Message>::new());
+/// ```
+///
+/// ```compile_fail
+/// let history = anthropic_ai_sdk::messages![user: "hi", bot: "hello"];
+/// ```
+#[macro_export]
+macro_rules! messages {
+    ($($role:ident: $content:expr),* $(,)?) => {
+        {
+            let messages: ::std::vec::Vec<$crate::types::message::Message> =
+                ::std::vec![$($crate::msg!($role: $content)),*];
+            messages
+        }
+    };
+}
@@ -4,14 +4,21 @@
 //! It provides functionality for creating message batches.
 
 use crate::client::AnthropicClient;
+use crate::types::auto_cache::apply_auto_cache;
+use crate::types::beta::Beta;
 use crate::types::message_batches::{
-    CancelMessageBatchParams, CancelResponse, CreateMessageBatchParams, DeleteMessageBatchParams,
-    DeleteResponse, ListMessageBatchesParams, ListMessageBatchesResponse, MessageBatch,
-    MessageBatchClient, MessageBatchError, RetrieveMessageBatchParams,
-    RetrieveMessageBatchResponse, RetrieveMessageBatchResultsParams,
-    RetrieveMessageBatchResultsResponse,
+    BatchGuard, BatchResult, BatchResultEntry, CancelMessageBatchParams, CancelResponse,
+    CheckpointedEntry, CreateMessageBatchParams, DeleteMessageBatchParams, DeleteResponse,
+    ListMessageBatchesParams, ListMessageBatchesResponse, MessageBatch, MessageBatchClient,
+    MessageBatchError, MessageRequest, PollOptions, ProcessingStatus, RequestSource, Resubmission,
+    ResultsCheckpoint, RetrieveMessageBatchParams, RetrieveMessageBatchResponse,
+    RetrieveMessageBatchResultsParams, RetrieveMessageBatchResultsResponse, decode_batch_results,
+    decode_results_from, resume_results,
 };
+use crate::types::pagination::Paginator;
 use async_trait::async_trait;
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use tokio::time::Instant;
 
 #[async_trait]
 impl MessageBatchClient for AnthropicClient {
@@ -32,6 +39,7 @@ impl MessageBatchClient for AnthropicClient {
     /// # Errors
     ///
     /// Returns a `MessageBatchError` if:
+    /// - The batch fails client-side validation
     /// - The request fails to send
     /// - The API returns an error response
     /// - The response cannot be parsed
@@ -68,7 +76,116 @@ impl MessageBatchClient for AnthropicClient {
         &'a self,
         body: &'a CreateMessageBatchParams,
     ) -> Result<MessageBatch, MessageBatchError> {
-        self.post("/messages/batches", Some(body)).await
+        body.validate()?;
+        let cached = body
+            .requests
+            .iter()
+            .any(|request| request.params.auto_cache.is_some())
+            .then(|| {
+                let mut body = body.clone();
+                for request in &mut body.requests {
+                    if let Some(params) = apply_auto_cache(&request.params) {
+                        request.params = params;
+                    }
+                }
+                body
+            });
+        let body = cached.as_ref().unwrap_or(body);
+        let betas = body.betas();
+        let betas: Vec<&str> = betas.iter().map(Beta::as_str).collect();
+        self.post_with_betas("/messages/batches", Some(body), &betas)
+            .await
+    }
+
+    /// Creates a message batch guarded against being left behind
+    ///
+    /// Like [`create_message_batch`](MessageBatchClient::create_message_batch),
+    /// but returns a [`BatchGuard`] that cancels the batch when dropped,
+    /// unless [`BatchGuard::keep`] is called first. See [`BatchGuard`] for
+    /// the limits of the cancel on drop.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `create_message_batch`.
+    ///
+    /// # Examples
+    ///
+    /// Dropping the guard early sends the cancel request:
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     CreateMessageBatchParams, MessageBatchClient, MessageBatchError, MessageRequest,
+    /// };
+    /// # use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    /// # use tokio::net::TcpListener;
+    /// #
+    /// # /// Answers every request with a batch and reports its request line
+    /// # async fn mock_server() -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+    /// #     let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    /// #     let url = format!("http://{}", listener.local_addr().unwrap());
+    /// #     let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    /// #     tokio::spawn(async move {
+    /// #         loop {
+    /// #             let (mut socket, _) = listener.accept().await.unwrap();
+    /// #             let mut request = Vec::new();
+    /// #             let mut buffer = [0; 4096];
+    /// #             while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+    /// #                 let n = socket.read(&mut buffer).await.unwrap();
+    /// #                 request.extend_from_slice(&buffer[..n]);
+    /// #             }
+    /// #             let request = String::from_utf8_lossy(&request).to_string();
+    /// #             let body = r#"{"id":"msgbatch_01","type":"message_batch","processing_status":"in_progress",
+    /// #                 "request_counts":{"processing":1,"succeeded":0,"errored":0,"canceled":0,"expired":0},
+    /// #                 "created_at":"2025-01-01T00:00:00Z","expires_at":"2025-01-02T00:00:00Z"}"#;
+    /// #             let response = format!(
+    /// #                 "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+    /// #                 body.len()
+    /// #             );
+    /// #             socket.write_all(response.as_bytes()).await.unwrap();
+    /// #             sender.send(request.lines().next().unwrap().to_string()).unwrap();
+    /// #         }
+    /// #     });
+    /// #     (url, receiver)
+    /// # }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (url, mut requests) = mock_server().await;
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_api_base_url(url)
+    ///     .build::<MessageBatchError>()?;
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-haiku-latest".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hello")],
+    ///     max_tokens: 1024,
+    /// });
+    /// let batch = CreateMessageBatchParams::new(vec![MessageRequest::new(params).with_custom_id("req-0")]);
+    /// let guard = client.create_message_batch_guarded(&batch).await?;
+    /// assert_eq!(requests.recv().await.unwrap(), "POST /messages/batches HTTP/1.1");
+    ///
+    /// // The pipeline aborts before the batch is kept
+    /// drop(guard);
+    /// assert_eq!(
+    ///     requests.recv().await.unwrap(),
+    ///     "POST /messages/batches/msgbatch_01/cancel HTTP/1.1"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn create_message_batch_guarded<'a>(
+        &'a self,
+        params: &'a CreateMessageBatchParams,
+    ) -> Result<BatchGuard<Self>, MessageBatchError>
+    where
+        Self: Clone + Sized + Send + Sync + 'static,
+    {
+        let batch = self.create_message_batch(params).await?;
+        Ok(BatchGuard::new(self.clone(), batch))
     }
 
     /// List message batches
@@ -110,6 +227,47 @@ impl MessageBatchClient for AnthropicClient {
         .await
     }
 
+    /// List message batches one by one
+    ///
+    /// Starts at the page selected by `params` and follows the pagination
+    /// cursors until there are no more results. Paging goes backwards when
+    /// `before_id` is set and forwards otherwise.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of message batches; it ends after the last batch or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     ListMessageBatchesParams, MessageBatchClient, MessageBatchError,
+    /// };
+    /// use futures_util::TryStreamExt;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
+    /// let params = ListMessageBatchesParams::new().limit(100);
+    /// let batches = client.list_message_batches_stream(Some(&params));
+    /// futures_util::pin_mut!(batches);
+    /// while let Some(batch) = batches.try_next().await? {
+    ///     println!("{}: {:?}", batch.id, batch.request_counts);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_message_batches_stream<'a>(
+        &'a self,
+        params: Option<&'a ListMessageBatchesParams>,
+    ) -> impl Stream<Item = Result<MessageBatch, MessageBatchError>> + Send + 'a {
+        Paginator::new(
+            params.cloned().unwrap_or_default(),
+            move |params| async move { self.list_message_batches(Some(&params)).await },
+        )
+        .items()
+    }
+
     /// Retrieve a message batch
     ///
     /// Retrieve a message batch by ID
@@ -155,7 +313,9 @@ impl MessageBatchClient for AnthropicClient {
     ///
     /// # Returns
     ///
-    /// Returns a message batch results
+    /// Returns the results of all requests in the batch. Prefer
+    /// [`stream_message_batch_results`](MessageBatchClient::stream_message_batch_results)
+    /// for large batches, which does not buffer the whole results file.
     ///
     /// # Errors
     ///
@@ -181,38 +341,401 @@ impl MessageBatchClient for AnthropicClient {
         &'a self,
         params: &'a RetrieveMessageBatchResultsParams,
     ) -> Result<RetrieveMessageBatchResultsResponse, MessageBatchError> {
-        self.get::<RetrieveMessageBatchResultsResponse, RetrieveMessageBatchResultsParams, MessageBatchError>(
-            &format!("/messages/batches/{}/results", params.message_batch_id),
-            None,
-        )
-        .await
+        self.stream_message_batch_results(params)
+            .await?
+            .try_collect()
+            .await
     }
 
-    /// Cancel a message batch
+    /// Stream message batch results
     ///
-    /// Cancels a message batch by ID
+    /// Downloads the `.jsonl` results file of an ended batch and decodes it
+    /// line by line as it arrives.
     ///
     /// # Returns
     ///
-    /// Returns a cancel response
+    /// Returns a stream of result entries, one per request in the batch.
     ///
     /// # Errors
     ///
     /// Returns a `MessageBatchError` if:
     /// - The request fails to send
     /// - The API returns an error response
-    /// - The response cannot be parsed
+    ///
+    /// Items of the stream are errors if the download fails or a line cannot be parsed.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use anthropic_ai_sdk::client::AnthropicClient;
-    /// use anthropic_ai_sdk::types::message_batches::{CancelMessageBatchParams, MessageBatchClient, MessageBatchError};
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     BatchResult, MessageBatchClient, MessageBatchError, RetrieveMessageBatchResultsParams,
+    /// };
+    /// use futures_util::TryStreamExt;
     ///
     /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
-    /// let response = client.cancel_message_batch(&CancelMessageBatchParams::new("batch_id")).await?;
-    /// println!("Response: {:?}", response);
+    /// let params = RetrieveMessageBatchResultsParams::new("msgbatch_batch_id");
+    /// let mut results = client.stream_message_batch_results(&params).await?;
+    /// while let Some(entry) = results.try_next().await? {
+    ///     match entry.result {
+    ///         BatchResult::Succeeded { message } => println!("{}: {:?}", entry.custom_id, message.text()),
+    ///         other => println!("{}: {:?}", entry.custom_id, other),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn stream_message_batch_results<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchResultsParams,
+    ) -> Result<
+        impl Stream<Item = Result<BatchResultEntry, MessageBatchError>> + Send + 'a,
+        MessageBatchError,
+    > {
+        let (response, permit) = self
+            .get_response::<MessageBatchError>(
+                &format!("/messages/batches/{}/results", params.message_batch_id),
+                &[],
+            )
+            .await?;
+        // The permit is held until the stream is dropped
+        Ok(Box::pin(decode_batch_results(response.bytes_stream()).map(
+            move |entry| {
+                let _permit = &permit;
+                entry
+            },
+        )))
+    }
+
+    /// Stream message batch results after a checkpoint
+    ///
+    /// Like [`stream_message_batch_results`](Self::stream_message_batch_results),
+    /// with each entry paired with the [`ResultsCheckpoint`] after it. With
+    /// a byte offset in `checkpoint`, only the rest of the results file is
+    /// requested with a `Range` header. The range starts one byte early, so
+    /// that it can be satisfied even when the checkpoint is at the end of
+    /// the file. If the server sends the whole file instead, the entries up
+    /// to the checkpoint are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    ///
+    /// Items of the stream are errors if the download fails or a line cannot
+    /// be parsed, and [`MessageBatchError::CheckpointNotFound`] at the end if
+    /// the custom_id of a checkpoint without offset is not in the results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     MessageBatchClient, MessageBatchError, ResultsCheckpoint,
+    ///     RetrieveMessageBatchResultsParams,
+    /// };
+    /// use futures_util::TryStreamExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let results = concat!(
+    ///     r#"{"custom_id":"doc-1","result":{"type":"expired"}}"#, "\n",
+    ///     r#"{"custom_id":"doc-2","result":{"type":"canceled"}}"#, "\n",
+    /// );
+    /// let request = RecordedRequest {
+    ///     method: "GET".to_string(),
+    ///     path: "/messages/batches/msgbatch_01/results".to_string(),
+    ///     headers: Vec::new(),
+    ///     body: String::new(),
+    /// };
+    /// let response = |status, body: &str| RecordedResponse {
+    ///     status,
+    ///     headers: Vec::new(),
+    ///     body: body.to_string(),
+    ///     events: Vec::new(),
+    /// };
+    /// // The server answers the resumed request with the requested range only
+    /// let first_line = results.find('\n').unwrap() + 1;
+    /// let path = std::env::temp_dir().join(format!("batch-resume-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         Interaction { request: request.clone(), response: response(200, results) },
+    ///         Interaction { request, response: response(206, &results[first_line - 1..]) },
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<MessageBatchError>()?;
+    /// let params = RetrieveMessageBatchResultsParams::new("msgbatch_01");
+    ///
+    /// // Process the first entry, then crash
+    /// let start = ResultsCheckpoint::start();
+    /// let mut entries = client.stream_message_batch_results_from(&params, &start).await?;
+    /// let checkpoint = entries.try_next().await?.unwrap().checkpoint;
+    /// assert_eq!(checkpoint.offset, first_line as u64);
+    /// assert_eq!(checkpoint.last_custom_id.as_deref(), Some("doc-1"));
+    /// drop(entries);
+    ///
+    /// let rest: Vec<_> = client
+    ///     .stream_message_batch_results_from(&params, &checkpoint)
+    ///     .await?
+    ///     .try_collect()
+    ///     .await?;
+    /// assert_eq!(rest.len(), 1);
+    /// assert_eq!(rest[0].entry.custom_id, "doc-2");
+    /// assert_eq!(rest[0].checkpoint.offset, results.len() as u64);
+    /// # server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn stream_message_batch_results_from<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchResultsParams,
+        checkpoint: &'a ResultsCheckpoint,
+    ) -> Result<
+        impl Stream<Item = Result<CheckpointedEntry, MessageBatchError>> + Send + 'a,
+        MessageBatchError,
+    > {
+        let start = checkpoint.offset.saturating_sub(1);
+        let headers = match start {
+            0 => Vec::new(),
+            start => vec![("range", format!("bytes={}-", start))],
+        };
+        let (response, permit) = self
+            .get_response_with_headers::<MessageBatchError>(
+                &format!("/messages/batches/{}/results", params.message_batch_id),
+                &[],
+                &headers,
+            )
+            .await?;
+        let (offset, partial) = if response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+            (start, true)
+        } else {
+            (0, false)
+        };
+        let entries = decode_results_from(response.bytes_stream(), offset, partial);
+        // The permit is held until the stream is dropped
+        Ok(Box::pin(resume_results(entries, checkpoint.clone()).map(
+            move |entry| {
+                let _permit = &permit;
+                entry
+            },
+        )))
+    }
+
+    /// Wait for a message batch to end
+    ///
+    /// Retrieves the batch repeatedly until its processing status is `ended`,
+    /// backing off between polls as configured by `options`. When rate
+    /// limited, the next poll waits at least as long as the API's
+    /// `retry-after` header asks.
+    ///
+    /// # Returns
+    ///
+    /// Returns the ended batch
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - The batch does not end within `options.max_wait` (`MessageBatchError::Timeout`)
+    /// - A request fails to send
+    /// - The API returns an error response other than a rate limit
+    /// - A response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::{Duration, Instant};
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     MessageBatchClient, MessageBatchError, PollOptions, ProcessingStatus,
+    ///     RetrieveMessageBatchParams,
+    /// };
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let batch = |status: &str, processing: u32| {
+    ///     let body = serde_json::json!({
+    ///         "id": "msgbatch_01", "type": "message_batch", "processing_status": status,
+    ///         "request_counts": {"processing": processing, "succeeded": 2 - processing,
+    ///             "errored": 0, "canceled": 0, "expired": 0},
+    ///         "created_at": "2025-01-01T00:00:00Z", "expires_at": "2025-01-02T00:00:00Z"
+    ///     });
+    ///     RecordedResponse::json(200, body.to_string())
+    /// };
+    /// let mut rate_limited = RecordedResponse::json(
+    ///     429,
+    ///     r#"{"type": "error", "error": {"type": "rate_limit_error", "message": "Slow down"}}"#,
+    /// );
+    /// rate_limited.headers.push(("retry-after".to_string(), "0.2".to_string()));
+    /// let poll = |response| Interaction {
+    ///     request: RecordedRequest {
+    ///         method: "GET".to_string(),
+    ///         path: "/messages/batches/msgbatch_01".to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response,
+    /// };
+    /// let path = std::env::temp_dir().join(format!("batch-wait-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         poll(batch("in_progress", 2)),
+    ///         poll(rate_limited),
+    ///         poll(batch("in_progress", 1)),
+    ///         poll(batch("ended", 0)),
+    ///         poll(batch("in_progress", 2)),
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<MessageBatchError>()?;
+    /// let params = RetrieveMessageBatchParams::new("msgbatch_01");
+    ///
+    /// let progress = Arc::new(Mutex::new(Vec::new()));
+    /// let seen = progress.clone();
+    /// let options = PollOptions::new()
+    ///     .with_interval(Duration::from_millis(10))
+    ///     .with_on_progress(move |counts| seen.lock().unwrap().push(counts.processing));
+    /// let started = Instant::now();
+    /// let batch = client.wait_for_message_batch(&params, &options).await?;
+    /// assert_eq!(batch.processing_status, ProcessingStatus::Ended);
+    /// assert_eq!(*progress.lock().unwrap(), [2, 1, 0]);
+    /// // The rate limited poll waited for the retry-after delay
+    /// assert!(started.elapsed() >= Duration::from_millis(200));
+    ///
+    /// // With a deadline, the last state is returned once the next poll would miss it
+    /// let options = PollOptions::new()
+    ///     .with_interval(Duration::from_secs(60))
+    ///     .with_max_wait(Duration::from_secs(1));
+    /// match client.wait_for_message_batch(&params, &options).await {
+    ///     Err(MessageBatchError::Timeout { last: Some(last) }) => {
+    ///         assert_eq!(last.processing_status, ProcessingStatus::InProgress);
+    ///     }
+    ///     other => panic!("expected a timeout, got {:?}", other),
+    /// }
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn wait_for_message_batch<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchParams,
+        options: &'a PollOptions,
+    ) -> Result<MessageBatch, MessageBatchError> {
+        let deadline = options.max_wait.map(|max_wait| Instant::now() + max_wait);
+        let mut interval = options.interval;
+        let mut last = None;
+        loop {
+            let delay = match self.retrieve_message_batch(params).await {
+                Ok(batch) => {
+                    if let Some(on_progress) = &options.on_progress {
+                        on_progress(&batch.request_counts);
+                    }
+                    if batch.processing_status == ProcessingStatus::Ended {
+                        return Ok(batch);
+                    }
+                    last = Some(Box::new(batch));
+                    interval
+                }
+                Err(MessageBatchError::RateLimited { retry_after, .. }) => {
+                    retry_after.map_or(interval, |retry_after| retry_after.max(interval))
+                }
+                Err(e) => return Err(e),
+            };
+            if deadline.is_some_and(|deadline| Instant::now() + delay > deadline) {
+                return Err(MessageBatchError::Timeout { last });
+            }
+            tokio::time::sleep(delay).await;
+            interval = options.next_interval(interval);
+        }
+    }
+
+    /// Cancel a message batch
+    ///
+    /// Cancels a message batch by ID
+    ///
+    /// # Returns
+    ///
+    /// Returns the batch, now in `canceling` status
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - The request fails to send
+    /// - The batch has already ended (`MessageBatchError::Conflict`)
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     CancelMessageBatchParams, MessageBatchClient, MessageBatchError, ProcessingStatus,
+    /// };
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let canceling = r#"{"id": "msgbatch_01", "type": "message_batch", "processing_status": "canceling",
+    ///     "request_counts": {"processing": 1, "succeeded": 0, "errored": 0, "canceled": 0, "expired": 0},
+    ///     "created_at": "2025-01-01T00:00:00Z", "expires_at": "2025-01-02T00:00:00Z"}"#;
+    /// let ended = r#"{"type": "error", "error": {"type": "invalid_request_error",
+    ///     "message": "Batch has already ended"}}"#;
+    /// let interaction = |response| Interaction {
+    ///     request: RecordedRequest {
+    ///         method: "POST".to_string(),
+    ///         path: "/messages/batches/msgbatch_01/cancel".to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response,
+    /// };
+    /// let path = std::env::temp_dir().join(format!("batch-cancel-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         interaction(RecordedResponse::json(200, canceling)),
+    ///         interaction(RecordedResponse::json(409, ended)),
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path)
+    ///     .mode(VcrMode::Replay)
+    ///     .matcher(RequestMatcher::new().body(false))
+    ///     .start()
+    ///     .await?;
+    /// let client = AnthropicClient::builder("test-key", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<MessageBatchError>()?;
+    ///
+    /// let params = CancelMessageBatchParams::new("msgbatch_01");
+    /// let batch = client.cancel_message_batch(&params).await?;
+    /// assert_eq!(batch.processing_status, ProcessingStatus::Canceling);
+    ///
+    /// match client.cancel_message_batch(&params).await {
+    ///     Err(MessageBatchError::Conflict(message)) => assert_eq!(message, "Batch has already ended"),
+    ///     other => panic!("expected a conflict, got {:?}", other),
+    /// }
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
     /// # Ok(())
     /// # }
     /// ```
@@ -239,19 +762,59 @@ impl MessageBatchClient for AnthropicClient {
     ///
     /// Returns a `MessageBatchError` if:
     /// - The request fails to send
+    /// - The batch is still processing (`MessageBatchError::Conflict`)
     /// - The API returns an error response
     /// - The response cannot be parsed
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// use anthropic_ai_sdk::client::AnthropicClient;
-    /// use anthropic_ai_sdk::types::message_batches::{DeleteMessageBatchParams, MessageBatchClient, MessageBatchError};
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     DeleteMessageBatchParams, MessageBatchClient, MessageBatchError,
+    /// };
     ///
-    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
-    /// let response = client.delete_message_batch(&DeleteMessageBatchParams::new("batch_id")).await?;
-    /// println!("Response: {:?}", response);
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let processing = r#"{"type": "error", "error": {"type": "invalid_request_error",
+    ///     "message": "Batch is still processing"}}"#;
+    /// let deleted = r#"{"id": "msgbatch_01", "type": "message_batch_deleted"}"#;
+    /// let interaction = |response| Interaction {
+    ///     request: RecordedRequest {
+    ///         method: "DELETE".to_string(),
+    ///         path: "/messages/batches/msgbatch_01".to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response,
+    /// };
+    /// let path = std::env::temp_dir().join(format!("batch-delete-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         interaction(RecordedResponse::json(409, processing)),
+    ///         interaction(RecordedResponse::json(200, deleted)),
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+    /// let client = AnthropicClient::builder("test-key", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<MessageBatchError>()?;
+    ///
+    /// let params = DeleteMessageBatchParams::new("msgbatch_01");
+    /// match client.delete_message_batch(&params).await {
+    ///     Err(MessageBatchError::Conflict(message)) => assert_eq!(message, "Batch is still processing"),
+    ///     other => panic!("expected a conflict, got {:?}", other),
+    /// }
+    ///
+    /// // Once the batch has ended
+    /// let response = client.delete_message_batch(&params).await?;
+    /// assert_eq!(response.obj_type, "message_batch_deleted");
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
     /// # Ok(())
     /// # }
     /// ```
@@ -265,4 +828,154 @@ impl MessageBatchClient for AnthropicClient {
         )
         .await
     }
+
+    /// Resubmits the requests of a batch that failed with a retryable error
+    ///
+    /// Reads the results of the batch, picks the `errored` entries whose
+    /// error is retryable (see
+    /// [`ApiErrorDetail::is_retryable`](crate::error::ApiErrorDetail::is_retryable)),
+    /// and creates a new batch with their original requests and custom_ids.
+    /// The requests are looked up in `requests`, which can be the original
+    /// request list or a lookup closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_batch_id` - ID of the ended batch
+    /// * `requests` - Source of the original requests, by custom_id
+    ///
+    /// # Returns
+    ///
+    /// Returns the new batch, if any request was retried, with the
+    /// resubmitted custom_ids, the skipped non-retryable failures, and the
+    /// custom_ids that could not be found in `requests`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - The results cannot be read
+    /// - The new batch cannot be created
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     CreateMessageBatchParams, MessageBatchClient, MessageBatchError, MessageRequest,
+    /// };
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = |custom_id: &str| {
+    ///     let messages = vec![Message::new_text(Role::User, format!("Summarize {custom_id}"))];
+    ///     let params = RequiredMessageParams::new("claude-3-5-haiku-20241022", messages, 256);
+    ///     MessageRequest::new(CreateMessageParams::new(params)).with_custom_id(custom_id)
+    /// };
+    /// // doc-3 failed with a retryable error but its request was lost
+    /// let requests = vec![request("doc-1"), request("doc-2"), request("doc-4")];
+    ///
+    /// let errored = |custom_id: &str, type_: &str| {
+    ///     serde_json::json!({"custom_id": custom_id, "result": {"type": "errored", "error": {
+    ///         "type": "error", "error": {"type": type_, "message": "failed"}}}})
+    ///     .to_string()
+    /// };
+    /// let results = [
+    ///     errored("doc-1", "overloaded_error"),
+    ///     errored("doc-2", "invalid_request_error"),
+    ///     errored("doc-3", "api_error"),
+    ///     r#"{"custom_id": "doc-4", "result": {"type": "expired"}}"#.to_string(),
+    /// ]
+    /// .join("\n");
+    /// let batch = r#"{"id": "msgbatch_02", "type": "message_batch", "processing_status": "in_progress",
+    ///     "request_counts": {"processing": 1, "succeeded": 0, "errored": 0, "canceled": 0, "expired": 0},
+    ///     "created_at": "2025-01-01T00:00:00Z", "expires_at": "2025-01-02T00:00:00Z"}"#;
+    /// // Only doc-1 is resubmitted, the replay fails on any other body
+    /// let retried = CreateMessageBatchParams::new(vec![request("doc-1")]);
+    /// let path = std::env::temp_dir().join(format!("batch-resubmit-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![
+    ///         Interaction {
+    ///             request: RecordedRequest {
+    ///                 method: "GET".to_string(),
+    ///                 path: "/messages/batches/msgbatch_01/results".to_string(),
+    ///                 headers: Vec::new(),
+    ///                 body: String::new(),
+    ///             },
+    ///             response: RecordedResponse::json(200, results),
+    ///         },
+    ///         Interaction {
+    ///             request: RecordedRequest {
+    ///                 method: "POST".to_string(),
+    ///                 path: "/messages/batches".to_string(),
+    ///                 headers: Vec::new(),
+    ///                 body: serde_json::to_string(&retried)?,
+    ///             },
+    ///             response: RecordedResponse::json(200, batch),
+    ///         },
+    ///     ],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<MessageBatchError>()?;
+    ///
+    /// let resubmission = client.resubmit_errors("msgbatch_01", &requests).await?;
+    /// assert_eq!(resubmission.batch.unwrap().id, "msgbatch_02");
+    /// assert_eq!(resubmission.resubmitted, ["doc-1"]);
+    /// assert_eq!(resubmission.skipped.len(), 1);
+    /// assert_eq!(resubmission.skipped[0].custom_id, "doc-2");
+    /// assert_eq!(resubmission.missing, ["doc-3"]);
+    /// server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn resubmit_errors<'a, S>(
+        &'a self,
+        message_batch_id: &'a str,
+        requests: &'a S,
+    ) -> Result<Resubmission, MessageBatchError>
+    where
+        S: RequestSource + ?Sized,
+    {
+        let params = RetrieveMessageBatchResultsParams::new(message_batch_id);
+        let entries = self.stream_message_batch_results(&params).await?;
+        futures_util::pin_mut!(entries);
+
+        let mut retry = Vec::new();
+        let mut resubmission = Resubmission {
+            batch: None,
+            resubmitted: Vec::new(),
+            skipped: Vec::new(),
+            missing: Vec::new(),
+        };
+        while let Some(entry) = entries.try_next().await? {
+            let BatchResult::Errored { error } = &entry.result else {
+                continue;
+            };
+            if !error.error.is_retryable() {
+                resubmission.skipped.push(entry);
+                continue;
+            }
+            match requests.request(&entry.custom_id) {
+                Some(params) => {
+                    retry.push(MessageRequest::new(params).with_custom_id(&entry.custom_id));
+                    resubmission.resubmitted.push(entry.custom_id);
+                }
+                None => resubmission.missing.push(entry.custom_id),
+            }
+        }
+
+        if !retry.is_empty() {
+            let params = CreateMessageBatchParams::new(retry);
+            resubmission.batch = Some(self.create_message_batch(&params).await?);
+        }
+        Ok(resubmission)
+    }
 }
@@ -3,15 +3,25 @@
 //! This module contains the implementations for the Anthropic Messages API endpoints.
 //! It provides functionality for creating messages and counting tokens.
 
+use std::borrow::Cow;
+
 use eventsource_stream::Eventsource;
 use futures_util::Stream;
-use reqwest::header::HeaderValue;
+use serde::Deserialize;
+use serde_json::value::RawValue;
 
 use crate::client::AnthropicClient;
+use crate::error::{ApiErrorResponse, retry_after};
+use crate::types::auto_cache::apply_auto_cache;
+use crate::types::beta::Beta;
+use crate::types::estimate::estimate_request_tokens;
 use crate::types::message::{
     CountMessageTokensParams, CountMessageTokensResponse, CreateMessageParams,
-    CreateMessageResponse, MessageClient, MessageError, StreamEvent,
+    CreateMessageResponse, MessageClient, MessageError, StreamEvent, Usage,
+    normalize_messages_with_report, validate_roles,
 };
+use crate::types::model::{ModelClient, ModelSpec};
+use crate::types::rate_limit::{InputTokenEstimate, RateLimitPermit, RateLimiter};
 use async_trait::async_trait;
 use futures_util::StreamExt;
 
@@ -36,6 +46,7 @@ impl MessageClient for AnthropicClient {
     /// # Errors
     ///
     /// Returns a `MessageError` if:
+    /// - The parameters fail client-side validation
     /// - The request fails to send
     /// - The API returns an error response
     /// - The response cannot be parsed
@@ -67,7 +78,25 @@ impl MessageClient for AnthropicClient {
         &'a self,
         body: Option<&'a CreateMessageParams>,
     ) -> Result<CreateMessageResponse, MessageError> {
-        self.post("/messages", body).await
+        let Some(body) = body else {
+            return self.post("/messages", None::<&CreateMessageParams>).await;
+        };
+        let body = &*self.prepare_params(body).await;
+        body.validate_contents(self.params_model_spec(body).await)?;
+        let mut cache_key = None;
+        if let Some(cache) = self.response_cache().filter(|_| body.stream != Some(true)) {
+            match cache.lookup(body).await {
+                Ok(response) => return Ok(response),
+                Err(_) if body.bypass_response_cache => {}
+                Err(key) => cache_key = Some((cache, key)),
+            }
+        }
+        let raw = self.send_message_raw(body).await?;
+        let response = parse_message(&raw)?;
+        if let Some((cache, key)) = cache_key {
+            cache.store(key, &response).await;
+        }
+        Ok(response)
     }
 
     /// Counts the number of tokens in a message
@@ -104,7 +133,10 @@ impl MessageClient for AnthropicClient {
         &'a self,
         body: Option<&'a CountMessageTokensParams>,
     ) -> Result<CountMessageTokensResponse, MessageError> {
-        self.post("/messages/count_tokens", body).await
+        let betas = body.map(|b| b.betas()).unwrap_or_default();
+        let betas: Vec<&str> = betas.iter().map(Beta::as_str).collect();
+        self.post_with_betas("/messages/count_tokens", body, &betas)
+            .await
     }
 
     /// Creates a message with streaming enabled
@@ -121,6 +153,7 @@ impl MessageClient for AnthropicClient {
     /// # Errors
     ///
     /// Returns a `MessageError` if:
+    /// - The parameters fail client-side validation
     /// - The request fails to send
     async fn create_message_streaming<'a>(
         &'a self,
@@ -132,41 +165,55 @@ impl MessageClient for AnthropicClient {
                 "Stream parameter must be set to true for streaming".to_string(),
             ));
         }
+        let body = &*self.prepare_params(body).await;
 
-        let url = format!("{}/messages", AnthropicClient::DEFAULT_API_BASE_URL);
+        body.validate_contents(self.params_model_spec(body).await)?;
+        let request_body = body.request_body()?;
 
-        let client = &self.get_client();
-        let request = client
-            .request(reqwest::Method::POST, &url)
-            .header(
-                "x-api-key",
-                HeaderValue::from_str(self.get_api_key()).unwrap(),
-            )
-            .header(
-                "anthropic-version",
-                HeaderValue::from_str(self.get_api_version()).unwrap(),
+        let rate_limit = self.acquire_rate_limit(body).await;
+        let betas = body.betas();
+        let betas: Vec<&str> = betas.iter().map(Beta::as_str).collect();
+        let sent = self
+            .send_body_response(
+                reqwest::Method::POST,
+                "/messages",
+                request_body.into(),
+                "application/json",
+                &betas,
             )
-            .json(body);
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| MessageError::RequestFailed(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.map_err(|e| {
-                MessageError::RequestFailed(format!("Failed to read error response: {}", e))
-            })?;
-            return Err(MessageError::ApiError(error_text));
-        }
+            .await;
+        let (response, permit) = match sent {
+            Ok(sent) => sent,
+            Err(send_error) => {
+                let error = MessageError::RequestFailed(send_error.to_string());
+                release_unsent(rate_limit, &send_error, &error);
+                return Err(error);
+            }
+        };
+        let response = match error_status(response).await {
+            Ok(response) => response,
+            Err(error) => {
+                if let Some((limiter, permit)) = rate_limit {
+                    limiter.release(permit, &error);
+                }
+                return Err(error);
+            }
+        };
+        let mut usage = rate_limit.map(|(limiter, permit)| StreamUsageRecorder {
+            limiter,
+            permit: Some(permit),
+            usage: None,
+        });
 
         // Get the bytes stream and convert it to EventSource stream
         let bytes_stream = response.bytes_stream();
         let event_stream = bytes_stream.eventsource();
 
         // Map SSE events to our StreamEvent type
-        Ok(event_stream.map(|event_result| {
-            event_result
+        // The permit is held until the stream is dropped
+        Ok(event_stream.map(move |event_result| {
+            let _permit = &permit;
+            let event = event_result
                 .map_err(|e| MessageError::RequestFailed(e.to_string()))
                 .and_then(|event| {
                     serde_json::from_str::<StreamEvent>(&event.data).map_err(|e| {
@@ -175,7 +222,306 @@ impl MessageClient for AnthropicClient {
                             e, event.data
                         ))
                     })
-                })
+                });
+            if let (Some(usage), Ok(event)) = (&mut usage, &event) {
+                usage.observe(event);
+            }
+            event
         }))
     }
 }
+
+impl AnthropicClient {
+    /// Creates a message and returns the response body as untyped JSON
+    ///
+    /// Use this when the response contains fields the SDK's types do not
+    /// support yet. The parameters are validated and the request is rate
+    /// limited as with
+    /// [`create_message`](MessageClient::create_message), but the response
+    /// cache is not used.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageError` if the parameters fail validation, the
+    /// request fails or the API returns an error response.
+    pub async fn create_message_raw(
+        &self,
+        params: &CreateMessageParams,
+    ) -> Result<serde_json::Value, MessageError> {
+        let raw = self.create_message_raw_body(params).await?;
+        serde_json::from_str(raw.get())
+            .map_err(|e| MessageError::ApiError(format!("JSON parsing error: {}", e)))
+    }
+
+    /// Creates a message and returns both the typed response and the untouched response body
+    ///
+    /// # Errors
+    ///
+    /// In addition to the errors of
+    /// [`create_message_raw`](Self::create_message_raw), returns a
+    /// `MessageError` if the body does not parse as a
+    /// [`CreateMessageResponse`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, MessageError, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let path = std::env::temp_dir().join(format!("raw-{}.json", std::process::id()));
+    /// let body = r#"{"content": [{"type": "text", "text": "Hi"}], "id": "msg_01",
+    ///     "model": "claude-sonnet-4-5", "role": "assistant", "type": "message",
+    ///     "stop_reason": "end_turn", "usage": {"input_tokens": 9, "output_tokens": 2},
+    ///     "new_field": {"nested": [1, 2]}}"#;
+    /// let interaction = Interaction {
+    ///     request: RecordedRequest {
+    ///         method: "POST".to_string(),
+    ///         path: "/messages".to_string(),
+    ///         headers: Vec::new(),
+    ///         body: String::new(),
+    ///     },
+    ///     response: RecordedResponse::json(200, body),
+    /// };
+    /// Cassette {
+    ///     interactions: vec![interaction; 2],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path)
+    ///     .mode(VcrMode::Replay)
+    ///     .matcher(RequestMatcher::new().body(false))
+    ///     .start()
+    ///     .await?;
+    ///
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<MessageError>()?;
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-5".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hello")],
+    ///     max_tokens: 1024,
+    /// });
+    ///
+    /// let (response, raw) = client.create_message_with_raw(&params).await?;
+    /// assert_eq!(response.text(), "Hi");
+    /// assert_eq!(raw.get(), body);
+    ///
+    /// let value = client.create_message_raw(&params).await?;
+    /// assert_eq!(value["new_field"]["nested"][1], 2);
+    /// # server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_message_with_raw(
+        &self,
+        params: &CreateMessageParams,
+    ) -> Result<(CreateMessageResponse, Box<RawValue>), MessageError> {
+        let raw = self.create_message_raw_body(params).await?;
+        Ok((parse_message(&raw)?, raw))
+    }
+
+    /// Validate `params` and send them, returning the response body
+    async fn create_message_raw_body(
+        &self,
+        params: &CreateMessageParams,
+    ) -> Result<Box<RawValue>, MessageError> {
+        let params = &*self.prepare_params(params).await;
+        params.validate_contents(self.params_model_spec(params).await)?;
+        self.send_message_raw(params).await
+    }
+
+    /// Send validated `params`, rate limited if enabled, and return the response body
+    async fn send_message_raw(
+        &self,
+        params: &CreateMessageParams,
+    ) -> Result<Box<RawValue>, MessageError> {
+        let body = params.request_body()?;
+        let rate_limit = self.acquire_rate_limit(params).await;
+        let betas = params.betas();
+        let betas: Vec<&str> = betas.iter().map(Beta::as_str).collect();
+        let sent = self
+            .send_body_response(
+                reqwest::Method::POST,
+                "/messages",
+                body.into(),
+                "application/json",
+                &betas,
+            )
+            .await;
+        let (response, _permit) = match sent {
+            Ok(sent) => sent,
+            Err(send_error) => {
+                let error = MessageError::from(send_error.to_string());
+                release_unsent(rate_limit, &send_error, &error);
+                return Err(error);
+            }
+        };
+        let result: Result<Box<RawValue>, MessageError> = Self::parse_response(response).await;
+        if let Some((limiter, permit)) = rate_limit {
+            match &result {
+                Ok(raw) => match serde_json::from_str::<ResponseUsage>(raw.get()) {
+                    Ok(ResponseUsage { usage }) => limiter.record(permit, &usage),
+                    Err(_) => {
+                        let input_tokens = permit.input_tokens();
+                        limiter.record_tokens(permit, input_tokens, 0)
+                    }
+                },
+                Err(error) => limiter.release(permit, error),
+            }
+        }
+        result
+    }
+
+    /// Model limits to validate `params` against
+    ///
+    /// Limits set on the parameters take precedence over those of the model.
+    async fn params_model_spec(&self, params: &CreateMessageParams) -> Option<ModelSpec> {
+        match params.model_spec {
+            Some(spec) => Some(spec),
+            None => self.get_model_spec(&params.model).await,
+        }
+    }
+
+    /// `params` with `max_tokens` filled in, if it is 0 and the client has a
+    /// default, with roles normalized, if enabled, and with the breakpoints of
+    /// their auto cache strategy
+    async fn prepare_params<'p>(
+        &self,
+        params: &'p CreateMessageParams,
+    ) -> Cow<'p, CreateMessageParams> {
+        let mut params = Cow::Borrowed(params);
+        if let Some(choice) = self.default_max_tokens().filter(|_| params.max_tokens == 0) {
+            let spec = self.params_model_spec(&params).await;
+            params.to_mut().fill_max_tokens(choice, spec);
+        }
+        if self.normalize_roles() && validate_roles(&params.messages).is_err() {
+            let messages = std::mem::take(&mut params.to_mut().messages);
+            let (messages, merges) = normalize_messages_with_report(messages);
+            params.to_mut().messages = messages;
+            self.report_role_merges(&merges);
+        }
+        if let Some(cached) = apply_auto_cache(&params) {
+            params = Cow::Owned(cached);
+        }
+        params
+    }
+
+    /// Wait for the rate limiter, if enabled, to let `params` be sent
+    async fn acquire_rate_limit(
+        &self,
+        params: &CreateMessageParams,
+    ) -> Option<(&RateLimiter, RateLimitPermit<'_>)> {
+        let limiter = self.rate_limiter()?;
+        let input_tokens = match limiter.input_token_estimate() {
+            InputTokenEstimate::Offline => estimate_request_tokens(params),
+            InputTokenEstimate::CountTokens => self
+                .count_tokens(Some(&CountMessageTokensParams::from(params)))
+                .await
+                .map_or_else(
+                    |_| estimate_request_tokens(params),
+                    |count| count.input_tokens,
+                ),
+        };
+        Some((limiter, limiter.acquire(&params.model, input_tokens).await))
+    }
+}
+
+/// `response`, or the API error it carries if its status is not a success
+async fn error_status(response: reqwest::Response) -> Result<reqwest::Response, MessageError> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status().as_u16();
+    let retry_after = retry_after(response.headers());
+    let error_text = response.text().await.map_err(|e| {
+        MessageError::RequestFailed(format!("Failed to read error response: {}", e))
+    })?;
+    Err(MessageError::from(
+        ApiErrorResponse::new(status, error_text).with_retry_after(retry_after),
+    ))
+}
+
+/// Settle the rate limit permit of a request that failed to send with `send_error`
+///
+/// The capacity is only given back if the connection failed, as the API then
+/// never saw the request; otherwise the permit is released with `error`.
+fn release_unsent(
+    rate_limit: Option<(&RateLimiter, RateLimitPermit<'_>)>,
+    send_error: &reqwest::Error,
+    error: &MessageError,
+) {
+    if let Some((limiter, permit)) = rate_limit {
+        if send_error.is_connect() {
+            drop(permit);
+        } else {
+            limiter.release(permit, error);
+        }
+    }
+}
+
+/// Parse a response body as a [`CreateMessageResponse`]
+fn parse_message(raw: &RawValue) -> Result<CreateMessageResponse, MessageError> {
+    serde_json::from_str(raw.get()).map_err(|e| {
+        MessageError::ApiError(format!(
+            "JSON parsing error: {}. Response body: {}",
+            e,
+            raw.get()
+        ))
+    })
+}
+
+/// The usage of a response body
+#[derive(Deserialize)]
+struct ResponseUsage {
+    usage: Usage,
+}
+
+/// Records the usage reported by a stream with the rate limiter
+///
+/// The usage is recorded when the stream is dropped, with the estimate
+/// standing in for input tokens if the stream ended before `message_start`.
+struct StreamUsageRecorder<'a> {
+    limiter: &'a RateLimiter,
+    permit: Option<RateLimitPermit<'a>>,
+    /// Usage reported so far, once `message_start` was received
+    usage: Option<Usage>,
+}
+
+impl StreamUsageRecorder<'_> {
+    fn observe(&mut self, event: &StreamEvent) {
+        match event {
+            StreamEvent::MessageStart { message } => self.usage = Some(message.usage.clone()),
+            StreamEvent::MessageDelta {
+                usage: Some(delta), ..
+            } => {
+                if let Some(usage) = &mut self.usage {
+                    usage.apply_delta(delta);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Drop for StreamUsageRecorder<'_> {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            let (input_tokens, output_tokens) = match &self.usage {
+                Some(usage) => (
+                    usage.input_tokens + usage.cache_creation_input_tokens(),
+                    usage.output_tokens,
+                ),
+                None => (permit.input_tokens(), 0),
+            };
+            self.limiter
+                .record_tokens(permit, input_tokens, output_tokens);
+        }
+    }
+}
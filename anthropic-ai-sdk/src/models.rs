@@ -4,8 +4,17 @@
 //! It provides functionality for listing available models and their capabilities.
 
 use crate::client::AnthropicClient;
-use crate::types::model::{ListModelsParams, ListModelsResponse, Model, ModelClient, ModelError};
+use crate::types::model::{
+    ListModelsParams, ListModelsResponse, Model, ModelClient, ModelError, ModelInfo, ModelSpec,
+};
+use crate::types::pagination::Paginator;
 use async_trait::async_trait;
+use futures_util::{Stream, TryStreamExt};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
 
 #[async_trait]
 impl ModelClient for AnthropicClient {
@@ -100,8 +109,339 @@ impl ModelClient for AnthropicClient {
     ///     Ok(())
     /// }
     /// ```
-    async fn get_model<'a>(&'a self, model_id: &'a str) -> Result<Model, ModelError> {
+    async fn get_model<'a>(&'a self, model_id: &'a str) -> Result<ModelInfo, ModelError> {
         self.get(&format!("/models/{}", model_id), Option::<&()>::None)
             .await
     }
+
+    /// Lists models page by page
+    ///
+    /// Starts at the page selected by `params` and follows the pagination
+    /// cursors until there are no more results. Paging goes backwards when
+    /// `before_id` is set and forwards otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional parameters for the first page
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of pages; it ends after the last page or the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::model::{ListModelsParams, ModelClient, ModelError};
+    /// use futures_util::TryStreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), ModelError> {
+    ///     let client = AnthropicClient::new::<ModelError>("your-api-key", "2023-06-01")?;
+    ///
+    ///     let params = ListModelsParams::new().limit(20);
+    ///     let pages = client.list_models_pages(Some(&params));
+    ///     futures_util::pin_mut!(pages);
+    ///     while let Some(page) = pages.try_next().await? {
+    ///         for model in page.data {
+    ///             println!("Model: {} ({})", model.display_name, model.id);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn list_models_pages<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+    ) -> impl Stream<Item = Result<ListModelsResponse, ModelError>> + Send + 'a {
+        Paginator::new(
+            params.cloned().unwrap_or_default(),
+            move |params| async move { self.list_models(Some(&params)).await },
+        )
+        .pages()
+    }
+
+    /// Lists all models, following pagination to the end
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional parameters for the first page
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ModelError` if any page fails to load.
+    async fn list_all_models<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+    ) -> Result<Vec<ModelInfo>, ModelError> {
+        let pages: Vec<ListModelsResponse> = self.list_models_pages(params).try_collect().await?;
+        Ok(pages.into_iter().flat_map(|page| page.data).collect())
+    }
+
+    /// Resolves a model alias to the concrete model id
+    ///
+    /// Aliases such as `claude-3-7-sonnet-latest` resolve to their dated
+    /// snapshot; concrete ids resolve to themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The model alias or id to resolve
+    ///
+    /// # Errors
+    ///
+    /// Returns `ModelError::NotFound` if the model does not exist, or another
+    /// `ModelError` if the request fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::model::{ModelClient, ModelError};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), ModelError> {
+    ///     let client = AnthropicClient::new::<ModelError>("your-api-key", "2023-06-01")?;
+    ///
+    ///     match client.resolve_alias("claude-3-7-sonnet-latest").await {
+    ///         Ok(id) => println!("Using model {id}"),
+    ///         Err(ModelError::NotFound(message)) => eprintln!("Unknown model: {message}"),
+    ///         Err(e) => return Err(e),
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn resolve_alias<'a>(&'a self, alias: &'a str) -> Result<String, ModelError> {
+        if self.model_cache().is_some() {
+            if let Ok(models) = self.cached_models().await {
+                if models.iter().any(|model| model.id == alias) {
+                    return Ok(alias.to_string());
+                }
+            }
+        }
+        Ok(self.get_model(alias).await?.id)
+    }
+
+    /// Lists all models, through the client's model cache
+    ///
+    /// With the cache enabled (see
+    /// [`AnthropicClientBuilder::with_model_cache`](crate::client::AnthropicClientBuilder::with_model_cache)),
+    /// the first call fetches the list and later calls reuse it until the
+    /// cache expires. Concurrent calls wait for a single fetch, and a failed
+    /// fetch is returned again for 30 seconds instead of being retried.
+    /// Without the cache, every call fetches the list. Also available as
+    /// [`client.models().cached()`](Models::cached).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ModelError` if the list has to be fetched and any page fails to load.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::model::{ModelClient, ModelError};
+    ///
+    /// # async fn example() -> Result<(), ModelError> {
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_model_cache()
+    ///     .build::<ModelError>()?;
+    /// let models = client.cached_models().await?;
+    /// println!("{} models", models.len());
+    /// // Served from the cache
+    /// let models = client.models().cached().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn cached_models(&self) -> Result<Arc<Vec<ModelInfo>>, ModelError> {
+        match self.model_cache() {
+            Some(cache) => cache.get_or_fetch(|| self.list_all_models(None)).await,
+            None => Ok(Arc::new(self.list_all_models(None).await?)),
+        }
+    }
+
+    /// Limits of a model, for validating requests
+    ///
+    /// With the model cache enabled, the limits reported by the Models API
+    /// are used when available. Otherwise, or when the list cannot be
+    /// fetched, the SDK's built-in table is used. Returns `None` for models
+    /// that are in neither.
+    async fn get_model_spec<'a>(&'a self, model: &'a str) -> Option<ModelSpec> {
+        if self.model_cache().is_some() {
+            if let Ok(models) = self.cached_models().await {
+                let spec = models
+                    .iter()
+                    .find(|info| info.id == model)
+                    .and_then(ModelInfo::spec);
+                if spec.is_some() {
+                    return spec;
+                }
+            }
+        }
+        Model::from(model).spec()
+    }
+}
+
+impl AnthropicClient {
+    /// Models API access through the client's model cache
+    pub fn models(&self) -> Models<'_> {
+        Models { client: self }
+    }
+}
+
+/// Models API access through the client's model cache, from [`AnthropicClient::models`]
+///
+/// # Examples
+///
+/// A failed fetch is not repeated by the next caller:
+///
+/// ```
+/// use anthropic_ai_sdk::client::AnthropicClient;
+/// use anthropic_ai_sdk::testing::vcr::{
+///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+/// };
+/// use anthropic_ai_sdk::types::model::ModelError;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::env::temp_dir().join(format!("models-cache-{}.json", std::process::id()));
+/// let error = r#"{"type": "error", "error": {"type": "permission_error", "message": "Denied"}}"#;
+/// Cassette {
+///     interactions: vec![Interaction {
+///         request: RecordedRequest {
+///             method: "GET".to_string(),
+///             path: "/models".to_string(),
+///             headers: Vec::new(),
+///             body: String::new(),
+///         },
+///         response: RecordedResponse::json(403, error),
+///     }],
+/// }
+/// .save(&path)?;
+/// let server = Vcr::new(&path)
+///     .mode(VcrMode::Replay)
+///     .matcher(RequestMatcher::new().path(false).body(false))
+///     .start()
+///     .await?;
+/// let client = AnthropicClient::builder("test-key", "2023-06-01")
+///     .with_api_base_url(server.url())
+///     .with_model_cache()
+///     .build::<ModelError>()?;
+///
+/// assert!(client.models().cached().await.is_err());
+/// // Served from the cache, the cassette has no second response
+/// assert!(client.models().cached().await.is_err());
+/// assert!(client.models().spec("claude-sonnet-4-5").await.is_some());
+/// server.finish().await?;
+/// # std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Models<'a> {
+    client: &'a AnthropicClient,
+}
+
+impl Models<'_> {
+    /// All models, see [`ModelClient::cached_models`]
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ModelError` if the list has to be fetched and any page fails to load.
+    pub async fn cached(&self) -> Result<Arc<Vec<ModelInfo>>, ModelError> {
+        self.client.cached_models().await
+    }
+
+    /// Limits of a model, see [`ModelClient::get_model_spec`]
+    pub async fn spec(&self, model: &str) -> Option<ModelSpec> {
+        self.client.get_model_spec(model).await
+    }
+}
+
+/// Cache of the Models API list
+///
+/// A single caller fetches the list while concurrent callers wait for its
+/// result; no lock is held during the fetch. A failed fetch is remembered
+/// for [`ModelCache::ERROR_TTL`], so callers fall back without retrying the
+/// request in the meantime.
+pub(crate) struct ModelCache {
+    ttl: Duration,
+    state: Mutex<CacheState>,
+}
+
+/// Result of a fetch of the Models API list
+type Fetched = Result<Arc<Vec<ModelInfo>>, ModelError>;
+
+#[derive(Default)]
+struct CacheState {
+    entry: Option<(Instant, Fetched)>,
+    /// Closed when the fetch in progress ends, successfully or not
+    fetching: Option<watch::Receiver<()>>,
+}
+
+impl ModelCache {
+    /// How long a failed fetch is remembered
+    pub(crate) const ERROR_TTL: Duration = Duration::from_secs(30);
+
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// The cached list, fetched with `fetch` if missing or expired
+    pub(crate) async fn get_or_fetch<F, Fut>(
+        &self,
+        fetch: F,
+    ) -> Result<Arc<Vec<ModelInfo>>, ModelError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<ModelInfo>, ModelError>>,
+    {
+        let _fetching = loop {
+            let mut waiting = {
+                let mut state = self.lock();
+                if let Some((fetched_at, result)) = &state.entry {
+                    let ttl = if result.is_ok() {
+                        self.ttl
+                    } else {
+                        Self::ERROR_TTL
+                    };
+                    if fetched_at.elapsed() < ttl {
+                        return result.clone();
+                    }
+                }
+                match &state.fetching {
+                    // A fetch whose caller was dropped has closed its channel
+                    Some(fetching) if fetching.has_changed().is_ok() => fetching.clone(),
+                    _ => {
+                        let (sender, receiver) = watch::channel(());
+                        state.fetching = Some(receiver);
+                        break sender;
+                    }
+                }
+            };
+            // Only fails once the fetch has ended
+            let _ = waiting.changed().await;
+        };
+        let result = fetch().await.map(Arc::new);
+        let mut state = self.lock();
+        state.entry = Some((Instant::now(), result.clone()));
+        state.fetching = None;
+        result
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl std::fmt::Debug for ModelCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModelCache")
+            .field("ttl", &self.ttl)
+            .finish()
+    }
 }
@@ -0,0 +1,299 @@
+//! Mock Messages API client
+//!
+//! This module contains [`MockMessageClient`], a [`MessageClient`] answering
+//! from a script of canned responses and recording every request.
+//!
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use futures_util::Stream;
+
+use crate::testing::StreamScenario;
+use crate::types::estimate::{estimate_message_tokens, estimate_tokens};
+use crate::types::message::{
+    ContentBlock, CountMessageTokensParams, CountMessageTokensResponse, CreateMessageParams,
+    CreateMessageResponse, MessageClient, MessageDeltaContent, MessageError, Role, StopReason,
+    StreamEvent, Usage,
+};
+
+/// A [`MessageClient`] answering from a script, for unit tests
+///
+/// Responses are queued with the builder methods and returned in order, by
+/// both [`create_message`](MessageClient::create_message) and
+/// [`create_message_streaming`](MessageClient::create_message_streaming),
+/// which streams the events that would have produced the response. Every
+/// request is recorded for later assertions. Calls are numbered from 1 in
+/// the order they are made; `count_tokens` calls are not numbered and answer
+/// with an [estimate](crate::types::estimate) unless a count is set.
+///
+/// A call without a queued response panics, failing the test.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageAccumulator, MessageClient, MessageError,
+///     RequiredMessageParams, Role, StopReason,
+/// };
+/// use futures_util::StreamExt;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let client = MockMessageClient::new()
+///     .tool_use_response("get_weather", serde_json::json!({"city": "Paris"}))
+///     .fail_on_call(2, MessageError::ApiError("overloaded".to_string()))
+///     .text_response("It is sunny in Paris.")
+///     .expect_calls(3);
+///
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-3-7-sonnet-20250219".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Weather in Paris?")],
+///     max_tokens: 1024,
+/// });
+///
+/// let response = client.create_message(Some(&params)).await.unwrap();
+/// assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+/// assert!(client.create_message(Some(&params)).await.is_err());
+/// let response = client.create_message(Some(&params)).await.unwrap();
+/// assert_eq!(response.text(), "It is sunny in Paris.");
+///
+/// assert_eq!(client.requests().len(), 3);
+/// assert_eq!(client.last_request().unwrap().messages[0].text(), Some("Weather in Paris?"));
+///
+/// // Streamed responses accumulate to the queued response
+/// let streaming = MockMessageClient::new().text_response("Hi");
+/// let events = streaming.create_message_streaming(&params).await.unwrap();
+/// let mut accumulator = MessageAccumulator::new();
+/// for event in events.collect::<Vec<_>>().await {
+///     accumulator.push(&event.unwrap()).unwrap();
+/// }
+/// assert_eq!(accumulator.finish().unwrap().text(), "Hi");
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MockMessageClient {
+    state: Mutex<MockState>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    responses: VecDeque<Result<CreateMessageResponse, MessageError>>,
+    failures: HashMap<usize, MessageError>,
+    requests: Vec<CreateMessageParams>,
+    count_requests: Vec<CountMessageTokensParams>,
+    token_count: Option<u32>,
+    expected_calls: Option<usize>,
+}
+
+impl MockMessageClient {
+    /// Create a client without any queued response
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response
+    pub fn response(self, response: CreateMessageResponse) -> Self {
+        self.with_state(|state| state.responses.push_back(Ok(response)))
+    }
+
+    /// Queue a response with a single text block, ending the turn
+    pub fn text_response(self, text: impl Into<String>) -> Self {
+        self.response(mock_response(
+            vec![ContentBlock::text(text)],
+            StopReason::EndTurn,
+        ))
+    }
+
+    /// Queue a response with a single text block, cut off by `max_tokens`
+    pub fn truncated_response(self, text: impl Into<String>) -> Self {
+        self.response(mock_response(
+            vec![ContentBlock::text(text)],
+            StopReason::MaxTokens,
+        ))
+    }
+
+    /// Queue a response calling a tool with the given input
+    ///
+    /// The tool use gets a generated ID; find it in the response to answer it.
+    pub fn tool_use_response(self, name: impl Into<String>, input: serde_json::Value) -> Self {
+        let (block, _) = ContentBlock::tool_use(name, input);
+        self.response(mock_response(vec![block], StopReason::ToolUse))
+    }
+
+    /// Queue an error in place of a response
+    pub fn error(self, error: MessageError) -> Self {
+        self.with_state(|state| state.responses.push_back(Err(error)))
+    }
+
+    /// Fail call number `call`, counted from 1, with `error`
+    ///
+    /// The failing call does not consume a queued response.
+    pub fn fail_on_call(self, call: usize, error: MessageError) -> Self {
+        self.with_state(|state| {
+            state.failures.insert(call, error);
+        })
+    }
+
+    /// Answer every `count_tokens` request with `input_tokens`
+    pub fn token_count(self, input_tokens: u32) -> Self {
+        self.with_state(|state| state.token_count = Some(input_tokens))
+    }
+
+    /// Assert when the client is dropped that exactly `calls` calls were made
+    pub fn expect_calls(self, calls: usize) -> Self {
+        self.with_state(|state| state.expected_calls = Some(calls))
+    }
+
+    /// Parameters of every message request so far, in order
+    pub fn requests(&self) -> Vec<CreateMessageParams> {
+        self.state().requests.clone()
+    }
+
+    /// Parameters of the latest message request
+    pub fn last_request(&self) -> Option<CreateMessageParams> {
+        self.state().requests.last().cloned()
+    }
+
+    /// Parameters of every `count_tokens` request so far, in order
+    pub fn count_requests(&self) -> Vec<CountMessageTokensParams> {
+        self.state().count_requests.clone()
+    }
+
+    /// Number of message requests so far
+    pub fn call_count(&self) -> usize {
+        self.state().requests.len()
+    }
+
+    /// Number of queued responses not returned yet
+    pub fn remaining(&self) -> usize {
+        self.state().responses.len()
+    }
+
+    fn with_state(self, f: impl FnOnce(&mut MockState)) -> Self {
+        f(&mut self.state());
+        self
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        // A panicking test must not hide the state from the assertions of others
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Record a request and take its scripted outcome
+    fn call(&self, params: &CreateMessageParams) -> Result<CreateMessageResponse, MessageError> {
+        let mut state = self.state();
+        state.requests.push(params.clone());
+        let call = state.requests.len();
+        if let Some(error) = state.failures.remove(&call) {
+            return Err(error);
+        }
+        match state.responses.pop_front() {
+            Some(outcome) => outcome,
+            None => panic!("MockMessageClient has no response queued for call {call}"),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageClient for MockMessageClient {
+    async fn create_message<'a>(
+        &'a self,
+        params: Option<&'a CreateMessageParams>,
+    ) -> Result<CreateMessageResponse, MessageError> {
+        self.call(&params.cloned().unwrap_or_default())
+    }
+
+    async fn count_tokens<'a>(
+        &'a self,
+        params: Option<&'a CountMessageTokensParams>,
+    ) -> Result<CountMessageTokensResponse, MessageError> {
+        let params = params.cloned().unwrap_or_default();
+        let mut state = self.state();
+        let input_tokens = state.token_count.unwrap_or_else(|| {
+            params
+                .system
+                .as_ref()
+                .map_or(0, |system| estimate_tokens(&system.text()))
+                + params
+                    .messages
+                    .iter()
+                    .map(estimate_message_tokens)
+                    .sum::<u32>()
+        });
+        state.count_requests.push(params);
+        Ok(CountMessageTokensResponse { input_tokens })
+    }
+
+    async fn create_message_streaming<'a>(
+        &'a self,
+        body: &'a CreateMessageParams,
+    ) -> Result<impl Stream<Item = Result<StreamEvent, MessageError>> + 'a, MessageError> {
+        let response = self.call(body)?;
+        Ok(futures_util::stream::iter(
+            response_events(response).into_iter().map(Ok),
+        ))
+    }
+}
+
+impl Drop for MockMessageClient {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let state = self.state();
+        if let Some(expected) = state.expected_calls {
+            assert_eq!(
+                state.requests.len(),
+                expected,
+                "MockMessageClient expected {} calls, got {}",
+                expected,
+                state.requests.len()
+            );
+        }
+    }
+}
+
+/// A response with the given content and plausible usage
+fn mock_response(content: Vec<ContentBlock>, stop_reason: StopReason) -> CreateMessageResponse {
+    let output_tokens = content
+        .iter()
+        .map(|block| match block {
+            ContentBlock::Text { text, .. } => estimate_tokens(text),
+            ContentBlock::ToolUse { name, input, .. } => {
+                estimate_tokens(name) + estimate_tokens(&input.to_string())
+            }
+            _ => 0,
+        })
+        .sum::<u32>()
+        .max(1);
+    CreateMessageResponse {
+        content,
+        id: "msg_mock".to_string(),
+        model: "claude-mock".to_string(),
+        role: Role::Assistant,
+        stop_reason: Some(stop_reason),
+        stop_sequence: None,
+        type_: "message".to_string(),
+        usage: Usage {
+            input_tokens: 10,
+            output_tokens,
+            ..Default::default()
+        },
+        container: None,
+        context_management: None,
+    }
+}
+
+/// The stream events the API would send for `response`
+fn response_events(response: CreateMessageResponse) -> Vec<StreamEvent> {
+    StreamScenario::from_response(&response).events(MessageDeltaContent {
+        stop_reason: response.stop_reason,
+        stop_sequence: response.stop_sequence,
+        container: response.container,
+        context_management: response.context_management,
+    })
+}
@@ -0,0 +1,13 @@
+//! Test helpers
+//!
+//! This module contains helpers for testing code built on the SDK without
+//! calling the API: [`MockMessageClient`] for unit tests, [`StreamScenario`]
+//! for building stream events and [`vcr`] for integration tests replaying
+//! recorded API traffic.
+//!
+pub mod mock;
+pub mod stream;
+pub mod vcr;
+
+pub use mock::MockMessageClient;
+pub use stream::StreamScenario;
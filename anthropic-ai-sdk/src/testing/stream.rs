@@ -0,0 +1,419 @@
+//! Stream event fixtures
+//!
+//! This module contains [`StreamScenario`], which builds the events of a
+//! streamed response with the bookkeeping of the API: a `message_start`
+//! first, numbered content blocks each opened and closed once, and a
+//! `message_delta` with the stop reason and usage before `message_stop`.
+//!
+use futures_util::Stream;
+
+use crate::types::estimate::estimate_tokens;
+use crate::types::message::{
+    ContentBlock, ContentBlockDelta, CreateMessageResponse, MessageDeltaContent, MessageError,
+    MessageStartContent, Role, StopReason, StreamEvent, StreamUsage, Usage,
+};
+
+/// Bytes of tool input JSON sent per `input_json_delta`
+const JSON_CHUNK_SIZE: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+enum ScenarioBlock {
+    Text(Vec<String>),
+    Thinking(Vec<String>, String),
+    ToolUse {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+    Other(ContentBlock),
+}
+
+/// Builder of a plausible sequence of stream events
+///
+/// Consecutive [`text`](Self::text) calls become deltas of the same text
+/// block, as do consecutive [`thinking`](Self::thinking) calls. Tool input is
+/// split into several `input_json_delta` events, as the API does. Output
+/// usage is [estimated](crate::types::estimate) from the content.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::StreamScenario;
+/// use anthropic_ai_sdk::types::message::{MessageAccumulator, StopReason, StreamEvent};
+///
+/// let events = StreamScenario::new()
+///     .text("Hello ")
+///     .text("world")
+///     .tool_use("get_weather", serde_json::json!({"city": "Paris", "unit": "celsius"}))
+///     .finish(StopReason::ToolUse);
+///
+/// assert!(matches!(events[0], StreamEvent::MessageStart { .. }));
+/// assert!(matches!(events.last(), Some(StreamEvent::MessageStop)));
+///
+/// let mut accumulator = MessageAccumulator::new();
+/// for event in &events {
+///     accumulator.push(event).unwrap();
+/// }
+/// let response = accumulator.finish().unwrap();
+/// assert_eq!(response.text(), "Hello world");
+/// assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+/// let tool_use = response.tool_uses().next().unwrap();
+/// assert_eq!(tool_use.name, "get_weather");
+/// assert_eq!(tool_use.input["unit"], "celsius");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamScenario {
+    id: String,
+    model: String,
+    usage: Usage,
+    output_tokens: Option<u32>,
+    blocks: Vec<ScenarioBlock>,
+}
+
+impl Default for StreamScenario {
+    fn default() -> Self {
+        Self {
+            id: "msg_mock".to_string(),
+            model: "claude-mock".to_string(),
+            usage: Usage {
+                input_tokens: 10,
+                ..Default::default()
+            },
+            output_tokens: None,
+            blocks: Vec::new(),
+        }
+    }
+}
+
+impl StreamScenario {
+    /// Start a scenario without content
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The scenario streaming the content of `response`
+    pub fn from_response(response: &CreateMessageResponse) -> Self {
+        let scenario = Self {
+            id: response.id.clone(),
+            model: response.model.clone(),
+            usage: response.usage.clone(),
+            output_tokens: Some(response.usage.output_tokens),
+            blocks: Vec::new(),
+        };
+        response
+            .content
+            .iter()
+            .cloned()
+            .fold(scenario, |scenario, block| scenario.push(block))
+    }
+
+    /// Set the message ID
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Set the model
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Set the input tokens reported by `message_start`
+    pub fn input_tokens(mut self, input_tokens: u32) -> Self {
+        self.usage.input_tokens = input_tokens;
+        self
+    }
+
+    /// Set the output tokens reported by `message_delta` instead of estimating them
+    pub fn output_tokens(mut self, output_tokens: u32) -> Self {
+        self.output_tokens = Some(output_tokens);
+        self
+    }
+
+    /// Stream a chunk of text
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        match self.blocks.last_mut() {
+            Some(ScenarioBlock::Text(chunks)) => chunks.push(text.into()),
+            _ => self.blocks.push(ScenarioBlock::Text(vec![text.into()])),
+        }
+        self
+    }
+
+    /// Stream a chunk of extended thinking
+    pub fn thinking(mut self, thinking: impl Into<String>) -> Self {
+        match self.blocks.last_mut() {
+            Some(ScenarioBlock::Thinking(chunks, _)) => chunks.push(thinking.into()),
+            _ => self.blocks.push(ScenarioBlock::Thinking(
+                vec![thinking.into()],
+                "sig_mock".to_string(),
+            )),
+        }
+        self
+    }
+
+    /// Stream a tool use block with a generated ID
+    pub fn tool_use(self, name: impl Into<String>, input: serde_json::Value) -> Self {
+        let (block, _) = ContentBlock::tool_use(name, input);
+        self.push(block)
+    }
+
+    /// Stream a content block
+    ///
+    /// Text, thinking and tool use blocks are streamed as deltas, other
+    /// blocks arrive whole in their `content_block_start` event.
+    pub fn block(self, block: ContentBlock) -> Self {
+        self.push(block)
+    }
+
+    fn push(mut self, block: ContentBlock) -> Self {
+        let block = match block {
+            ContentBlock::Text { text, .. } => ScenarioBlock::Text(vec![text]),
+            ContentBlock::Thinking {
+                thinking,
+                signature,
+            } => ScenarioBlock::Thinking(vec![thinking], signature),
+            ContentBlock::ToolUse { id, name, input } => ScenarioBlock::ToolUse { id, name, input },
+            block => ScenarioBlock::Other(block),
+        };
+        self.blocks.push(block);
+        self
+    }
+
+    /// End the message with `stop_reason` and return its events
+    pub fn finish(self, stop_reason: StopReason) -> Vec<StreamEvent> {
+        self.events(MessageDeltaContent {
+            stop_reason: Some(stop_reason),
+            stop_sequence: None,
+            container: None,
+            context_management: None,
+        })
+    }
+
+    /// End the message with `delta` and return its events
+    pub(crate) fn events(self, delta: MessageDeltaContent) -> Vec<StreamEvent> {
+        let mut output_tokens = 0;
+        let mut events = vec![
+            StreamEvent::MessageStart {
+                message: MessageStartContent {
+                    id: self.id,
+                    type_: "message".to_string(),
+                    role: Role::Assistant,
+                    content: Vec::new(),
+                    model: self.model,
+                    stop_reason: None,
+                    stop_sequence: None,
+                    usage: Usage {
+                        output_tokens: 1,
+                        ..self.usage
+                    },
+                    container: None,
+                    context_management: None,
+                },
+            },
+            StreamEvent::Ping,
+        ];
+        for (index, block) in self.blocks.into_iter().enumerate() {
+            let (start, deltas) = match block {
+                ScenarioBlock::Text(chunks) => (
+                    ContentBlock::text(""),
+                    chunks
+                        .into_iter()
+                        .map(|text| ContentBlockDelta::TextDelta { text })
+                        .collect(),
+                ),
+                ScenarioBlock::Thinking(chunks, signature) => {
+                    let mut deltas: Vec<_> = chunks
+                        .into_iter()
+                        .map(|thinking| ContentBlockDelta::ThinkingDelta { thinking })
+                        .collect();
+                    deltas.push(ContentBlockDelta::SignatureDelta { signature });
+                    let start = ContentBlock::Thinking {
+                        thinking: String::new(),
+                        signature: String::new(),
+                    };
+                    (start, deltas)
+                }
+                ScenarioBlock::ToolUse { id, name, input } => {
+                    let start = ContentBlock::ToolUse {
+                        id,
+                        name,
+                        input: serde_json::json!({}),
+                    };
+                    let deltas = json_chunks(&input.to_string())
+                        .map(|partial_json| ContentBlockDelta::InputJsonDelta { partial_json })
+                        .collect();
+                    (start, deltas)
+                }
+                ScenarioBlock::Other(block) => (block, Vec::new()),
+            };
+            output_tokens += deltas
+                .iter()
+                .map(|delta| match delta {
+                    ContentBlockDelta::TextDelta { text } => estimate_tokens(text),
+                    ContentBlockDelta::InputJsonDelta { partial_json } => {
+                        estimate_tokens(partial_json)
+                    }
+                    ContentBlockDelta::ThinkingDelta { thinking } => estimate_tokens(thinking),
+                    ContentBlockDelta::SignatureDelta { .. }
+                    | ContentBlockDelta::CitationsDelta { .. }
+                    | ContentBlockDelta::Unknown { .. } => 0,
+                })
+                .sum::<u32>();
+            events.push(StreamEvent::ContentBlockStart {
+                index,
+                content_block: start,
+            });
+            events.extend(
+                deltas
+                    .into_iter()
+                    .map(|delta| StreamEvent::ContentBlockDelta { index, delta }),
+            );
+            events.push(StreamEvent::ContentBlockStop { index });
+        }
+        events.push(StreamEvent::MessageDelta {
+            delta,
+            usage: Some(StreamUsage {
+                output_tokens: self.output_tokens.unwrap_or(output_tokens.max(1)),
+                ..Default::default()
+            }),
+        });
+        events.push(StreamEvent::MessageStop);
+        events
+    }
+
+    /// End the message with `stop_reason` and stream its events
+    pub fn finish_stream(
+        self,
+        stop_reason: StopReason,
+    ) -> impl Stream<Item = Result<StreamEvent, MessageError>> + Send + 'static {
+        futures_util::stream::iter(self.finish(stop_reason).into_iter().map(Ok))
+    }
+
+    /// End the message with `stop_reason` and render it as server-sent events
+    ///
+    /// See [`to_sse`] for the format.
+    ///
+    /// # Examples
+    ///
+    /// Parsing a scenario with the client, served by a [`Vcr`](crate::testing::vcr::Vcr)
+    /// replaying it:
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::testing::StreamScenario;
+    /// use anthropic_ai_sdk::testing::vcr::{
+    ///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+    /// };
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, MessageAccumulator, MessageClient, MessageError,
+    ///     RequiredMessageParams, Role, StopReason,
+    /// };
+    /// use futures_util::StreamExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sse = StreamScenario::new()
+    ///     .thinking("The user greets me.")
+    ///     .text("Hello ")
+    ///     .text("there, ünïcödé included")
+    ///     .finish_sse(StopReason::EndTurn);
+    ///
+    /// let path = std::env::temp_dir().join(format!("scenario-{}.json", std::process::id()));
+    /// Cassette {
+    ///     interactions: vec![Interaction {
+    ///         request: RecordedRequest {
+    ///             method: "POST".to_string(),
+    ///             path: "/messages".to_string(),
+    ///             headers: Vec::new(),
+    ///             body: String::new(),
+    ///         },
+    ///         response: RecordedResponse {
+    ///             events: sse.split_inclusive("\n\n").map(str::to_string).collect(),
+    ///             ..RecordedResponse::event_stream(Vec::<String>::new())
+    ///         },
+    ///     }],
+    /// }
+    /// .save(&path)?;
+    /// let server = Vcr::new(&path)
+    ///     .mode(VcrMode::Replay)
+    ///     .matcher(RequestMatcher::new().body(false))
+    ///     .start()
+    ///     .await?;
+    ///
+    /// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+    ///     .with_api_base_url(server.url())
+    ///     .build::<MessageError>()?;
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-7-sonnet-20250219".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hi")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_stream(true);
+    /// let mut stream = Box::pin(client.create_message_streaming(&params).await?);
+    /// let mut accumulator = MessageAccumulator::new();
+    /// while let Some(event) = stream.next().await {
+    ///     accumulator.push(&event?)?;
+    /// }
+    /// let response = accumulator.finish()?;
+    /// assert_eq!(response.text(), "Hello there, ünïcödé included");
+    /// assert_eq!(response.stop_reason, Some(StopReason::EndTurn));
+    /// # drop(stream);
+    /// # server.finish().await?;
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn finish_sse(self, stop_reason: StopReason) -> String {
+        to_sse(&self.finish(stop_reason))
+    }
+}
+
+/// Render events as the `text/event-stream` body sent by the API
+///
+/// Each event has an `event` line with its type and a `data` line with its
+/// JSON, followed by a blank line.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::stream::to_sse;
+/// use anthropic_ai_sdk::types::message::StreamEvent;
+///
+/// assert_eq!(
+///     to_sse(&[StreamEvent::MessageStop]),
+///     "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"
+/// );
+/// ```
+pub fn to_sse(events: &[StreamEvent]) -> String {
+    sse_events(events).concat()
+}
+
+/// Render each event as a server-sent event, see [`to_sse`]
+pub fn sse_events(events: &[StreamEvent]) -> Vec<String> {
+    events
+        .iter()
+        .map(|event| {
+            let data = serde_json::to_value(event).unwrap_or_default();
+            let name = data["type"].as_str().unwrap_or("message").to_string();
+            format!("event: {}\ndata: {}\n\n", name, data)
+        })
+        .collect()
+}
+
+/// Split `json` into chunks of about [`JSON_CHUNK_SIZE`] bytes on character boundaries
+fn json_chunks(json: &str) -> impl Iterator<Item = String> + '_ {
+    let mut rest = json;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let mut end = JSON_CHUNK_SIZE.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        let (chunk, tail) = rest.split_at(end);
+        rest = tail;
+        Some(chunk.to_string())
+    })
+}
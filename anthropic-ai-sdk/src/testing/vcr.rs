@@ -0,0 +1,1033 @@
+//! Record and replay API traffic
+//!
+//! This module contains [`Vcr`], a local HTTP server standing in for the API
+//! in integration tests. Point an [`AnthropicClient`](crate::client::AnthropicClient)
+//! at [`VcrServer::url`] and run the test once in [`VcrMode::Record`] against
+//! the real API to write a cassette file, then in [`VcrMode::Replay`], e.g.
+//! in CI, to serve the recorded responses without network access.
+//!
+//! # Examples
+//!
+//! ```
+//! use anthropic_ai_sdk::client::AnthropicClient;
+//! use anthropic_ai_sdk::testing::vcr::{
+//!     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+//! };
+//! use anthropic_ai_sdk::types::message::{
+//!     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
+//! };
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let params = CreateMessageParams::new(RequiredMessageParams {
+//!     model: "claude-3-7-sonnet-20250219".to_string(),
+//!     messages: vec![Message::new_text(Role::User, "Hello")],
+//!     max_tokens: 1024,
+//! })
+//! .with_user_id("user-1");
+//!
+//! // A cassette as written in record mode
+//! let path = std::env::temp_dir().join(format!("vcr-doc-{}.json", std::process::id()));
+//! let response = r#"{"id": "msg_01", "type": "message", "role": "assistant",
+//!     "model": "claude-3-7-sonnet-20250219", "stop_reason": "end_turn",
+//!     "content": [{"type": "text", "text": "Hi!"}],
+//!     "usage": {"input_tokens": 8, "output_tokens": 3}}"#;
+//! Cassette {
+//!     interactions: vec![Interaction {
+//!         request: RecordedRequest {
+//!             method: "POST".to_string(),
+//!             path: "/messages".to_string(),
+//!             headers: Vec::new(),
+//!             body: serde_json::to_string(&params.clone().with_user_id("user-2"))?,
+//!         },
+//!         response: RecordedResponse::json(200, response),
+//!     }],
+//! }
+//! .save(&path)?;
+//!
+//! let server = Vcr::new(&path)
+//!     .mode(VcrMode::Replay)
+//!     .matcher(RequestMatcher::new().ignore_field("metadata.user_id"))
+//!     .start()
+//!     .await?;
+//! let client = AnthropicClient::builder("test-key", "2023-06-01")
+//!     .with_api_base_url(server.url())
+//!     .build::<MessageError>()?;
+//!
+//! let reply = client.create_message(Some(&params)).await?;
+//! assert_eq!(reply.text(), "Hi!");
+//!
+//! // Unmatched requests fail with a diff against the closest recorded request
+//! let shorter = CreateMessageParams { max_tokens: 512, ..params };
+//! assert!(client.create_message(Some(&shorter)).await.is_err());
+//! assert!(server.unmatched()[0].contains("-  \"max_tokens\": 1024,\n+  \"max_tokens\": 512,"));
+//! assert!(server.finish().await.is_err());
+//! # std::fs::remove_file(&path)?;
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use futures_util::StreamExt;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::client::AnthropicClient;
+
+/// Environment variable selecting the mode of [`VcrMode::from_env`]
+pub const VCR_MODE_ENV: &str = "ANTHROPIC_VCR_MODE";
+
+/// Text replacing the API key in recorded bodies
+const REDACTED: &str = "[REDACTED]";
+
+/// Request headers kept in cassettes
+const RECORDED_REQUEST_HEADERS: &[&str] = &["anthropic-version", "anthropic-beta", "content-type"];
+
+/// Response headers kept in cassettes, in addition to `anthropic-*` headers
+const RECORDED_RESPONSE_HEADERS: &[&str] = &["content-type", "request-id", "retry-after"];
+
+/// Error types for recording and replaying
+#[derive(Debug, Error)]
+pub enum VcrError {
+    #[error("Failed to access cassette: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid cassette: {0}")]
+    Cassette(#[from] serde_json::Error),
+    #[error("{} requests did not match the cassette:\n{}", .0.len(), .0.join("\n"))]
+    Unmatched(Vec<String>),
+}
+
+/// Whether a [`Vcr`] records or replays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VcrMode {
+    /// Forward requests to the API and write them to the cassette
+    Record,
+    /// Serve responses from the cassette, failing unmatched requests
+    #[default]
+    Replay,
+}
+
+impl VcrMode {
+    /// Record if [`VCR_MODE_ENV`] is set to `record`, replay otherwise
+    pub fn from_env() -> Self {
+        match std::env::var(VCR_MODE_ENV) {
+            Ok(mode) if mode.eq_ignore_ascii_case("record") => VcrMode::Record,
+            _ => VcrMode::Replay,
+        }
+    }
+}
+
+/// Recorded requests and their responses, in order
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Read a cassette file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VcrError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Write the cassette to a file, creating its directory if needed
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VcrError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// A recorded request and its response
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A recorded request, without authentication headers
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RecordedRequest {
+    /// HTTP method, e.g. `POST`
+    pub method: String,
+    /// Path relative to the API base URL, including the query string
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    /// Body, decoded as UTF-8 with invalid bytes replaced
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub body: String,
+}
+
+/// A recorded response
+///
+/// Server-sent event streams are recorded event by event in `events` and
+/// replayed the same way.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub body: String,
+    /// Events of a `text/event-stream` response, each with its trailing blank line
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+impl RecordedResponse {
+    /// A JSON response
+    pub fn json(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: body.into(),
+            events: Vec::new(),
+        }
+    }
+
+    /// A server-sent event stream, given the `data` of each event
+    ///
+    /// The event name is taken from the `type` field of the data.
+    pub fn event_stream<I, S>(data: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let events = data
+            .into_iter()
+            .map(|data| {
+                let data = data.as_ref();
+                let name = serde_json::from_str::<serde_json::Value>(data)
+                    .ok()
+                    .and_then(|value| value["type"].as_str().map(str::to_string))
+                    .unwrap_or_else(|| "message".to_string());
+                format!("event: {}\ndata: {}\n\n", name, data)
+            })
+            .collect();
+        Self {
+            status: 200,
+            headers: vec![("content-type".to_string(), "text/event-stream".to_string())],
+            body: String::new(),
+            events,
+        }
+    }
+
+    fn is_event_stream(&self) -> bool {
+        !self.events.is_empty()
+    }
+}
+
+/// Which parts of a request must equal the recorded one to replay it
+///
+/// By default the method, the path and the body must match. JSON bodies are
+/// compared as values, so formatting and key order do not matter, and fields
+/// can be ignored by their dotted path, such as `metadata.user_id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestMatcher {
+    method: bool,
+    path: bool,
+    body: bool,
+    ignored_fields: Vec<String>,
+}
+
+impl Default for RequestMatcher {
+    fn default() -> Self {
+        Self {
+            method: true,
+            path: true,
+            body: true,
+            ignored_fields: Vec::new(),
+        }
+    }
+}
+
+impl RequestMatcher {
+    /// Match on the method, the path and the body
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the method must match
+    pub fn method(mut self, method: bool) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set whether the path and query string must match
+    pub fn path(mut self, path: bool) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Set whether the body must match
+    pub fn body(mut self, body: bool) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Ignore a field of JSON bodies, given by its dotted path
+    pub fn ignore_field(mut self, path: impl Into<String>) -> Self {
+        self.ignored_fields.push(path.into());
+        self
+    }
+
+    /// The compared parts of a request, one line per item for diffs
+    fn key(&self, request: &RecordedRequest) -> Vec<String> {
+        let mut lines = Vec::new();
+        match (self.method, self.path) {
+            (true, true) => lines.push(format!("{} {}", request.method, request.path)),
+            (true, false) => lines.push(request.method.clone()),
+            (false, true) => lines.push(request.path.clone()),
+            (false, false) => {}
+        }
+        if self.body {
+            let body = match serde_json::from_str::<serde_json::Value>(&request.body) {
+                Ok(mut value) => {
+                    for field in &self.ignored_fields {
+                        remove_field(&mut value, field);
+                    }
+                    serde_json::to_string_pretty(&value).unwrap_or_default()
+                }
+                Err(_) => request.body.clone(),
+            };
+            lines.extend(body.lines().map(str::to_string));
+        }
+        lines
+    }
+}
+
+fn remove_field(value: &mut serde_json::Value, path: &str) {
+    match path.split_once('.') {
+        Some((head, rest)) => {
+            if let Some(child) = value.get_mut(head) {
+                remove_field(child, rest);
+            }
+        }
+        None => {
+            if let Some(object) = value.as_object_mut() {
+                object.remove(path);
+            }
+        }
+    }
+}
+
+type Redactor = Arc<dyn Fn(&mut Interaction) + Send + Sync>;
+
+/// Configuration of a record and replay server
+///
+/// Recorded interactions never contain the `x-api-key` header, and the API
+/// key is replaced in bodies. Redaction callbacks can scrub more, such as
+/// personal data; in replay mode they are applied to incoming requests
+/// before matching as well, so redacted fields still match.
+///
+/// # Examples
+///
+/// Recording a streamed response, here from a replaying server standing in
+/// for the API:
+///
+/// ```
+/// use anthropic_ai_sdk::client::AnthropicClient;
+/// use anthropic_ai_sdk::testing::vcr::{
+///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+/// };
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
+/// };
+/// use futures_util::StreamExt;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let dir = std::env::temp_dir().join(format!("vcr-record-{}", std::process::id()));
+/// let events = [
+///     r#"{"type": "message_start", "message": {"content": [], "id": "msg_01",
+///         "model": "claude-3-7-sonnet-20250219", "role": "assistant", "type": "message",
+///         "stop_reason": null, "stop_sequence": null,
+///         "usage": {"input_tokens": 8, "output_tokens": 1}}}"#,
+///     r#"{"type": "message_stop"}"#,
+/// ];
+/// Cassette {
+///     interactions: vec![Interaction {
+///         request: RecordedRequest {
+///             method: "POST".to_string(),
+///             path: "/messages".to_string(),
+///             headers: Vec::new(),
+///             body: String::new(),
+///         },
+///         response: RecordedResponse::event_stream(events),
+///     }],
+/// }
+/// .save(dir.join("api.json"))?;
+/// let api = Vcr::new(dir.join("api.json"))
+///     .mode(VcrMode::Replay)
+///     .matcher(RequestMatcher::new().body(false))
+///     .start()
+///     .await?;
+///
+/// let recorder = Vcr::new(dir.join("recorded.json"))
+///     .mode(VcrMode::Record)
+///     .upstream(api.url())
+///     .redact(|interaction| interaction.request.headers.clear())
+///     .start()
+///     .await?;
+/// let client = AnthropicClient::builder("sk-secret", "2023-06-01")
+///     .with_api_base_url(recorder.url())
+///     .build::<MessageError>()?;
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-3-7-sonnet-20250219".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Hello")],
+///     max_tokens: 1024,
+/// })
+/// .with_stream(true);
+/// let received: Vec<_> = client.create_message_streaming(&params).await?.collect().await;
+/// assert_eq!(received.len(), 2);
+/// recorder.finish().await?;
+///
+/// let recorded = Cassette::load(dir.join("recorded.json"))?;
+/// let interaction = &recorded.interactions[0];
+/// assert!(interaction.request.body.contains("\"Hello\""));
+/// assert_eq!(interaction.response.events.len(), 2);
+/// assert!(!std::fs::read_to_string(dir.join("recorded.json"))?.contains("sk-secret"));
+/// # api.finish().await?;
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Vcr {
+    path: PathBuf,
+    mode: VcrMode,
+    matcher: RequestMatcher,
+    redactors: Vec<Redactor>,
+    upstream: String,
+}
+
+impl fmt::Debug for Vcr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vcr")
+            .field("path", &self.path)
+            .field("mode", &self.mode)
+            .field("matcher", &self.matcher)
+            .field("redactors", &self.redactors.len())
+            .field("upstream", &self.upstream)
+            .finish()
+    }
+}
+
+impl Vcr {
+    /// Record to or replay from the cassette at `path`, in the mode of [`VcrMode::from_env`]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: VcrMode::from_env(),
+            matcher: RequestMatcher::default(),
+            redactors: Vec::new(),
+            upstream: AnthropicClient::DEFAULT_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Set the mode
+    pub fn mode(mut self, mode: VcrMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set how incoming requests are matched against the cassette
+    pub fn matcher(mut self, matcher: RequestMatcher) -> Self {
+        self.matcher = matcher;
+        self
+    }
+
+    /// Add a callback editing interactions before they are recorded
+    pub fn redact(mut self, redactor: impl Fn(&mut Interaction) + Send + Sync + 'static) -> Self {
+        self.redactors.push(Arc::new(redactor));
+        self
+    }
+
+    /// Set the API base URL requests are forwarded to in record mode
+    ///
+    /// # Examples
+    ///
+    /// Streamed responses are recorded event by event, even when a network
+    /// chunk ends within a character:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use anthropic_ai_sdk::testing::vcr::{Cassette, Vcr, VcrMode};
+    /// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// // An upstream sending "é" split across two chunks
+    /// let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    /// let upstream = format!("http://{}", listener.local_addr()?);
+    /// tokio::spawn(async move {
+    ///     let (mut socket, _) = listener.accept().await.unwrap();
+    ///     let mut request = [0; 4096];
+    ///     let _ = socket.read(&mut request).await.unwrap();
+    ///     let event = "event: ping\ndata: {\"text\": \"café\"}\n\n".as_bytes();
+    ///     let split = event.len() - 5;
+    ///     socket
+    ///         .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ntransfer-encoding: chunked\r\n\r\n")
+    ///         .await
+    ///         .unwrap();
+    ///     for part in [&event[..split], &event[split..]] {
+    ///         let mut chunk = format!("{:x}\r\n", part.len()).into_bytes();
+    ///         chunk.extend_from_slice(part);
+    ///         chunk.extend_from_slice(b"\r\n");
+    ///         socket.write_all(&chunk).await.unwrap();
+    ///         socket.flush().await.unwrap();
+    ///         tokio::time::sleep(Duration::from_millis(50)).await;
+    ///     }
+    ///     socket.write_all(b"0\r\n\r\n").await.unwrap();
+    /// });
+    ///
+    /// let path = std::env::temp_dir().join(format!("vcr-split-{}.json", std::process::id()));
+    /// let server = Vcr::new(&path)
+    ///     .mode(VcrMode::Record)
+    ///     .upstream(upstream)
+    ///     .start()
+    ///     .await?;
+    /// let body = reqwest::Client::new()
+    ///     .post(format!("{}/messages", server.url()))
+    ///     .body("{}")
+    ///     .send()
+    ///     .await?
+    ///     .text()
+    ///     .await?;
+    /// assert!(body.contains("café"));
+    /// server.finish().await?;
+    ///
+    /// let cassette = Cassette::load(&path)?;
+    /// assert_eq!(
+    ///     cassette.interactions[0].response.events,
+    ///     ["event: ping\ndata: {\"text\": \"café\"}\n\n"]
+    /// );
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn upstream(mut self, upstream: impl Into<String>) -> Self {
+        self.upstream = upstream.into();
+        self
+    }
+
+    /// Start the server on a free local port
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port cannot be bound or, in replay mode, the
+    /// cassette cannot be read.
+    pub async fn start(self) -> Result<VcrServer, VcrError> {
+        let cassette = match self.mode {
+            VcrMode::Record => Cassette::default(),
+            VcrMode::Replay => Cassette::load(&self.path)?,
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let used = vec![false; cassette.interactions.len()];
+        let inner = Arc::new(Inner {
+            mode: self.mode,
+            matcher: self.matcher,
+            redactors: self.redactors,
+            upstream: self.upstream.trim_end_matches('/').to_string(),
+            http: reqwest::Client::new(),
+            state: Mutex::new(State {
+                cassette,
+                used,
+                unmatched: Vec::new(),
+            }),
+        });
+        let task = tokio::spawn({
+            let inner = inner.clone();
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(inner.clone().serve(stream));
+                }
+            }
+        });
+        Ok(VcrServer {
+            url,
+            path: self.path,
+            inner,
+            task,
+            finished: false,
+        })
+    }
+}
+
+/// A running record and replay server, stopped when dropped
+///
+/// Call [`finish`](Self::finish) at the end of the test to write the
+/// cassette in record mode and to check that every request matched in replay
+/// mode. A server dropped without finishing does both as well, panicking if a
+/// request did not match.
+#[derive(Debug)]
+pub struct VcrServer {
+    url: String,
+    path: PathBuf,
+    inner: Arc<Inner>,
+    task: JoinHandle<()>,
+    finished: bool,
+}
+
+impl VcrServer {
+    /// Base URL to build the client with
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Requests that did not match the cassette so far, with their diffs
+    pub fn unmatched(&self) -> Vec<String> {
+        self.inner.state().unmatched.clone()
+    }
+
+    /// Stop the server, write the cassette in record mode and check for unmatched requests
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cassette cannot be written or, in replay mode,
+    /// a request did not match the cassette.
+    pub async fn finish(mut self) -> Result<(), VcrError> {
+        self.finished = true;
+        self.close()
+    }
+
+    fn close(&mut self) -> Result<(), VcrError> {
+        self.task.abort();
+        let state = self.inner.state();
+        match self.inner.mode {
+            VcrMode::Record => state.cassette.save(&self.path),
+            VcrMode::Replay if state.unmatched.is_empty() => Ok(()),
+            VcrMode::Replay => Err(VcrError::Unmatched(state.unmatched.clone())),
+        }
+    }
+}
+
+impl Drop for VcrServer {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        if let Err(error) = self.close() {
+            if !std::thread::panicking() {
+                panic!("{}", error);
+            }
+        }
+    }
+}
+
+struct Inner {
+    mode: VcrMode,
+    matcher: RequestMatcher,
+    redactors: Vec<Redactor>,
+    upstream: String,
+    http: reqwest::Client,
+    state: Mutex<State>,
+}
+
+impl fmt::Debug for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inner").field("mode", &self.mode).finish()
+    }
+}
+
+struct State {
+    /// The cassette being replayed or recorded
+    cassette: Cassette,
+    /// Which interactions were replayed
+    used: Vec<bool>,
+    unmatched: Vec<String>,
+}
+
+/// A request read from a connection
+struct HttpRequest {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Inner {
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Answer one request on `stream`, then close the connection
+    async fn serve(self: Arc<Self>, stream: TcpStream) {
+        let mut stream = BufReader::new(stream);
+        let result = match read_request(&mut stream).await {
+            Ok(request) => match self.mode {
+                VcrMode::Record => self.record(request, stream.get_mut()).await,
+                VcrMode::Replay => self.replay(request, stream.get_mut()).await,
+            },
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            tracing::warn!("VCR connection failed: {}", error);
+        }
+    }
+
+    async fn replay(&self, request: HttpRequest, stream: &mut TcpStream) -> std::io::Result<()> {
+        let mut incoming = Interaction {
+            request: recorded_request(&request, None),
+            response: RecordedResponse::json(0, ""),
+        };
+        for redactor in &self.redactors {
+            redactor(&mut incoming);
+        }
+        let key = self.matcher.key(&incoming.request);
+
+        let response = {
+            let mut state = self.state();
+            let position = (0..state.cassette.interactions.len()).find(|&index| {
+                !state.used[index]
+                    && self
+                        .matcher
+                        .key(&state.cassette.interactions[index].request)
+                        == key
+            });
+            match position {
+                Some(index) => {
+                    state.used[index] = true;
+                    state.cassette.interactions[index].response.clone()
+                }
+                None => {
+                    let report = self.mismatch_report(&state, &key);
+                    state.unmatched.push(report.clone());
+                    let body = serde_json::json!({
+                        "type": "error",
+                        "error": {"type": "vcr_unmatched_request", "message": report},
+                    });
+                    RecordedResponse::json(501, body.to_string())
+                }
+            }
+        };
+        write_response(stream, &response).await
+    }
+
+    /// Describe an unmatched request with a diff against the closest candidate
+    fn mismatch_report(&self, state: &State, key: &[String]) -> String {
+        let title = key.first().cloned().unwrap_or_default();
+        let closest = state
+            .cassette
+            .interactions
+            .iter()
+            .zip(&state.used)
+            .map(|(interaction, used)| (self.matcher.key(&interaction.request), *used))
+            .max_by_key(|(candidate, used)| {
+                (
+                    !used,
+                    candidate.first() == key.first(),
+                    common_lines(candidate, key),
+                )
+            });
+        match closest {
+            Some((candidate, used)) => {
+                let note = if used { " (already replayed)" } else { "" };
+                format!(
+                    "No recorded interaction matches {}, closest candidate{}:\n{}",
+                    title,
+                    note,
+                    diff(&candidate, key)
+                )
+            }
+            None => format!(
+                "No recorded interaction matches {}, the cassette is empty",
+                title
+            ),
+        }
+    }
+
+    async fn record(&self, request: HttpRequest, stream: &mut TcpStream) -> std::io::Result<()> {
+        let api_key = header(&request.headers, "x-api-key").map(str::to_string);
+        let method = reqwest::Method::from_bytes(request.method.as_bytes())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let mut upstream = self
+            .http
+            .request(method, format!("{}{}", self.upstream, request.path))
+            .body(request.body.clone());
+        for (name, value) in &request.headers {
+            if !matches!(
+                name.as_str(),
+                "host" | "content-length" | "connection" | "transfer-encoding"
+            ) {
+                upstream = upstream.header(name, value);
+            }
+        }
+        let response = match upstream.send().await {
+            Ok(response) => response,
+            Err(error) => {
+                let body = serde_json::json!({
+                    "type": "error",
+                    "error": {"type": "vcr_upstream_error", "message": error.to_string()},
+                });
+                return write_response(stream, &RecordedResponse::json(502, body.to_string()))
+                    .await;
+            }
+        };
+
+        let status = response.status().as_u16();
+        let headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| {
+                RECORDED_RESPONSE_HEADERS.contains(&name.as_str())
+                    || name.as_str().starts_with("anthropic-")
+            })
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let streaming = header(&headers, "content-type")
+            .is_some_and(|content_type| content_type.starts_with("text/event-stream"));
+        let mut recorded = RecordedResponse {
+            status,
+            headers,
+            body: String::new(),
+            events: Vec::new(),
+        };
+
+        if streaming {
+            // Forward events as they arrive, so streaming clients see them live
+            write_head(stream, status, &recorded.headers, None).await?;
+            // Events are decoded once complete, as a chunk may end within a character
+            let mut buffer = Vec::new();
+            let mut chunks = response.bytes_stream();
+            while let Some(chunk) = chunks.next().await {
+                let chunk = chunk.map_err(std::io::Error::other)?;
+                write_chunk(stream, &chunk).await?;
+                buffer.extend_from_slice(&chunk);
+                let mut start = 0;
+                while let Some(end) = buffer[start..].windows(2).position(|w| w == b"\n\n") {
+                    let end = start + end + 2;
+                    let event = String::from_utf8_lossy(&buffer[start..end]).into_owned();
+                    recorded.events.push(event);
+                    start = end;
+                }
+                buffer.drain(..start);
+            }
+            if !buffer.is_empty() {
+                recorded
+                    .events
+                    .push(String::from_utf8_lossy(&buffer).into_owned());
+            }
+            write_chunk(stream, b"").await?;
+        } else {
+            let body = response.bytes().await.map_err(std::io::Error::other)?;
+            recorded.body = String::from_utf8_lossy(&body).into_owned();
+            write_head(stream, status, &recorded.headers, Some(body.len())).await?;
+            stream.write_all(&body).await?;
+        }
+        stream.flush().await?;
+
+        let mut interaction = Interaction {
+            request: recorded_request(&request, api_key.as_deref()),
+            response: recorded,
+        };
+        if let Some(api_key) = api_key.as_deref().filter(|key| !key.is_empty()) {
+            let response = &mut interaction.response;
+            response.body = response.body.replace(api_key, REDACTED);
+            for event in &mut response.events {
+                *event = event.replace(api_key, REDACTED);
+            }
+        }
+        for redactor in &self.redactors {
+            redactor(&mut interaction);
+        }
+        self.state().cassette.interactions.push(interaction);
+        Ok(())
+    }
+}
+
+/// The recorded form of a request, with `api_key` replaced in the body
+fn recorded_request(request: &HttpRequest, api_key: Option<&str>) -> RecordedRequest {
+    let mut body = String::from_utf8_lossy(&request.body).into_owned();
+    if let Some(api_key) = api_key.filter(|key| !key.is_empty()) {
+        body = body.replace(api_key, REDACTED);
+    }
+    RecordedRequest {
+        method: request.method.clone(),
+        path: request.path.clone(),
+        headers: request
+            .headers
+            .iter()
+            .filter(|(name, _)| RECORDED_REQUEST_HEADERS.contains(&name.as_str()))
+            .cloned()
+            .collect(),
+        body,
+    }
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn invalid_data(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Read an HTTP/1.1 request with a `content-length` or chunked body
+async fn read_request(stream: &mut BufReader<TcpStream>) -> std::io::Result<HttpRequest> {
+    let mut line = String::new();
+    stream.read_line(&mut line).await?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(invalid_data("invalid request line"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        if stream.read_line(&mut line).await? == 0 {
+            return Err(invalid_data("connection closed in headers"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    let mut body = Vec::new();
+    if header(&headers, "transfer-encoding").is_some_and(|value| value.contains("chunked")) {
+        loop {
+            line.clear();
+            stream.read_line(&mut line).await?;
+            let size = line.trim().split(';').next().unwrap_or_default();
+            let size =
+                usize::from_str_radix(size, 16).map_err(|_| invalid_data("invalid chunk size"))?;
+            if size == 0 {
+                // Skip the trailer
+                loop {
+                    line.clear();
+                    if stream.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+                        break;
+                    }
+                }
+                break;
+            }
+            let start = body.len();
+            body.resize(start + size + 2, 0);
+            stream.read_exact(&mut body[start..]).await?;
+            body.truncate(start + size);
+        }
+    } else if let Some(length) = header(&headers, "content-length") {
+        let length: usize = length
+            .parse()
+            .map_err(|_| invalid_data("invalid content-length"))?;
+        body.resize(length, 0);
+        stream.read_exact(&mut body).await?;
+    }
+
+    Ok(HttpRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Write the status line and headers, with a chunked body if `length` is `None`
+async fn write_head(
+    stream: &mut TcpStream,
+    status: u16,
+    headers: &[(String, String)],
+    length: Option<usize>,
+) -> std::io::Result<()> {
+    let reason = StatusCode::from_u16(status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("Unknown");
+    let mut head = format!("HTTP/1.1 {} {}\r\nconnection: close\r\n", status, reason);
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    match length {
+        Some(length) => head.push_str(&format!("content-length: {}\r\n\r\n", length)),
+        None => head.push_str("transfer-encoding: chunked\r\n\r\n"),
+    }
+    stream.write_all(head.as_bytes()).await
+}
+
+/// Write one chunk of a chunked body, the empty chunk ending it
+async fn write_chunk(stream: &mut TcpStream, chunk: &[u8]) -> std::io::Result<()> {
+    stream
+        .write_all(format!("{:x}\r\n", chunk.len()).as_bytes())
+        .await?;
+    stream.write_all(chunk).await?;
+    stream.write_all(b"\r\n").await?;
+    stream.flush().await
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    response: &RecordedResponse,
+) -> std::io::Result<()> {
+    if response.is_event_stream() {
+        write_head(stream, response.status, &response.headers, None).await?;
+        for event in &response.events {
+            write_chunk(stream, event.as_bytes()).await?;
+            tokio::task::yield_now().await;
+        }
+        write_chunk(stream, b"").await?;
+    } else {
+        let body = response.body.as_bytes();
+        write_head(stream, response.status, &response.headers, Some(body.len())).await?;
+        stream.write_all(body).await?;
+    }
+    stream.flush().await
+}
+
+/// Longest common subsequence table of two line lists
+fn lcs_table(a: &[String], b: &[String]) -> Vec<Vec<usize>> {
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+    table
+}
+
+fn common_lines(a: &[String], b: &[String]) -> usize {
+    lcs_table(a, b)[0][0]
+}
+
+/// Line diff from `recorded` to `incoming`, `-` for recorded and `+` for incoming lines
+fn diff(recorded: &[String], incoming: &[String]) -> String {
+    let table = lcs_table(recorded, incoming);
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < recorded.len() || j < incoming.len() {
+        if i < recorded.len() && j < incoming.len() && recorded[i] == incoming[j] {
+            lines.push(format!(" {}", recorded[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < recorded.len() && (j == incoming.len() || table[i + 1][j] >= table[i][j + 1])
+        {
+            lines.push(format!("-{}", recorded[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+{}", incoming[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,270 @@
+//! API Key Management
+//!
+//! This module contains the types and functions for managing the API keys of an organization.
+//!
+//! The Admin API never returns key material; keys are identified by their ID
+//! and a partial hint only.
+//!
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+use crate::types::admin::AdminError;
+use crate::types::pagination::{CursorParams, Page};
+
+#[async_trait]
+pub trait ApiKeyClient {
+    /// List a page of API keys
+    async fn list_api_keys<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+    ) -> Result<ListApiKeysResponse, AdminError>;
+
+    /// List API keys one by one, following pagination to the end
+    fn list_api_keys_stream<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+    ) -> impl futures_util::Stream<Item = Result<ApiKeyInfo, AdminError>> + Send + 'a;
+
+    /// Get an API key
+    async fn get_api_key_info<'a>(&'a self, api_key_id: &'a str) -> Result<ApiKeyInfo, AdminError>;
+
+    /// Update the name or status of an API key
+    async fn update_api_key<'a>(
+        &'a self,
+        api_key_id: &'a str,
+        params: &'a UpdateApiKeyParams,
+    ) -> Result<ApiKeyInfo, AdminError>;
+}
+
+/// Status of an API key
+///
+/// Values not known to this SDK are preserved in [`ApiKeyStatus::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiKeyStatus {
+    Active,
+    Inactive,
+    Archived,
+    /// A status not known to this SDK
+    Other(String),
+}
+
+impl ApiKeyStatus {
+    /// The wire representation of the status
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiKeyStatus::Active => "active",
+            ApiKeyStatus::Inactive => "inactive",
+            ApiKeyStatus::Archived => "archived",
+            ApiKeyStatus::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for ApiKeyStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "active" => ApiKeyStatus::Active,
+            "inactive" => ApiKeyStatus::Inactive,
+            "archived" => ApiKeyStatus::Archived,
+            other => ApiKeyStatus::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for ApiKeyStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiKeyStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(ApiKeyStatus::from(value.as_str()))
+    }
+}
+
+/// Metadata of an API key
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::api_keys::{ApiKeyInfo, ApiKeyStatus};
+///
+/// let key: ApiKeyInfo = serde_json::from_str(r#"{
+///     "id": "apikey_01Rj2N8SVvo6BePZj99NhmiT",
+///     "type": "api_key",
+///     "name": "Production",
+///     "partial_key_hint": "sk-ant-api03-R2D...igAA",
+///     "status": "active",
+///     "workspace_id": "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+///     "created_at": "2024-10-30T23:58:27.427722Z",
+///     "created_by": {"id": "user_01WCz1FkmYMm4gnmykNKUu3Q", "type": "user"}
+/// }"#).unwrap();
+/// assert_eq!(key.status, ApiKeyStatus::Active);
+/// assert_eq!(key.created_by.id, "user_01WCz1FkmYMm4gnmykNKUu3Q");
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ApiKeyInfo {
+    /// Type of the resource (always "api_key")
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Unique identifier for the API key
+    pub id: String,
+
+    /// Name of the API key
+    pub name: String,
+
+    /// Partially redacted hint of the key, for recognizing it
+    pub partial_key_hint: Option<String>,
+
+    /// Status of the API key
+    pub status: ApiKeyStatus,
+
+    /// ID of the workspace the key belongs to, or `None` for the default workspace
+    pub workspace_id: Option<String>,
+
+    /// Creation timestamp of the API key
+    #[serde(with = "rfc3339")]
+    pub created_at: OffsetDateTime,
+
+    /// Who created the API key
+    pub created_by: ApiKeyCreator,
+}
+
+/// Creator of an API key
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ApiKeyCreator {
+    /// ID of the creator
+    pub id: String,
+    /// Type of the creator (e.g. "user")
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// Response structure for listing API keys
+pub type ListApiKeysResponse = Page<ApiKeyInfo>;
+
+/// Parameters for listing API keys
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ListApiKeysParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+    /// Only list keys with this status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ApiKeyStatus>,
+    /// Only list keys of this workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+    /// Only list keys created by this user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by_user_id: Option<String>,
+}
+
+impl ListApiKeysParams {
+    /// Create a new ListApiKeysParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.min(1000));
+        self
+    }
+
+    /// Set the status filter
+    pub fn status(mut self, status: ApiKeyStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Set the workspace filter
+    pub fn workspace_id(mut self, workspace_id: impl Into<String>) -> Self {
+        self.workspace_id = Some(workspace_id.into());
+        self
+    }
+
+    /// Set the creator filter
+    pub fn created_by_user_id(mut self, created_by_user_id: impl Into<String>) -> Self {
+        self.created_by_user_id = Some(created_by_user_id.into());
+        self
+    }
+}
+
+impl CursorParams for ListApiKeysParams {
+    fn before_cursor(&self) -> Option<&str> {
+        self.before_id.as_deref()
+    }
+
+    fn set_before_cursor(&mut self, id: String) {
+        self.before_id = Some(id);
+    }
+
+    fn set_after_cursor(&mut self, id: String) {
+        self.after_id = Some(id);
+    }
+}
+
+/// Parameters for updating an API key
+///
+/// Fields left unset are not changed.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::api_keys::{ApiKeyStatus, UpdateApiKeyParams};
+///
+/// let params = UpdateApiKeyParams::new().status(ApiKeyStatus::Inactive);
+/// assert_eq!(serde_json::to_string(&params).unwrap(), r#"{"status":"inactive"}"#);
+/// ```
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct UpdateApiKeyParams {
+    /// New name of the API key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// New status of the API key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ApiKeyStatus>,
+}
+
+impl UpdateApiKeyParams {
+    /// Create a new UpdateApiKeyParams that changes nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the new name
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the new status
+    pub fn status(mut self, status: ApiKeyStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+}
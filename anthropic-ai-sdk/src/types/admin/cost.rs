@@ -0,0 +1,328 @@
+//! Cost Report
+//!
+//! This module contains the types and functions for the organization cost report.
+//!
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+use crate::types::admin::AdminError;
+use crate::types::admin::usage::{BucketWidth, format_rfc3339};
+use crate::types::message::ServiceTier;
+
+pub use crate::types::decimal::{Decimal, ParseDecimalError};
+
+#[async_trait]
+pub trait CostReportClient {
+    /// Get a page of the cost report
+    async fn get_cost_report<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+    ) -> Result<CostReport, AdminError>;
+
+    /// Get the cost report bucket by bucket, following pagination to the end
+    fn cost_report_stream<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+    ) -> impl futures_util::Stream<Item = Result<CostBucket, AdminError>> + Send + 'a;
+
+    /// Get the total cost of each workspace between `starting_at` and `ending_at`
+    async fn get_workspace_costs(
+        &self,
+        starting_at: OffsetDateTime,
+        ending_at: OffsetDateTime,
+    ) -> Result<Vec<WorkspaceCost>, AdminError>;
+}
+
+/// Currency of a cost amount
+///
+/// Values not known to this SDK are preserved in [`Currency::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Currency {
+    Usd,
+    /// A currency not known to this SDK
+    Other(String),
+}
+
+impl Currency {
+    /// The wire representation of the currency
+    pub fn as_str(&self) -> &str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for Currency {
+    fn from(value: &str) -> Self {
+        match value {
+            "USD" => Currency::Usd,
+            other => Currency::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(Currency::from(value.as_str()))
+    }
+}
+
+/// Dimension to group the costs of a bucket by
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CostGroupBy {
+    WorkspaceId,
+    Description,
+}
+
+impl CostGroupBy {
+    /// The wire representation of the dimension
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostGroupBy::WorkspaceId => "workspace_id",
+            CostGroupBy::Description => "description",
+        }
+    }
+}
+
+/// Parameters for the cost report
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::cost::{CostGroupBy, CostReportParams};
+/// use time::OffsetDateTime;
+/// use time::format_description::well_known::Rfc3339;
+///
+/// let params = CostReportParams::new(OffsetDateTime::parse("2025-01-01T00:00:00Z", &Rfc3339)?)
+///     .group_by(CostGroupBy::WorkspaceId)
+///     .group_by(CostGroupBy::Description);
+/// let query = params.to_query();
+/// assert_eq!(query.iter().filter(|(name, _)| *name == "group_by[]").count(), 2);
+/// # Ok::<(), time::error::Parse>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostReportParams {
+    /// Start of the report, rounded down to the bucket width
+    pub starting_at: OffsetDateTime,
+    /// End of the report (exclusive)
+    pub ending_at: Option<OffsetDateTime>,
+    /// Width of the time buckets; the cost report only supports days
+    pub bucket_width: Option<BucketWidth>,
+    /// Dimensions to group the costs by
+    pub group_by: Vec<CostGroupBy>,
+    /// Maximum number of buckets per page
+    pub limit: Option<u16>,
+    /// Cursor of the page to get, taken from [`CostReport::next_page`]
+    pub page: Option<String>,
+}
+
+impl CostReportParams {
+    /// Create new CostReportParams starting at `starting_at`
+    pub fn new(starting_at: OffsetDateTime) -> Self {
+        Self {
+            starting_at,
+            ending_at: None,
+            bucket_width: None,
+            group_by: Vec::new(),
+            limit: None,
+            page: None,
+        }
+    }
+
+    /// Set the ending_at parameter
+    pub fn ending_at(mut self, ending_at: OffsetDateTime) -> Self {
+        self.ending_at = Some(ending_at);
+        self
+    }
+
+    /// Set the bucket_width parameter
+    pub fn bucket_width(mut self, bucket_width: BucketWidth) -> Self {
+        self.bucket_width = Some(bucket_width);
+        self
+    }
+
+    /// Add a dimension to group by
+    pub fn group_by(mut self, group_by: CostGroupBy) -> Self {
+        self.group_by.push(group_by);
+        self
+    }
+
+    /// Set the limit parameter
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the page parameter
+    pub fn page(mut self, page: impl Into<String>) -> Self {
+        self.page = Some(page.into());
+        self
+    }
+
+    /// The query string pairs of the parameters
+    pub fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("starting_at", format_rfc3339(self.starting_at))];
+        if let Some(ending_at) = self.ending_at {
+            query.push(("ending_at", format_rfc3339(ending_at)));
+        }
+        if let Some(bucket_width) = self.bucket_width {
+            query.push(("bucket_width", bucket_width.as_str().to_string()));
+        }
+        query.extend(
+            self.group_by
+                .iter()
+                .map(|group_by| ("group_by[]", group_by.as_str().to_string())),
+        );
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(page) = &self.page {
+            query.push(("page", page.clone()));
+        }
+        query
+    }
+}
+
+/// A page of the cost report
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CostReport {
+    /// Time buckets of the page
+    pub data: Vec<CostBucket>,
+    /// Indicates if there are more results
+    pub has_more: bool,
+    /// Cursor of the next page
+    pub next_page: Option<String>,
+}
+
+/// Costs within one time bucket
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::cost::{CostBucket, Currency, Decimal};
+///
+/// let bucket: CostBucket = serde_json::from_str(r#"{
+///     "starting_at": "2025-01-01T00:00:00Z",
+///     "ending_at": "2025-01-02T00:00:00Z",
+///     "results": [{
+///         "currency": "USD",
+///         "amount": "123.78912",
+///         "workspace_id": "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+///         "description": "Claude Sonnet 4 Usage - Input Tokens",
+///         "cost_type": "tokens",
+///         "context_window": "0-200k",
+///         "model": "claude-sonnet-4-20250514",
+///         "service_tier": "standard",
+///         "token_type": "uncached_input_tokens"
+///     }]
+/// }"#).unwrap();
+/// let result = &bucket.results[0];
+/// assert_eq!(result.currency, Currency::Usd);
+/// assert_eq!(result.amount, Decimal::new(12378912, 5));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CostBucket {
+    /// Start of the bucket (inclusive)
+    #[serde(with = "rfc3339")]
+    pub starting_at: OffsetDateTime,
+    /// End of the bucket (exclusive)
+    #[serde(with = "rfc3339")]
+    pub ending_at: OffsetDateTime,
+    /// Costs per combination of the grouped dimensions
+    pub results: Vec<CostResult>,
+}
+
+/// Cost for one combination of the grouped dimensions
+///
+/// Dimensions that were not grouped by are `None`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CostResult {
+    /// Currency of the amount
+    pub currency: Currency,
+    /// Cost in the lowest unit of the currency, e.g. cents for USD
+    pub amount: Decimal,
+    /// Workspace of the group; `None` for the default workspace or when not grouped by
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    /// Description of the cost item
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Type of the cost, e.g. "tokens", "web_search" or "code_execution"
+    #[serde(default)]
+    pub cost_type: Option<String>,
+    /// Context window of the cost item, e.g. "0-200k"
+    #[serde(default)]
+    pub context_window: Option<String>,
+    /// Model of the cost item
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Service tier of the cost item
+    #[serde(default)]
+    pub service_tier: Option<ServiceTier>,
+    /// Token type of the cost item, e.g. "uncached_input_tokens"
+    #[serde(default)]
+    pub token_type: Option<String>,
+}
+
+/// Total cost of one workspace
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct WorkspaceCost {
+    /// The workspace; `None` for the default workspace
+    pub workspace_id: Option<String>,
+    /// Currency of the amount
+    pub currency: Currency,
+    /// Total cost in the lowest unit of the currency, e.g. cents for USD
+    pub amount: Decimal,
+}
+
+/// Sum cost buckets into one total per workspace and currency
+///
+/// The result is sorted by workspace, with the default workspace first.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::cost::{CostBucket, Decimal, sum_by_workspace};
+///
+/// let day = |amount: &str| -> CostBucket {
+///     serde_json::from_str(&format!(r#"{{
+///         "starting_at": "2025-01-01T00:00:00Z",
+///         "ending_at": "2025-01-02T00:00:00Z",
+///         "results": [{{"currency": "USD", "amount": "{amount}", "workspace_id": "wrkspc_a"}}]
+///     }}"#)).unwrap()
+/// };
+/// let totals = sum_by_workspace(&[day("0.10"), day("0.20")]);
+/// assert_eq!(totals[0].workspace_id.as_deref(), Some("wrkspc_a"));
+/// assert_eq!(totals[0].amount, "0.3".parse::<Decimal>().unwrap());
+/// ```
+pub fn sum_by_workspace<'a>(
+    buckets: impl IntoIterator<Item = &'a CostBucket>,
+) -> Vec<WorkspaceCost> {
+    let mut totals: BTreeMap<(Option<String>, Currency), Decimal> = BTreeMap::new();
+    for result in buckets.into_iter().flat_map(|bucket| &bucket.results) {
+        *totals
+            .entry((result.workspace_id.clone(), result.currency.clone()))
+            .or_default() += result.amount;
+    }
+    totals
+        .into_iter()
+        .map(|((workspace_id, currency), amount)| WorkspaceCost {
+            workspace_id,
+            currency,
+            amount,
+        })
+        .collect()
+}
@@ -0,0 +1,231 @@
+//! Organization Invites
+//!
+//! This module contains the types and functions for inviting users to an organization.
+//!
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+use crate::types::admin::AdminError;
+use crate::types::admin::users::OrganizationRole;
+use crate::types::pagination::{CursorParams, Page};
+
+#[async_trait]
+pub trait InviteClient {
+    /// List a page of invites
+    async fn list_invites<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+    ) -> Result<ListInvitesResponse, AdminError>;
+
+    /// List invites one by one, following pagination to the end
+    fn list_invites_stream<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+    ) -> impl futures_util::Stream<Item = Result<Invite, AdminError>> + Send + 'a;
+
+    /// Get an invite
+    async fn get_invite<'a>(&'a self, invite_id: &'a str) -> Result<Invite, AdminError>;
+
+    /// Invite a user to the organization
+    async fn create_invite<'a>(
+        &'a self,
+        email: &'a str,
+        role: OrganizationRole,
+    ) -> Result<Invite, AdminError>;
+
+    /// Invite a user unless they already have a pending invite
+    async fn ensure_invite<'a>(
+        &'a self,
+        email: &'a str,
+        role: OrganizationRole,
+    ) -> Result<Invite, AdminError>;
+
+    /// Delete an invite
+    async fn delete_invite<'a>(&'a self, invite_id: &'a str) -> Result<InviteDeleted, AdminError>;
+}
+
+/// Status of an invite
+///
+/// Values not known to this SDK are preserved in [`InviteStatus::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InviteStatus {
+    Pending,
+    Accepted,
+    Expired,
+    Deleted,
+    /// A status not known to this SDK
+    Other(String),
+}
+
+impl InviteStatus {
+    /// The wire representation of the status
+    pub fn as_str(&self) -> &str {
+        match self {
+            InviteStatus::Pending => "pending",
+            InviteStatus::Accepted => "accepted",
+            InviteStatus::Expired => "expired",
+            InviteStatus::Deleted => "deleted",
+            InviteStatus::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for InviteStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "pending" => InviteStatus::Pending,
+            "accepted" => InviteStatus::Accepted,
+            "expired" => InviteStatus::Expired,
+            "deleted" => InviteStatus::Deleted,
+            other => InviteStatus::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for InviteStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for InviteStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(InviteStatus::from(value.as_str()))
+    }
+}
+
+/// An invite to the organization
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::invites::{Invite, InviteStatus};
+///
+/// let invite: Invite = serde_json::from_str(r#"{
+///     "id": "invite_015gWxCN9Hfg2QhZwTK7Mdeu",
+///     "type": "invite",
+///     "email": "user@example.com",
+///     "role": "user",
+///     "invited_at": "2024-10-30T23:58:27.427722Z",
+///     "expires_at": "2024-11-20T23:58:27.427722Z",
+///     "status": "pending"
+/// }"#).unwrap();
+/// assert_eq!(invite.status, InviteStatus::Pending);
+/// assert!(invite.is_expired());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Invite {
+    /// Type of the resource (always "invite")
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Unique identifier for the invite
+    pub id: String,
+
+    /// Email of the invited user
+    pub email: String,
+
+    /// Role the user gets in the organization
+    pub role: OrganizationRole,
+
+    /// When the invite was created
+    #[serde(with = "rfc3339")]
+    pub invited_at: OffsetDateTime,
+
+    /// When the invite expires
+    #[serde(with = "rfc3339")]
+    pub expires_at: OffsetDateTime,
+
+    /// Status of the invite
+    pub status: InviteStatus,
+}
+
+impl Invite {
+    /// Whether the invite has expired, by status or by its expiry time
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(OffsetDateTime::now_utc())
+    }
+
+    /// Whether the invite has expired as of `now`
+    pub fn is_expired_at(&self, now: OffsetDateTime) -> bool {
+        self.status == InviteStatus::Expired || self.expires_at <= now
+    }
+}
+
+/// Response structure for listing invites
+pub type ListInvitesResponse = Page<Invite>;
+
+/// Parameters for listing invites
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ListInvitesParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+impl ListInvitesParams {
+    /// Create a new ListInvitesParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.min(1000));
+        self
+    }
+}
+
+impl CursorParams for ListInvitesParams {
+    fn before_cursor(&self) -> Option<&str> {
+        self.before_id.as_deref()
+    }
+
+    fn set_before_cursor(&mut self, id: String) {
+        self.before_id = Some(id);
+    }
+
+    fn set_after_cursor(&mut self, id: String) {
+        self.after_id = Some(id);
+    }
+}
+
+/// Request body for creating an invite
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct CreateInviteParams<'a> {
+    /// Email of the user to invite
+    pub email: &'a str,
+    /// Role the user gets in the organization
+    pub role: OrganizationRole,
+}
+
+/// Response for deleting an invite
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InviteDeleted {
+    /// ID of the deleted invite
+    pub id: String,
+    /// Type of the response (always "invite_deleted")
+    #[serde(rename = "type")]
+    pub type_: String,
+}
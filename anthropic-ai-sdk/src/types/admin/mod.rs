@@ -0,0 +1,118 @@
+//! Admin API
+//!
+//! This module contains the types and functions for the Anthropic Admin API.
+//!
+//! Admin endpoints require an admin API key (`sk-ant-admin...`), set with
+//! [`AnthropicClientBuilder::with_admin_key`](crate::client::AnthropicClientBuilder::with_admin_key)
+//! or used as the client's API key. Regular API keys are never sent to admin
+//! endpoints.
+//!
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::error::ApiErrorResponse;
+use crate::types::pagination::RateLimitError;
+
+pub mod api_keys;
+pub mod cost;
+pub mod invites;
+pub mod usage;
+pub mod users;
+pub mod workspace_members;
+pub mod workspaces;
+
+/// Error types for the Admin API
+///
+/// # Examples
+///
+/// A client without an admin key refuses admin requests before sending them,
+/// so the replay server below, which has no recorded responses, is never hit:
+///
+/// ```
+/// use anthropic_ai_sdk::client::AnthropicClient;
+/// use anthropic_ai_sdk::testing::vcr::{Cassette, Vcr, VcrMode};
+/// use anthropic_ai_sdk::types::admin::AdminError;
+/// use anthropic_ai_sdk::types::admin::users::OrganizationMemberClient;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::env::temp_dir().join(format!("admin-keys-{}.json", std::process::id()));
+/// Cassette::default().save(&path)?;
+/// let server = Vcr::new(&path).mode(VcrMode::Replay).start().await?;
+///
+/// let client = AnthropicClient::builder("sk-ant-api03-regular-key", "2023-06-01")
+///     .with_api_base_url(server.url())
+///     .build::<AdminError>()?;
+/// let result = client.get_organization_member("user_01").await;
+/// assert!(matches!(result, Err(AdminError::MissingAdminKey)));
+///
+/// // A regular key given as the admin key is caught as well
+/// let client = AnthropicClient::builder("sk-ant-api03-regular-key", "2023-06-01")
+///     .with_api_base_url(server.url())
+///     .with_admin_key("sk-ant-REDACTED")
+///     .build::<AdminError>()?;
+/// let result = client.get_organization_member("user_01").await;
+/// assert!(matches!(result, Err(AdminError::NotAnAdminKey)));
+/// server.finish().await?;
+/// # std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Error)]
+pub enum AdminError {
+    #[error("No admin API key configured; admin endpoints require an sk-ant-admin key")]
+    MissingAdminKey,
+    #[error("The configured admin key is not an admin API key (expected an sk-ant-admin key)")]
+    NotAnAdminKey,
+    #[error("API request failed: {0}")]
+    RequestFailed(String),
+    #[error("API error: {0}")]
+    ApiError(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// Delay requested by the API before retrying
+        retry_after: Option<Duration>,
+    },
+}
+
+impl From<String> for AdminError {
+    fn from(error: String) -> Self {
+        AdminError::ApiError(error)
+    }
+}
+
+impl From<ApiErrorResponse> for AdminError {
+    fn from(error: ApiErrorResponse) -> Self {
+        if error.is_not_found() {
+            AdminError::NotFound(error.message.unwrap_or(error.body))
+        } else if error.is_conflict() {
+            AdminError::Conflict(error.message.unwrap_or(error.body))
+        } else if error.is_rate_limited() {
+            AdminError::RateLimited {
+                message: error.message.unwrap_or(error.body),
+                retry_after: error.retry_after,
+            }
+        } else {
+            AdminError::ApiError(error.body)
+        }
+    }
+}
+
+impl RateLimitError for AdminError {
+    fn is_rate_limited(&self) -> bool {
+        matches!(self, AdminError::RateLimited { .. })
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            AdminError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
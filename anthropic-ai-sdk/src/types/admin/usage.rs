@@ -0,0 +1,353 @@
+//! Usage Report
+//!
+//! This module contains the types and functions for the organization usage report.
+//!
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use time::serde::rfc3339;
+
+use crate::types::admin::AdminError;
+use crate::types::message::ServiceTier;
+
+#[async_trait]
+pub trait UsageReportClient {
+    /// Get a page of the messages usage report
+    async fn get_messages_usage_report<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+    ) -> Result<UsageReport, AdminError>;
+
+    /// Get the messages usage report bucket by bucket, following pagination to the end
+    fn messages_usage_report_stream<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+    ) -> impl futures_util::Stream<Item = Result<UsageBucket, AdminError>> + Send + 'a;
+}
+
+/// Width of the time buckets of a report
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BucketWidth {
+    /// One minute buckets
+    #[serde(rename = "1m")]
+    Minute,
+    /// One hour buckets
+    #[serde(rename = "1h")]
+    Hour,
+    /// One day buckets
+    #[serde(rename = "1d")]
+    Day,
+}
+
+impl BucketWidth {
+    /// The wire representation of the bucket width
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BucketWidth::Minute => "1m",
+            BucketWidth::Hour => "1h",
+            BucketWidth::Day => "1d",
+        }
+    }
+}
+
+/// Dimension to group the usage of a bucket by
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageGroupBy {
+    ApiKeyId,
+    WorkspaceId,
+    Model,
+    ServiceTier,
+    ContextWindow,
+}
+
+impl UsageGroupBy {
+    /// The wire representation of the dimension
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageGroupBy::ApiKeyId => "api_key_id",
+            UsageGroupBy::WorkspaceId => "workspace_id",
+            UsageGroupBy::Model => "model",
+            UsageGroupBy::ServiceTier => "service_tier",
+            UsageGroupBy::ContextWindow => "context_window",
+        }
+    }
+}
+
+/// Parameters for the messages usage report
+///
+/// Array filters are sent as repeated `name[]` query parameters.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::usage::{BucketWidth, UsageGroupBy, UsageReportParams};
+/// use time::OffsetDateTime;
+/// use time::format_description::well_known::Rfc3339;
+///
+/// let params = UsageReportParams::new(OffsetDateTime::parse("2025-01-01T00:00:00Z", &Rfc3339)?)
+///     .ending_at(OffsetDateTime::parse("2025-01-08T00:00:00Z", &Rfc3339)?)
+///     .bucket_width(BucketWidth::Day)
+///     .group_by(UsageGroupBy::WorkspaceId)
+///     .group_by(UsageGroupBy::Model);
+/// let query = params.to_query();
+/// assert!(query.contains(&("starting_at", "2025-01-01T00:00:00Z".to_string())));
+/// assert!(query.contains(&("bucket_width", "1d".to_string())));
+/// assert_eq!(query.iter().filter(|(name, _)| *name == "group_by[]").count(), 2);
+/// # Ok::<(), time::error::Parse>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageReportParams {
+    /// Start of the report, rounded down to the bucket width
+    pub starting_at: OffsetDateTime,
+    /// End of the report (exclusive)
+    pub ending_at: Option<OffsetDateTime>,
+    /// Width of the time buckets
+    pub bucket_width: Option<BucketWidth>,
+    /// Dimensions to group the usage by
+    pub group_by: Vec<UsageGroupBy>,
+    /// Restrict the report to these models
+    pub models: Vec<String>,
+    /// Restrict the report to these API keys
+    pub api_key_ids: Vec<String>,
+    /// Restrict the report to these workspaces
+    pub workspace_ids: Vec<String>,
+    /// Restrict the report to these service tiers
+    pub service_tiers: Vec<ServiceTier>,
+    /// Maximum number of buckets per page
+    pub limit: Option<u16>,
+    /// Cursor of the page to get, taken from [`UsageReport::next_page`]
+    pub page: Option<String>,
+}
+
+impl UsageReportParams {
+    /// Create new UsageReportParams starting at `starting_at`
+    pub fn new(starting_at: OffsetDateTime) -> Self {
+        Self {
+            starting_at,
+            ending_at: None,
+            bucket_width: None,
+            group_by: Vec::new(),
+            models: Vec::new(),
+            api_key_ids: Vec::new(),
+            workspace_ids: Vec::new(),
+            service_tiers: Vec::new(),
+            limit: None,
+            page: None,
+        }
+    }
+
+    /// Set the ending_at parameter
+    pub fn ending_at(mut self, ending_at: OffsetDateTime) -> Self {
+        self.ending_at = Some(ending_at);
+        self
+    }
+
+    /// Set the bucket_width parameter
+    pub fn bucket_width(mut self, bucket_width: BucketWidth) -> Self {
+        self.bucket_width = Some(bucket_width);
+        self
+    }
+
+    /// Add a dimension to group by
+    pub fn group_by(mut self, group_by: UsageGroupBy) -> Self {
+        self.group_by.push(group_by);
+        self
+    }
+
+    /// Add a model to filter by
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.models.push(model.into());
+        self
+    }
+
+    /// Add an API key to filter by
+    pub fn api_key_id(mut self, api_key_id: impl Into<String>) -> Self {
+        self.api_key_ids.push(api_key_id.into());
+        self
+    }
+
+    /// Add a workspace to filter by
+    pub fn workspace_id(mut self, workspace_id: impl Into<String>) -> Self {
+        self.workspace_ids.push(workspace_id.into());
+        self
+    }
+
+    /// Add a service tier to filter by
+    pub fn service_tier(mut self, service_tier: ServiceTier) -> Self {
+        self.service_tiers.push(service_tier);
+        self
+    }
+
+    /// Set the limit parameter
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the page parameter
+    pub fn page(mut self, page: impl Into<String>) -> Self {
+        self.page = Some(page.into());
+        self
+    }
+
+    /// The query string pairs of the parameters
+    pub fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("starting_at", format_rfc3339(self.starting_at))];
+        if let Some(ending_at) = self.ending_at {
+            query.push(("ending_at", format_rfc3339(ending_at)));
+        }
+        if let Some(bucket_width) = self.bucket_width {
+            query.push(("bucket_width", bucket_width.as_str().to_string()));
+        }
+        query.extend(
+            self.group_by
+                .iter()
+                .map(|group_by| ("group_by[]", group_by.as_str().to_string())),
+        );
+        query.extend(self.models.iter().map(|model| ("models[]", model.clone())));
+        query.extend(
+            self.api_key_ids
+                .iter()
+                .map(|id| ("api_key_ids[]", id.clone())),
+        );
+        query.extend(
+            self.workspace_ids
+                .iter()
+                .map(|id| ("workspace_ids[]", id.clone())),
+        );
+        query.extend(
+            self.service_tiers
+                .iter()
+                .map(|tier| ("service_tiers[]", tier.as_str().to_string())),
+        );
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(page) = &self.page {
+            query.push(("page", page.clone()));
+        }
+        query
+    }
+}
+
+pub(crate) fn format_rfc3339(value: OffsetDateTime) -> String {
+    value
+        .format(&Rfc3339)
+        .expect("OffsetDateTime is always representable as RFC 3339")
+}
+
+/// A page of the messages usage report
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UsageReport {
+    /// Time buckets of the page
+    pub data: Vec<UsageBucket>,
+    /// Indicates if there are more results
+    pub has_more: bool,
+    /// Cursor of the next page
+    pub next_page: Option<String>,
+}
+
+/// Usage within one time bucket
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::usage::UsageBucket;
+///
+/// let bucket: UsageBucket = serde_json::from_str(r#"{
+///     "starting_at": "2025-01-01T00:00:00Z",
+///     "ending_at": "2025-01-02T00:00:00Z",
+///     "results": [{
+///         "uncached_input_tokens": 1500,
+///         "cache_creation": {"ephemeral_1h_input_tokens": 100, "ephemeral_5m_input_tokens": 50},
+///         "cache_read_input_tokens": 200,
+///         "output_tokens": 500,
+///         "server_tool_use": {"web_search_requests": 3},
+///         "api_key_id": null,
+///         "workspace_id": "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+///         "model": "claude-sonnet-4-20250514",
+///         "service_tier": "standard",
+///         "context_window": "0-200k"
+///     }]
+/// }"#).unwrap();
+/// let result = &bucket.results[0];
+/// assert_eq!(result.total_input_tokens(), 1850);
+/// assert_eq!(result.server_tool_use.web_search_requests, 3);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UsageBucket {
+    /// Start of the bucket (inclusive)
+    #[serde(with = "rfc3339")]
+    pub starting_at: OffsetDateTime,
+    /// End of the bucket (exclusive)
+    #[serde(with = "rfc3339")]
+    pub ending_at: OffsetDateTime,
+    /// Usage per combination of the grouped dimensions
+    pub results: Vec<UsageResult>,
+}
+
+/// Usage for one combination of the grouped dimensions
+///
+/// Dimensions that were not grouped by are `None`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct UsageResult {
+    /// Input tokens that were neither written to nor read from the cache
+    pub uncached_input_tokens: u64,
+    /// Input tokens written to the cache
+    #[serde(default)]
+    pub cache_creation: CacheCreationUsage,
+    /// Input tokens read from the cache
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+    /// Output tokens
+    pub output_tokens: u64,
+    /// Server tool usage
+    #[serde(default)]
+    pub server_tool_use: ServerToolUsage,
+    /// API key of the group
+    #[serde(default)]
+    pub api_key_id: Option<String>,
+    /// Workspace of the group
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    /// Model of the group
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Service tier of the group
+    #[serde(default)]
+    pub service_tier: Option<ServiceTier>,
+    /// Context window of the group, e.g. "0-200k"
+    #[serde(default)]
+    pub context_window: Option<String>,
+}
+
+impl UsageResult {
+    /// Input tokens of all kinds: uncached, written to and read from the cache
+    pub fn total_input_tokens(&self) -> u64 {
+        self.uncached_input_tokens
+            + self.cache_creation.ephemeral_1h_input_tokens
+            + self.cache_creation.ephemeral_5m_input_tokens
+            + self.cache_read_input_tokens
+    }
+}
+
+/// Input tokens written to the cache, by cache lifetime
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheCreationUsage {
+    /// Tokens written to the one hour cache
+    #[serde(default)]
+    pub ephemeral_1h_input_tokens: u64,
+    /// Tokens written to the five minute cache
+    #[serde(default)]
+    pub ephemeral_5m_input_tokens: u64,
+}
+
+/// Server tool usage
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ServerToolUsage {
+    /// Number of web search requests
+    #[serde(default)]
+    pub web_search_requests: u64,
+}
@@ -0,0 +1,221 @@
+//! Organization Member Management
+//!
+//! This module contains the types and functions for managing the members of an organization.
+//!
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+use crate::types::admin::AdminError;
+use crate::types::pagination::{CursorParams, Page};
+
+#[async_trait]
+pub trait OrganizationMemberClient {
+    /// List a page of organization members
+    async fn list_organization_members<'a>(
+        &'a self,
+        params: Option<&'a ListOrganizationMembersParams>,
+    ) -> Result<ListOrganizationMembersResponse, AdminError>;
+
+    /// List organization members one by one, following pagination to the end
+    fn list_organization_members_stream<'a>(
+        &'a self,
+        params: Option<&'a ListOrganizationMembersParams>,
+    ) -> impl futures_util::Stream<Item = Result<OrganizationMember, AdminError>> + Send + 'a;
+
+    /// Get an organization member
+    async fn get_organization_member<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> Result<OrganizationMember, AdminError>;
+
+    /// Change the role of an organization member
+    async fn update_organization_member<'a>(
+        &'a self,
+        user_id: &'a str,
+        role: OrganizationRole,
+    ) -> Result<OrganizationMember, AdminError>;
+
+    /// Remove a member from the organization
+    async fn remove_organization_member<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> Result<OrganizationMemberDeleted, AdminError>;
+}
+
+/// Role of a member in an organization
+///
+/// Values not known to this SDK are preserved in [`OrganizationRole::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OrganizationRole {
+    User,
+    Developer,
+    Billing,
+    Admin,
+    ClaudeCodeUser,
+    /// A role not known to this SDK
+    Other(String),
+}
+
+impl OrganizationRole {
+    /// The wire representation of the role
+    pub fn as_str(&self) -> &str {
+        match self {
+            OrganizationRole::User => "user",
+            OrganizationRole::Developer => "developer",
+            OrganizationRole::Billing => "billing",
+            OrganizationRole::Admin => "admin",
+            OrganizationRole::ClaudeCodeUser => "claude_code_user",
+            OrganizationRole::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for OrganizationRole {
+    fn from(value: &str) -> Self {
+        match value {
+            "user" => OrganizationRole::User,
+            "developer" => OrganizationRole::Developer,
+            "billing" => OrganizationRole::Billing,
+            "admin" => OrganizationRole::Admin,
+            "claude_code_user" => OrganizationRole::ClaudeCodeUser,
+            other => OrganizationRole::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for OrganizationRole {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for OrganizationRole {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(OrganizationRole::from(value.as_str()))
+    }
+}
+
+/// A member of the organization
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::users::{OrganizationMember, OrganizationRole};
+///
+/// let member: OrganizationMember = serde_json::from_str(r#"{
+///     "id": "user_01WCz1FkmYMm4gnmykNKUu3Q",
+///     "type": "user",
+///     "email": "user@example.com",
+///     "name": "Jane Doe",
+///     "role": "developer",
+///     "added_at": "2024-10-30T23:58:27.427722Z"
+/// }"#).unwrap();
+/// assert_eq!(member.role, OrganizationRole::Developer);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OrganizationMember {
+    /// Type of the resource (always "user")
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Unique identifier for the user
+    pub id: String,
+
+    /// Email of the user
+    pub email: String,
+
+    /// Name of the user
+    pub name: String,
+
+    /// Role of the user in the organization
+    pub role: OrganizationRole,
+
+    /// When the user joined the organization
+    #[serde(with = "rfc3339")]
+    pub added_at: OffsetDateTime,
+}
+
+/// Response structure for listing organization members
+pub type ListOrganizationMembersResponse = Page<OrganizationMember>;
+
+/// Parameters for listing organization members
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ListOrganizationMembersParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+    /// Only list the member with this email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl ListOrganizationMembersParams {
+    /// Create a new ListOrganizationMembersParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.min(1000));
+        self
+    }
+
+    /// Set the email filter
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+}
+
+impl CursorParams for ListOrganizationMembersParams {
+    fn before_cursor(&self) -> Option<&str> {
+        self.before_id.as_deref()
+    }
+
+    fn set_before_cursor(&mut self, id: String) {
+        self.before_id = Some(id);
+    }
+
+    fn set_after_cursor(&mut self, id: String) {
+        self.after_id = Some(id);
+    }
+}
+
+/// Request body for updating an organization member
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct UpdateOrganizationMemberParams {
+    /// New role of the member
+    pub role: OrganizationRole,
+}
+
+/// Response for removing an organization member
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OrganizationMemberDeleted {
+    /// ID of the removed user
+    pub id: String,
+    /// Type of the response (always "user_deleted")
+    #[serde(rename = "type")]
+    pub type_: String,
+}
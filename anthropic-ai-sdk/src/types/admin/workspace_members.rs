@@ -0,0 +1,230 @@
+//! Workspace Member Management
+//!
+//! This module contains the types and functions for managing the members of a workspace.
+//!
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::types::admin::AdminError;
+use crate::types::pagination::{CursorParams, Page};
+
+#[async_trait]
+pub trait WorkspaceMemberClient {
+    /// List a page of workspace members
+    async fn list_workspace_members<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+    ) -> Result<ListWorkspaceMembersResponse, AdminError>;
+
+    /// List workspace members one by one, following pagination to the end
+    fn list_workspace_members_stream<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+    ) -> impl futures_util::Stream<Item = Result<WorkspaceMember, AdminError>> + Send + 'a;
+
+    /// Get a workspace member
+    async fn get_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+    ) -> Result<WorkspaceMember, AdminError>;
+
+    /// Add a user of the organization to a workspace
+    async fn add_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError>;
+
+    /// Add a user to a workspace, or return the existing membership
+    async fn ensure_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError>;
+
+    /// Change the role of a workspace member
+    async fn update_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError>;
+
+    /// Remove a member from a workspace
+    async fn remove_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+    ) -> Result<WorkspaceMemberDeleted, AdminError>;
+}
+
+/// Role of a member in a workspace
+///
+/// Values not known to this SDK are preserved in [`WorkspaceRole::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WorkspaceRole {
+    WorkspaceUser,
+    WorkspaceDeveloper,
+    WorkspaceAdmin,
+    WorkspaceBilling,
+    /// A role not known to this SDK
+    Other(String),
+}
+
+impl WorkspaceRole {
+    /// The wire representation of the role
+    pub fn as_str(&self) -> &str {
+        match self {
+            WorkspaceRole::WorkspaceUser => "workspace_user",
+            WorkspaceRole::WorkspaceDeveloper => "workspace_developer",
+            WorkspaceRole::WorkspaceAdmin => "workspace_admin",
+            WorkspaceRole::WorkspaceBilling => "workspace_billing",
+            WorkspaceRole::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for WorkspaceRole {
+    fn from(value: &str) -> Self {
+        match value {
+            "workspace_user" => WorkspaceRole::WorkspaceUser,
+            "workspace_developer" => WorkspaceRole::WorkspaceDeveloper,
+            "workspace_admin" => WorkspaceRole::WorkspaceAdmin,
+            "workspace_billing" => WorkspaceRole::WorkspaceBilling,
+            other => WorkspaceRole::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for WorkspaceRole {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for WorkspaceRole {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(WorkspaceRole::from(value.as_str()))
+    }
+}
+
+/// A member of a workspace
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::workspace_members::{WorkspaceMember, WorkspaceRole};
+///
+/// let member: WorkspaceMember = serde_json::from_str(r#"{
+///     "type": "workspace_member",
+///     "user_id": "user_01WCz1FkmYMm4gnmykNKUu3Q",
+///     "workspace_id": "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+///     "workspace_role": "workspace_developer"
+/// }"#).unwrap();
+/// assert_eq!(member.workspace_role, WorkspaceRole::WorkspaceDeveloper);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkspaceMember {
+    /// Type of the resource (always "workspace_member")
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// ID of the user
+    pub user_id: String,
+
+    /// ID of the workspace
+    pub workspace_id: String,
+
+    /// Role of the user in the workspace
+    pub workspace_role: WorkspaceRole,
+}
+
+/// Response structure for listing workspace members
+pub type ListWorkspaceMembersResponse = Page<WorkspaceMember>;
+
+/// Parameters for listing workspace members
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ListWorkspaceMembersParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+impl ListWorkspaceMembersParams {
+    /// Create a new ListWorkspaceMembersParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.min(1000));
+        self
+    }
+}
+
+impl CursorParams for ListWorkspaceMembersParams {
+    fn before_cursor(&self) -> Option<&str> {
+        self.before_id.as_deref()
+    }
+
+    fn set_before_cursor(&mut self, id: String) {
+        self.before_id = Some(id);
+    }
+
+    fn set_after_cursor(&mut self, id: String) {
+        self.after_id = Some(id);
+    }
+}
+
+/// Request body for adding a workspace member
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct AddWorkspaceMemberParams<'a> {
+    /// ID of the user to add
+    pub user_id: &'a str,
+    /// Role of the user in the workspace
+    pub workspace_role: WorkspaceRole,
+}
+
+/// Request body for updating a workspace member
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct UpdateWorkspaceMemberParams {
+    /// New role of the user in the workspace
+    pub workspace_role: WorkspaceRole,
+}
+
+/// Response for removing a workspace member
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkspaceMemberDeleted {
+    /// Type of the response (always "workspace_member_deleted")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// ID of the removed user
+    pub user_id: String,
+    /// ID of the workspace
+    pub workspace_id: String,
+}
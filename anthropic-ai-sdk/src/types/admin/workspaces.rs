@@ -0,0 +1,165 @@
+//! Workspace Management
+//!
+//! This module contains the types and functions for managing the workspaces of an organization.
+//!
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+use crate::types::admin::AdminError;
+use crate::types::pagination::{CursorParams, Page};
+
+#[async_trait]
+pub trait WorkspaceClient {
+    /// List a page of workspaces
+    async fn list_workspaces<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+    ) -> Result<ListWorkspacesResponse, AdminError>;
+
+    /// List workspaces one by one, following pagination to the end
+    fn list_workspaces_stream<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+    ) -> impl futures_util::Stream<Item = Result<Workspace, AdminError>> + Send + 'a;
+
+    /// Get a workspace
+    async fn get_workspace<'a>(&'a self, workspace_id: &'a str) -> Result<Workspace, AdminError>;
+
+    /// Create a workspace
+    async fn create_workspace<'a>(&'a self, name: &'a str) -> Result<Workspace, AdminError>;
+
+    /// Rename a workspace
+    async fn update_workspace<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        name: &'a str,
+    ) -> Result<Workspace, AdminError>;
+
+    /// Archive a workspace
+    async fn archive_workspace<'a>(
+        &'a self,
+        workspace_id: &'a str,
+    ) -> Result<Workspace, AdminError>;
+}
+
+/// A workspace of the organization
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::workspaces::Workspace;
+///
+/// let workspace: Workspace = serde_json::from_str(r##"{
+///     "id": "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+///     "type": "workspace",
+///     "name": "Customer A",
+///     "created_at": "2024-10-30T23:58:27.427722Z",
+///     "archived_at": "2024-11-01T23:59:27.427722Z",
+///     "display_color": "#6C5BB9"
+/// }"##).unwrap();
+/// assert!(workspace.is_archived());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Workspace {
+    /// Type of the resource (always "workspace")
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Unique identifier for the workspace
+    pub id: String,
+
+    /// Name of the workspace
+    pub name: String,
+
+    /// Creation timestamp of the workspace
+    #[serde(with = "rfc3339")]
+    pub created_at: OffsetDateTime,
+
+    /// When the workspace was archived, if it is archived
+    #[serde(default, with = "rfc3339::option")]
+    pub archived_at: Option<OffsetDateTime>,
+
+    /// Hex color code of the workspace in the Console
+    pub display_color: String,
+}
+
+impl Workspace {
+    /// Whether the workspace has been archived
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+}
+
+/// Response structure for listing workspaces
+pub type ListWorkspacesResponse = Page<Workspace>;
+
+/// Parameters for listing workspaces
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ListWorkspacesParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+    /// Whether to include archived workspaces (excluded by default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_archived: Option<bool>,
+}
+
+impl ListWorkspacesParams {
+    /// Create a new ListWorkspacesParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.min(1000));
+        self
+    }
+
+    /// Set whether archived workspaces are included
+    pub fn include_archived(mut self, include_archived: bool) -> Self {
+        self.include_archived = Some(include_archived);
+        self
+    }
+}
+
+impl CursorParams for ListWorkspacesParams {
+    fn before_cursor(&self) -> Option<&str> {
+        self.before_id.as_deref()
+    }
+
+    fn set_before_cursor(&mut self, id: String) {
+        self.before_id = Some(id);
+    }
+
+    fn set_after_cursor(&mut self, id: String) {
+        self.after_id = Some(id);
+    }
+}
+
+/// Request body for creating or renaming a workspace
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct WorkspaceNameParams<'a> {
+    /// Name of the workspace
+    pub name: &'a str,
+}
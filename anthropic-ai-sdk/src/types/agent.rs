@@ -0,0 +1,1204 @@
+//! Tool use loops
+//!
+//! This module contains [`run_tools`], which keeps calling the Messages API and
+//! executing the tools the model asks for until it ends its turn.
+//!
+//! # Examples
+//!
+//! ```
+//! use anthropic_ai_sdk::testing::MockMessageClient;
+//! use anthropic_ai_sdk::types::agent::{FnTool, RunToolsOptions, ToolRegistry, run_tools};
+//! use anthropic_ai_sdk::types::message::{
+//!     ContentBlock, CreateMessageParams, Message, MessageError, RequiredMessageParams, Role,
+//!     StopReason, Tool, ToolResultContent,
+//! };
+//! use anthropic_ai_sdk::types::tool::ParamType;
+//! use serde::Deserialize;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), MessageError> {
+//! #[derive(Deserialize)]
+//! struct Weather {
+//!     city: String,
+//! }
+//!
+//! let definition = Tool::builder("get_weather")
+//!     .param("city", ParamType::String, "City name", true)
+//!     .build()
+//!     .unwrap();
+//! let registry = ToolRegistry::new().register(FnTool::new(definition, |input: Weather| async move {
+//!     Ok(format!("Sunny in {}", input.city).into())
+//! }));
+//! let params = CreateMessageParams::new(RequiredMessageParams {
+//!     model: "claude-3-7-sonnet-20250219".to_string(),
+//!     messages: vec![Message::new_text(Role::User, "What's the weather in Paris?")],
+//!     max_tokens: 1024,
+//! })
+//! .with_tools(registry.tools());
+//!
+//! // The model asks for the weather, then answers with the tool result
+//! let client = MockMessageClient::new()
+//!     .tool_use_response("get_weather", serde_json::json!({"city": "Paris"}))
+//!     .text_response("It is sunny in Paris.")
+//!     .expect_calls(2);
+//!
+//! let run = run_tools(&client, params, &registry, &RunToolsOptions::new()).await?;
+//! assert_eq!(run.response.stop_reason, Some(StopReason::EndTurn));
+//! assert_eq!(run.response.text(), "It is sunny in Paris.");
+//! assert_eq!(run.steps.len(), 2);
+//! assert_eq!(run.steps[0].tool_calls[0].name, "get_weather");
+//! assert_eq!(run.conversation.messages().len(), 4);
+//!
+//! // The tool result was sent with the second request
+//! let request = client.last_request().unwrap();
+//! let blocks = request.messages[2].blocks();
+//! let [ContentBlock::ToolResult { content, .. }] = &*blocks else {
+//!     panic!("expected a tool result");
+//! };
+//! assert_eq!(*content, ToolResultContent::Text("Sunny in Paris".to_string()));
+//! # Ok(())
+//! # }
+//! ```
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use crate::types::budget::UsageTracker;
+use crate::types::conversation::{Conversation, TurnStream};
+use crate::types::message::{
+    ContentBlock, CreateMessageParams, CreateMessageResponse, MessageAccumulator, MessageClient,
+    MessageError, StopReason, StreamEvent, Tool, ToolResultContent,
+};
+use crate::types::tool::ToolUseRef;
+
+/// Output of a tool execution, sent back as the content of its `tool_result`
+pub type ToolOutput = ToolResultContent;
+
+/// Error types for tool executions
+///
+/// The error message is sent back to the model as an `is_error` tool result.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ToolError {
+    #[error("Unknown tool: {0}")]
+    UnknownTool(String),
+    #[error("Invalid tool input: {0}")]
+    InvalidInput(String),
+    #[error("{0}")]
+    Failed(String),
+    #[error("Tool timed out after {0:?}")]
+    Timeout(Duration),
+    #[error("Tool panicked: {0}")]
+    Panicked(String),
+    #[error("Tool use denied: {0}")]
+    Denied(String),
+}
+
+impl From<String> for ToolError {
+    fn from(error: String) -> Self {
+        ToolError::Failed(error)
+    }
+}
+
+impl From<serde_json::Error> for ToolError {
+    fn from(error: serde_json::Error) -> Self {
+        ToolError::InvalidInput(error.to_string())
+    }
+}
+
+/// A client tool the model can call
+///
+/// Implement this for tools with their own state, or wrap a closure in a
+/// [`FnTool`] for simple ones.
+#[async_trait]
+pub trait AgentTool: Send + Sync {
+    /// Name of the tool, as used in `tool_use` blocks
+    fn name(&self) -> &str;
+
+    /// Definition of the tool sent to the model
+    fn definition(&self) -> Tool;
+
+    /// Execute the tool with the input of a tool use
+    async fn call(&self, input: Value) -> Result<ToolResultContent, ToolError>;
+
+    /// Whether the tool may run at the same time as other tools
+    ///
+    /// Executions of tools returning `false` never overlap with any other
+    /// tool execution of the same turn.
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+}
+
+/// A tool calling an async closure with its input deserialized into `T`
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::agent::{AgentTool, FnTool};
+/// use anthropic_ai_sdk::types::message::{Tool, ToolResultContent};
+/// use anthropic_ai_sdk::types::tool::ParamType;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Weather {
+///     city: String,
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let definition = Tool::builder("get_weather")
+///     .param("city", ParamType::String, "City name", true)
+///     .build()
+///     .unwrap();
+/// let weather = FnTool::new(definition, |input: Weather| async move {
+///     Ok(format!("Sunny in {}", input.city).into())
+/// });
+///
+/// let output = weather.call(serde_json::json!({"city": "Paris"})).await.unwrap();
+/// assert_eq!(output, ToolResultContent::Text("Sunny in Paris".to_string()));
+/// assert!(weather.call(serde_json::json!({})).await.is_err());
+/// # }
+/// ```
+pub struct FnTool<T, F> {
+    definition: Tool,
+    function: F,
+    parallel_safe: bool,
+    input: PhantomData<fn() -> T>,
+}
+
+impl<T, F, Fut> FnTool<T, F>
+where
+    T: DeserializeOwned,
+    F: Fn(T) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<ToolOutput, ToolError>> + Send,
+{
+    /// Create a tool with the given definition, executed by `function`
+    pub fn new(definition: Tool, function: F) -> Self {
+        Self {
+            definition,
+            function,
+            parallel_safe: true,
+            input: PhantomData,
+        }
+    }
+
+    /// Set whether the tool may run at the same time as other tools
+    pub fn parallel_safe(mut self, parallel_safe: bool) -> Self {
+        self.parallel_safe = parallel_safe;
+        self
+    }
+
+    /// Create a tool whose input schema is generated from `T`
+    #[cfg(feature = "schemars")]
+    pub fn from_type(name: impl Into<String>, description: impl Into<String>, function: F) -> Self
+    where
+        T: schemars::JsonSchema,
+    {
+        Self::new(Tool::from_type::<T>(name, description), function)
+    }
+}
+
+#[async_trait]
+impl<T, F, Fut> AgentTool for FnTool<T, F>
+where
+    T: DeserializeOwned,
+    F: Fn(T) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<ToolOutput, ToolError>> + Send,
+{
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    fn definition(&self) -> Tool {
+        self.definition.clone()
+    }
+
+    async fn call(&self, input: Value) -> Result<ToolResultContent, ToolError> {
+        let input = serde_json::from_value(input)?;
+        (self.function)(input).await
+    }
+
+    fn parallel_safe(&self) -> bool {
+        self.parallel_safe
+    }
+}
+
+impl<T, F> std::fmt::Debug for FnTool<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnTool")
+            .field("definition", &self.definition)
+            .field("parallel_safe", &self.parallel_safe)
+            .finish()
+    }
+}
+
+/// Time a tool may run before its execution is reported as failed
+pub const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Client tools available to the model, dispatched by name
+///
+/// Each execution runs in its own task, so a tool that panics fails with
+/// [`ToolError::Panicked`] instead of taking the loop down, and a tool that
+/// runs longer than its timeout is cancelled and fails with
+/// [`ToolError::Timeout`]. The timeout is [`DEFAULT_TOOL_TIMEOUT`] unless set
+/// with [`with_timeout`](Self::with_timeout) or, for one tool,
+/// [`with_tool_timeout`](Self::with_tool_timeout). Executions need a Tokio
+/// runtime.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::agent::{FnTool, ToolError, ToolRegistry};
+/// use anthropic_ai_sdk::types::message::{
+///     ContentBlock, CreateMessageParams, Tool, ToolResultContent,
+/// };
+/// use anthropic_ai_sdk::types::tool::{ParamType, ToolUseRef};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let definition = Tool::builder("divide")
+///     .param("divisor", ParamType::Number, "Number to divide one by", true)
+///     .build()
+///     .unwrap();
+/// let registry = ToolRegistry::new().register(FnTool::new(definition, |input: serde_json::Value| async move {
+///     let divisor = input["divisor"].as_f64().unwrap_or_default();
+///     if divisor == 0.0 {
+///         return Err(ToolError::Failed("Division by zero".to_string()));
+///     }
+///     Ok((1.0 / divisor).to_string().into())
+/// }));
+/// let params = CreateMessageParams::default().with_tools(registry.tools());
+/// assert_eq!(params.tools.unwrap().len(), 1);
+///
+/// let (tool_use, _) = ContentBlock::tool_use("divide", serde_json::json!({"divisor": 0}));
+/// let call = registry.execute(ToolUseRef::from_block(&tool_use).unwrap()).await;
+/// assert!(call.is_error);
+/// assert_eq!(call.content, ToolResultContent::Text("Division by zero".to_string()));
+///
+/// let (tool_use, _) = ContentBlock::tool_use("multiply", serde_json::json!({}));
+/// let call = registry.execute(ToolUseRef::from_block(&tool_use).unwrap()).await;
+/// assert_eq!(call.content, ToolResultContent::Text("Unknown tool: multiply".to_string()));
+/// # }
+/// ```
+///
+/// Hanging and panicking tools fail without stalling or crashing the caller:
+///
+/// ```
+/// use std::time::Duration;
+/// use anthropic_ai_sdk::types::agent::{FnTool, ToolError, ToolRegistry};
+/// use anthropic_ai_sdk::types::message::{ContentBlock, Tool, ToolResultContent};
+/// use anthropic_ai_sdk::types::tool::ToolUseRef;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let definition = |name: &str| Tool::builder(name).build().unwrap();
+/// let registry = ToolRegistry::new()
+///     .register(FnTool::new(definition("hang"), |_: serde_json::Value| async {
+///         std::future::pending::<Result<ToolResultContent, ToolError>>().await
+///     }))
+///     .register(FnTool::new(definition("crash"), |_: serde_json::Value| async {
+///         panic!("index out of bounds")
+///     }))
+///     .with_tool_timeout("hang", Duration::from_millis(10));
+///
+/// let (tool_use, _) = ContentBlock::tool_use("hang", serde_json::json!({}));
+/// let call = registry.execute(ToolUseRef::from_block(&tool_use).unwrap()).await;
+/// assert!(call.is_error);
+/// assert_eq!(call.content, ToolResultContent::Text("Tool timed out after 10ms".to_string()));
+///
+/// let (tool_use, _) = ContentBlock::tool_use("crash", serde_json::json!({}));
+/// let call = registry.execute(ToolUseRef::from_block(&tool_use).unwrap()).await;
+/// assert!(call.is_error);
+/// assert_eq!(
+///     call.content,
+///     ToolResultContent::Text("Tool panicked: index out of bounds".to_string())
+/// );
+/// # }
+/// ```
+pub struct ToolRegistry {
+    tools: Vec<Arc<dyn AgentTool>>,
+    timeout: Duration,
+    tool_timeouts: HashMap<String, Duration>,
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        Self {
+            tools: Vec::new(),
+            timeout: DEFAULT_TOOL_TIMEOUT,
+            tool_timeouts: HashMap::new(),
+        }
+    }
+}
+
+impl ToolRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the time every tool may run, unless set for the tool itself
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the time the tool named `name` may run
+    pub fn with_tool_timeout(mut self, name: impl Into<String>, timeout: Duration) -> Self {
+        self.tool_timeouts.insert(name.into(), timeout);
+        self
+    }
+
+    /// Time the tool named `name` may run
+    pub fn timeout(&self, name: &str) -> Duration {
+        self.tool_timeouts
+            .get(name)
+            .copied()
+            .unwrap_or(self.timeout)
+    }
+
+    /// Add a tool
+    ///
+    /// A tool added earlier under the same name is replaced.
+    pub fn register(self, tool: impl AgentTool + 'static) -> Self {
+        self.register_boxed(Box::new(tool))
+    }
+
+    /// Add a boxed tool
+    ///
+    /// A tool added earlier under the same name is replaced.
+    pub fn register_boxed(mut self, tool: Box<dyn AgentTool>) -> Self {
+        let tool: Arc<dyn AgentTool> = Arc::from(tool);
+        match self.tools.iter().position(|t| t.name() == tool.name()) {
+            Some(index) => self.tools[index] = tool,
+            None => self.tools.push(tool),
+        }
+        self
+    }
+
+    /// The tool named `name`
+    pub fn get(&self, name: &str) -> Option<&dyn AgentTool> {
+        self.tools
+            .iter()
+            .find(|tool| tool.name() == name)
+            .map(|tool| &**tool)
+    }
+
+    /// Whether a tool named `name` is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Definitions of every tool, in registration order, for
+    /// [`with_tools`](CreateMessageParams::with_tools)
+    pub fn tools(&self) -> Vec<Tool> {
+        self.tools.iter().map(|tool| tool.definition()).collect()
+    }
+
+    /// Execute a tool use with the tool of the same name
+    ///
+    /// Failures, including calls of unknown tools, timeouts and panics, are
+    /// reported in the returned call rather than as an error.
+    pub async fn execute(&self, tool_use: ToolUseRef<'_>) -> ToolCall {
+        let tool = self.tools.iter().find(|tool| tool.name() == tool_use.name);
+        let result = match tool {
+            Some(tool) => {
+                let timeout = self.timeout(tool_use.name);
+                isolate(tool.clone(), tool_use.input.clone(), timeout).await
+            }
+            None => Err(ToolError::UnknownTool(tool_use.name.to_string())),
+        };
+        let (content, is_error) = match result {
+            Ok(content) => (content, false),
+            Err(error) => (ToolResultContent::Text(error.to_string()), true),
+        };
+        ToolCall {
+            id: tool_use.id.to_string(),
+            name: tool_use.name.to_string(),
+            input: tool_use.input.clone(),
+            content,
+            is_error,
+            approval: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.tools.iter().map(|tool| tool.name()).collect();
+        f.debug_struct("ToolRegistry")
+            .field("tools", &names)
+            .finish()
+    }
+}
+
+/// Call `tool` in its own task, cancelling it after `timeout`
+async fn isolate(
+    tool: Arc<dyn AgentTool>,
+    input: Value,
+    timeout: Duration,
+) -> Result<ToolResultContent, ToolError> {
+    let mut task = tokio::spawn(async move { tool.call(input).await });
+    match tokio::time::timeout(timeout, &mut task).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(error)) => match error.try_into_panic() {
+            Ok(panic) => Err(ToolError::Panicked(panic_message(panic))),
+            Err(error) => Err(ToolError::Failed(error.to_string())),
+        },
+        Err(_) => {
+            task.abort();
+            Err(ToolError::Timeout(timeout))
+        }
+    }
+}
+
+/// The message a task panicked with
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic payload".to_string(),
+        },
+    }
+}
+
+/// Decision of an [`ApprovalHook`] about a pending tool use
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolApproval {
+    /// Execute the tool with the input of the tool use
+    Approve,
+    /// Do not execute the tool, answer the tool use with an error containing the reason
+    Deny(String),
+    /// Execute the tool with this input instead
+    Modify(Value),
+}
+
+/// Asks for approval before a tool loop executes a tool
+///
+/// The hook is awaited with the name and input of every tool use of the
+/// registry before it is executed, one tool use at a time and in order, so
+/// it can wait for a human decision. Tools that need no approval can simply
+/// be approved. Async closures taking `(&str, &Value)` implement this trait.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::agent::{
+///     FnTool, RunToolsOptions, ToolApproval, ToolRegistry, run_tools,
+/// };
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, RequiredMessageParams, Role, ToolResultContent,
+/// };
+/// use anthropic_ai_sdk::types::tool::ParamType;
+/// use serde_json::{Value, json};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let send_email = anthropic_ai_sdk::types::message::Tool::builder("send_email")
+///     .param("to", ParamType::String, "Recipient", true)
+///     .build()?;
+/// let registry = ToolRegistry::new().register(FnTool::new(send_email, |input: Value| async move {
+///     Ok(format!("Sent to {}", input["to"].as_str().unwrap()).into())
+/// }));
+///
+/// // Redirect internal mail, refuse everything else
+/// let options = RunToolsOptions::new().approval(|_name: &str, input: &Value| {
+///     let to = input["to"].as_str().unwrap_or_default().to_string();
+///     async move {
+///         match to.strip_suffix("@example.com") {
+///             Some(user) => ToolApproval::Modify(json!({"to": format!("{user}@test.example.com")})),
+///             None => ToolApproval::Deny("external recipients need a manager".to_string()),
+///         }
+///     }
+/// });
+///
+/// let client = MockMessageClient::new()
+///     .tool_use_response("send_email", json!({"to": "ann@example.com"}))
+///     .tool_use_response("send_email", json!({"to": "bob@elsewhere.com"}))
+///     .text_response("Done");
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-sonnet-4-5".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Send the report")],
+///     max_tokens: 1024,
+/// });
+/// let run = run_tools(&client, params, &registry, &options).await?;
+///
+/// let modified = &run.steps[0].tool_calls[0];
+/// assert_eq!(modified.content, ToolResultContent::Text("Sent to ann@test.example.com".into()));
+/// assert_eq!(
+///     modified.approval,
+///     Some(ToolApproval::Modify(json!({"to": "ann@test.example.com"})))
+/// );
+/// let denied = &run.steps[1].tool_calls[0];
+/// assert!(denied.is_error);
+/// assert_eq!(
+///     denied.content,
+///     ToolResultContent::Text("Tool use denied: external recipients need a manager".into())
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait ApprovalHook: Send + Sync {
+    /// Decide whether the tool `name` may run with `input`
+    async fn approve(&self, name: &str, input: &Value) -> ToolApproval;
+}
+
+#[async_trait]
+impl<F, Fut> ApprovalHook for F
+where
+    F: Fn(&str, &Value) -> Fut + Send + Sync,
+    Fut: Future<Output = ToolApproval> + Send,
+{
+    async fn approve(&self, name: &str, input: &Value) -> ToolApproval {
+        self(name, input).await
+    }
+}
+
+/// Number of tools executed at a time by default when they run in parallel
+pub const DEFAULT_MAX_PARALLEL_TOOLS: usize = 4;
+
+/// Options of [`run_tools`] and [`stream_tools`]
+#[derive(Clone)]
+pub struct RunToolsOptions {
+    /// Maximum number of requests to send
+    pub max_iterations: usize,
+    /// Whether the tool uses of a response are executed concurrently
+    pub parallel: bool,
+    /// Maximum number of tools executed at a time when `parallel` is set
+    pub max_parallel: usize,
+    /// Tracker checked before and updated after every request
+    pub usage_tracker: Option<Arc<UsageTracker>>,
+    /// Hook asked before every tool execution
+    pub approval: Option<Arc<dyn ApprovalHook>>,
+}
+
+impl Default for RunToolsOptions {
+    fn default() -> Self {
+        Self {
+            max_iterations: 10,
+            parallel: true,
+            max_parallel: DEFAULT_MAX_PARALLEL_TOOLS,
+            usage_tracker: None,
+            approval: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for RunToolsOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunToolsOptions")
+            .field("max_iterations", &self.max_iterations)
+            .field("parallel", &self.parallel)
+            .field("max_parallel", &self.max_parallel)
+            .field("usage_tracker", &self.usage_tracker)
+            .field("approval", &self.approval.is_some())
+            .finish()
+    }
+}
+
+/// Options are equal if they share the same hook, if any
+impl PartialEq for RunToolsOptions {
+    fn eq(&self, other: &Self) -> bool {
+        let same_approval = match (&self.approval, &other.approval) {
+            (Some(hook), Some(other)) => Arc::ptr_eq(hook, other),
+            (hook, other) => hook.is_none() && other.is_none(),
+        };
+        self.max_iterations == other.max_iterations
+            && self.parallel == other.parallel
+            && self.max_parallel == other.max_parallel
+            && self.usage_tracker == other.usage_tracker
+            && same_approval
+    }
+}
+
+impl Eq for RunToolsOptions {}
+
+impl RunToolsOptions {
+    /// Create new options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of requests to send
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    /// Set whether the tool uses of a response are executed concurrently
+    ///
+    /// Enabled by default. Tools that are not
+    /// [parallel safe](AgentTool::parallel_safe) run on their own either way.
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Set the maximum number of tools executed at a time; 0 is treated as 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::time::Duration;
+    /// use anthropic_ai_sdk::testing::MockMessageClient;
+    /// use anthropic_ai_sdk::types::agent::{FnTool, RunToolsOptions, ToolRegistry, run_tools};
+    /// use anthropic_ai_sdk::types::message::{
+    ///     ContentBlock, CreateMessageParams, CreateMessageResponse, Message,
+    ///     RequiredMessageParams, Role, Tool, ToolResultContent,
+    /// };
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// // Tools recording how many executions overlap
+    /// let running = Arc::new(AtomicUsize::new(0));
+    /// let overlap = Arc::new(AtomicUsize::new(0));
+    /// let fake = |name: &str| {
+    ///     let (running, overlap) = (running.clone(), overlap.clone());
+    ///     let name = name.to_string();
+    ///     FnTool::new(Tool::builder(&name).build().unwrap(), move |_: serde_json::Value| {
+    ///         let (running, overlap, name) = (running.clone(), overlap.clone(), name.clone());
+    ///         async move {
+    ///             let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+    ///             overlap.fetch_max(now, Ordering::SeqCst);
+    ///             tokio::time::sleep(Duration::from_millis(20)).await;
+    ///             running.fetch_sub(1, Ordering::SeqCst);
+    ///             Ok(ToolResultContent::Text(name))
+    ///         }
+    ///     })
+    /// };
+    /// let registry = ToolRegistry::new()
+    ///     .register(fake("a"))
+    ///     .register(fake("b"))
+    ///     .register(fake("c"))
+    ///     .register(fake("exclusive").parallel_safe(false));
+    ///
+    /// let turn = |names: &[&str]| -> CreateMessageResponse {
+    ///     let content: Vec<_> = names
+    ///         .iter()
+    ///         .map(|name| ContentBlock::tool_use(*name, serde_json::json!({})).0)
+    ///         .collect();
+    ///     serde_json::from_value(serde_json::json!({
+    ///         "id": "msg_01", "type": "message", "role": "assistant",
+    ///         "model": "claude-sonnet-4-5", "content": content, "stop_reason": "tool_use",
+    ///         "usage": {"input_tokens": 10, "output_tokens": 5}
+    ///     }))
+    ///     .unwrap()
+    /// };
+    /// let client = MockMessageClient::new()
+    ///     .response(turn(&["c", "a", "b"]))
+    ///     .response(turn(&["a", "exclusive", "b"]))
+    ///     .text_response("Done");
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-5".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Run the tools")],
+    ///     max_tokens: 1024,
+    /// });
+    ///
+    /// let options = RunToolsOptions::new().max_parallel(3);
+    /// let run = run_tools(&client, params, &registry, &options).await?;
+    /// assert_eq!(overlap.load(Ordering::SeqCst), 3);
+    ///
+    /// // Results are sent in the order of the tool uses
+    /// let results: Vec<_> = run.steps[0].tool_calls.iter().map(|call| &call.name).collect();
+    /// assert_eq!(results, ["c", "a", "b"]);
+    /// let sent = &client.requests()[1].messages[2];
+    /// let ids: Vec<_> = sent.blocks().iter().map(|block| match block {
+    ///     ContentBlock::ToolResult { tool_use_id, .. } => tool_use_id.clone(),
+    ///     _ => unreachable!(),
+    /// }).collect();
+    /// let uses: Vec<_> = run.steps[0].tool_calls.iter().map(|call| call.id.clone()).collect();
+    /// assert_eq!(ids, uses);
+    ///
+    /// // The exclusive tool ran on its own
+    /// overlap.store(0, Ordering::SeqCst);
+    /// let client = MockMessageClient::new()
+    ///     .response(turn(&["exclusive", "a"]))
+    ///     .text_response("Done");
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-5".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Run the tools")],
+    ///     max_tokens: 1024,
+    /// });
+    /// run_tools(&client, params, &registry, &options).await?;
+    /// assert_eq!(overlap.load(Ordering::SeqCst), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = max_parallel.max(1);
+        self
+    }
+
+    /// Record the usage of every request with `tracker` and stop once its budget is used up
+    pub fn usage_tracker(mut self, tracker: Arc<UsageTracker>) -> Self {
+        self.usage_tracker = Some(tracker);
+        self
+    }
+
+    /// Ask `hook` for approval before executing any tool, see [`ApprovalHook`]
+    pub fn approval(mut self, hook: impl ApprovalHook + 'static) -> Self {
+        self.approval = Some(Arc::new(hook));
+        self
+    }
+
+    /// Number of tools executed at a time
+    fn concurrency(&self) -> usize {
+        if self.parallel {
+            self.max_parallel.max(1)
+        } else {
+            1
+        }
+    }
+}
+
+/// A tool use and the result of executing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    /// ID of the tool use
+    pub id: String,
+    /// Name of the tool
+    pub name: String,
+    /// Input of the tool use
+    pub input: Value,
+    /// Output of the tool, or the error message if it failed
+    pub content: ToolResultContent,
+    /// Whether the execution failed
+    pub is_error: bool,
+    /// Decision of the [approval hook](RunToolsOptions::approval), if one was asked
+    ///
+    /// A [modified](ToolApproval::Modify) tool use was executed with the new
+    /// input, while `input` is the one the model sent.
+    pub approval: Option<ToolApproval>,
+}
+
+impl ToolCall {
+    /// The `tool_result` block answering the tool use
+    pub fn to_block(&self) -> ContentBlock {
+        if self.is_error {
+            ContentBlock::tool_error(&self.id, self.content.clone())
+        } else {
+            ContentBlock::tool_result(&self.id, self.content.clone())
+        }
+    }
+}
+
+/// A response of a tool loop and the tools executed for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolRunStep {
+    /// Response of the model
+    pub response: CreateMessageResponse,
+    /// Tool uses of the response that were executed, in order
+    pub tool_calls: Vec<ToolCall>,
+}
+
+/// Outcome of [`run_tools`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolRun {
+    /// The last response of the model
+    pub response: CreateMessageResponse,
+    /// Every response of the loop, including the last one
+    pub steps: Vec<ToolRunStep>,
+    /// The full history and total usage of the loop
+    pub conversation: Conversation,
+}
+
+impl ToolRun {
+    /// Whether the model ended its turn, rather than the loop hitting
+    /// [`max_iterations`](RunToolsOptions::max_iterations)
+    pub fn is_complete(&self) -> bool {
+        match &self.response.stop_reason {
+            Some(StopReason::ToolUse) => false,
+            Some(reason) => !reason.is_continuable(),
+            None => true,
+        }
+    }
+}
+
+/// Send `params` and execute the requested tools until the model ends its turn
+///
+/// While the model stops to use tools, every `tool_use` block of its response
+/// is executed with `registry` and the results are sent back in the next user
+/// message. Failed executions, including tools that time out or panic (see
+/// [`ToolRegistry`]), are sent as `is_error` tool results. With an
+/// [approval hook](RunToolsOptions::approval), the tool uses of a response
+/// are all decided on before any of them is executed. Paused turns are
+/// continued as they are. The loop stops after
+/// [`max_iterations`](RunToolsOptions::max_iterations) requests, leaving the
+/// tool uses of the last response unanswered.
+///
+/// # Errors
+///
+/// Returns the error of the first request that fails, or
+/// [`MessageError::BudgetExceeded`] once the budget of the
+/// [`usage_tracker`](RunToolsOptions::usage_tracker) is used up.
+pub async fn run_tools<C>(
+    client: &C,
+    params: CreateMessageParams,
+    registry: &ToolRegistry,
+    options: &RunToolsOptions,
+) -> Result<ToolRun, MessageError>
+where
+    C: MessageClient + Sync + ?Sized,
+{
+    let mut conversation = start(params, options);
+    let mut steps = Vec::new();
+    let mut response = conversation.continue_turn(client).await?.into_response();
+    let mut iterations = 1;
+    loop {
+        let tool_calls = match next_step(&response, iterations, options) {
+            NextStep::Finish => {
+                steps.push(ToolRunStep {
+                    response: response.clone(),
+                    tool_calls: Vec::new(),
+                });
+                break;
+            }
+            NextStep::Execute => {
+                let mut approvals = Vec::new();
+                for tool_use in response.content.iter().filter_map(ToolUseRef::from_block) {
+                    approvals.push(approve(registry, options, tool_use).await);
+                }
+                execute_all(registry, &response, approvals, options.concurrency()).await
+            }
+            NextStep::Continue => Vec::new(),
+        };
+        let next = if tool_calls.is_empty() {
+            conversation.continue_turn(client).await?.into_response()
+        } else {
+            let results = tool_calls.iter().map(ToolCall::to_block).collect();
+            conversation
+                .send_blocks(client, results)
+                .await?
+                .into_response()
+        };
+        steps.push(ToolRunStep {
+            response: std::mem::replace(&mut response, next),
+            tool_calls,
+        });
+        iterations += 1;
+    }
+    Ok(ToolRun {
+        response,
+        steps,
+        conversation,
+    })
+}
+
+/// Event of a tool loop run with [`stream_tools`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolRunEvent {
+    /// Event of a streamed response
+    Stream(StreamEvent),
+    /// A tool use was executed, sent once every tool use of the response ran
+    ToolCall(ToolCall),
+    /// The loop has ended, always the last event
+    Finished(Box<ToolRun>),
+}
+
+/// Stream the responses of a tool loop as they arrive
+///
+/// The loop is the one of [`run_tools`], with every request streamed. Events
+/// of the responses are forwarded as they arrive, followed by the executed
+/// [tool calls](ToolRunEvent::ToolCall) of each response and finally by the
+/// whole [run](ToolRunEvent::Finished). With an
+/// [approval hook](RunToolsOptions::approval), the stream pauses after each
+/// completed `tool_use` block until the hook has decided on it; the tools run
+/// once the response is complete. Dropping the stream stops the loop.
+///
+/// After an error, the stream ends with it.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::agent::{
+///     FnTool, RunToolsOptions, ToolApproval, ToolRegistry, ToolRunEvent, stream_tools,
+/// };
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, RequiredMessageParams, Role, StreamEvent,
+/// };
+/// use anthropic_ai_sdk::types::tool::ParamType;
+/// use futures_util::StreamExt;
+/// use serde_json::{Value, json};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let definition = anthropic_ai_sdk::types::message::Tool::builder("pay")
+///     .param("amount", ParamType::Number, "Amount in USD", true)
+///     .build()?;
+/// let registry = ToolRegistry::new()
+///     .register(FnTool::new(definition, |_: Value| async { Ok("Paid".into()) }));
+/// let options = RunToolsOptions::new().approval(|_name: &str, _input: &Value| async {
+///     ToolApproval::Deny("payments are disabled".to_string())
+/// });
+///
+/// let client = MockMessageClient::new()
+///     .tool_use_response("pay", json!({"amount": 100}))
+///     .text_response("I could not pay.");
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-sonnet-4-5".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Pay the invoice")],
+///     max_tokens: 1024,
+/// });
+/// let events: Vec<_> = stream_tools(&client, params, &registry, &options).collect().await;
+///
+/// let calls: Vec<_> = events
+///     .iter()
+///     .filter_map(|event| match event {
+///         Ok(ToolRunEvent::ToolCall(call)) => Some(call),
+///         _ => None,
+///     })
+///     .collect();
+/// assert_eq!(calls.len(), 1);
+/// assert!(calls[0].is_error);
+/// assert!(matches!(
+///     events.last(),
+///     Some(Ok(ToolRunEvent::Finished(run))) if run.response.text() == "I could not pay."
+/// ));
+/// assert!(events.iter().any(|event| matches!(event, Ok(ToolRunEvent::Stream(StreamEvent::MessageStop)))));
+/// # Ok(())
+/// # }
+/// ```
+pub fn stream_tools<'a, C>(
+    client: &'a C,
+    params: CreateMessageParams,
+    registry: &'a ToolRegistry,
+    options: &'a RunToolsOptions,
+) -> impl Stream<Item = Result<ToolRunEvent, MessageError>> + 'a
+where
+    C: MessageClient + Sync + ?Sized,
+{
+    let (sender, receiver) = mpsc::channel(1);
+    let driver = async move {
+        let result = async {
+            let mut conversation = start(params, options);
+            let mut steps = Vec::new();
+            let mut results = None;
+            let mut iterations = 1;
+            loop {
+                // Tool uses of the last response are left unanswered, so not approved either
+                let approving = iterations < options.max_iterations;
+                conversation.check_budget()?;
+                let request = conversation.request(results.take(), true);
+                let mut accumulator = MessageAccumulator::new();
+                let mut approvals = Vec::new();
+                {
+                    let events = client.create_message_streaming(&request).await?;
+                    let mut events = pin!(events);
+                    while let Some(event) = events.next().await {
+                        let event = event?;
+                        accumulator.push(&event)?;
+                        let completed = match &event {
+                            StreamEvent::ContentBlockStop { index } if approving => accumulator
+                                .response()
+                                .and_then(|response| response.content.get(*index))
+                                .filter(|block| matches!(block, ContentBlock::ToolUse { .. }))
+                                .cloned(),
+                            _ => None,
+                        };
+                        if sender.send(Ok(ToolRunEvent::Stream(event))).await.is_err() {
+                            return Ok(());
+                        }
+                        if let Some(tool_use) = completed.as_ref().and_then(ToolUseRef::from_block)
+                        {
+                            approvals.push(approve(registry, options, tool_use).await);
+                        }
+                    }
+                }
+                let response = accumulator.finish()?;
+                conversation.record(request.messages, response.clone());
+
+                let tool_calls = match next_step(&response, iterations, options) {
+                    NextStep::Finish => {
+                        steps.push(ToolRunStep {
+                            response,
+                            tool_calls: Vec::new(),
+                        });
+                        break;
+                    }
+                    NextStep::Execute => {
+                        execute_all(registry, &response, approvals, options.concurrency()).await
+                    }
+                    NextStep::Continue => Vec::new(),
+                };
+                for call in &tool_calls {
+                    if sender
+                        .send(Ok(ToolRunEvent::ToolCall(call.clone())))
+                        .await
+                        .is_err()
+                    {
+                        return Ok(());
+                    }
+                }
+                if !tool_calls.is_empty() {
+                    results = Some(tool_calls.iter().map(ToolCall::to_block).collect());
+                }
+                steps.push(ToolRunStep {
+                    response,
+                    tool_calls,
+                });
+                iterations += 1;
+            }
+            let run = ToolRun {
+                response: steps[steps.len() - 1].response.clone(),
+                steps,
+                conversation,
+            };
+            let _ = sender.send(Ok(ToolRunEvent::Finished(Box::new(run)))).await;
+            Ok(())
+        }
+        .await;
+        if let Err(error) = result {
+            let _ = sender.send(Err(error)).await;
+        }
+    };
+    TurnStream {
+        driver: Some(Box::pin(driver)),
+        receiver,
+    }
+}
+
+/// Conversation of a tool loop starting with `params`
+fn start(params: CreateMessageParams, options: &RunToolsOptions) -> Conversation {
+    let conversation = Conversation::from_params(params);
+    match &options.usage_tracker {
+        Some(tracker) => conversation.with_usage_tracker(tracker.clone()),
+        None => conversation,
+    }
+}
+
+/// What a tool loop does after a response
+enum NextStep {
+    /// Return the response
+    Finish,
+    /// Execute its tool uses and send the results
+    Execute,
+    /// Continue the paused turn
+    Continue,
+}
+
+/// The step after the response to request number `iterations`
+fn next_step(
+    response: &CreateMessageResponse,
+    iterations: usize,
+    options: &RunToolsOptions,
+) -> NextStep {
+    let tool_use = response.stop_reason == Some(StopReason::ToolUse);
+    let continuable = response
+        .stop_reason
+        .as_ref()
+        .is_some_and(StopReason::is_continuable);
+    if !(tool_use || continuable) || iterations >= options.max_iterations {
+        NextStep::Finish
+    } else if tool_use {
+        NextStep::Execute
+    } else {
+        NextStep::Continue
+    }
+}
+
+/// Ask the approval hook of `options` about a tool use of the registry
+async fn approve(
+    registry: &ToolRegistry,
+    options: &RunToolsOptions,
+    tool_use: ToolUseRef<'_>,
+) -> Option<ToolApproval> {
+    let hook = options.approval.as_ref()?;
+    if !registry.contains(tool_use.name) {
+        return None;
+    }
+    let approval = hook.approve(tool_use.name, tool_use.input).await;
+    match &approval {
+        ToolApproval::Approve => {}
+        ToolApproval::Deny(reason) => {
+            tracing::info!(
+                tool = tool_use.name,
+                id = tool_use.id,
+                reason,
+                "tool use denied"
+            );
+        }
+        ToolApproval::Modify(input) => {
+            tracing::info!(
+                tool = tool_use.name,
+                id = tool_use.id,
+                original = %tool_use.input,
+                modified = %input,
+                "tool input modified"
+            );
+        }
+    }
+    Some(approval)
+}
+
+/// Execute every tool use of `response`, `concurrency` at a time
+///
+/// `approvals` holds the decision about each tool use, in order; tool uses
+/// without one are executed. The calls are returned in the order of the tool
+/// uses. Tools that are not parallel safe hold the lock exclusively, the
+/// others share it.
+async fn execute_all(
+    registry: &ToolRegistry,
+    response: &CreateMessageResponse,
+    approvals: Vec<Option<ToolApproval>>,
+    concurrency: usize,
+) -> Vec<ToolCall> {
+    let lock = tokio::sync::RwLock::new(());
+    let tool_uses = response.content.iter().filter_map(ToolUseRef::from_block);
+    let approvals = approvals.into_iter().chain(std::iter::repeat(None));
+    futures_util::stream::iter(tool_uses.zip(approvals))
+        .map(|(tool_use, approval)| {
+            let lock = &lock;
+            async move {
+                let input = match &approval {
+                    Some(ToolApproval::Deny(reason)) => {
+                        let error = ToolError::Denied(reason.clone());
+                        return ToolCall {
+                            id: tool_use.id.to_string(),
+                            name: tool_use.name.to_string(),
+                            input: tool_use.input.clone(),
+                            content: ToolResultContent::Text(error.to_string()),
+                            is_error: true,
+                            approval,
+                        };
+                    }
+                    Some(ToolApproval::Modify(input)) => input,
+                    Some(ToolApproval::Approve) | None => tool_use.input,
+                };
+                let approved = ToolUseRef { input, ..tool_use };
+                let parallel_safe = registry
+                    .get(tool_use.name)
+                    .is_none_or(|tool| tool.parallel_safe());
+                let mut call = if parallel_safe {
+                    let _shared = lock.read().await;
+                    registry.execute(approved).await
+                } else {
+                    let _exclusive = lock.write().await;
+                    registry.execute(approved).await
+                };
+                call.input = tool_use.input.clone();
+                call.approval = approval;
+                call
+            }
+        })
+        .buffered(concurrency)
+        .collect()
+        .await
+}
@@ -0,0 +1,114 @@
+//! Answers of the model
+//!
+//! This module contains [`Answer`], the reply returned by convenience methods
+//! such as [`Conversation::send`](crate::types::conversation::Conversation::send).
+//! It reads like the text of the reply but keeps the full response for when
+//! the usage or stop reason are needed after all.
+//!
+use std::fmt;
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::message::{CreateMessageResponse, StopReason, Usage};
+use crate::types::tool::ToolUseRef;
+
+/// Reply of the model that dereferences to its text
+///
+/// The text of all text blocks is concatenated once, when the answer is
+/// created. An answer serializes exactly like its
+/// [`CreateMessageResponse`], so cached responses can be read as answers.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::conversation::Conversation;
+/// use anthropic_ai_sdk::types::message::StopReason;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = MockMessageClient::new().text_response("Paris");
+/// let mut conversation = Conversation::new("claude-sonnet-4-5", 1024);
+/// let answer = conversation.send(&client, "Capital of France?").await?;
+///
+/// assert_eq!(&*answer, "Paris");
+/// assert!(answer.starts_with("Par"));
+/// assert_eq!(answer.stop_reason(), Some(&StopReason::EndTurn));
+/// assert_eq!(answer.usage().input_tokens, 10);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(from = "CreateMessageResponse", into = "CreateMessageResponse")]
+pub struct Answer {
+    text: String,
+    response: CreateMessageResponse,
+}
+
+impl Answer {
+    /// Concatenated text of the reply
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The full response
+    pub fn response(&self) -> &CreateMessageResponse {
+        &self.response
+    }
+
+    /// Take the full response
+    pub fn into_response(self) -> CreateMessageResponse {
+        self.response
+    }
+
+    /// Token usage of the request
+    pub fn usage(&self) -> &Usage {
+        &self.response.usage
+    }
+
+    /// Why the model stopped
+    pub fn stop_reason(&self) -> Option<&StopReason> {
+        self.response.stop_reason.as_ref()
+    }
+
+    /// Tool uses of the reply, in order
+    pub fn tool_uses(&self) -> impl Iterator<Item = ToolUseRef<'_>> {
+        self.response.tool_uses()
+    }
+}
+
+impl From<CreateMessageResponse> for Answer {
+    fn from(response: CreateMessageResponse) -> Self {
+        Self {
+            text: response.text(),
+            response,
+        }
+    }
+}
+
+impl From<Answer> for CreateMessageResponse {
+    fn from(answer: Answer) -> Self {
+        answer.response
+    }
+}
+
+impl Deref for Answer {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl AsRef<str> for Answer {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
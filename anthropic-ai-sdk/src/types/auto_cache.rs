@@ -0,0 +1,370 @@
+//! Automatic prompt cache breakpoints
+//!
+//! This module contains [`AutoCacheStrategy`], which decides where the cache
+//! breakpoints of a request go so that its stable prefix is cached, and
+//! [`place_breakpoints`], which applies a strategy and reports the result.
+//!
+use std::borrow::Cow;
+
+use crate::types::estimate::{
+    MESSAGE_OVERHEAD_TOKENS, TOOL_USE_OVERHEAD_TOKENS, estimate_block_tokens,
+    estimate_message_tokens, estimate_tokens,
+};
+use crate::types::message::{
+    CacheControl, ContentBlock, CreateMessageParams, Message, Role, SystemPrompt, ToolUnion,
+};
+use crate::types::model::Model;
+use crate::types::prompt::MAX_CACHE_BREAKPOINTS;
+
+/// Where [`place_breakpoints`] puts cache breakpoints
+///
+/// Breakpoints already set in the request are kept and count towards the
+/// limit of [`MAX_CACHE_BREAKPOINTS`]; no breakpoint is added to a prefix
+/// shorter than the model's
+/// [minimum cacheable length](Model::min_cacheable_tokens), as estimated
+/// offline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AutoCacheStrategy {
+    /// Cache the tool definitions and the system prompt
+    ///
+    /// Suits requests whose messages differ every time, e.g. tasks sharing a
+    /// long system prompt.
+    SystemAndTools,
+    /// Cache the tools, the system prompt and the whole history
+    ///
+    /// Suits multi-turn conversations. Besides the system prompt and the
+    /// tools, the last message and the end of the previous request get a
+    /// breakpoint, so each turn reads what the turn before cached. If
+    /// breakpoints run short, the last message comes first, then the system
+    /// prompt, the previous request and the tools. Prefixes estimated below
+    /// `min_tokens` get no breakpoint either.
+    LongestStablePrefix {
+        /// Minimum estimated tokens of a prefix worth a breakpoint
+        min_tokens: u32,
+    },
+}
+
+/// Block of a request that may hold a cache breakpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BreakpointLocation {
+    /// Tool definition at `index` of the tools
+    Tool { index: usize },
+    /// Block at `block` of the system prompt
+    System { block: usize },
+    /// Block at `block` of the message at `message`
+    Message { message: usize, block: usize },
+}
+
+/// A cache breakpoint considered by [`place_breakpoints`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheBreakpoint {
+    /// Block the breakpoint goes after
+    pub location: BreakpointLocation,
+    /// Offline estimate of the tokens of the prefix up to the block
+    pub estimated_tokens: u32,
+}
+
+/// Where [`place_breakpoints`] put cache breakpoints and why others were left out
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CacheReport {
+    /// Breakpoints added to the request
+    pub placed: Vec<CacheBreakpoint>,
+    /// Breakpoints left out because their prefix is below the minimum length
+    pub too_short: Vec<CacheBreakpoint>,
+    /// Breakpoints left out because the request had no breakpoint left
+    pub over_limit: Vec<CacheBreakpoint>,
+    /// Number of breakpoints the request already had
+    pub existing: usize,
+}
+
+/// Copy of `params` with cache breakpoints placed according to `strategy`
+///
+/// The client applies the strategy set with
+/// [`CreateMessageParams::with_auto_cache`] right before sending a request
+/// and logs the report at debug level; call this to inspect the placement.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::auto_cache::{
+///     AutoCacheStrategy, BreakpointLocation, place_breakpoints,
+/// };
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, RequiredMessageParams, Role,
+/// };
+///
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-sonnet-4-5".to_string(),
+///     messages: vec![
+///         Message::new_text(Role::User, "Summarize the manual."),
+///         Message::new_text(Role::Assistant, "It explains the setup."),
+///         Message::new_text(Role::User, "And the maintenance?"),
+///     ],
+///     max_tokens: 1024,
+/// })
+/// .with_system("You answer questions about this manual: ".repeat(300));
+///
+/// let strategy = AutoCacheStrategy::LongestStablePrefix { min_tokens: 0 };
+/// let (cached, report) = place_breakpoints(&params, strategy);
+/// let placed: Vec<_> = report.placed.iter().map(|breakpoint| breakpoint.location).collect();
+/// assert_eq!(
+///     placed,
+///     [
+///         BreakpointLocation::Message { message: 2, block: 0 },
+///         BreakpointLocation::System { block: 0 },
+///         BreakpointLocation::Message { message: 0, block: 0 },
+///     ]
+/// );
+/// assert!(cached.messages[2].blocks()[0].cache_control().is_some());
+/// // The original parameters are left unchanged
+/// assert!(params.messages[2].blocks()[0].cache_control().is_none());
+///
+/// // A short prompt is below the minimum cacheable length of the model
+/// let short = params.clone().with_system("Be brief.");
+/// let (_, report) = place_breakpoints(&short, AutoCacheStrategy::SystemAndTools);
+/// assert!(report.placed.is_empty());
+/// assert_eq!(report.too_short.len(), 1);
+/// ```
+pub fn place_breakpoints(
+    params: &CreateMessageParams,
+    strategy: AutoCacheStrategy,
+) -> (CreateMessageParams, CacheReport) {
+    let mut params = params.clone();
+    let existing = count_breakpoints(&params);
+    let mut report = CacheReport {
+        existing,
+        ..Default::default()
+    };
+    let minimum = Model::from(params.model.as_str())
+        .min_cacheable_tokens()
+        .unwrap_or(0);
+    let minimum = match strategy {
+        AutoCacheStrategy::SystemAndTools => minimum,
+        AutoCacheStrategy::LongestStablePrefix { min_tokens } => minimum.max(min_tokens),
+    };
+
+    let prefix = Prefix::new(&params);
+    let tools = prefix.last_tool(&params);
+    let system = prefix.last_system_block(&params);
+    let candidates = match strategy {
+        AutoCacheStrategy::SystemAndTools => vec![system, tools],
+        AutoCacheStrategy::LongestStablePrefix { .. } => {
+            let last = params.messages.len().checked_sub(1);
+            let previous = previous_request_end(&params.messages);
+            vec![
+                last.and_then(|index| prefix.message(&params, index)),
+                system,
+                previous
+                    .filter(|&index| Some(index) != last)
+                    .and_then(|index| prefix.message(&params, index)),
+                tools,
+            ]
+        }
+    };
+
+    let mut available = MAX_CACHE_BREAKPOINTS.saturating_sub(existing);
+    for candidate in candidates.into_iter().flatten() {
+        if candidate.estimated_tokens < minimum {
+            report.too_short.push(candidate);
+        } else if available == 0 {
+            report.over_limit.push(candidate);
+        } else {
+            set_breakpoint(&mut params, candidate.location);
+            report.placed.push(candidate);
+            available -= 1;
+        }
+    }
+    (params, report)
+}
+
+/// Copy of `params` with the breakpoints of their auto cache strategy, if any
+///
+/// The report is logged at debug level.
+pub(crate) fn apply_auto_cache(params: &CreateMessageParams) -> Option<CreateMessageParams> {
+    let (params, report) = place_breakpoints(params, params.auto_cache?);
+    tracing::debug!(
+        placed = ?report.placed,
+        too_short = ?report.too_short,
+        over_limit = ?report.over_limit,
+        existing = report.existing,
+        "placed cache breakpoints"
+    );
+    Some(params)
+}
+
+/// Estimated tokens of the sections of a request, in prompt order
+struct Prefix {
+    /// Tokens up to and including each tool
+    tools: Vec<u32>,
+    /// Tokens of the tools, the overhead of tool use and the system prompt
+    system: u32,
+    /// Tokens up to the message at each index, excluding it
+    messages: Vec<u32>,
+}
+
+impl Prefix {
+    fn new(params: &CreateMessageParams) -> Self {
+        let mut total = 0;
+        let mut tools = Vec::new();
+        for tool in params.tools.iter().flatten() {
+            total += serde_json::to_string(tool).map_or(0, |json| estimate_tokens(&json));
+            tools.push(total);
+        }
+        if !tools.is_empty() {
+            total += TOOL_USE_OVERHEAD_TOKENS;
+        }
+        let system = total
+            + params
+                .system
+                .as_ref()
+                .map_or(0, |system| estimate_tokens(&system.text()));
+        let mut messages = Vec::new();
+        let mut total = system;
+        for message in &params.messages {
+            messages.push(total);
+            total += estimate_message_tokens(message);
+        }
+        Self {
+            tools,
+            system,
+            messages,
+        }
+    }
+
+    /// Breakpoint after the last client tool
+    fn last_tool(&self, params: &CreateMessageParams) -> Option<CacheBreakpoint> {
+        let tools = params.tools.as_ref()?;
+        let index = tools.iter().rposition(
+            |tool| matches!(tool, ToolUnion::Custom(tool) if tool.cache_control.is_none()),
+        )?;
+        if tools[index..]
+            .iter()
+            .any(|tool| matches!(tool, ToolUnion::Custom(tool) if tool.cache_control.is_some()))
+        {
+            return None;
+        }
+        Some(CacheBreakpoint {
+            location: BreakpointLocation::Tool { index },
+            estimated_tokens: self.tools[index],
+        })
+    }
+
+    /// Breakpoint after the last block of the system prompt
+    fn last_system_block(&self, params: &CreateMessageParams) -> Option<CacheBreakpoint> {
+        let blocks = system_blocks(params.system.as_ref()?);
+        let block = last_cacheable(&blocks)?;
+        Some(CacheBreakpoint {
+            location: BreakpointLocation::System { block },
+            estimated_tokens: self.system,
+        })
+    }
+
+    /// Breakpoint after the last block of the message at `index` that accepts one
+    fn message(&self, params: &CreateMessageParams, index: usize) -> Option<CacheBreakpoint> {
+        let blocks = params.messages[index].blocks();
+        let block = last_cacheable(&blocks)?;
+        let tokens: u32 = blocks[..=block].iter().map(estimate_block_tokens).sum();
+        Some(CacheBreakpoint {
+            location: BreakpointLocation::Message {
+                message: index,
+                block,
+            },
+            estimated_tokens: self.messages[index] + MESSAGE_OVERHEAD_TOKENS + tokens,
+        })
+    }
+}
+
+/// Index of the last block that accepts a breakpoint, unless a later block has one already
+fn last_cacheable(blocks: &[ContentBlock]) -> Option<usize> {
+    let index = blocks.iter().rposition(accepts_cache_control)?;
+    if blocks[index..]
+        .iter()
+        .any(|block| block.cache_control().is_some())
+    {
+        return None;
+    }
+    Some(index)
+}
+
+/// Blocks of a system prompt, with a plain text prompt viewed as a single text block
+fn system_blocks(system: &SystemPrompt) -> Cow<'_, [ContentBlock]> {
+    match system {
+        SystemPrompt::Text(text) => Cow::Owned(vec![ContentBlock::text(text)]),
+        SystemPrompt::Blocks(blocks) => Cow::Borrowed(blocks),
+    }
+}
+
+fn accepts_cache_control(block: &ContentBlock) -> bool {
+    matches!(
+        block,
+        ContentBlock::Text { .. }
+            | ContentBlock::SearchResult { .. }
+            | ContentBlock::Document { .. }
+    )
+}
+
+/// Index of the last message sent by the previous request of a conversation
+///
+/// That is the last user message before the last assistant reply.
+fn previous_request_end(messages: &[Message]) -> Option<usize> {
+    let reply = messages
+        .iter()
+        .rposition(|message| message.role == Role::Assistant)?;
+    messages[..reply]
+        .iter()
+        .rposition(|message| message.role == Role::User)
+}
+
+/// Number of cache breakpoints set in `params`
+fn count_breakpoints(params: &CreateMessageParams) -> usize {
+    let tools = params
+        .tools
+        .iter()
+        .flatten()
+        .filter(|tool| matches!(tool, ToolUnion::Custom(tool) if tool.cache_control.is_some()))
+        .count();
+    let system = params.system.as_ref().map_or(0, |system| {
+        system_blocks(system)
+            .iter()
+            .filter(|block| block.cache_control().is_some())
+            .count()
+    });
+    let messages: usize = params
+        .messages
+        .iter()
+        .map(|message| {
+            message
+                .blocks()
+                .iter()
+                .filter(|block| block.cache_control().is_some())
+                .count()
+        })
+        .sum();
+    tools + system + messages
+}
+
+fn set_breakpoint(params: &mut CreateMessageParams, location: BreakpointLocation) {
+    let mark = |block: ContentBlock| block.with_cache_control(CacheControl::ephemeral());
+    match location {
+        BreakpointLocation::Tool { index } => {
+            if let Some(ToolUnion::Custom(tool)) =
+                params.tools.as_mut().and_then(|tools| tools.get_mut(index))
+            {
+                tool.cache_control = Some(CacheControl::ephemeral());
+            }
+        }
+        BreakpointLocation::System { block } => {
+            if let Some(system) = params.system.as_mut() {
+                let mut blocks = system_blocks(system).into_owned();
+                blocks[block] = mark(blocks[block].clone());
+                *system = SystemPrompt::Blocks(blocks);
+            }
+        }
+        BreakpointLocation::Message { message, block } => {
+            let message = &mut params.messages[message];
+            let mut blocks = message.blocks().into_owned();
+            blocks[block] = mark(blocks[block].clone());
+            *message = Message::new_blocks(message.role.clone(), blocks);
+        }
+    }
+}
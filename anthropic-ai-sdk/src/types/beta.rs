@@ -0,0 +1,120 @@
+//! Beta feature flags
+//!
+//! Beta features are enabled per request with the `anthropic-beta` header.
+//!
+use serde::{Deserialize, Serialize};
+
+/// Beta feature flag sent in the `anthropic-beta` header
+///
+/// Flags not known to this SDK can be sent with [`Beta::Other`].
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::beta::Beta;
+///
+/// assert_eq!(Beta::FilesApi.as_str(), "files-api-2025-04-14");
+/// assert_eq!(Beta::from("files-api-2025-04-14"), Beta::FilesApi);
+/// assert_eq!(Beta::from("context-1m-2025-08-07").as_str(), "context-1m-2025-08-07");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Beta {
+    /// 1 hour lifetime for prompt cache entries
+    PromptCaching1hTtl,
+    /// Files API and file sources in content blocks
+    FilesApi,
+    /// Remote MCP servers
+    Mcp,
+    /// Computer use tools
+    ComputerUse,
+    /// Token-efficient tool use
+    TokenEfficientTools,
+    /// Up to 128k output tokens
+    Output128k,
+    /// Fine-grained streaming of tool inputs
+    FineGrainedToolStreaming,
+    /// Code execution tool
+    CodeExecution,
+    /// Search result content blocks
+    SearchResults,
+    /// Context management
+    ContextManagement,
+    /// A beta flag not known to this SDK
+    Other(String),
+}
+
+impl Beta {
+    /// The wire representation of the beta flag
+    pub fn as_str(&self) -> &str {
+        match self {
+            Beta::PromptCaching1hTtl => "extended-cache-ttl-2025-04-11",
+            Beta::FilesApi => "files-api-2025-04-14",
+            Beta::Mcp => "mcp-client-2025-04-04",
+            Beta::ComputerUse => "computer-use-2025-01-24",
+            Beta::TokenEfficientTools => "token-efficient-tools-2025-02-19",
+            Beta::Output128k => "output-128k-2025-02-19",
+            Beta::FineGrainedToolStreaming => "fine-grained-tool-streaming-2025-05-14",
+            Beta::CodeExecution => "code-execution-2025-05-22",
+            Beta::SearchResults => "search-results-2025-06-09",
+            Beta::ContextManagement => "context-management-2025-06-27",
+            Beta::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for Beta {
+    fn from(value: &str) -> Self {
+        match value {
+            "extended-cache-ttl-2025-04-11" => Beta::PromptCaching1hTtl,
+            "files-api-2025-04-14" => Beta::FilesApi,
+            "mcp-client-2025-04-04" => Beta::Mcp,
+            "computer-use-2025-01-24" => Beta::ComputerUse,
+            "token-efficient-tools-2025-02-19" => Beta::TokenEfficientTools,
+            "output-128k-2025-02-19" => Beta::Output128k,
+            "fine-grained-tool-streaming-2025-05-14" => Beta::FineGrainedToolStreaming,
+            "code-execution-2025-05-22" => Beta::CodeExecution,
+            "search-results-2025-06-09" => Beta::SearchResults,
+            "context-management-2025-06-27" => Beta::ContextManagement,
+            other => Beta::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Beta {
+    fn from(value: String) -> Self {
+        Beta::from(value.as_str())
+    }
+}
+
+impl std::fmt::Display for Beta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Beta {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Beta {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(Beta::from(value.as_str()))
+    }
+}
+
+/// Combine explicit and detected beta flags, dropping duplicates
+///
+/// The order of first appearance is kept.
+pub(crate) fn merge_betas(explicit: &[Beta], detected: Vec<Beta>) -> Vec<Beta> {
+    let mut betas: Vec<Beta> = Vec::with_capacity(explicit.len() + detected.len());
+    for beta in explicit.iter().cloned().chain(detected) {
+        if !betas.contains(&beta) {
+            betas.push(beta);
+        }
+    }
+    betas
+}
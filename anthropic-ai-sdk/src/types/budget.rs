@@ -0,0 +1,176 @@
+//! Usage budgets
+//!
+//! This module contains [`UsageTracker`], which adds up the usage and cost of
+//! requests and stops [`Conversation`](crate::types::conversation::Conversation)s
+//! and [tool loops](crate::types::agent::run_tools) once a budget is used up.
+//!
+use std::sync::Mutex;
+
+use crate::types::decimal::Decimal;
+use crate::types::message::{MessageError, Usage};
+use crate::types::pricing::{PricingTable, cost};
+
+/// Usage added up by a [`UsageTracker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct UsageTotals {
+    /// Number of requests recorded
+    pub requests: u64,
+    /// Uncached input tokens
+    pub input_tokens: u64,
+    /// Output tokens
+    pub output_tokens: u64,
+    /// Input tokens written to the prompt cache
+    pub cache_creation_input_tokens: u64,
+    /// Input tokens read from the prompt cache
+    pub cache_read_input_tokens: u64,
+    /// Estimated cost in USD of the requests to models with known prices
+    pub cost: Decimal,
+    /// Number of requests to models missing from the pricing table
+    pub unpriced_requests: u64,
+}
+
+impl UsageTotals {
+    /// All tokens, input, output and cache
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_input_tokens
+            + self.cache_read_input_tokens
+    }
+}
+
+/// Running totals of the usage of requests, with an optional budget
+///
+/// Share one tracker between conversations through an `Arc` to give them a
+/// common budget, e.g. per end user or for a whole organization. Requests
+/// are priced with the [built-in prices](PricingTable::builtin) unless
+/// another table is set; requests to unknown models count towards the token
+/// budget only.
+///
+/// The budget is checked before each request: once the totals reach a
+/// limit, [`check`](Self::check) returns [`MessageError::BudgetExceeded`]
+/// and no further request is sent. A request that is already running may
+/// still take the totals past the limit.
+///
+/// Trackers compare equal only to themselves.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::budget::UsageTracker;
+/// use anthropic_ai_sdk::types::conversation::Conversation;
+/// use anthropic_ai_sdk::types::message::MessageError;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let tracker = Arc::new(UsageTracker::new().with_token_budget(20));
+/// let client = MockMessageClient::new().text_response("Hi").text_response("Hello");
+///
+/// let mut first = Conversation::new("claude-sonnet-4-5", 1024).with_usage_tracker(tracker.clone());
+/// let mut second = Conversation::new("claude-sonnet-4-5", 1024).with_usage_tracker(tracker.clone());
+/// first.send(&client, "Hello").await?;
+/// second.send(&client, "Hello").await?;
+/// assert_eq!(tracker.totals().requests, 2);
+/// assert!(tracker.totals().total_tokens() >= 20);
+///
+/// let error = first.send(&client, "Are you there?").await.unwrap_err();
+/// assert!(matches!(error, MessageError::BudgetExceeded(_)));
+/// assert_eq!(client.call_count(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+    pricing: PricingTable,
+    max_tokens: Option<u64>,
+    max_cost: Option<Decimal>,
+    totals: Mutex<UsageTotals>,
+}
+
+impl PartialEq for UsageTracker {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for UsageTracker {}
+
+impl UsageTracker {
+    /// Create a tracker without a budget, using the built-in prices
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Price requests with `pricing`
+    pub fn with_pricing(mut self, pricing: PricingTable) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    /// Stop once this many tokens, input, output and cache, have been used
+    pub fn with_token_budget(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Stop once the estimated cost in USD reaches `max_cost`
+    pub fn with_cost_budget(mut self, max_cost: Decimal) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Add the usage of a request to `model`
+    pub fn record(&self, model: &str, usage: &Usage) {
+        let breakdown = cost(usage, model, &self.pricing);
+        let mut totals = self.totals.lock().unwrap();
+        totals.requests += 1;
+        totals.input_tokens += u64::from(usage.input_tokens);
+        totals.output_tokens += u64::from(usage.output_tokens);
+        totals.cache_creation_input_tokens += u64::from(usage.cache_creation_input_tokens());
+        totals.cache_read_input_tokens += u64::from(usage.cache_read_input_tokens());
+        match breakdown {
+            Some(breakdown) => totals.cost += breakdown.total(),
+            None => totals.unpriced_requests += 1,
+        }
+    }
+
+    /// Usage recorded so far
+    pub fn totals(&self) -> UsageTotals {
+        *self.totals.lock().unwrap()
+    }
+
+    /// Whether the totals have reached the budget
+    pub fn is_exceeded(&self) -> bool {
+        self.check().is_err()
+    }
+
+    /// Check the budget before sending a request
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MessageError::BudgetExceeded`] once the totals have reached
+    /// the token or cost budget.
+    pub fn check(&self) -> Result<(), MessageError> {
+        let totals = self.totals();
+        if let Some(max_tokens) = self.max_tokens {
+            let used = totals.total_tokens();
+            if used >= max_tokens {
+                return Err(MessageError::BudgetExceeded(format!(
+                    "used {} of {} tokens",
+                    used, max_tokens
+                )));
+            }
+        }
+        if let Some(max_cost) = self.max_cost {
+            if totals.cost >= max_cost {
+                return Err(MessageError::BudgetExceeded(format!(
+                    "used ${} of ${}",
+                    totals.cost, max_cost
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+//! Document chunking
+//!
+//! This module contains [`DocumentChunker`], which splits a long text into
+//! document or search result blocks that each stay under a token estimate, so
+//! a book can be sent in one request or spread over a batch.
+//!
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::types::estimate::{estimate_block_tokens, estimate_tokens, saturating_sum};
+use crate::types::message::{ContentBlock, DocumentSource};
+
+/// Boundaries a text is split at, from the most to the least preferred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Paragraph,
+    Line,
+    Sentence,
+    Word,
+    Grapheme,
+}
+
+const LEVELS: [Level; 5] = [
+    Level::Paragraph,
+    Level::Line,
+    Level::Sentence,
+    Level::Word,
+    Level::Grapheme,
+];
+
+/// Kind of blocks written by a [`DocumentChunker`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ChunkFormat {
+    /// Documents with a [content source](DocumentSource::Content) of one text block
+    Document,
+    /// `search_result` blocks with citations enabled
+    SearchResult {
+        /// Source of every search result, e.g. the URL or path of the text
+        source: String,
+    },
+}
+
+/// Blocks of a chunked text
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentChunks {
+    /// One block per chunk, in order
+    pub blocks: Vec<ContentBlock>,
+    /// Estimated tokens of all blocks together
+    pub estimated_tokens: u32,
+}
+
+/// Splitter of long texts into blocks under a token estimate
+///
+/// Texts are split between paragraphs where possible, then between lines,
+/// sentences and words. A single word longer than a chunk is split between
+/// extended grapheme clusters as defined by Unicode (UAX #29), so combining
+/// marks, emoji sequences, flags and Hangul syllables are never cut. Sizes
+/// are measured with [`estimate_tokens`], so leave some headroom below hard
+/// limits.
+///
+/// Each block is titled "part N/M", after the [`title`](Self::title) if one
+/// is set.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::chunk::DocumentChunker;
+/// use anthropic_ai_sdk::types::message::ContentBlock;
+///
+/// let text = "The first paragraph is short.\n\nThe second one is not much longer.";
+/// let chunker = DocumentChunker::new(10).title("Notes").search_results("notes.txt");
+/// assert_eq!(
+///     chunker.split(text),
+///     ["The first paragraph is short.", "The second one is not much longer."]
+/// );
+///
+/// let chunks = chunker.chunk(text);
+/// assert_eq!(chunks.blocks.len(), 2);
+/// match &chunks.blocks[1] {
+///     ContentBlock::SearchResult { source, title, .. } => {
+///         assert_eq!(source, "notes.txt");
+///         assert_eq!(title, "Notes, part 2/2");
+///     }
+///     _ => unreachable!(),
+/// }
+/// assert!(chunks.estimated_tokens > 14);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DocumentChunker {
+    max_tokens: u32,
+    title: Option<String>,
+    format: ChunkFormat,
+}
+
+impl DocumentChunker {
+    /// Chunker writing documents of at most `max_tokens` estimated tokens of text
+    pub fn new(max_tokens: u32) -> Self {
+        Self {
+            max_tokens: max_tokens.max(1),
+            title: None,
+            format: ChunkFormat::Document,
+        }
+    }
+
+    /// Title put before the part number of every block
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Write the chunks as `search_result` blocks from `source`
+    pub fn search_results(self, source: impl Into<String>) -> Self {
+        self.format(ChunkFormat::SearchResult {
+            source: source.into(),
+        })
+    }
+
+    /// Write the chunks in the given format
+    pub fn format(mut self, format: ChunkFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Split `text` into chunks without surrounding whitespace
+    ///
+    /// A chunk only exceeds the maximum if it is a single grapheme cluster.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::chunk::DocumentChunker;
+    ///
+    /// // Each flag is a pair of regional indicators estimated at two tokens
+    /// assert_eq!(DocumentChunker::new(3).split("🇫🇷🇩🇪🇯🇵"), ["🇫🇷", "🇩🇪", "🇯🇵"]);
+    /// // Joined emoji and syllables of conjoining jamo stay whole
+    /// assert_eq!(DocumentChunker::new(5).split("👨‍👩‍👧👨‍👩‍👧"), ["👨‍👩‍👧", "👨‍👩‍👧"]);
+    /// assert_eq!(
+    ///     DocumentChunker::new(1).split("\u{1100}\u{1161}\u{11A8}\u{1100}\u{1161}\u{11A8}"),
+    ///     ["\u{1100}\u{1161}\u{11A8}", "\u{1100}\u{1161}\u{11A8}"]
+    /// );
+    /// ```
+    pub fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let mut chunks = Vec::new();
+        pack(text, self.max_tokens, 0, &mut chunks);
+        chunks
+            .into_iter()
+            .map(str::trim)
+            .filter(|chunk| !chunk.is_empty())
+            .collect()
+    }
+
+    /// Split `text` into blocks and estimate their tokens
+    pub fn chunk(&self, text: &str) -> DocumentChunks {
+        let chunks = self.split(text);
+        let total = chunks.len();
+        let blocks: Vec<ContentBlock> = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                let title = match &self.title {
+                    Some(title) => format!("{}, part {}/{}", title, index + 1, total),
+                    None => format!("part {}/{}", index + 1, total),
+                };
+                match &self.format {
+                    ChunkFormat::Document => ContentBlock::Document {
+                        source: DocumentSource::content([ContentBlock::text(chunk)]),
+                        title: Some(title),
+                        context: None,
+                        citations: None,
+                        cache_control: None,
+                    },
+                    ChunkFormat::SearchResult { source } => {
+                        ContentBlock::search_result(source.clone(), title, [chunk])
+                    }
+                }
+            })
+            .collect();
+        DocumentChunks {
+            estimated_tokens: saturating_sum(blocks.iter().map(estimate_block_tokens)),
+            blocks,
+        }
+    }
+}
+
+/// Append chunks of `text` of at most `max_tokens` to `chunks`, splitting at `LEVELS[level]`
+///
+/// Pieces split at whitespace or punctuation are measured separately and
+/// their estimates added up, which is exact for such pieces. A word is
+/// split at the longest run of grapheme clusters that fits.
+fn pack<'a>(text: &'a str, max_tokens: u32, level: usize, chunks: &mut Vec<&'a str>) {
+    if estimate_tokens(text) <= max_tokens {
+        chunks.push(text);
+        return;
+    }
+    let kind = LEVELS[level];
+    let mut ends = boundaries(text, kind);
+    ends.push(text.len());
+    if kind == Level::Grapheme {
+        let (mut start, mut first) = (0, 0);
+        while first < ends.len() {
+            let remaining = &ends[first..];
+            let fits = |end: &usize| estimate_tokens(&text[start..*end]) <= max_tokens;
+            // Estimates only grow with the text: double the clusters tried
+            // until they no longer fit, then search back
+            let mut bound = 1;
+            while bound < remaining.len() && fits(&remaining[bound - 1]) {
+                bound *= 2;
+            }
+            let fitting = remaining[..bound.min(remaining.len())].partition_point(fits);
+            // A cluster longer than a chunk is taken on its own
+            let last = first + fitting.max(1) - 1;
+            chunks.push(&text[start..ends[last]]);
+            (start, first) = (ends[last], last + 1);
+        }
+        return;
+    }
+    let (mut start, mut end, mut tokens) = (0, 0, 0u32);
+    for piece_end in ends {
+        let piece = &text[end..piece_end];
+        let piece_tokens = estimate_tokens(piece);
+        if tokens.saturating_add(piece_tokens) <= max_tokens {
+            (end, tokens) = (piece_end, tokens + piece_tokens);
+            continue;
+        }
+        if end > start {
+            chunks.push(&text[start..end]);
+        }
+        if piece_tokens <= max_tokens {
+            (start, end, tokens) = (end, piece_end, piece_tokens);
+        } else {
+            pack(piece, max_tokens, level + 1, chunks);
+            (start, end, tokens) = (piece_end, piece_end, 0);
+        }
+    }
+    if end > start {
+        chunks.push(&text[start..end]);
+    }
+}
+
+/// Byte offsets in `text` where a piece may start, excluding the start and end
+///
+/// Pieces always start at an extended grapheme cluster boundary.
+fn boundaries(text: &str, level: Level) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    // Last character before the cluster that is neither whitespace nor closing a sentence
+    let mut last: Option<char> = None;
+    let mut after_whitespace = false;
+    let mut newlines = 0;
+    for (offset, cluster) in text.grapheme_indices(true) {
+        // The base character of the cluster, before any marks or joiners
+        let Some(c) = cluster.chars().next() else {
+            continue;
+        };
+        if offset == 0 {
+            last = Some(c);
+            after_whitespace = c.is_whitespace();
+            continue;
+        }
+        let boundary = match level {
+            Level::Grapheme => true,
+            _ if c.is_whitespace() => false,
+            _ if after_whitespace => match level {
+                Level::Paragraph => newlines >= 2,
+                Level::Line => newlines >= 1,
+                Level::Sentence => newlines >= 1 || last.is_some_and(ends_sentence),
+                _ => true,
+            },
+            Level::Sentence => last.is_some_and(ends_sentence_unspaced) && !is_closing(c),
+            _ => false,
+        };
+        if boundary {
+            offsets.push(offset);
+        }
+
+        if c.is_whitespace() {
+            newlines += usize::from(cluster.contains('\n'));
+        } else {
+            newlines = 0;
+            // Closing quotes and brackets are skipped to find the end of a sentence
+            if !is_closing(c) || last.is_some_and(|last| !ends_sentence(last)) {
+                last = Some(c);
+            }
+        }
+        after_whitespace = c.is_whitespace();
+    }
+    offsets
+}
+
+/// Whether a sentence ends after `c` when whitespace follows
+fn ends_sentence(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '…') || ends_sentence_unspaced(c)
+}
+
+/// Whether a sentence ends after `c` even without whitespace, as in CJK text
+fn ends_sentence_unspaced(c: char) -> bool {
+    matches!(c, '。' | '！' | '？' | '．')
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(
+        c,
+        '"' | '\'' | ')' | ']' | '}' | '”' | '’' | '»' | '」' | '』' | '）' | '】'
+    )
+}
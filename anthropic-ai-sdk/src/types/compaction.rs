@@ -0,0 +1,258 @@
+//! History compaction
+//!
+//! This module contains the [`Compactor`] trait, used by
+//! [`Conversation::compact_if_needed`](crate::types::conversation::Conversation::compact_if_needed)
+//! to replace the oldest turns of a conversation with a summary, and
+//! [`ModelCompactor`], which has the model write the summary.
+//!
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::types::message::{
+    ContentBlock, CreateMessageParams, Message, MessageClient, MessageContent, MessageError, Role,
+    ToolResultContent,
+};
+
+/// Summarizes a span of conversation history
+#[async_trait]
+pub trait Compactor: Send + Sync {
+    /// Summarize `messages`, the oldest turns of a conversation continued with `params`
+    async fn summarize(
+        &self,
+        params: &CreateMessageParams,
+        messages: &[Message],
+    ) -> Result<String, MessageError>;
+}
+
+/// Instruction sent to the model by [`ModelCompactor`] by default
+pub const DEFAULT_SUMMARY_PROMPT: &str = "Summarize the following turns of a conversation between \
+     a user and an assistant. Keep every fact, decision, open question and tool result that later \
+     turns may rely on. Answer with the summary only.";
+
+/// A [`Compactor`] that asks the model of the conversation for the summary
+///
+/// The turns are sent as a plain text transcript, so tool use blocks do not
+/// need their tool definitions and thinking blocks are left out.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::compaction::{Compactor, DEFAULT_SUMMARY_PROMPT, ModelCompactor};
+/// use anthropic_ai_sdk::types::message::{
+///     ContentBlock, CreateMessageParams, Message, MessageError, RequiredMessageParams, Role,
+/// };
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), MessageError> {
+/// let client = MockMessageClient::new().text_response("The user asked for the weather.");
+/// let (tool_use, id) = ContentBlock::tool_use("get_weather", serde_json::json!({"city": "Paris"}));
+/// let messages = vec![
+///     Message::new_text(Role::User, "Weather in Paris?"),
+///     Message::new_blocks(
+///         Role::Assistant,
+///         vec![
+///             ContentBlock::Thinking {
+///                 thinking: "Call the tool.".to_string(),
+///                 signature: "sig".to_string(),
+///             },
+///             tool_use,
+///         ],
+///     ),
+///     Message::new_blocks(Role::User, vec![ContentBlock::tool_result(id, "Sunny")]),
+/// ];
+/// let params =
+///     CreateMessageParams::new(RequiredMessageParams::new("claude-sonnet-4-5", Vec::new(), 1024));
+///
+/// let compactor = ModelCompactor::new(&client).max_tokens(256);
+/// let summary = compactor.summarize(&params, &messages).await?;
+/// assert_eq!(summary, "The user asked for the weather.");
+///
+/// let request = client.last_request().unwrap();
+/// assert_eq!(request.model, "claude-sonnet-4-5");
+/// assert_eq!(request.max_tokens, 256);
+/// assert_eq!(request.system.unwrap().text(), DEFAULT_SUMMARY_PROMPT);
+/// assert_eq!(
+///     request.messages[0].text(),
+///     Some(
+///         "User: Weather in Paris?\n\n\
+///          Assistant: [Called get_weather: {\"city\":\"Paris\"}]\n\n\
+///          User: [Tool result: Sunny]"
+///     )
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ModelCompactor<'a, C: ?Sized> {
+    client: &'a C,
+    prompt: String,
+    max_tokens: u32,
+    model: Option<String>,
+}
+
+impl<'a, C: MessageClient + Sync + ?Sized> ModelCompactor<'a, C> {
+    /// Create a compactor sending its requests with `client`
+    pub fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            prompt: DEFAULT_SUMMARY_PROMPT.to_string(),
+            max_tokens: 1024,
+            model: None,
+        }
+    }
+
+    /// Set the instruction for writing the summary
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Set the maximum length of the summary in tokens
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Write summaries with `model` rather than the model of the conversation
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+}
+
+#[async_trait]
+impl<C: MessageClient + Sync + ?Sized> Compactor for ModelCompactor<'_, C> {
+    async fn summarize(
+        &self,
+        params: &CreateMessageParams,
+        messages: &[Message],
+    ) -> Result<String, MessageError> {
+        let request = CreateMessageParams {
+            model: self.model.clone().unwrap_or_else(|| params.model.clone()),
+            max_tokens: self.max_tokens,
+            system: Some(self.prompt.clone().into()),
+            messages: vec![Message::new_text(Role::User, transcript(messages))],
+            ..Default::default()
+        };
+        let response = self.client.create_message(Some(&request)).await?;
+        Ok(response.text())
+    }
+}
+
+/// A span of history replaced by a summary
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Compaction {
+    /// Text of the summary
+    pub summary: String,
+    /// Number of messages the summary replaced
+    pub replaced: usize,
+    /// The replaced messages, if they were retained
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub original: Vec<Message>,
+}
+
+/// When and how to compact a conversation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionOptions {
+    /// Input tokens above which the history is compacted
+    pub threshold: u32,
+    /// Number of most recent turns never compacted
+    pub keep_turns: usize,
+    /// Whether the replaced messages are kept in the [`Compaction`]
+    pub retain_original: bool,
+}
+
+impl CompactionOptions {
+    /// Compact when the history exceeds `threshold` input tokens
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            keep_turns: 2,
+            retain_original: false,
+        }
+    }
+
+    /// Set the number of most recent turns never compacted
+    pub fn keep_turns(mut self, keep_turns: usize) -> Self {
+        self.keep_turns = keep_turns.max(1);
+        self
+    }
+
+    /// Set whether the replaced messages are kept in the [`Compaction`]
+    pub fn retain_original(mut self, retain_original: bool) -> Self {
+        self.retain_original = retain_original;
+        self
+    }
+}
+
+/// Tags around the summary in the block replacing compacted turns
+const SUMMARY_OPEN: &str = "<conversation_summary>\n";
+const SUMMARY_CLOSE: &str = "\n</conversation_summary>";
+
+/// Block that replaces compacted turns, tagged so it is told apart from user text
+pub(crate) fn summary_block(summary: &str) -> ContentBlock {
+    ContentBlock::text(format!("{SUMMARY_OPEN}{summary}{SUMMARY_CLOSE}"))
+}
+
+/// The summary in a block made by [`summary_block`]
+pub(crate) fn summary_text(block: &ContentBlock) -> Option<&str> {
+    match block {
+        ContentBlock::Text { text, .. } => {
+            text.strip_prefix(SUMMARY_OPEN)?.strip_suffix(SUMMARY_CLOSE)
+        }
+        _ => None,
+    }
+}
+
+/// Render messages as a plain text transcript
+fn transcript(messages: &[Message]) -> String {
+    let mut transcript = String::new();
+    for message in messages {
+        let speaker = match message.role {
+            Role::User => "User",
+            Role::Assistant => "Assistant",
+        };
+        let blocks = match &message.content {
+            MessageContent::Text { content } => vec![content.clone()],
+            MessageContent::Blocks { content } => content.iter().filter_map(block_text).collect(),
+        };
+        if blocks.is_empty() {
+            continue;
+        }
+        transcript.push_str(speaker);
+        transcript.push_str(": ");
+        transcript.push_str(&blocks.join("\n"));
+        transcript.push_str("\n\n");
+    }
+    transcript.trim_end().to_string()
+}
+
+fn block_text(block: &ContentBlock) -> Option<String> {
+    match block {
+        ContentBlock::Text { text, .. } => Some(text.clone()),
+        ContentBlock::ToolUse { name, input, .. } => Some(format!("[Called {}: {}]", name, input)),
+        ContentBlock::ToolResult {
+            content, is_error, ..
+        } => {
+            let content = match content {
+                ToolResultContent::Text(text) => text.clone(),
+                ToolResultContent::Blocks(blocks) => blocks
+                    .iter()
+                    .filter_map(block_text)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            };
+            let label = if *is_error {
+                "Tool error"
+            } else {
+                "Tool result"
+            };
+            Some(format!("[{}: {}]", label, content))
+        }
+        ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. } => None,
+        ContentBlock::Image { .. } => Some("[Image]".to_string()),
+        ContentBlock::Document { .. } => Some("[Document]".to_string()),
+        _ => None,
+    }
+}
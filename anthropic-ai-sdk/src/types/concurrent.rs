@@ -0,0 +1,404 @@
+//! Concurrent requests
+//!
+//! This module contains [`MapConcurrent`], returned by
+//! [`MessageClient::map_concurrent`], which sends many requests with bounded
+//! parallelism, and [`ConcurrencyLimit`], the cap on the requests in flight of
+//! a client.
+//!
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_util::stream::BoxStream;
+use futures_util::{Stream, StreamExt};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::types::message::{
+    CreateMessageParams, CreateMessageResponse, MessageClient, MessageError,
+};
+use crate::types::pagination::{RateLimitError, backoff};
+
+type Progressed<'a> = Arc<dyn Fn(&Progress) + Send + Sync + 'a>;
+
+/// Results of [`MapConcurrent`] so far, passed to its progress callback
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of requests that completed, successfully or not
+    pub completed: usize,
+    /// Number of requests that failed
+    pub failed: usize,
+    /// Number of requests that failed since the last success
+    pub consecutive_failures: usize,
+}
+
+/// Stream of the results of many requests sent with bounded parallelism
+///
+/// The requests are sent in the order of their parameters, with at most
+/// `concurrency` in flight, and yielded as `(index, result)` pairs in the
+/// order they complete. Parameters are only taken from the iterator when a
+/// request can be sent and no task is spawned, so the stream does nothing
+/// unless polled.
+///
+/// Requests rejected by a rate limit are retried up to
+/// [`max_retries`](Self::max_retries) times like the pages of a
+/// [`Paginator`](crate::types::pagination::Paginator). Once
+/// [`max_consecutive_failures`](Self::max_consecutive_failures) requests in a
+/// row have failed, no further requests are sent; the requests in flight
+/// still complete, so the results cover a prefix of the parameters.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
+/// };
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let prompts = ["one", "two", "three", "four"].map(|prompt| {
+///     CreateMessageParams::new(RequiredMessageParams {
+///         model: "claude-3-7-sonnet-20250219".to_string(),
+///         messages: vec![Message::new_text(Role::User, prompt)],
+///         max_tokens: 1024,
+///     })
+/// });
+///
+/// // The first request is rate limited once and retried
+/// let rate_limited = MessageError::RateLimited {
+///     message: "Number of requests has exceeded your rate limit".to_string(),
+///     retry_after: Some(Duration::ZERO),
+/// };
+/// let client = MockMessageClient::new()
+///     .fail_on_call(1, rate_limited)
+///     .text_response("1")
+///     .text_response("2")
+///     .text_response("3")
+///     .text_response("4");
+/// let results = client.map_concurrent(prompts.clone(), 2).collect_ordered().await;
+/// assert_eq!(results.len(), 4);
+/// assert!(results.iter().all(Result::is_ok));
+/// assert_eq!(client.call_count(), 5);
+///
+/// // Stop after two failures in a row
+/// let client = MockMessageClient::new()
+///     .error(MessageError::ApiError("overloaded".to_string()))
+///     .error(MessageError::ApiError("overloaded".to_string()))
+///     .text_response("never requested");
+/// let results = client
+///     .map_concurrent(prompts, 1)
+///     .max_consecutive_failures(2)
+///     .on_progress(|progress| println!("{} failed", progress.failed))
+///     .collect_ordered()
+///     .await;
+/// assert_eq!(results.len(), 2);
+/// assert_eq!(client.remaining(), 1);
+/// # }
+/// ```
+pub struct MapConcurrent<'a, C: ?Sized> {
+    client: &'a C,
+    params: Option<Box<dyn Iterator<Item = CreateMessageParams> + Send + 'a>>,
+    concurrency: usize,
+    max_retries: u32,
+    max_consecutive_failures: Option<usize>,
+    on_progress: Option<Progressed<'a>>,
+    stream: Option<BoxStream<'a, (usize, Result<CreateMessageResponse, MessageError>)>>,
+}
+
+impl<C: ?Sized> fmt::Debug for MapConcurrent<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapConcurrent")
+            .field("concurrency", &self.concurrency)
+            .field("max_retries", &self.max_retries)
+            .field("max_consecutive_failures", &self.max_consecutive_failures)
+            .field("started", &self.stream.is_some())
+            .finish()
+    }
+}
+
+impl<'a, C: MessageClient + Sync + ?Sized> MapConcurrent<'a, C> {
+    /// Default number of retries of a rate limited request
+    pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+    /// Send a request for each of `params` with `client`, at most `concurrency` at a time
+    ///
+    /// A `concurrency` of 0 is treated as 1.
+    pub fn new<I>(client: &'a C, params: I, concurrency: usize) -> Self
+    where
+        I: IntoIterator<Item = CreateMessageParams>,
+        I::IntoIter: Send + 'a,
+    {
+        Self {
+            client,
+            params: Some(Box::new(params.into_iter())),
+            concurrency: concurrency.max(1),
+            max_retries: Self::DEFAULT_MAX_RETRIES,
+            max_consecutive_failures: None,
+            on_progress: None,
+            stream: None,
+        }
+    }
+
+    /// Set how often a rate limited request is retried
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Stop sending requests after `max` failures in a row
+    pub fn max_consecutive_failures(mut self, max: usize) -> Self {
+        self.max_consecutive_failures = Some(max.max(1));
+        self
+    }
+
+    /// Call `on_progress` after each completed request
+    pub fn on_progress(mut self, on_progress: impl Fn(&Progress) + Send + Sync + 'a) -> Self {
+        self.on_progress = Some(Arc::new(on_progress));
+        self
+    }
+
+    /// Wait for all requests and return their results in the order of the parameters
+    ///
+    /// If the stream stopped after repeated failures, the results cover the
+    /// parameters up to the last request sent.
+    pub async fn collect_ordered(self) -> Vec<Result<CreateMessageResponse, MessageError>> {
+        let mut results: Vec<_> = self.collect().await;
+        results.sort_unstable_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    fn start(&mut self) -> BoxStream<'a, (usize, Result<CreateMessageResponse, MessageError>)> {
+        let client = self.client;
+        let max_retries = self.max_retries;
+        let max_failures = self.max_consecutive_failures;
+        let on_progress = self.on_progress.take();
+        let progress = Arc::new(Mutex::new(Progress::default()));
+        let params = self
+            .params
+            .take()
+            .unwrap_or_else(|| Box::new(std::iter::empty()));
+
+        let stopped = progress.clone();
+        futures_util::stream::iter(params.enumerate())
+            .take_while(move |_| {
+                let progress = stopped.lock().unwrap();
+                let stop = max_failures.is_some_and(|max| progress.consecutive_failures >= max);
+                std::future::ready(!stop)
+            })
+            .map(move |(index, params)| {
+                let progress = progress.clone();
+                let on_progress = on_progress.clone();
+                async move {
+                    let result = send(client, &params, max_retries).await;
+                    let snapshot = {
+                        let mut progress = progress.lock().unwrap();
+                        progress.completed += 1;
+                        if result.is_ok() {
+                            progress.consecutive_failures = 0;
+                        } else {
+                            progress.failed += 1;
+                            progress.consecutive_failures += 1;
+                        }
+                        progress.clone()
+                    };
+                    if let Some(on_progress) = on_progress {
+                        on_progress(&snapshot);
+                    }
+                    (index, result)
+                }
+            })
+            .buffer_unordered(self.concurrency)
+            .boxed()
+    }
+}
+
+impl<'a, C: MessageClient + Sync + ?Sized> Stream for MapConcurrent<'a, C> {
+    type Item = (usize, Result<CreateMessageResponse, MessageError>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.stream.is_none() {
+            this.stream = Some(this.start());
+        }
+        this.stream.as_mut().unwrap().poll_next_unpin(cx)
+    }
+}
+
+/// Send `params`, retrying rate limited requests
+async fn send<C>(
+    client: &C,
+    params: &CreateMessageParams,
+    max_retries: u32,
+) -> Result<CreateMessageResponse, MessageError>
+where
+    C: MessageClient + Sync + ?Sized,
+{
+    let mut attempt = 0;
+    loop {
+        match client.create_message(Some(params)).await {
+            Err(error) if error.is_rate_limited() && attempt < max_retries => {
+                tokio::time::sleep(backoff(&error, attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Cap on the requests a client has in flight, with statistics
+///
+/// Set with
+/// [`AnthropicClientBuilder::with_max_concurrent_requests`](crate::client::AnthropicClientBuilder::with_max_concurrent_requests)
+/// and shared by clones of the client. Every request takes a permit before it
+/// is sent and holds it until its response has been read; streamed responses
+/// hold it until the stream ends or is dropped.
+///
+/// Waiting for a permit ends when the request future is dropped, so a
+/// deadline such as [`tokio::time::timeout`] around the request also bounds
+/// the wait, without leaking a permit.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use anthropic_ai_sdk::client::AnthropicClient;
+/// use anthropic_ai_sdk::testing::StreamScenario;
+/// use anthropic_ai_sdk::testing::stream::sse_events;
+/// use anthropic_ai_sdk::testing::vcr::{
+///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+/// };
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
+///     StopReason,
+/// };
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::env::temp_dir().join(format!("in-flight-{}.json", std::process::id()));
+/// let events = StreamScenario::new().text("Hi").finish(StopReason::EndTurn);
+/// Cassette {
+///     interactions: vec![Interaction {
+///         request: RecordedRequest {
+///             method: "POST".to_string(),
+///             path: "/messages".to_string(),
+///             headers: Vec::new(),
+///             body: String::new(),
+///         },
+///         response: RecordedResponse {
+///             events: sse_events(&events),
+///             ..RecordedResponse::event_stream(Vec::<String>::new())
+///         },
+///     }],
+/// }
+/// .save(&path)?;
+/// let server = Vcr::new(&path)
+///     .mode(VcrMode::Replay)
+///     .matcher(RequestMatcher::new().body(false))
+///     .start()
+///     .await?;
+///
+/// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+///     .with_api_base_url(server.url())
+///     .with_max_concurrent_requests(1)
+///     .build::<MessageError>()?;
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-3-7-sonnet-20250219".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Hi")],
+///     max_tokens: 1024,
+/// });
+///
+/// // An open stream holds the only permit, shared with clones of the client
+/// let streaming = params.clone().with_stream(true);
+/// let stream = client.create_message_streaming(&streaming).await?;
+/// let clone = client.clone();
+/// let limit = clone.concurrency_limit().unwrap();
+/// assert_eq!(limit.in_flight(), 1);
+///
+/// // so another request waits until its deadline
+/// let request = clone.create_message(Some(&params));
+/// assert!(tokio::time::timeout(Duration::from_millis(50), request).await.is_err());
+/// assert_eq!(limit.waiting(), 0);
+///
+/// drop(stream);
+/// assert_eq!(limit.in_flight(), 0);
+/// assert_eq!(limit.permits_acquired(), 1);
+/// # server.finish().await?;
+/// # std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConcurrencyLimit {
+    semaphore: Arc<Semaphore>,
+    max: usize,
+    waiting: AtomicUsize,
+    acquired: AtomicU64,
+    wait_nanos: AtomicU64,
+}
+
+impl ConcurrencyLimit {
+    /// Allow at most `max` requests in flight, at least 1
+    pub fn new(max: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(max)),
+            max,
+            waiting: AtomicUsize::new(0),
+            acquired: AtomicU64::new(0),
+            wait_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Maximum number of requests in flight
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Number of requests in flight
+    pub fn in_flight(&self) -> usize {
+        self.max - self.semaphore.available_permits()
+    }
+
+    /// Number of requests waiting for a permit
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::Relaxed)
+    }
+
+    /// Number of permits given out so far
+    pub fn permits_acquired(&self) -> u64 {
+        self.acquired.load(Ordering::Relaxed)
+    }
+
+    /// Total time requests spent waiting for a permit
+    pub fn total_wait(&self) -> Duration {
+        Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Wait for a permit
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        let started = Instant::now();
+        self.waiting.fetch_add(1, Ordering::Relaxed);
+        // Decrements `waiting` on completion and on cancellation alike
+        let _waiting = WaitingGuard(&self.waiting);
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed");
+        let waited = u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.wait_nanos.fetch_add(waited, Ordering::Relaxed);
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        permit
+    }
+}
+
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,187 @@
+//! Continuing truncated replies
+//!
+//! This module contains the types of
+//! [`MessageClient::create_with_continuation`], which continues replies cut
+//! off by `max_tokens`.
+//!
+use thiserror::Error;
+
+use crate::types::conversation::push_merged;
+use crate::types::message::{
+    ContentBlock, CreateMessageParams, CreateMessageResponse, Message, MessageClient, MessageError,
+    Role, StopReason,
+};
+
+/// Error types for [`MessageClient::create_with_continuation`]
+#[derive(Debug, Error)]
+pub enum ContinuationError {
+    #[error(transparent)]
+    Message(#[from] MessageError),
+    /// The reply was cut off in the input of a tool call, which cannot be continued
+    #[error("Reply was truncated by max_tokens in the tool call {name}")]
+    IncompleteToolUse {
+        name: String,
+        response: Box<CreateMessageResponse>,
+    },
+    /// A continuation was cut off by `max_tokens` without adding any content
+    #[error("Continuation of a truncated reply added no content")]
+    NoProgress {
+        response: Box<CreateMessageResponse>,
+    },
+}
+
+impl ContinuationError {
+    /// The reply stitched together so far, if a request succeeded
+    pub fn response(&self) -> Option<&CreateMessageResponse> {
+        match self {
+            ContinuationError::Message(_) => None,
+            ContinuationError::IncompleteToolUse { response, .. }
+            | ContinuationError::NoProgress { response } => Some(response),
+        }
+    }
+}
+
+/// Response of [`MessageClient::create_with_continuation`]
+///
+/// While a reply stops with [`StopReason::MaxTokens`], the reply so far is
+/// appended to the messages as an assistant prefill and the request is sent
+/// again. The API rejects a prefill ending with whitespace, so trailing
+/// whitespace is removed from the reply before it is continued. The text of
+/// each continuation is appended to the last text block of the reply, other
+/// blocks are added after it.
+///
+/// The stitched response has the ID, stop reason and stop sequence of the
+/// last request and the usage of all requests added up. If the reply is
+/// still truncated after `max_continues` continuations, it is returned with
+/// the `max_tokens` stop reason.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, RequiredMessageParams, Role, StopReason,
+/// };
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = MockMessageClient::new()
+///     .truncated_response("Once upon a time, ")
+///     .text_response(" there was a crab.");
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-sonnet-4-5".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Tell me a story")],
+///     max_tokens: 5,
+/// });
+///
+/// let continued = client.create_with_continuation(&params, 3).await?;
+/// assert_eq!(continued.continuations, 1);
+/// assert_eq!(continued.response.text(), "Once upon a time, there was a crab.");
+/// assert_eq!(continued.response.stop_reason, Some(StopReason::EndTurn));
+///
+/// let prefill = &client.last_request().unwrap().messages[1];
+/// assert_eq!(prefill.role, Role::Assistant);
+/// assert_eq!(prefill.text(), Some("Once upon a time,"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinuedResponse {
+    /// The reply stitched together from all requests
+    pub response: CreateMessageResponse,
+    /// Number of follow-up requests sent
+    pub continuations: u32,
+}
+
+/// Send `params` and continue the reply at most `max_continues` times
+pub(crate) async fn create_with_continuation<C>(
+    client: &C,
+    params: &CreateMessageParams,
+    max_continues: u32,
+) -> Result<ContinuedResponse, ContinuationError>
+where
+    C: MessageClient + Sync + ?Sized,
+{
+    let mut response = client.create_message(Some(params)).await?;
+    let mut continuations = 0;
+    let mut request = params.clone();
+    while response.stop_reason == Some(StopReason::MaxTokens) && continuations < max_continues {
+        if let Some(name) = incomplete_tool_use(&response) {
+            return Err(ContinuationError::IncompleteToolUse {
+                name,
+                response: Box::new(response),
+            });
+        }
+        trim_trailing_whitespace(&mut response.content);
+        request.messages = params.messages.clone();
+        push_merged(
+            &mut request.messages,
+            Message::new(Role::Assistant, response.content.clone()),
+        );
+        let continuation = client.create_message(Some(&request)).await?;
+        continuations += 1;
+        let progressed = continuation.content.iter().any(|block| match block {
+            ContentBlock::Text { text, .. } => !text.is_empty(),
+            _ => true,
+        });
+        let stuck = !progressed && continuation.stop_reason == Some(StopReason::MaxTokens);
+        stitch(&mut response, continuation);
+        if stuck {
+            return Err(ContinuationError::NoProgress {
+                response: Box::new(response),
+            });
+        }
+    }
+    if let Some(name) = incomplete_tool_use(&response) {
+        return Err(ContinuationError::IncompleteToolUse {
+            name,
+            response: Box::new(response),
+        });
+    }
+    Ok(ContinuedResponse {
+        response,
+        continuations,
+    })
+}
+
+/// Name of the tool whose call was cut off by `max_tokens`, if any
+fn incomplete_tool_use(response: &CreateMessageResponse) -> Option<String> {
+    if response.stop_reason != Some(StopReason::MaxTokens) {
+        return None;
+    }
+    match response.content.last()? {
+        ContentBlock::ToolUse { name, .. }
+        | ContentBlock::ServerToolUse { name, .. }
+        | ContentBlock::McpToolUse { name, .. } => Some(name.clone()),
+        _ => None,
+    }
+}
+
+fn trim_trailing_whitespace(content: &mut Vec<ContentBlock>) {
+    if let Some(ContentBlock::Text { text, .. }) = content.last_mut() {
+        text.truncate(text.trim_end().len());
+        if text.is_empty() {
+            content.pop();
+        }
+    }
+}
+
+/// Append the content of `continuation` to `response`, merging adjacent text
+fn stitch(response: &mut CreateMessageResponse, continuation: CreateMessageResponse) {
+    let mut blocks = continuation.content.into_iter();
+    if let (Some(ContentBlock::Text { text, .. }), Some(ContentBlock::Text { .. })) =
+        (response.content.last_mut(), blocks.as_slice().first())
+    {
+        if let Some(ContentBlock::Text { text: next, .. }) = blocks.next() {
+            text.push_str(&next);
+        }
+    }
+    response.content.extend(blocks);
+    response.id = continuation.id;
+    response.stop_reason = continuation.stop_reason;
+    response.stop_sequence = continuation.stop_sequence;
+    response.usage += continuation.usage;
+    if continuation.container.is_some() {
+        response.container = continuation.container;
+    }
+}
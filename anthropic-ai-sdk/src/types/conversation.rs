@@ -0,0 +1,1319 @@
+//! Conversations
+//!
+//! This module contains [`Conversation`], which keeps the history of a
+//! multi-turn exchange with the Messages API and sends each new turn with it.
+//!
+//! # Examples
+//!
+//! ```
+//! use anthropic_ai_sdk::testing::MockMessageClient;
+//! use anthropic_ai_sdk::types::conversation::Conversation;
+//! use anthropic_ai_sdk::types::message::MessageError;
+//! use futures_util::StreamExt;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), MessageError> {
+//! let client = MockMessageClient::new()
+//!     .text_response("Hi")
+//!     .text_response("Hi again")
+//!     .text_response("streamed");
+//! let mut conversation = Conversation::new("claude-3-7-sonnet-20250219", 1024)
+//!     .with_system("Be brief.");
+//!
+//! assert_eq!(conversation.send(&client, "Hello").await?.text(), "Hi");
+//! assert_eq!(conversation.send(&client, "Again").await?.text(), "Hi again");
+//! assert_eq!(conversation.messages().len(), 4);
+//! assert_eq!(conversation.usage().input_tokens, 20);
+//!
+//! // Every turn sends the whole history
+//! let requests = client.requests();
+//! assert_eq!(requests[1].messages.len(), 3);
+//! assert_eq!(requests[1].messages[1].text(), Some("Hi"));
+//!
+//! // Streamed turns are recorded once the stream has ended
+//! let events: Vec<_> = conversation.stream(&client, "Stream it").collect().await;
+//! assert!(events.iter().all(Result::is_ok));
+//! assert_eq!(conversation.messages()[5].text(), Some("streamed"));
+//!
+//! // The whole state can be persisted and restored
+//! let saved = serde_json::to_string(&conversation).unwrap();
+//! let restored: Conversation = serde_json::from_str(&saved).unwrap();
+//! assert_eq!(restored, conversation);
+//! # Ok(())
+//! # }
+//! ```
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_util::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use crate::types::answer::Answer;
+use crate::types::budget::UsageTracker;
+use crate::types::compaction::{
+    Compaction, CompactionOptions, Compactor, summary_block, summary_text,
+};
+use crate::types::estimate::estimate_request_tokens;
+use crate::types::message::{
+    Container, ContentBlock, CountMessageTokensParams, CreateMessageParams, CreateMessageResponse,
+    Message, MessageAccumulator, MessageClient, MessageContent, MessageError, Role, StreamEvent,
+    SystemPrompt, Thinking, Usage,
+};
+use crate::types::pricing::PricingTable;
+use crate::types::tool::ToolUseRef;
+use crate::types::transcript::{TextReportOptions, text_report};
+
+/// Version of the format written by [`Conversation::to_json`]
+pub const CONVERSATION_FORMAT_VERSION: u32 = 1;
+
+/// Error types for saving and loading conversations
+#[derive(Debug, Error)]
+pub enum PersistError {
+    #[error("Invalid conversation JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(
+        "Conversation format version {0} is not supported, expected at most {CONVERSATION_FORMAT_VERSION}"
+    )]
+    UnsupportedVersion(serde_json::Value),
+    #[error("Failed to access conversation file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// History of a multi-turn conversation and the parameters to continue it with
+///
+/// Every turn sends the default parameters together with the full history,
+/// then records the assistant reply unchanged, so thinking and `tool_use`
+/// blocks are sent back exactly as the API returned them. A turn is only added
+/// to the history once it succeeded, so a failed request can simply be retried.
+///
+/// Conversations can be serialized to persist them, preferably with
+/// [`to_json`](Self::to_json), which records the format version. Fields of
+/// the parameters that are not part of the request body, such as
+/// [`betas`](CreateMessageParams::betas), are not serialized.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Conversation {
+    /// Parameters sent with every turn, their messages are ignored
+    params: CreateMessageParams,
+    /// Messages exchanged so far
+    messages: Vec<Message>,
+    /// Total usage of every turn
+    usage: Usage,
+    /// Compactions applied to the history, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    compactions: Vec<Compaction>,
+    /// Whether the first block of the first message is a synthetic summary
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    summarized: bool,
+    /// Code execution container of the last reply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    container: Option<Container>,
+    /// Usage of each request, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    requests: Vec<RequestUsage>,
+    /// Turns kept when the history is trimmed to fit the context window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auto_trim: Option<usize>,
+    /// Truncations applied by automatic trimming, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    trims: Vec<Truncation>,
+    /// Tracker checked before and updated after every request
+    #[serde(skip)]
+    usage_tracker: Option<Arc<UsageTracker>>,
+}
+
+impl Conversation {
+    /// Start an empty conversation with the given model
+    pub fn new(model: impl Into<String>, max_tokens: u32) -> Self {
+        Self::from_params(CreateMessageParams {
+            model: model.into(),
+            max_tokens,
+            ..Default::default()
+        })
+    }
+
+    /// Start a conversation with the given default parameters
+    ///
+    /// The messages of the parameters become the initial history.
+    pub fn from_params(mut params: CreateMessageParams) -> Self {
+        let messages = std::mem::take(&mut params.messages);
+        Self {
+            params,
+            messages,
+            ..Default::default()
+        }
+    }
+
+    /// Set the system prompt
+    pub fn with_system(mut self, system: impl Into<SystemPrompt>) -> Self {
+        self.params.system = Some(system.into());
+        self
+    }
+
+    /// The parameters sent with every turn
+    pub fn params(&self) -> &CreateMessageParams {
+        &self.params
+    }
+
+    /// Mutable access to the parameters sent with every turn
+    ///
+    /// Changes apply from the next turn on.
+    pub fn params_mut(&mut self) -> &mut CreateMessageParams {
+        &mut self.params
+    }
+
+    /// Messages exchanged so far
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// Consume the conversation and return its history
+    pub fn into_messages(self) -> Vec<Message> {
+        self.messages
+    }
+
+    /// Total usage of every turn so far
+    pub fn usage(&self) -> &Usage {
+        &self.usage
+    }
+
+    /// Usage of each request sent so far, oldest first
+    pub fn request_usage(&self) -> &[RequestUsage] {
+        &self.requests
+    }
+
+    /// Plain text transcript with the usage and cost of each request
+    ///
+    /// See [`to_text_report_with`](Self::to_text_report_with); tool payloads
+    /// are cut after
+    /// [`DEFAULT_MAX_PAYLOAD_CHARS`](crate::types::transcript::DEFAULT_MAX_PAYLOAD_CHARS)
+    /// characters.
+    pub fn to_text_report(&self, pricing: Option<&PricingTable>) -> String {
+        self.to_text_report_with(pricing, &TextReportOptions::new())
+    }
+
+    /// Plain text transcript with the usage and cost of each request
+    ///
+    /// The report is meant for logs and support tickets: it has no markup and
+    /// the same conversation always gives the same text, so reports of two
+    /// runs can be diffed. Each message is headed by its number and role,
+    /// followed by its blocks; tool inputs and results are written as compact
+    /// JSON or text and cut after
+    /// [`max_payload_chars`](TextReportOptions::max_payload_chars). Below
+    /// each reply, the usage of the requests that produced it is listed and,
+    /// with `pricing`, their cost. The report ends with the totals. The
+    /// [summary](Self::summary) of compacted turns is labeled as such, as it
+    /// was not written by the user.
+    ///
+    /// See [`TextReportOptions`] for an example.
+    pub fn to_text_report_with(
+        &self,
+        pricing: Option<&PricingTable>,
+        options: &TextReportOptions,
+    ) -> String {
+        text_report(self, pricing, options)
+    }
+
+    /// Record the usage of every turn with `tracker` and stop once its budget is used up
+    ///
+    /// Requests fail with [`MessageError::BudgetExceeded`] instead of being
+    /// sent. The tracker is not serialized with the conversation.
+    pub fn with_usage_tracker(mut self, tracker: Arc<UsageTracker>) -> Self {
+        self.usage_tracker = Some(tracker);
+        self
+    }
+
+    /// The usage tracker of this conversation, if any
+    pub fn usage_tracker(&self) -> Option<&Arc<UsageTracker>> {
+        self.usage_tracker.as_ref()
+    }
+
+    /// Drop the oldest turns instead of failing when the history outgrows the context window
+    ///
+    /// The input may use the [context window](CreateMessageParams::model_spec)
+    /// minus `max_tokens`, or the thinking budget if that is larger. Before
+    /// [`send_blocks`](Self::send_blocks),
+    /// [`continue_turn`](Self::continue_turn) and
+    /// [`stream_blocks`](Self::stream_blocks) send a request whose
+    /// [estimated](crate::types::estimate::estimate_request_tokens) input
+    /// exceeds that, and once more after the API rejected a request for its
+    /// [length](MessageError::is_context_length_exceeded), the history is
+    /// truncated with [`find_truncation`], keeping the last `keep_turns`
+    /// turns. A rejected request is retried once; streams are retried only
+    /// when the request is rejected before the first event. Each truncation
+    /// is recorded in [`trims`](Self::trims).
+    ///
+    /// Models without a known context window are never trimmed; give them
+    /// one with [`CreateMessageParams::with_model_spec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::MockMessageClient;
+    /// use anthropic_ai_sdk::types::conversation::Conversation;
+    /// use anthropic_ai_sdk::types::message::{Message, MessageError, Role};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let too_long = r#"{"type": "error", "error": {"type": "invalid_request_error",
+    ///     "message": "prompt is too long: 210000 tokens > 200000 maximum"}}"#;
+    /// // The history is counted at 150,000 tokens, over the 136,000 left by max_tokens
+    /// let client = MockMessageClient::new()
+    ///     .error(MessageError::ApiError(too_long.to_string()))
+    ///     .text_response("Sure")
+    ///     .token_count(150_000);
+    /// let mut conversation = Conversation::new("claude-sonnet-4-5", 64_000).with_auto_trim(1);
+    /// conversation.push(Message::new_text(Role::User, "Summarize this book: ..."));
+    /// conversation.push(Message::new_text(Role::Assistant, "The book is about ..."));
+    ///
+    /// conversation.send(&client, "Now write a haiku").await?;
+    /// assert_eq!(conversation.trims()[0].dropped, 2);
+    /// assert_eq!(client.requests()[1].messages.len(), 1);
+    /// assert_eq!(conversation.messages().len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_auto_trim(mut self, keep_turns: usize) -> Self {
+        self.auto_trim = Some(keep_turns);
+        self
+    }
+
+    /// Truncations applied by [automatic trimming](Self::with_auto_trim), oldest first
+    pub fn trims(&self) -> &[Truncation] {
+        &self.trims
+    }
+
+    /// Code execution container of the last reply that created or used one
+    ///
+    /// Unless the parameters name a container, the next turn reuses this one
+    /// while it has not expired.
+    pub fn container(&self) -> Option<&Container> {
+        self.container.as_ref()
+    }
+
+    /// Forget the container of the last reply, so the next turn starts a new one
+    ///
+    /// A container named by the parameters is still used.
+    pub fn clear_container(&mut self) {
+        self.container = None;
+    }
+
+    /// Number of turns in the history
+    ///
+    /// A turn starts with a user message that is not a tool result, so the
+    /// tool use exchanges of a reply belong to the turn they answer.
+    pub fn turn_count(&self) -> usize {
+        turn_starts(&self.messages).count()
+    }
+
+    /// Start a branch of the conversation with the same history
+    ///
+    /// The branch and the original continue independently: turns sent on one
+    /// are not seen by the other. The history, parameters and compactions are
+    /// copied, while the [usage](Self::usage) of the branch starts at zero so
+    /// each branch reports only its own turns.
+    ///
+    /// The history is deep-cloned rather than shared between branches. This
+    /// costs as much as sending one turn, since every request already copies
+    /// the whole history into its [`CreateMessageParams`], and it keeps
+    /// [`messages`](Self::messages) a plain slice that each branch can edit,
+    /// trim or compact on its own.
+    ///
+    /// Some state lives outside the conversation and is shared:
+    ///
+    /// - the [usage tracker](Self::with_usage_tracker), so all branches count
+    ///   towards the same budget;
+    /// - the code execution [container](Self::container), which the API keeps
+    ///   on its side, so files written by one branch are seen by the other.
+    ///   Call [`clear_container`](Self::clear_container) on a branch to give
+    ///   it a fresh container;
+    /// - the prompt cache, which the API keys by the request prefix, so
+    ///   branches sending the same history read the same cache entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::MockMessageClient;
+    /// use anthropic_ai_sdk::types::conversation::Conversation;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MockMessageClient::new()
+    ///     .text_response("Paris")
+    ///     .text_response("A formal answer")
+    ///     .text_response("A casual answer");
+    ///
+    /// let mut conversation = Conversation::new("claude-sonnet-4-5", 1024);
+    /// conversation.send(&client, "Capital of France?").await?;
+    ///
+    /// let mut formal = conversation.fork();
+    /// let mut casual = conversation.fork();
+    /// formal.send(&client, "Describe it formally").await?;
+    /// casual.send(&client, "Describe it casually").await?;
+    ///
+    /// assert_eq!(conversation.messages().len(), 2);
+    /// assert_eq!(formal.messages()[3].text(), Some("A formal answer"));
+    /// assert_eq!(casual.messages()[3].text(), Some("A casual answer"));
+    ///
+    /// // Each branch counts only its own turns
+    /// assert_eq!(conversation.usage().input_tokens, 10);
+    /// assert_eq!(formal.usage().input_tokens, 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fork(&self) -> Self {
+        Self {
+            usage: Usage::default(),
+            requests: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Start a branch of the conversation keeping only its first `turns` turns
+    ///
+    /// Use this to try another answer to an earlier turn: the history of the
+    /// branch ends right before turn number `turns`, counted from zero, was
+    /// sent. Everything else is as for [`fork`](Self::fork). A container is
+    /// kept even though files of the dropped turns may remain in it.
+    ///
+    /// Returns `None` if the conversation has fewer than `turns` turns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::MockMessageClient;
+    /// use anthropic_ai_sdk::types::conversation::Conversation;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MockMessageClient::new().text_response("Hi").text_response("Fine");
+    /// let mut conversation = Conversation::new("claude-sonnet-4-5", 1024);
+    /// conversation.send(&client, "Hello").await?;
+    /// conversation.send(&client, "How are you?").await?;
+    /// assert_eq!(conversation.turn_count(), 2);
+    ///
+    /// let branch = conversation.fork_at(1).unwrap();
+    /// assert_eq!(branch.messages(), &conversation.messages()[..2]);
+    /// assert!(conversation.fork_at(0).unwrap().messages().is_empty());
+    /// assert!(conversation.fork_at(3).is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fork_at(&self, turns: usize) -> Option<Self> {
+        let end = match turn_starts(&self.messages).nth(turns) {
+            Some(start) => start,
+            None if turns == self.turn_count() => self.messages.len(),
+            None => return None,
+        };
+        let mut branch = Self {
+            params: self.params.clone(),
+            messages: self.messages[..end].to_vec(),
+            container: self.container.clone(),
+            auto_trim: self.auto_trim,
+            usage_tracker: self.usage_tracker.clone(),
+            ..Default::default()
+        };
+        if end > 0 {
+            branch.compactions = self.compactions.clone();
+            branch.summarized = self.summarized;
+        }
+        Some(branch)
+    }
+
+    /// Replace the last assistant reply with `response`
+    ///
+    /// Use this for a "regenerate" action: send the same history again, e.g.
+    /// from a [`fork_at`](Self::fork_at) branch, and adopt the new reply. Its
+    /// usage is added to the conversation and its container is recorded.
+    ///
+    /// Returns the replaced message, or `None` without changing anything if
+    /// the history does not end with an assistant message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::MockMessageClient;
+    /// use anthropic_ai_sdk::types::conversation::Conversation;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MockMessageClient::new()
+    ///     .text_response("A dull joke")
+    ///     .text_response("A better joke");
+    /// let mut conversation = Conversation::new("claude-sonnet-4-5", 1024);
+    /// conversation.send(&client, "Tell me a joke").await?;
+    ///
+    /// let mut retry = conversation.fork_at(0).unwrap();
+    /// let answer = retry.send(&client, "Tell me a joke").await?;
+    /// let replaced = conversation.replace_last_response(answer.into_response()).unwrap();
+    /// assert_eq!(replaced.text(), Some("A dull joke"));
+    /// assert_eq!(conversation.messages().len(), 2);
+    /// assert_eq!(conversation.messages()[1].text(), Some("A better joke"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replace_last_response(&mut self, response: CreateMessageResponse) -> Option<Message> {
+        if self.messages.last()?.role != Role::Assistant {
+            return None;
+        }
+        self.record_usage(&response);
+        let replaced = self.messages.pop();
+        self.messages.push(Message::from(response));
+        replaced
+    }
+
+    /// Serialize the conversation to JSON tagged with [`CONVERSATION_FORMAT_VERSION`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a tool input or other JSON value fails to serialize.
+    pub fn to_json(&self) -> Result<String, PersistError> {
+        let mut value = serde_json::to_value(self)?;
+        if let serde_json::Value::Object(fields) = &mut value {
+            fields.insert("version".to_string(), CONVERSATION_FORMAT_VERSION.into());
+        }
+        Ok(serde_json::to_string(&value)?)
+    }
+
+    /// Deserialize a conversation written by [`to_json`](Self::to_json)
+    ///
+    /// Conversations written in older format versions are migrated; JSON
+    /// without a `version` field is read as the unversioned format of plain
+    /// serde serialization.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid or was written in a newer
+    /// format version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::conversation::{Conversation, PersistError};
+    /// use anthropic_ai_sdk::types::message::{ContentBlock, Message, Role};
+    ///
+    /// let mut conversation = Conversation::new("claude-3-7-sonnet-20250219", 1024);
+    /// let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    /// conversation.push(Message::new_blocks(
+    ///     Role::User,
+    ///     vec![ContentBlock::image_from_bytes(png).unwrap(), ContentBlock::text("Describe it")],
+    /// ));
+    /// let (tool_use, id) = ContentBlock::tool_use("zoom", serde_json::json!({"factor": 2}));
+    /// let unknown: ContentBlock =
+    ///     serde_json::from_str(r#"{"type": "hologram", "frames": [1, 2]}"#).unwrap();
+    /// conversation.push(Message::new_blocks(
+    ///     Role::Assistant,
+    ///     vec![
+    ///         ContentBlock::Thinking {
+    ///             thinking: "Zoom in first.".to_string(),
+    ///             signature: "sig".to_string(),
+    ///         },
+    ///         unknown,
+    ///         tool_use,
+    ///     ],
+    /// ));
+    /// conversation.push(Message::new_blocks(Role::User, vec![ContentBlock::tool_result(id, "done")]));
+    ///
+    /// let json = conversation.to_json().unwrap();
+    /// assert_eq!(Conversation::from_json(&json).unwrap(), conversation);
+    ///
+    /// // The unversioned format is still read
+    /// let unversioned = serde_json::to_string(&conversation).unwrap();
+    /// assert_eq!(Conversation::from_json(&unversioned).unwrap(), conversation);
+    ///
+    /// let newer = r#"{"version": 999, "params": {}, "messages": [], "usage": {}}"#;
+    /// assert!(matches!(
+    ///     Conversation::from_json(newer),
+    ///     Err(PersistError::UnsupportedVersion(version)) if version == 999
+    /// ));
+    ///
+    /// // A version that is not an integer is not mistaken for the unversioned format
+    /// for version in [r#""2""#, "1.5", "-1", "null"] {
+    ///     let json = format!(r#"{{"version": {version}, "params": {{}}, "messages": [], "usage": {{}}}}"#);
+    ///     assert!(matches!(
+    ///         Conversation::from_json(&json),
+    ///         Err(PersistError::UnsupportedVersion(_))
+    ///     ));
+    /// }
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, PersistError> {
+        let mut value: serde_json::Value = serde_json::from_str(json)?;
+        let version = match &mut value {
+            serde_json::Value::Object(fields) => match fields.remove("version") {
+                Some(version) => match version.as_u64() {
+                    Some(number) if number <= u64::from(CONVERSATION_FORMAT_VERSION) => number,
+                    _ => return Err(PersistError::UnsupportedVersion(version)),
+                },
+                None => 0,
+            },
+            _ => 0,
+        };
+        for from in version..u64::from(CONVERSATION_FORMAT_VERSION) {
+            migrate(&mut value, from);
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Write the conversation to a file with [`to_json`](Self::to_json)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the conversation fails to serialize or the file
+    /// cannot be written.
+    #[cfg(feature = "fs")]
+    pub async fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), PersistError> {
+        tokio::fs::write(path, self.to_json()?).await?;
+        Ok(())
+    }
+
+    /// Read a conversation from a file with [`from_json`](Self::from_json)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not contain a
+    /// conversation in a supported format version.
+    #[cfg(feature = "fs")]
+    pub async fn load(path: impl AsRef<std::path::Path>) -> Result<Self, PersistError> {
+        Self::from_json(&tokio::fs::read_to_string(path).await?)
+    }
+
+    /// Compactions applied to the history so far, oldest first
+    pub fn compactions(&self) -> &[Compaction] {
+        &self.compactions
+    }
+
+    /// Text of the summary replacing the oldest turns, if the history was compacted
+    ///
+    /// The summary is the first block of the first message, wrapped in
+    /// `<conversation_summary>` tags so the model and exports of the history
+    /// can tell it was not written by the user.
+    pub fn summary(&self) -> Option<&str> {
+        if !self.summarized {
+            return None;
+        }
+        match &self.messages.first()?.content {
+            MessageContent::Blocks { content } => summary_text(content.first()?),
+            MessageContent::Text { .. } => None,
+        }
+    }
+
+    /// Append a message to the history without sending it
+    ///
+    /// Content of a message with the same role as the last one is merged into
+    /// it, so roles keep alternating.
+    pub fn push(&mut self, message: Message) {
+        push_merged(&mut self.messages, message);
+    }
+
+    /// Tool uses of the last assistant reply that still need a result
+    ///
+    /// Send their results with [`send_blocks`](Self::send_blocks) to continue.
+    pub fn pending_tool_uses(&self) -> Vec<ToolUseRef<'_>> {
+        match self.messages.last() {
+            Some(message) if message.role == Role::Assistant => match &message.content {
+                MessageContent::Blocks { content } => {
+                    content.iter().filter_map(ToolUseRef::from_block).collect()
+                }
+                MessageContent::Text { .. } => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    /// Send a user message and record the reply
+    ///
+    /// The [`Answer`] dereferences to the text of the reply and keeps the full
+    /// response.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the request, the history is left unchanged.
+    pub async fn send<C>(
+        &mut self,
+        client: &C,
+        text: impl Into<String>,
+    ) -> Result<Answer, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        self.send_blocks(client, vec![ContentBlock::text(text)])
+            .await
+    }
+
+    /// Send a user message made of content blocks and record the reply
+    ///
+    /// Use this to answer the [pending tool uses](Self::pending_tool_uses)
+    /// with `tool_result` blocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the request, the history is left unchanged.
+    pub async fn send_blocks<C>(
+        &mut self,
+        client: &C,
+        blocks: Vec<ContentBlock>,
+    ) -> Result<Answer, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        self.create(client, Some(blocks)).await.map(Answer::from)
+    }
+
+    /// Continue a paused assistant turn and record the rest of the reply
+    ///
+    /// Replies with a [continuable](crate::types::message::StopReason::is_continuable)
+    /// stop reason are continued by sending the history unchanged. The content
+    /// of the continuation is merged into the last assistant message.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the request, the history is left unchanged.
+    pub async fn continue_turn<C>(&mut self, client: &C) -> Result<Answer, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        self.create(client, None).await.map(Answer::from)
+    }
+
+    /// Stream the reply to a user message
+    ///
+    /// The turn is recorded once the stream has ended. If the stream fails or
+    /// is dropped early, the history is left unchanged.
+    pub fn stream<'a, C>(
+        &'a mut self,
+        client: &'a C,
+        text: impl Into<String>,
+    ) -> impl Stream<Item = Result<StreamEvent, MessageError>> + 'a
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        self.stream_blocks(client, vec![ContentBlock::text(text)])
+    }
+
+    /// Stream the reply to a user message made of content blocks
+    ///
+    /// The turn is recorded once the stream has ended. If the stream fails or
+    /// is dropped early, the history is left unchanged, apart from turns
+    /// dropped by [automatic trimming](Self::with_auto_trim).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::MockMessageClient;
+    /// use anthropic_ai_sdk::types::conversation::Conversation;
+    /// use anthropic_ai_sdk::types::message::{ContentBlock, Message, MessageError, Role};
+    /// use futures_util::TryStreamExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let too_long = r#"{"type": "error", "error": {"type": "invalid_request_error",
+    ///     "message": "prompt is too long: 210000 tokens > 200000 maximum"}}"#;
+    /// let client = MockMessageClient::new()
+    ///     .error(MessageError::ApiError(too_long.to_string()))
+    ///     .text_response("Sure")
+    ///     .token_count(150_000);
+    /// let mut conversation = Conversation::new("claude-sonnet-4-5", 64_000).with_auto_trim(1);
+    /// conversation.push(Message::new_text(Role::User, "Summarize this book: ..."));
+    /// conversation.push(Message::new_text(Role::Assistant, "The book is about ..."));
+    ///
+    /// let blocks = vec![ContentBlock::text("Now write a haiku")];
+    /// let events: Vec<_> = conversation.stream_blocks(&client, blocks).try_collect().await?;
+    /// assert!(!events.is_empty());
+    /// assert_eq!(conversation.trims()[0].dropped, 2);
+    /// assert_eq!(client.requests()[1].messages.len(), 1);
+    /// assert_eq!(conversation.messages().len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_blocks<'a, C>(
+        &'a mut self,
+        client: &'a C,
+        blocks: Vec<ContentBlock>,
+    ) -> impl Stream<Item = Result<StreamEvent, MessageError>> + 'a
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        let blocks = Some(blocks);
+        let (sender, receiver) = mpsc::channel(1);
+        let driver = async move {
+            let result = async {
+                let request = self.prepare(client, &blocks, true).await?;
+                let mut retry = None;
+                let response = {
+                    let events = match client.create_message_streaming(&request).await {
+                        Ok(events) => events,
+                        Err(error) => {
+                            let trimmed =
+                                self.retry_trimmed(client, error, &request, blocks).await?;
+                            client
+                                .create_message_streaming(retry.insert(trimmed))
+                                .await?
+                        }
+                    };
+                    let mut events = std::pin::pin!(events);
+                    let mut accumulator = MessageAccumulator::new();
+                    while let Some(event) = events.next().await {
+                        let event = event?;
+                        accumulator.push(&event)?;
+                        if sender.send(Ok(event)).await.is_err() {
+                            return Ok(None);
+                        }
+                    }
+                    accumulator.finish()?
+                };
+                let messages = retry.unwrap_or(request).messages;
+                Ok(Some((messages, response)))
+            }
+            .await;
+            match result {
+                Ok(Some((messages, response))) => self.record(messages, response),
+                Ok(None) => {}
+                Err(error) => {
+                    let _ = sender.send(Err(error)).await;
+                }
+            }
+        };
+        TurnStream {
+            driver: Some(Box::pin(driver)),
+            receiver,
+        }
+    }
+
+    /// Drop the oldest turns until the history fits `max_input_tokens`
+    ///
+    /// The system prompt, the last `keep_turns` turns and complete tool use
+    /// exchanges are always kept; see [`find_truncation`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of a `count_tokens` request, the history is left
+    /// unchanged.
+    pub async fn truncate_to_fit<C>(
+        &mut self,
+        client: &C,
+        max_input_tokens: u32,
+        keep_turns: usize,
+    ) -> Result<Truncation, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        let truncation = find_truncation(
+            client,
+            &self.params,
+            &self.messages,
+            max_input_tokens,
+            keep_turns,
+        )
+        .await?;
+        self.drop_oldest(truncation.dropped);
+        if truncation.dropped > 0 {
+            self.summarized = false;
+        }
+        Ok(truncation)
+    }
+
+    /// Compact the history if it exceeds the threshold of `options`
+    ///
+    /// The input tokens of the next request are counted with `client`. Above
+    /// the threshold, every turn but the last [`keep_turns`](CompactionOptions::keep_turns)
+    /// is summarized with `compactor`, see [`compact`](Self::compact).
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the `count_tokens` request or of the compactor,
+    /// the history is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::MockMessageClient;
+    /// use anthropic_ai_sdk::types::compaction::{CompactionOptions, ModelCompactor};
+    /// use anthropic_ai_sdk::types::conversation::Conversation;
+    /// use anthropic_ai_sdk::types::message::{ContentBlock, MessageError};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), MessageError> {
+    /// let chat = MockMessageClient::new()
+    ///     .text_response("Hi")
+    ///     .text_response("Hi again")
+    ///     .text_response("Fine, thanks.");
+    /// let mut conversation = Conversation::new("claude-3-7-sonnet-20250219", 1024)
+    ///     .with_system("Be brief.");
+    /// for text in ["Hello", "Hello again", "How are you?"] {
+    ///     conversation.send(&chat, text).await?;
+    /// }
+    ///
+    /// let summarizer = MockMessageClient::new().text_response("The user said hello twice.");
+    /// let compactor = ModelCompactor::new(&summarizer);
+    /// let options = CompactionOptions::new(40).keep_turns(1).retain_original(true);
+    ///
+    /// // Below the threshold, the history is left as it is
+    /// let small = MockMessageClient::new().token_count(40);
+    /// assert!(conversation.compact_if_needed(&small, &compactor, &options).await?.is_none());
+    /// assert_eq!(conversation.messages().len(), 6);
+    ///
+    /// let large = MockMessageClient::new().token_count(41);
+    /// let compaction = conversation.compact_if_needed(&large, &compactor, &options).await?;
+    /// assert_eq!(compaction.unwrap().replaced, 4);
+    /// assert_eq!(conversation.compactions()[0].original.len(), 4);
+    ///
+    /// // The last turn is kept, after the summary
+    /// let messages = conversation.messages();
+    /// assert_eq!(messages.len(), 2);
+    /// assert_eq!(conversation.summary(), Some("The user said hello twice."));
+    /// assert!(matches!(
+    ///     &messages[0].blocks()[1],
+    ///     ContentBlock::Text { text, .. } if text == "How are you?"
+    /// ));
+    /// assert_eq!(messages[1].text(), Some("Fine, thanks."));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn compact_if_needed<C, S>(
+        &mut self,
+        client: &C,
+        compactor: &S,
+        options: &CompactionOptions,
+    ) -> Result<Option<&Compaction>, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+        S: Compactor + ?Sized,
+    {
+        let mut request = CountMessageTokensParams::from(&self.params);
+        request.messages = self.messages.clone();
+        let input_tokens = client.count_tokens(Some(&request)).await?.input_tokens;
+        if input_tokens <= options.threshold {
+            return Ok(None);
+        }
+        self.compact(compactor, options).await
+    }
+
+    /// Replace every turn but the last [`keep_turns`](CompactionOptions::keep_turns)
+    /// with a summary written by `compactor`
+    ///
+    /// The summary becomes the first block of the first kept user message, so
+    /// roles keep alternating, and is returned by [`summary`](Self::summary).
+    /// The block wraps it in `<conversation_summary>` tags.
+    /// A previous summary is part of the summarized turns. The history is
+    /// only cut where [`find_truncation`] would cut it. Returns `None` if
+    /// there are not enough turns to compact.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the compactor, the history is left unchanged.
+    pub async fn compact<S>(
+        &mut self,
+        compactor: &S,
+        options: &CompactionOptions,
+    ) -> Result<Option<&Compaction>, MessageError>
+    where
+        S: Compactor + ?Sized,
+    {
+        let Some(&cut) = cut_points(&self.messages, options.keep_turns).last() else {
+            return Ok(None);
+        };
+        let summary = compactor
+            .summarize(&self.params, &self.messages[..cut])
+            .await?;
+
+        let original = self.drop_oldest(cut);
+        let first = &mut self.messages[0];
+        let mut blocks = vec![summary_block(&summary)];
+        blocks.extend(first.blocks().into_owned());
+        *first = Message::new_blocks(Role::User, blocks);
+        self.summarized = true;
+        self.compactions.push(Compaction {
+            summary,
+            replaced: original.len(),
+            original: if options.retain_original {
+                original
+            } else {
+                Vec::new()
+            },
+        });
+        Ok(self.compactions.last())
+    }
+
+    /// Send the next request, with `blocks` appended as a user message, and record the reply
+    async fn create<C>(
+        &mut self,
+        client: &C,
+        blocks: Option<Vec<ContentBlock>>,
+    ) -> Result<CreateMessageResponse, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        let mut request = self.prepare(client, &blocks, false).await?;
+        let response = match client.create_message(Some(&request)).await {
+            Ok(response) => response,
+            Err(error) => {
+                request = self.retry_trimmed(client, error, &request, blocks).await?;
+                client.create_message(Some(&request)).await?
+            }
+        };
+        self.record(request.messages, response.clone());
+        Ok(response)
+    }
+
+    /// Check the budget and build the next request, trimmed first if it exceeds the trim limit
+    async fn prepare<C>(
+        &mut self,
+        client: &C,
+        blocks: &Option<Vec<ContentBlock>>,
+        stream: bool,
+    ) -> Result<CreateMessageParams, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        self.check_budget()?;
+        let request = self.request(blocks.clone(), stream);
+        if self
+            .trim_limit()
+            .is_some_and(|limit| estimate_request_tokens(&request) > limit)
+            && self.trim(client, &request.messages).await?
+        {
+            return Ok(self.request(blocks.clone(), stream));
+        }
+        Ok(request)
+    }
+
+    /// The request to retry after `error` rejected `request`, trimmed to fit
+    ///
+    /// Returns `error` unless it is a context length error and trimming dropped messages.
+    async fn retry_trimmed<C>(
+        &mut self,
+        client: &C,
+        error: MessageError,
+        request: &CreateMessageParams,
+        blocks: Option<Vec<ContentBlock>>,
+    ) -> Result<CreateMessageParams, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        if !error.is_context_length_exceeded()
+            || self.trim_limit().is_none()
+            || !self.trim(client, &request.messages).await?
+        {
+            return Err(error);
+        }
+        Ok(self.request(blocks, request.stream == Some(true)))
+    }
+
+    /// Input tokens the history may use with automatic trimming, if enabled
+    fn trim_limit(&self) -> Option<u32> {
+        self.auto_trim?;
+        let reserved = match &self.params.thinking {
+            Some(Thinking::Enabled { budget_tokens }) => self.params.max_tokens.max(*budget_tokens),
+            _ => self.params.max_tokens,
+        };
+        let spec = self.params.model_spec()?;
+        Some(spec.context_window.saturating_sub(reserved))
+    }
+
+    /// Drop the oldest turns of `messages`, the history and next user message, to fit the limit
+    ///
+    /// Returns whether any message was dropped.
+    async fn trim<C>(&mut self, client: &C, messages: &[Message]) -> Result<bool, MessageError>
+    where
+        C: MessageClient + Sync + ?Sized,
+    {
+        let (Some(keep_turns), Some(limit)) = (self.auto_trim, self.trim_limit()) else {
+            return Ok(false);
+        };
+        let truncation = find_truncation(client, &self.params, messages, limit, keep_turns).await?;
+        if truncation.dropped == 0 {
+            return Ok(false);
+        }
+        tracing::info!(
+            dropped = truncation.dropped,
+            input_tokens = truncation.input_tokens,
+            fits = truncation.fits,
+            "Trimmed conversation history to fit the context window"
+        );
+        self.drop_oldest(truncation.dropped);
+        self.summarized = false;
+        self.trims.push(truncation);
+        Ok(true)
+    }
+
+    /// Parameters for the next request, with `blocks` appended as a user message
+    pub(crate) fn request(
+        &self,
+        blocks: Option<Vec<ContentBlock>>,
+        stream: bool,
+    ) -> CreateMessageParams {
+        let mut messages = self.messages.clone();
+        if let Some(blocks) = blocks {
+            push_merged(&mut messages, Message::new_blocks(Role::User, blocks));
+        }
+        let container = self.params.container.clone().or_else(|| {
+            self.container
+                .as_ref()
+                .filter(|container| !container.is_expired())
+                .map(|container| container.id.clone())
+        });
+        CreateMessageParams {
+            messages,
+            stream: stream.then_some(true),
+            container,
+            ..self.params.clone()
+        }
+    }
+
+    pub(crate) fn check_budget(&self) -> Result<(), MessageError> {
+        self.usage_tracker
+            .as_ref()
+            .map_or(Ok(()), |tracker| tracker.check())
+    }
+
+    /// Make the messages of a successful request the history and add the reply
+    pub(crate) fn record(&mut self, messages: Vec<Message>, response: CreateMessageResponse) {
+        self.messages = messages;
+        self.record_usage(&response);
+        push_merged(&mut self.messages, Message::from(response));
+    }
+
+    /// Remove the oldest `count` messages from the history and return them
+    fn drop_oldest(&mut self, count: usize) -> Vec<Message> {
+        for request in &mut self.requests {
+            request.message = request
+                .message
+                .and_then(|message| message.checked_sub(count));
+        }
+        self.messages.drain(..count).collect()
+    }
+
+    /// Add the usage of `response` and keep its container
+    ///
+    /// Call this before the reply is added to the history.
+    fn record_usage(&mut self, response: &CreateMessageResponse) {
+        let merged = self
+            .messages
+            .last()
+            .is_some_and(|message| message.role == Role::Assistant);
+        self.requests.push(RequestUsage {
+            message: Some(self.messages.len() - usize::from(merged)),
+            model: response.model.clone(),
+            usage: response.usage.clone(),
+        });
+        self.usage += response.usage.clone();
+        if let Some(tracker) = &self.usage_tracker {
+            tracker.record(&response.model, &response.usage);
+        }
+        if response.container.is_some() {
+            self.container = response.container.clone();
+        }
+    }
+}
+
+impl From<CreateMessageParams> for Conversation {
+    fn from(params: CreateMessageParams) -> Self {
+        Self::from_params(params)
+    }
+}
+
+/// Usage of one request of a [`Conversation`]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RequestUsage {
+    /// Index of the assistant message holding the reply
+    ///
+    /// `None` once the message was removed from the history by truncation or
+    /// compaction.
+    pub message: Option<usize>,
+    /// Model that handled the request
+    pub model: String,
+    /// Usage reported for the request
+    pub usage: Usage,
+}
+
+/// Upgrade a serialized conversation from format version `from` to the next one
+fn migrate(_value: &mut serde_json::Value, from: u64) {
+    match from {
+        // Unversioned conversations have the layout of version 1
+        0 => {}
+        _ => unreachable!("no migration from format version {}", from),
+    }
+}
+
+/// Where to cut a history to fit a token budget
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Truncation {
+    /// Number of oldest messages to drop
+    pub dropped: usize,
+    /// Input tokens of the request with the remaining messages
+    pub input_tokens: u32,
+    /// Whether the remaining messages fit the budget
+    ///
+    /// If not, dropping more messages would drop the turns to keep.
+    pub fits: bool,
+}
+
+/// Find how many of the oldest messages to drop to fit `max_input_tokens`
+///
+/// Tokens are counted with the model, system prompt, tools and thinking
+/// configuration of `params`, whose own messages are ignored. The history is
+/// only cut before a user message that starts a turn, so the kept history
+/// starts with a user message and no tool result is separated from its tool
+/// use. The last `keep_turns` turns, at least one, are always kept.
+///
+/// The cut point is found by binary search, so the number of `count_tokens`
+/// requests grows with the logarithm of the number of turns.
+///
+/// # Errors
+///
+/// Returns the error of the first `count_tokens` request that fails.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::conversation::find_truncation;
+/// use anthropic_ai_sdk::types::estimate::estimate_message_tokens;
+/// use anthropic_ai_sdk::types::message::{
+///     ContentBlock, CreateMessageParams, Message, MessageError, Role,
+/// };
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), MessageError> {
+/// let (tool_use, id) = ContentBlock::tool_use("lookup", serde_json::json!({}));
+/// let messages = vec![
+///     Message::new_text(Role::User, "First question"),
+///     Message::new_text(Role::Assistant, "First answer"),
+///     Message::new_text(Role::User, "Second question"),
+///     Message::new_blocks(Role::Assistant, vec![tool_use]),
+///     Message::new_blocks(Role::User, vec![ContentBlock::tool_result(id, "42")]),
+///     Message::new_text(Role::Assistant, "Second answer"),
+/// ];
+/// let params = CreateMessageParams::default();
+///
+/// // The mock counts tokens with the estimate
+/// let client = MockMessageClient::new();
+/// let last_turn: u32 = messages[2..].iter().map(estimate_message_tokens).sum();
+///
+/// // Cutting right before the tool result would orphan it
+/// let truncation = find_truncation(&client, &params, &messages, last_turn - 1, 1).await?;
+/// assert_eq!(truncation.dropped, 2);
+/// assert_eq!(truncation.input_tokens, last_turn);
+/// assert!(!truncation.fits);
+///
+/// let truncation = find_truncation(&client, &params, &messages, last_turn, 1).await?;
+/// assert_eq!((truncation.dropped, truncation.fits), (2, true));
+/// assert_eq!(client.count_requests().last().unwrap().messages, &messages[2..]);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn find_truncation<C>(
+    client: &C,
+    params: &CreateMessageParams,
+    messages: &[Message],
+    max_input_tokens: u32,
+    keep_turns: usize,
+) -> Result<Truncation, MessageError>
+where
+    C: MessageClient + Sync + ?Sized,
+{
+    let count = |dropped: usize| {
+        let mut request = CountMessageTokensParams::from(params);
+        request.messages = messages[dropped..].to_vec();
+        async move { client.count_tokens(Some(&request)).await }
+    };
+    let truncation = |dropped: usize, input_tokens: u32| Truncation {
+        dropped,
+        input_tokens,
+        fits: input_tokens <= max_input_tokens,
+    };
+
+    let full = count(0).await?.input_tokens;
+    if full <= max_input_tokens {
+        return Ok(truncation(0, full));
+    }
+
+    let cuts = cut_points(messages, keep_turns);
+    let Some(&deepest) = cuts.last() else {
+        return Ok(truncation(0, full));
+    };
+    let deepest_tokens = count(deepest).await?.input_tokens;
+    if deepest_tokens > max_input_tokens {
+        return Ok(truncation(deepest, deepest_tokens));
+    }
+
+    // Smallest cut that fits, knowing that the deepest one does
+    let (mut low, mut high, mut high_tokens) = (0, cuts.len() - 1, deepest_tokens);
+    while low < high {
+        let middle = (low + high) / 2;
+        let tokens = count(cuts[middle]).await?.input_tokens;
+        if tokens <= max_input_tokens {
+            (high, high_tokens) = (middle, tokens);
+        } else {
+            low = middle + 1;
+        }
+    }
+    Ok(truncation(cuts[high], high_tokens))
+}
+
+/// Indices before which a history may be cut, keeping the last `keep_turns` turns
+///
+/// A turn starts with a user message that is not a tool result.
+fn cut_points(messages: &[Message], keep_turns: usize) -> Vec<usize> {
+    let turns: Vec<usize> = turn_starts(messages).collect();
+    match turns.len().checked_sub(keep_turns.max(1)) {
+        Some(last) => turns[..=last]
+            .iter()
+            .copied()
+            .filter(|&cut| cut > 0)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Indices of the user messages that start a turn
+fn turn_starts(messages: &[Message]) -> impl Iterator<Item = usize> + '_ {
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| message.role == Role::User && !has_tool_result(message))
+        .map(|(index, _)| index)
+}
+
+fn has_tool_result(message: &Message) -> bool {
+    match &message.content {
+        MessageContent::Blocks { content } => content
+            .iter()
+            .any(|block| matches!(block, ContentBlock::ToolResult { .. })),
+        MessageContent::Text { .. } => false,
+    }
+}
+
+/// Append `message`, merging it into the last message if both have the same role
+pub(crate) fn push_merged(messages: &mut Vec<Message>, message: Message) {
+    match messages.last_mut() {
+        Some(last) if last.role == message.role => {
+            for block in message.blocks().into_owned() {
+                last.push_block(block);
+            }
+        }
+        _ => messages.push(message),
+    }
+}
+
+/// Events of a streamed turn, forwarded from the future recording it
+pub(crate) struct TurnStream<'a, T = StreamEvent> {
+    pub(crate) driver: Option<Pin<Box<dyn Future<Output = ()> + 'a>>>,
+    pub(crate) receiver: mpsc::Receiver<Result<T, MessageError>>,
+}
+
+impl<T> Stream for TurnStream<'_, T> {
+    type Item = Result<T, MessageError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(driver) = self.driver.as_mut() {
+            if driver.as_mut().poll(cx).is_ready() {
+                self.driver = None;
+            }
+        }
+        self.receiver.poll_recv(cx)
+    }
+}
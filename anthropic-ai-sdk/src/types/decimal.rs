@@ -0,0 +1,321 @@
+//! Exact decimal numbers
+//!
+//! This module contains [`Decimal`], the number type of prices and cost
+//! amounts, shared by the pricing table and the Admin API cost report.
+//!
+use std::fmt;
+use std::ops::{Add, AddAssign};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// An exact decimal number, used for prices and cost amounts
+///
+/// Amounts are kept as an integer mantissa and a decimal scale, so sums are
+/// exact and never pick up floating point rounding errors. Values are
+/// normalized, so `1.50` and `1.5` are equal.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::decimal::Decimal;
+///
+/// let a: Decimal = "0.1".parse().unwrap();
+/// let b: Decimal = "0.2".parse().unwrap();
+/// assert_eq!((a + b).to_string(), "0.3");
+///
+/// let cents: Decimal = "12345.60".parse().unwrap();
+/// assert_eq!(cents.div_pow10(2).to_string(), "123.456");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+/// Error returned when parsing a [`Decimal`] fails
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid decimal: {0:?}")]
+pub struct ParseDecimalError(String);
+
+impl Decimal {
+    /// Zero
+    pub const ZERO: Decimal = Decimal {
+        mantissa: 0,
+        scale: 0,
+    };
+
+    /// Maximum number of decimal places
+    pub const MAX_SCALE: u32 = 28;
+
+    /// Create a decimal with the value `mantissa * 10^-scale`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is larger than [`Decimal::MAX_SCALE`].
+    pub const fn new(mantissa: i128, scale: u32) -> Self {
+        assert!(scale <= Self::MAX_SCALE, "decimal scale out of range");
+        Self { mantissa, scale }.normalized()
+    }
+
+    /// The integer mantissa of the normalized value
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// The number of decimal places of the normalized value
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Divide by `10^places`, e.g. to turn cents into dollars
+    ///
+    /// Results needing more than [`Decimal::MAX_SCALE`] decimal places are
+    /// rounded to that many, half away from zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::decimal::Decimal;
+    ///
+    /// let price = Decimal::new(15, Decimal::MAX_SCALE);
+    /// assert_eq!(price.div_pow10(1), Decimal::new(2, Decimal::MAX_SCALE));
+    /// assert_eq!(price.div_pow10(40), Decimal::ZERO);
+    /// ```
+    pub fn div_pow10(self, places: u32) -> Self {
+        let scale = self.scale.saturating_add(places);
+        if scale <= Self::MAX_SCALE {
+            return Self::new(self.mantissa, scale);
+        }
+        let dropped = scale - Self::MAX_SCALE;
+        // 10^39 exceeds every mantissa, so the result rounds to zero
+        let Some(divisor) = 10u128.checked_pow(dropped) else {
+            return Self::ZERO;
+        };
+        let magnitude = self.mantissa.unsigned_abs();
+        let mut quotient = magnitude / divisor;
+        if (magnitude % divisor) * 2 >= divisor {
+            quotient += 1;
+        }
+        // The quotient is at most `i128::MAX / 10 + 1`, so it fits
+        let quotient = quotient as i128;
+        let mantissa = if self.mantissa < 0 {
+            -quotient
+        } else {
+            quotient
+        };
+        Self::new(mantissa, Self::MAX_SCALE)
+    }
+
+    /// Add two decimals, returning `None` on overflow
+    pub fn checked_add(self, other: Decimal) -> Option<Decimal> {
+        let scale = self.scale.max(other.scale);
+        let a = self.mantissa.checked_mul(10i128.pow(scale - self.scale))?;
+        let b = other
+            .mantissa
+            .checked_mul(10i128.pow(scale - other.scale))?;
+        Some(
+            Self {
+                mantissa: a.checked_add(b)?,
+                scale,
+            }
+            .normalized(),
+        )
+    }
+
+    /// Add two decimals, giving up precision rather than overflowing
+    ///
+    /// When the exact sum does not fit, decimal places are dropped, rounding
+    /// half away from zero, until it does. Sums beyond the integer range
+    /// saturate at the largest or smallest value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::decimal::Decimal;
+    ///
+    /// let big = Decimal::new(i128::MAX, 0);
+    /// assert_eq!(big.saturating_add(big), big);
+    ///
+    /// let tiny = Decimal::new(15, Decimal::MAX_SCALE);
+    /// let large = Decimal::new(10i128.pow(20), 0);
+    /// assert!(large.checked_add(tiny).is_none());
+    /// assert_eq!(large.saturating_add(tiny), large);
+    /// ```
+    pub fn saturating_add(self, other: Decimal) -> Decimal {
+        if let Some(sum) = self.checked_add(other) {
+            return sum;
+        }
+        let mut scale = self.scale.max(other.scale);
+        while scale > 0 {
+            scale -= 1;
+            if let Some(sum) = self.round_to(scale).checked_add(other.round_to(scale)) {
+                return sum;
+            }
+        }
+        let limit = if self.mantissa.is_negative() {
+            i128::MIN
+        } else {
+            i128::MAX
+        };
+        Self::new(limit, 0)
+    }
+
+    /// Round to at most `scale` decimal places, half away from zero
+    fn round_to(self, scale: u32) -> Self {
+        if self.scale <= scale {
+            return self;
+        }
+        let divisor = 10u128.pow(self.scale - scale);
+        let magnitude = self.mantissa.unsigned_abs();
+        let mut quotient = magnitude / divisor;
+        if (magnitude % divisor) * 2 >= divisor {
+            quotient += 1;
+        }
+        // Dividing by at least 10 leaves room for the rounding increment
+        let quotient = quotient as i128;
+        let mantissa = if self.mantissa < 0 {
+            -quotient
+        } else {
+            quotient
+        };
+        Self::new(mantissa, scale)
+    }
+
+    /// Approximate the value as a float, e.g. for display or charts
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    const fn normalized(mut self) -> Self {
+        if self.mantissa == 0 {
+            return Self::ZERO;
+        }
+        while self.scale > 0 && self.mantissa % 10 == 0 {
+            self.mantissa /= 10;
+            self.scale -= 1;
+        }
+        self
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseDecimalError(s.to_string());
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(error());
+        }
+        if fraction.len() > Self::MAX_SCALE as usize {
+            return Err(error());
+        }
+        let mut mantissa: i128 = 0;
+        for c in integer.chars().chain(fraction.chars()) {
+            let digit = c.to_digit(10).ok_or_else(error)?;
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(digit as i128))
+                .ok_or_else(error)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+        Ok(Self::new(mantissa, fraction.len() as u32))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{sign}{integer}.{fraction}")
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    /// Same as [`Decimal::saturating_add`], so sums never panic
+    fn add(self, other: Decimal) -> Decimal {
+        self.saturating_add(other)
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let scale = self.scale.max(other.scale);
+        let rescaled = |d: &Decimal| d.mantissa.checked_mul(10i128.pow(scale - d.scale));
+        match (rescaled(self), rescaled(other)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => self.to_f64().total_cmp(&other.to_f64()),
+        }
+    }
+}
+
+impl AddAssign for Decimal {
+    fn add_assign(&mut self, other: Decimal) {
+        *self = *self + other;
+    }
+}
+
+impl std::iter::Sum for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Decimal {
+        iter.fold(Decimal::ZERO, Add::add)
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DecimalVisitor;
+
+        impl serde::de::Visitor<'_> for DecimalVisitor {
+            type Value = Decimal;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a decimal string or number")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Decimal, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Decimal, E> {
+                Ok(Decimal::new(value as i128, 0))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Decimal, E> {
+                Ok(Decimal::new(value as i128, 0))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Decimal, E> {
+                value.to_string().parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
@@ -0,0 +1,257 @@
+//! Offline token estimation
+//!
+//! This module estimates token counts locally, for character counters and
+//! budget checks that cannot wait for a `count_tokens` request. The estimates
+//! are approximate: they follow a heuristic rather than the model's
+//! tokenizer, and are meant to be within about 15% of the real count for
+//! English prose and code. Use [`MessageClient::count_tokens`] where an exact
+//! count matters.
+//!
+//! [`MessageClient::count_tokens`]: crate::types::message::MessageClient::count_tokens
+//!
+//! # Calibration
+//!
+//! `fixtures/token_counts.json` holds `count_tokens` requests with the
+//! `input_tokens` the API returned for them. The `estimate-tokens` example
+//! records more entries with `cargo run -- --record`. Every entry must be
+//! estimated within the tolerance:
+//!
+//! ```
+//! use anthropic_ai_sdk::types::estimate::estimate_request_tokens;
+//! use anthropic_ai_sdk::types::message::{CountMessageTokensParams, CreateMessageParams};
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Recorded {
+//!     request: CountMessageTokensParams,
+//!     input_tokens: u32,
+//! }
+//!
+//! let fixture = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/token_counts.json"));
+//! let recorded: Vec<Recorded> = serde_json::from_str(fixture).unwrap();
+//! assert!(!recorded.is_empty());
+//! for Recorded { request, input_tokens } in recorded {
+//!     let params = CreateMessageParams {
+//!         model: request.model,
+//!         messages: request.messages,
+//!         system: request.system,
+//!         tools: request.tools,
+//!         ..Default::default()
+//!     };
+//!     let estimated = estimate_request_tokens(&params);
+//!     let deviation = (f64::from(estimated) - f64::from(input_tokens)) / f64::from(input_tokens);
+//!     assert!(deviation.abs() <= 0.15, "estimated {estimated}, counted {input_tokens}");
+//! }
+//! ```
+//!
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use crate::types::image::image_dimensions;
+use crate::types::message::{
+    ContentBlock, CreateMessageParams, DocumentSource, ImageSource, Message, ToolResultContent,
+};
+
+/// Approximate tokens added for the role and delimiters of each message
+pub const MESSAGE_OVERHEAD_TOKENS: u32 = 4;
+
+/// Approximate tokens of the system prompt added when tools are provided
+pub const TOOL_USE_OVERHEAD_TOKENS: u32 = 346;
+
+/// Tokens assumed for an image whose size is unknown
+///
+/// This is the cost of an image at the largest size the API processes
+/// without resizing.
+pub const MAX_IMAGE_TOKENS: u32 = 1600;
+
+/// Longest image edge in pixels before the API scales an image down
+pub(crate) const MAX_IMAGE_EDGE: u32 = 1568;
+
+/// Estimate the number of tokens of a text
+///
+/// The heuristic counts one token per six characters of a word, rounded up,
+/// or per three characters for words with non-ASCII letters, one token per
+/// punctuation character, one per line break and one per CJK character.
+/// Other whitespace is free, since it is merged into the following word.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::estimate::estimate_tokens;
+///
+/// assert_eq!(estimate_tokens(""), 0);
+/// assert_eq!(estimate_tokens("Hello, world!"), 4);
+/// assert_eq!(estimate_tokens("internationalization"), 4);
+/// ```
+pub fn estimate_tokens(text: &str) -> u32 {
+    let mut tokens: u64 = 0;
+    // Length of the current word and whether it is all ASCII
+    let (mut word, mut ascii) = (0u64, true);
+    for c in text.chars() {
+        if c.is_alphanumeric() && !is_cjk(c) {
+            word += 1;
+            ascii &= c.is_ascii();
+            continue;
+        }
+        tokens += word.div_ceil(if ascii { 6 } else { 3 });
+        (word, ascii) = (0, true);
+        if c == '\n' || !c.is_whitespace() {
+            tokens += 1;
+        }
+    }
+    tokens += word.div_ceil(if ascii { 6 } else { 3 });
+    u32::try_from(tokens).unwrap_or(u32::MAX)
+}
+
+/// Estimate the number of tokens of an image of the given size in pixels
+///
+/// Images are billed about `width * height / 750` tokens after images with
+/// an edge longer than 1568 pixels are scaled down to fit, which caps the
+/// estimate at [`MAX_IMAGE_TOKENS`].
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::estimate::estimate_image_tokens;
+///
+/// assert_eq!(estimate_image_tokens(1000, 1000), 1334);
+/// assert_eq!(estimate_image_tokens(4000, 4000), 1600);
+/// ```
+pub fn estimate_image_tokens(width: u32, height: u32) -> u32 {
+    let (mut width, mut height) = (u64::from(width), u64::from(height));
+    let edge = width.max(height);
+    if edge > u64::from(MAX_IMAGE_EDGE) {
+        width = width * u64::from(MAX_IMAGE_EDGE) / edge;
+        height = height * u64::from(MAX_IMAGE_EDGE) / edge;
+    }
+    let tokens = (width * height).div_ceil(750);
+    u32::try_from(tokens)
+        .unwrap_or(u32::MAX)
+        .min(MAX_IMAGE_TOKENS)
+}
+
+/// Estimate the number of tokens of a content block
+///
+/// The size of base64 images is read from their header; images passed by
+/// URL or file ID are assumed to cost [`MAX_IMAGE_TOKENS`]. PDFs and other
+/// documents not given as text or content blocks are not estimated and count
+/// as zero.
+pub fn estimate_block_tokens(block: &ContentBlock) -> u32 {
+    match block {
+        ContentBlock::Text { text, .. } => estimate_tokens(text),
+        ContentBlock::Thinking { thinking, .. } => estimate_tokens(thinking),
+        ContentBlock::Image { source } => match source {
+            ImageSource::Base64 { data, .. } => STANDARD
+                .decode(data)
+                .ok()
+                .and_then(|bytes| image_dimensions(&bytes))
+                .map_or(MAX_IMAGE_TOKENS, |(width, height)| {
+                    estimate_image_tokens(width, height)
+                }),
+            _ => MAX_IMAGE_TOKENS,
+        },
+        ContentBlock::Document { source, title, .. } => {
+            let title = title.as_deref().map_or(0, estimate_tokens);
+            match source {
+                DocumentSource::Text { data, .. } => title.saturating_add(estimate_tokens(data)),
+                DocumentSource::Content { content } => {
+                    title.saturating_add(saturating_sum(content.iter().map(estimate_block_tokens)))
+                }
+                _ => title,
+            }
+        }
+        ContentBlock::SearchResult {
+            source,
+            title,
+            content,
+            ..
+        } => saturating_sum([
+            estimate_tokens(source),
+            estimate_tokens(title),
+            saturating_sum(content.iter().map(estimate_block_tokens)),
+        ]),
+        ContentBlock::ToolUse { name, input, .. } => {
+            estimate_tokens(name).saturating_add(estimate_tokens(&input.to_string()))
+        }
+        ContentBlock::ToolResult { content, .. } => match content {
+            ToolResultContent::Text(text) => estimate_tokens(text),
+            ToolResultContent::Blocks(blocks) => {
+                saturating_sum(blocks.iter().map(estimate_block_tokens))
+            }
+        },
+        other => serde_json::to_string(other).map_or(0, |json| estimate_tokens(&json)),
+    }
+}
+
+/// Estimate the number of tokens of a message, including [`MESSAGE_OVERHEAD_TOKENS`]
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::estimate::estimate_message_tokens;
+/// use anthropic_ai_sdk::types::message::{Message, Role};
+///
+/// assert_eq!(estimate_message_tokens(&Message::new_text(Role::User, "Hello, world!")), 8);
+/// ```
+pub fn estimate_message_tokens(message: &Message) -> u32 {
+    MESSAGE_OVERHEAD_TOKENS.saturating_add(saturating_sum(
+        message.blocks().iter().map(estimate_block_tokens),
+    ))
+}
+
+/// Estimate the input tokens of a request
+///
+/// Counts the system prompt, the messages and, if tools are provided, the
+/// keys and values of their definitions and [`TOOL_USE_OVERHEAD_TOKENS`].
+pub fn estimate_request_tokens(params: &CreateMessageParams) -> u32 {
+    let system = params
+        .system
+        .as_ref()
+        .map_or(0, |system| estimate_tokens(&system.text()));
+    let messages = saturating_sum(params.messages.iter().map(estimate_message_tokens));
+    let tools = match &params.tools {
+        Some(tools) if !tools.is_empty() => TOOL_USE_OVERHEAD_TOKENS.saturating_add(
+            serde_json::to_value(tools).map_or(0, |json| estimate_json_tokens(&json)),
+        ),
+        _ => 0,
+    };
+    saturating_sum([system, messages, tools])
+}
+
+/// Estimate the tokens of a JSON value from its keys and values
+///
+/// The tokenizer merges most of the quotes, colons and commas of JSON into
+/// neighbouring tokens, so the structure only counts one token per object or
+/// array. Calibrated against the tool definitions of `fixtures/token_counts.json`.
+fn estimate_json_tokens(value: &serde_json::Value) -> u32 {
+    match value {
+        serde_json::Value::Object(object) => saturating_sum(
+            object
+                .iter()
+                .map(|(key, value)| {
+                    estimate_tokens(key).saturating_add(estimate_json_tokens(value))
+                })
+                .chain([1]),
+        ),
+        serde_json::Value::Array(values) => {
+            saturating_sum(values.iter().map(estimate_json_tokens).chain([1]))
+        }
+        serde_json::Value::String(text) => estimate_tokens(text),
+        _ => 1,
+    }
+}
+
+/// Sum of token estimates, saturating at `u32::MAX` like the estimates themselves
+pub(crate) fn saturating_sum(tokens: impl IntoIterator<Item = u32>) -> u32 {
+    tokens.into_iter().fold(0, u32::saturating_add)
+}
+
+/// Whether `c` is a CJK ideograph, kana or hangul syllable
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}')
+}
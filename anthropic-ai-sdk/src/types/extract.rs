@@ -0,0 +1,333 @@
+//! Structured output extraction
+//!
+//! This module contains the types of the helpers making the model answer with
+//! data rather than prose: [`MessageClient::create_json`], which parses a reply
+//! prefilled to be JSON, and `MessageClient::extract` (with the `schemars`
+//! feature), which makes the model answer with a value of a Rust type by
+//! forcing it to call a tool whose input schema is generated from the type.
+//!
+#[cfg(feature = "schemars")]
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+
+#[cfg(feature = "schemars")]
+use crate::types::message::{ContentBlock, Message, Role, StopReason, Tool, ToolChoice, ToolUnion};
+use crate::types::message::{
+    CreateMessageParams, CreateMessageResponse, MessageClient, MessageError,
+};
+#[cfg(feature = "schemars")]
+use crate::types::tool::ToolUseRef;
+
+/// Prefill starting the reply of [`MessageClient::create_json`] when the
+/// parameters have none
+pub const JSON_PREFILL: &str = "{";
+
+/// Error types for JSON replies
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::extract::JsonError;
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, RequiredMessageParams, Role,
+/// };
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// // Replies continue the `{` prefill
+/// let client = MockMessageClient::new()
+///     .text_response("\"a\": 1}\n```")
+///     .text_response("\"a\": }");
+/// let params = CreateMessageParams::new(RequiredMessageParams::new(
+///     "claude-3-7-sonnet-20250219",
+///     vec![Message::new_text(Role::User, "Reply with an object")],
+///     1024,
+/// ));
+///
+/// let (value, _) = client.create_json(&params).await.unwrap();
+/// assert_eq!(value, serde_json::json!({"a": 1}));
+///
+/// let error = client.create_json(&params).await.unwrap_err();
+/// let JsonError::Parse { text, .. } = error else { panic!() };
+/// assert_eq!(text, "{\"a\": }");
+/// # }
+/// ```
+#[derive(Debug, Error)]
+pub enum JsonError {
+    #[error(transparent)]
+    Message(#[from] MessageError),
+    #[error("Reply is not valid JSON: {error}")]
+    Parse {
+        error: serde_json::Error,
+        /// Text of the reply including the prefill, without code fences
+        text: String,
+        response: Box<CreateMessageResponse>,
+    },
+}
+
+/// Remove a Markdown code fence around `text`
+///
+/// The info string of the opening fence, such as `json`, is removed as well.
+/// Text without fences is returned trimmed.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::extract::strip_code_fences;
+///
+/// assert_eq!(strip_code_fences("```json\n{\"a\": 1}\n```"), r#"{"a": 1}"#);
+/// assert_eq!(strip_code_fences("{\"a\": 1}\n```\n"), r#"{"a": 1}"#);
+/// assert_eq!(strip_code_fences(" [1, 2] "), "[1, 2]");
+/// ```
+pub fn strip_code_fences(text: &str) -> &str {
+    let mut text = text.trim();
+    if let Some(rest) = text.strip_prefix("```") {
+        text = rest.split_once('\n').map_or("", |(_, rest)| rest);
+    }
+    if let Some(rest) = text.trim_end().strip_suffix("```") {
+        text = rest;
+    }
+    text.trim()
+}
+
+/// Send `params` with a JSON prefill and parse the reply
+pub(crate) async fn create_json<C>(
+    client: &C,
+    params: &CreateMessageParams,
+) -> Result<(Value, CreateMessageResponse), JsonError>
+where
+    C: MessageClient + Sync + ?Sized,
+{
+    let mut request = CreateMessageParams {
+        stream: None,
+        ..params.clone()
+    };
+    if request.prefill().is_none() {
+        request = request.with_prefill(JSON_PREFILL);
+    }
+    let response = client.create_message(Some(&request)).await?;
+    let text = response.text_with_prefill(&request);
+    let text = strip_code_fences(&text);
+    // Parse the first value only, the model may comment on it afterwards
+    let parsed = serde_json::Deserializer::from_str(text)
+        .into_iter::<Value>()
+        .next()
+        .unwrap_or_else(|| serde_json::from_str(text));
+    match parsed {
+        Ok(value) => Ok((value, response)),
+        Err(error) => Err(JsonError::Parse {
+            error,
+            text: text.to_string(),
+            response: Box::new(response),
+        }),
+    }
+}
+
+/// Name of the tool the model is made to call with the output
+#[cfg(feature = "schemars")]
+pub const EXTRACT_TOOL_NAME: &str = "structured_output";
+
+/// Error types for structured output extraction
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::extract::{EXTRACT_TOOL_NAME, ExtractError};
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, RequiredMessageParams, Role, ToolChoice,
+/// };
+/// use schemars::JsonSchema;
+/// use serde::Deserialize;
+/// use serde_json::json;
+///
+/// #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let params = CreateMessageParams::new(RequiredMessageParams::new(
+///     "claude-3-7-sonnet-20250219",
+///     vec![Message::new_text(Role::User, "Ada is 42 years old.")],
+///     2048,
+/// ))
+/// .with_thinking(1024);
+/// let wrong_type = json!({"name": "Ada", "age": "forty-two"});
+/// let client = MockMessageClient::new()
+///     .tool_use_response(EXTRACT_TOOL_NAME, wrong_type.clone())
+///     .tool_use_response(EXTRACT_TOOL_NAME, wrong_type)
+///     .tool_use_response(EXTRACT_TOOL_NAME, json!({"name": "Ada", "age": 42}))
+///     .text_response("Ada is 42.")
+///     .truncated_response("");
+///
+/// // Output that does not match the schema
+/// let error = client.extract::<Person>(&params).await.unwrap_err();
+/// assert!(matches!(error, ExtractError::InvalidOutput { .. }));
+/// // The output tool is forced, which the API only allows without thinking
+/// let request = client.last_request().unwrap();
+/// assert_eq!(request.tool_choice, Some(ToolChoice::tool(EXTRACT_TOOL_NAME)));
+/// assert_eq!(request.thinking, None);
+///
+/// // Invalid output is sent back once for the model to correct
+/// let extraction = client.extract_with_retry::<Person>(&params).await.unwrap();
+/// assert_eq!(extraction.value, Person { name: "Ada".to_string(), age: 42 });
+///
+/// // A reply in plain text
+/// let error = client.extract::<Person>(&params).await.unwrap_err();
+/// assert!(matches!(error, ExtractError::NoToolUse { .. }));
+///
+/// // A reply cut off by max_tokens
+/// let error = client.extract::<Person>(&params).await.unwrap_err();
+/// assert!(matches!(error, ExtractError::Truncated { .. }));
+/// assert_eq!(client.remaining(), 0);
+/// # }
+/// ```
+#[cfg(feature = "schemars")]
+#[derive(Debug, Error)]
+pub enum ExtractError {
+    #[error(transparent)]
+    Message(#[from] MessageError),
+    #[error("Model answered in text instead of calling the output tool")]
+    NoToolUse {
+        response: Box<CreateMessageResponse>,
+    },
+    #[error("Output does not match the schema: {error}")]
+    InvalidOutput {
+        error: serde_json::Error,
+        response: Box<CreateMessageResponse>,
+    },
+    #[error("Output was truncated by max_tokens")]
+    Truncated {
+        response: Box<CreateMessageResponse>,
+    },
+}
+
+#[cfg(feature = "schemars")]
+impl ExtractError {
+    /// The response that could not be extracted from, if the request succeeded
+    pub fn response(&self) -> Option<&CreateMessageResponse> {
+        match self {
+            ExtractError::Message(_) => None,
+            ExtractError::NoToolUse { response }
+            | ExtractError::InvalidOutput { response, .. }
+            | ExtractError::Truncated { response } => Some(response),
+        }
+    }
+}
+
+/// A value extracted from the model's answer
+#[cfg(feature = "schemars")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extraction<T> {
+    /// The extracted value
+    pub value: T,
+    /// The response the value was extracted from
+    pub response: CreateMessageResponse,
+}
+
+/// `params` with the tools replaced by the output tool for `T`, which the
+/// model is required to call
+///
+/// Any tools of `params` are dropped, and extended thinking is turned off
+/// because the API rejects a forced tool choice while thinking is enabled.
+/// [`MessageClient::extract`] sends requests built this way. Build batch
+/// requests with it and read their results with [`parse_extraction`].
+#[cfg(feature = "schemars")]
+pub fn extraction_request<T: schemars::JsonSchema>(
+    params: &CreateMessageParams,
+) -> CreateMessageParams {
+    let tool = Tool::from_type::<T>(EXTRACT_TOOL_NAME, "Respond with the requested output");
+    CreateMessageParams {
+        tools: Some(vec![ToolUnion::Custom(tool)]),
+        tool_choice: Some(ToolChoice::tool(EXTRACT_TOOL_NAME)),
+        thinking: None,
+        stream: None,
+        ..params.clone()
+    }
+}
+
+/// Parse the output tool call of a response to an [`extraction_request`]
+///
+/// # Errors
+///
+/// Returns [`ExtractError::Truncated`] if the output was cut off by
+/// `max_tokens`, [`ExtractError::NoToolUse`] if the model did not call the
+/// output tool and [`ExtractError::InvalidOutput`] if its input is not a `T`.
+#[cfg(feature = "schemars")]
+pub fn parse_extraction<T: DeserializeOwned>(
+    response: CreateMessageResponse,
+) -> Result<Extraction<T>, ExtractError> {
+    if response.stop_reason == Some(StopReason::MaxTokens) {
+        return Err(ExtractError::Truncated {
+            response: Box::new(response),
+        });
+    }
+    let Some(tool_use) = response
+        .content
+        .iter()
+        .filter_map(ToolUseRef::from_block)
+        .find(|tool_use| tool_use.name == EXTRACT_TOOL_NAME)
+    else {
+        return Err(ExtractError::NoToolUse {
+            response: Box::new(response),
+        });
+    };
+    match tool_use.parse_input::<T>() {
+        Ok(value) => Ok(Extraction { value, response }),
+        Err(error) => Err(ExtractError::InvalidOutput {
+            error,
+            response: Box::new(response),
+        }),
+    }
+}
+
+/// Send `params` with the output tool forced and parse its input into `T`
+///
+/// With `retry`, output failing to deserialize is sent back once as an
+/// `is_error` tool result so that the model can correct it.
+#[cfg(feature = "schemars")]
+pub(crate) async fn extract<C, T>(
+    client: &C,
+    params: &CreateMessageParams,
+    retry: bool,
+) -> Result<Extraction<T>, ExtractError>
+where
+    C: MessageClient + Sync + ?Sized,
+    T: schemars::JsonSchema + DeserializeOwned,
+{
+    let mut request = extraction_request::<T>(params);
+    let mut retries = usize::from(retry);
+    loop {
+        let response = client.create_message(Some(&request)).await?;
+        let (error, response) = match parse_extraction::<T>(response) {
+            Err(ExtractError::InvalidOutput { error, response }) if retries > 0 => {
+                (error, response)
+            }
+            result => return result,
+        };
+        retries -= 1;
+
+        let tool_use = response
+            .content
+            .iter()
+            .filter_map(ToolUseRef::from_block)
+            .find(|tool_use| tool_use.name == EXTRACT_TOOL_NAME)
+            .expect("invalid output comes from the output tool");
+        let feedback = ContentBlock::tool_error(
+            tool_use.id,
+            format!(
+                "Invalid input: {}. Call the tool again with a corrected input.",
+                error
+            ),
+        );
+        request.messages.push(Message::from(*response));
+        request
+            .messages
+            .push(Message::new_blocks(Role::User, vec![feedback]));
+    }
+}
@@ -0,0 +1,242 @@
+//! Model fallback chains
+//!
+//! This module contains [`ModelChain`], a policy of models to try in order,
+//! each with its own number of attempts, used by
+//! [`MessageClient::create_message_with_chain`].
+//!
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use crate::types::message::{
+    CreateMessageParams, CreateMessageResponse, MessageClient, MessageError,
+};
+use crate::types::pagination::backoff;
+
+type ErrorFilter = Arc<dyn Fn(&MessageError) -> bool + Send + Sync>;
+
+/// One model of a [`ModelChain`]
+#[derive(Clone)]
+pub struct ChainStage {
+    model: String,
+    max_attempts: u32,
+    retry_if: Option<ErrorFilter>,
+}
+
+impl fmt::Debug for ChainStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainStage")
+            .field("model", &self.model)
+            .field("max_attempts", &self.max_attempts)
+            .field("retry_if", &self.retry_if.is_some())
+            .finish()
+    }
+}
+
+impl ChainStage {
+    /// Try `model` once
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            max_attempts: 1,
+            retry_if: None,
+        }
+    }
+
+    /// Number of requests sent to this model before moving on; 0 is treated as 1
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Only retry or fall back after errors for which `filter` returns `true`
+    ///
+    /// By default every error except a validation error is retried.
+    pub fn retry_if(
+        mut self,
+        filter: impl Fn(&MessageError) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retry_if = Some(Arc::new(filter));
+        self
+    }
+
+    /// Model of this stage
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn should_retry(&self, error: &MessageError) -> bool {
+        match &self.retry_if {
+            Some(filter) => filter(error),
+            None => !matches!(error, MessageError::Validation(_)),
+        }
+    }
+}
+
+/// Models to try in order, each with its own number of attempts
+///
+/// Each stage sends the parameters with `model` replaced by the stage's
+/// model. A failed attempt is retried on the same model, after the delay
+/// requested by a rate limit or an exponential backoff starting at one
+/// second like the pages of a
+/// [`Paginator`](crate::types::pagination::Paginator). Once a stage has used
+/// up its attempts, the next stage is tried right away. An error rejected by
+/// the stage's [`retry_if`](ChainStage::retry_if) filter ends the chain.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::fallback::{ChainStage, ModelChain};
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
+/// };
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let chain = ModelChain::new()
+///     .stage(ChainStage::new("claude-sonnet-4-5").max_attempts(2))
+///     .stage(ChainStage::new("claude-haiku-4-5"));
+///
+/// let rate_limited = || MessageError::RateLimited {
+///     message: "Number of requests has exceeded your rate limit".to_string(),
+///     retry_after: Some(Duration::ZERO),
+/// };
+/// let client = MockMessageClient::new()
+///     .error(rate_limited())
+///     .error(rate_limited())
+///     .text_response("Hello from haiku");
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-sonnet-4-5".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Hello")],
+///     max_tokens: 1024,
+/// });
+///
+/// let response = client.create_message_with_chain(&params, &chain).await?;
+/// assert_eq!(response.response.text(), "Hello from haiku");
+/// let path: Vec<_> = response
+///     .attempts
+///     .iter()
+///     .map(|attempt| (attempt.model.as_str(), attempt.attempt, attempt.error.is_some()))
+///     .collect();
+/// assert_eq!(
+///     path,
+///     [
+///         ("claude-sonnet-4-5", 1, true),
+///         ("claude-sonnet-4-5", 2, true),
+///         ("claude-haiku-4-5", 1, false),
+///     ]
+/// );
+/// assert_eq!(client.requests()[2].model, "claude-haiku-4-5");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ModelChain {
+    stages: Vec<ChainStage>,
+}
+
+impl ModelChain {
+    /// Create an empty chain
+    ///
+    /// A chain without stages sends the parameters once, unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a stage after the existing ones
+    pub fn stage(mut self, stage: ChainStage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Stages of this chain
+    pub fn stages(&self) -> &[ChainStage] {
+        &self.stages
+    }
+}
+
+/// One request sent while following a [`ModelChain`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainAttempt {
+    /// Model the request was sent to
+    pub model: String,
+    /// Number of the attempt on this model, starting at 1
+    pub attempt: u32,
+    /// The error of a failed attempt
+    pub error: Option<String>,
+    /// Time until the response or error was received
+    pub duration: Duration,
+}
+
+/// Response of [`MessageClient::create_message_with_chain`]
+#[derive(Debug, Clone)]
+pub struct ChainResponse {
+    /// Response of the successful attempt
+    pub response: CreateMessageResponse,
+    /// Every attempt, in order, ending with the successful one
+    pub attempts: Vec<ChainAttempt>,
+}
+
+/// Error of [`MessageClient::create_message_with_chain`] once no stage succeeded
+#[derive(Debug, Error)]
+#[error("{error} (after {} attempts)", attempts.len())]
+pub struct ChainError {
+    /// Error of the last attempt
+    #[source]
+    pub error: MessageError,
+    /// Every attempt, in order, ending with the failed one
+    pub attempts: Vec<ChainAttempt>,
+}
+
+/// Send `params` through the stages of `chain`
+pub(crate) async fn create_message_with_chain<C>(
+    client: &C,
+    params: &CreateMessageParams,
+    chain: &ModelChain,
+) -> Result<ChainResponse, ChainError>
+where
+    C: MessageClient + Sync + ?Sized,
+{
+    let unchanged = [ChainStage::new(params.model.clone())];
+    let stages = if chain.stages.is_empty() {
+        &unchanged[..]
+    } else {
+        &chain.stages
+    };
+    let mut attempts = Vec::new();
+    let mut request = params.clone();
+    for (index, stage) in stages.iter().enumerate() {
+        request.model = stage.model.clone();
+        for attempt in 1..=stage.max_attempts {
+            let started = Instant::now();
+            let result = client.create_message(Some(&request)).await;
+            let mut log = ChainAttempt {
+                model: stage.model.clone(),
+                attempt,
+                error: None,
+                duration: started.elapsed(),
+            };
+            let error = match result {
+                Ok(response) => {
+                    attempts.push(log);
+                    return Ok(ChainResponse { response, attempts });
+                }
+                Err(error) => error,
+            };
+            log.error = Some(error.to_string());
+            attempts.push(log);
+            let last = index + 1 == stages.len() && attempt == stage.max_attempts;
+            if last || !stage.should_retry(&error) {
+                return Err(ChainError { error, attempts });
+            }
+            if attempt < stage.max_attempts {
+                tokio::time::sleep(backoff(&error, attempt - 1)).await;
+            }
+        }
+    }
+    unreachable!("every stage makes at least one attempt")
+}
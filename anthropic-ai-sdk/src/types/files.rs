@@ -0,0 +1,331 @@
+//! Files API
+//!
+//! This module contains the types and functions for the Anthropic Files API.
+//!
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::error::ApiErrorResponse;
+use crate::types::pagination::{CursorParams, Page, RateLimitError};
+
+/// Error types for the Files API
+#[derive(Debug, Error)]
+pub enum FileError {
+    #[error("File of {size} bytes exceeds the maximum size of {limit} bytes")]
+    FileTooLarge { size: u64, limit: u64 },
+    #[error("Request too large: {0}")]
+    RequestTooLarge(String),
+    #[error("API request failed: {0}")]
+    RequestFailed(String),
+    #[error("API error: {0}")]
+    ApiError(String),
+    #[error("File not found: {0}")]
+    NotFound(String),
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// Delay requested by the API before retrying
+        retry_after: Option<Duration>,
+    },
+    #[error("File is not downloadable: {0}")]
+    NotDownloadable(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<String> for FileError {
+    fn from(error: String) -> Self {
+        FileError::ApiError(error)
+    }
+}
+
+impl From<ApiErrorResponse> for FileError {
+    fn from(error: ApiErrorResponse) -> Self {
+        if error.status == 413 {
+            FileError::RequestTooLarge(error.message.unwrap_or(error.body))
+        } else if error.is_not_found() {
+            FileError::NotFound(error.message.unwrap_or(error.body))
+        } else if error.is_rate_limited() {
+            FileError::RateLimited {
+                message: error.message.unwrap_or(error.body),
+                retry_after: error.retry_after,
+            }
+        } else {
+            FileError::ApiError(error.body)
+        }
+    }
+}
+
+impl RateLimitError for FileError {
+    fn is_rate_limited(&self) -> bool {
+        matches!(self, FileError::RateLimited { .. })
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            FileError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+pub trait FileClient {
+    /// Upload a file from memory
+    async fn upload_file<'a>(
+        &'a self,
+        filename: &'a str,
+        content: Vec<u8>,
+        mime_type: &'a str,
+    ) -> Result<FileObject, FileError>;
+
+    /// Upload a file read from `reader` without buffering it in memory
+    async fn upload_file_stream<'a, R>(
+        &'a self,
+        filename: &'a str,
+        reader: R,
+        mime_type: &'a str,
+    ) -> Result<FileObject, FileError>
+    where
+        R: AsyncRead + Send + 'static;
+
+    /// List a page of uploaded files
+    async fn list_files<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> Result<ListFilesResponse, FileError>;
+
+    /// List uploaded files one by one, following pagination to the end
+    fn list_files_stream<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> impl futures_util::Stream<Item = Result<FileObject, FileError>> + Send + 'a;
+
+    /// Get the metadata of an uploaded file
+    async fn get_file_metadata<'a>(&'a self, file_id: &'a str) -> Result<FileObject, FileError>;
+
+    /// Delete an uploaded file
+    async fn delete_file<'a>(&'a self, file_id: &'a str) -> Result<FileDeleted, FileError>;
+
+    /// Download the content of a file into memory
+    async fn download_file<'a>(&'a self, file_id: &'a str) -> Result<FileDownload, FileError>;
+
+    /// Download the content of a file into `writer` without buffering it in memory
+    async fn download_file_to<'a, W>(
+        &'a self,
+        file_id: &'a str,
+        writer: &'a mut W,
+    ) -> Result<FileContentInfo, FileError>
+    where
+        W: AsyncWrite + Unpin + Send;
+}
+
+/// Maximum size of an uploaded file in bytes
+pub const MAX_FILE_SIZE: u64 = 500 * 1024 * 1024;
+
+/// Metadata of an uploaded file
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::files::FileObject;
+/// use time::{Duration, OffsetDateTime};
+///
+/// let file: FileObject = serde_json::from_str(r#"{
+///     "type": "file",
+///     "id": "file_011CNha8iCJcU1wXNR6q4V8w",
+///     "filename": "report.pdf",
+///     "mime_type": "application/pdf",
+///     "size_bytes": 1024,
+///     "created_at": "2025-04-14T12:00:00Z"
+/// }"#).unwrap();
+/// assert!(file.created_at < OffsetDateTime::now_utc() - Duration::days(30));
+/// assert!(!file.downloadable);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileObject {
+    /// Type of the resource (always "file")
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Unique identifier for the file
+    pub id: String,
+
+    /// Original name of the uploaded file
+    pub filename: String,
+
+    /// MIME type of the file
+    pub mime_type: String,
+
+    /// Size of the file in bytes
+    pub size_bytes: u64,
+
+    /// Creation timestamp of the file
+    #[serde(with = "rfc3339")]
+    pub created_at: OffsetDateTime,
+
+    /// Whether the file content can be downloaded
+    #[serde(default)]
+    pub downloadable: bool,
+}
+
+/// Response structure for listing files
+pub type ListFilesResponse = Page<FileObject>;
+
+/// Parameters for listing files
+///
+/// Pagination is cursor-based, so files uploaded while paging do not shift
+/// the pages that follow.
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ListFilesParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+impl ListFilesParams {
+    /// Create a new ListFilesParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.min(1000));
+        self
+    }
+}
+
+impl CursorParams for ListFilesParams {
+    fn before_cursor(&self) -> Option<&str> {
+        self.before_id.as_deref()
+    }
+
+    fn set_before_cursor(&mut self, id: String) {
+        self.before_id = Some(id);
+    }
+
+    fn set_after_cursor(&mut self, id: String) {
+        self.after_id = Some(id);
+    }
+}
+
+/// Response for deleting a file
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileDeleted {
+    /// ID of the deleted file
+    pub id: String,
+    /// Type of the response (always "file_deleted")
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// Content type and name of downloaded file content, taken from the response headers
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FileContentInfo {
+    /// Value of the `content-type` header
+    pub content_type: Option<String>,
+    /// File name from the `content-disposition` header
+    pub filename: Option<String>,
+}
+
+impl FileContentInfo {
+    /// Read the content type and file name from response headers
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::files::FileContentInfo;
+    /// use reqwest::header::{CONTENT_DISPOSITION, CONTENT_TYPE, HeaderMap, HeaderValue};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(CONTENT_TYPE, HeaderValue::from_static("image/png"));
+    /// headers.insert(
+    ///     CONTENT_DISPOSITION,
+    ///     HeaderValue::from_static("attachment; filename=\"chart.png\"; filename*=UTF-8''chart%20v2.png"),
+    /// );
+    /// let info = FileContentInfo::from_headers(&headers);
+    /// assert_eq!(info.content_type.as_deref(), Some("image/png"));
+    /// assert_eq!(info.filename.as_deref(), Some("chart v2.png"));
+    /// ```
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        Self {
+            content_type: header(reqwest::header::CONTENT_TYPE).map(str::to_string),
+            filename: header(reqwest::header::CONTENT_DISPOSITION)
+                .and_then(content_disposition_filename),
+        }
+    }
+}
+
+/// File content downloaded into memory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDownload {
+    /// Content type and name of the file
+    pub info: FileContentInfo,
+    /// The raw file content
+    pub content: Vec<u8>,
+}
+
+/// Extract the file name from a `content-disposition` header value
+///
+/// The RFC 5987 `filename*` parameter is preferred over `filename`.
+fn content_disposition_filename(value: &str) -> Option<String> {
+    let mut plain = None;
+    for param in value.split(';').map(str::trim) {
+        let Some((name, value)) = param.split_once('=') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                // charset'language'percent-encoded-value
+                let encoded = value.trim().splitn(3, '\'').nth(2)?;
+                return percent_decode(encoded);
+            }
+            "filename" => plain = Some(value.trim().trim_matches('"').to_string()),
+            _ => {}
+        }
+    }
+    plain
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
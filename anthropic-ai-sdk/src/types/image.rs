@@ -0,0 +1,493 @@
+//! Image helpers
+//!
+//! This module contains helpers for building image content blocks from raw bytes.
+//!
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[cfg(feature = "image")]
+use crate::types::estimate::{MAX_IMAGE_EDGE, estimate_image_tokens};
+use crate::types::message::{ContentBlock, ImageSource};
+
+/// Maximum size of a single base64-encoded image accepted by the API (5MB)
+pub const MAX_IMAGE_SIZE: usize = 5 * 1024 * 1024;
+
+/// Error types for building image content blocks
+#[derive(Debug, Error)]
+pub enum ImageError {
+    #[error("Unsupported image format, expected PNG, JPEG, GIF or WebP")]
+    UnsupportedFormat,
+    #[error("Encoded image size of {size} bytes exceeds the limit of {max} bytes")]
+    TooLarge { size: usize, max: usize },
+    #[error("Image file not found: {0}")]
+    NotFound(std::path::PathBuf),
+    #[error("Failed to read image file: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "image")]
+    #[error("Failed to decode image: {0}")]
+    Decode(image::ImageError),
+    #[cfg(feature = "image")]
+    #[error("Failed to encode image: {0}")]
+    Encode(image::ImageError),
+}
+
+/// Media type of an image
+///
+/// Values not accepted by the API are preserved in [`ImageMediaType::Other`]
+/// and rejected by [`CreateMessageParams::validate`](crate::types::message::CreateMessageParams::validate).
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::image::ImageMediaType;
+///
+/// assert_eq!(ImageMediaType::from("image/jpeg"), ImageMediaType::Jpeg);
+/// assert!(!ImageMediaType::from("image/jpg").is_supported());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ImageMediaType {
+    Jpeg,
+    Png,
+    Gif,
+    Webp,
+    /// A media type not accepted by the API
+    Other(String),
+}
+
+impl ImageMediaType {
+    /// The wire representation of the media type
+    pub fn as_str(&self) -> &str {
+        match self {
+            ImageMediaType::Jpeg => "image/jpeg",
+            ImageMediaType::Png => "image/png",
+            ImageMediaType::Gif => "image/gif",
+            ImageMediaType::Webp => "image/webp",
+            ImageMediaType::Other(other) => other,
+        }
+    }
+
+    /// Whether the API accepts this media type
+    pub fn is_supported(&self) -> bool {
+        !matches!(self, ImageMediaType::Other(_))
+    }
+}
+
+impl From<&str> for ImageMediaType {
+    fn from(value: &str) -> Self {
+        match value {
+            "image/jpeg" => ImageMediaType::Jpeg,
+            "image/png" => ImageMediaType::Png,
+            "image/gif" => ImageMediaType::Gif,
+            "image/webp" => ImageMediaType::Webp,
+            other => ImageMediaType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for ImageMediaType {
+    fn from(value: String) -> Self {
+        ImageMediaType::from(value.as_str())
+    }
+}
+
+impl PartialEq<str> for ImageMediaType {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ImageMediaType {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl std::fmt::Display for ImageMediaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ImageMediaType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ImageMediaType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(ImageMediaType::from(value.as_str()))
+    }
+}
+
+/// Detect the media type of an image from its magic bytes
+///
+/// Returns `None` if the data is not a PNG, JPEG, GIF or WebP image.
+pub fn detect_media_type(bytes: &[u8]) -> Option<ImageMediaType> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(ImageMediaType::Png)
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(ImageMediaType::Jpeg)
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some(ImageMediaType::Gif)
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some(ImageMediaType::Webp)
+    } else {
+        None
+    }
+}
+
+/// Read the width and height of an image in pixels from its header
+///
+/// Returns `None` if the data is not a PNG, JPEG, GIF or WebP image or its
+/// header is truncated.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::image::image_dimensions;
+///
+/// let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x02\x80\0\0\x01\xe0";
+/// assert_eq!(image_dimensions(png), Some((640, 480)));
+/// assert_eq!(image_dimensions(b"GIF89a\x10\0\x20\0"), Some((16, 32)));
+/// ```
+pub fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32);
+    let le16 = |at: usize| Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32);
+    let le24 = |at: usize| {
+        let b = bytes.get(at..at + 3)?;
+        Some(u32::from_le_bytes([b[0], b[1], b[2], 0]))
+    };
+    match detect_media_type(bytes)? {
+        ImageMediaType::Png => {
+            let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+            let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+            Some((width, height))
+        }
+        ImageMediaType::Gif => Some((le16(6)?, le16(8)?)),
+        ImageMediaType::Webp => match bytes.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3FFF, le16(28)? & 0x3FFF)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        },
+        ImageMediaType::Jpeg => {
+            // Walk the segments up to the start of frame marker
+            let mut at = 2;
+            loop {
+                if *bytes.get(at)? != 0xFF {
+                    return None;
+                }
+                let marker = *bytes.get(at + 1)?;
+                match marker {
+                    0xFF => at += 1,
+                    0xD8 | 0x01 | 0xD0..=0xD7 => at += 2,
+                    0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                        return Some((be16(at + 7)?, be16(at + 5)?));
+                    }
+                    _ => at += 2 + be16(at + 2)? as usize,
+                }
+            }
+        }
+        ImageMediaType::Other(_) => None,
+    }
+}
+
+#[cfg(feature = "fs")]
+fn media_type_from_extension(path: &std::path::Path) -> Option<ImageMediaType> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some(ImageMediaType::Png),
+        "jpg" | "jpeg" => Some(ImageMediaType::Jpeg),
+        "gif" => Some(ImageMediaType::Gif),
+        "webp" => Some(ImageMediaType::Webp),
+        _ => None,
+    }
+}
+
+impl ContentBlock {
+    /// Create a base64 image block from raw image bytes
+    ///
+    /// The media type is detected from the magic bytes of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ImageError` if:
+    /// - The image is not a PNG, JPEG, GIF or WebP image
+    /// - The encoded image exceeds [`MAX_IMAGE_SIZE`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{ContentBlock, ImageSource};
+    ///
+    /// let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    /// let block = ContentBlock::image_from_bytes(png).unwrap();
+    /// assert!(matches!(
+    ///     block,
+    ///     ContentBlock::Image { source: ImageSource::Base64 { ref media_type, .. } }
+    ///         if media_type == "image/png"
+    /// ));
+    /// ```
+    pub fn image_from_bytes(bytes: &[u8]) -> Result<ContentBlock, ImageError> {
+        let media_type = detect_media_type(bytes).ok_or(ImageError::UnsupportedFormat)?;
+        Self::image_from_bytes_with_media_type(bytes, media_type)
+    }
+
+    /// Read an image file and create a base64 image block from it
+    ///
+    /// The media type is detected from the magic bytes of the file, falling back
+    /// to the file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ImageError` if:
+    /// - The file does not exist or cannot be read
+    /// - The image is not a PNG, JPEG, GIF or WebP image
+    /// - The encoded image exceeds [`MAX_IMAGE_SIZE`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::image::ImageError;
+    /// use anthropic_ai_sdk::types::message::{ContentBlock, ImageSource};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let dir = std::env::temp_dir().join(format!("image-from-path-{}", std::process::id()));
+    /// std::fs::create_dir_all(&dir)?;
+    /// let media_type = |block: ContentBlock| match block {
+    ///     ContentBlock::Image { source: ImageSource::Base64 { media_type, .. } } => media_type,
+    ///     other => panic!("expected a base64 image, got {:?}", other),
+    /// };
+    ///
+    /// // The magic bytes win over the extension
+    /// let path = dir.join("chart.jpg");
+    /// std::fs::write(&path, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")?;
+    /// assert_eq!(media_type(ContentBlock::image_from_path(&path).await?), "image/png");
+    ///
+    /// // Unknown bytes fall back to the extension
+    /// let path = dir.join("photo.webp");
+    /// std::fs::write(&path, b"not quite an image")?;
+    /// assert_eq!(media_type(ContentBlock::image_from_path(&path).await?), "image/webp");
+    ///
+    /// let path = dir.join("notes.txt");
+    /// std::fs::write(&path, b"not an image")?;
+    /// assert!(matches!(
+    ///     ContentBlock::image_from_path(&path).await,
+    ///     Err(ImageError::UnsupportedFormat)
+    /// ));
+    ///
+    /// let missing = dir.join("missing.png");
+    /// match ContentBlock::image_from_path(&missing).await {
+    ///     Err(ImageError::NotFound(path)) => assert_eq!(path, missing),
+    ///     other => panic!("expected a missing file, got {:?}", other),
+    /// }
+    /// # std::fs::remove_dir_all(&dir)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "fs")]
+    pub async fn image_from_path(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<ContentBlock, ImageError> {
+        let path = path.as_ref();
+        let bytes = tokio::fs::read(path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ImageError::NotFound(path.to_path_buf()),
+            _ => ImageError::Io(e),
+        })?;
+        let media_type = detect_media_type(&bytes)
+            .or_else(|| media_type_from_extension(path))
+            .ok_or(ImageError::UnsupportedFormat)?;
+        Self::image_from_bytes_with_media_type(&bytes, media_type)
+    }
+
+    fn image_from_bytes_with_media_type(
+        bytes: &[u8],
+        media_type: ImageMediaType,
+    ) -> Result<ContentBlock, ImageError> {
+        let size = bytes.len().div_ceil(3) * 4;
+        if size > MAX_IMAGE_SIZE {
+            return Err(ImageError::TooLarge {
+                size,
+                max: MAX_IMAGE_SIZE,
+            });
+        }
+        Ok(Self::image_from_base64(media_type, STANDARD.encode(bytes)))
+    }
+
+    /// Create a base64 image block from already encoded data
+    pub fn image_from_base64(
+        media_type: impl Into<ImageMediaType>,
+        data: impl Into<String>,
+    ) -> Self {
+        Self::image_source(ImageSource::base64(media_type, data))
+    }
+}
+
+/// JPEG quality used by [`ImageBlockBuilder`] by default
+#[cfg(feature = "image")]
+pub const DEFAULT_JPEG_QUALITY: u8 = 85;
+
+/// Builder of image blocks that fit the limits of the API
+///
+/// Images with an edge longer than [`max_dimension`](Self::max_dimension),
+/// 1568 pixels by default, are decoded, scaled down preserving the aspect
+/// ratio and re-encoded: as PNG if they have transparency, as JPEG with
+/// [`jpeg_quality`](Self::jpeg_quality) otherwise. Images over
+/// [`MAX_IMAGE_SIZE`] are re-encoded the same way without scaling. Images
+/// already within both limits are passed through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::image::ImageBlockBuilder;
+/// use anthropic_ai_sdk::types::message::{ContentBlock, ImageSource};
+/// use image::{DynamicImage, ImageFormat, RgbImage};
+///
+/// let encode = |width, height| {
+///     let mut bytes = std::io::Cursor::new(Vec::new());
+///     DynamicImage::ImageRgb8(RgbImage::new(width, height))
+///         .write_to(&mut bytes, ImageFormat::Png)
+///         .unwrap();
+///     bytes.into_inner()
+/// };
+///
+/// let image = ImageBlockBuilder::from_bytes(encode(1600, 1200))
+///     .max_dimension(400)
+///     .jpeg_quality(85)
+///     .build()
+///     .unwrap();
+/// assert!(image.resized);
+/// assert_eq!((image.width, image.height), (400, 300));
+/// assert_eq!(image.estimated_tokens, 160);
+/// assert!(matches!(
+///     image.block,
+///     ContentBlock::Image { source: ImageSource::Base64 { ref media_type, .. } }
+///         if media_type == "image/jpeg"
+/// ));
+///
+/// // Small enough images are sent as they are
+/// let icon = encode(64, 64);
+/// let image = ImageBlockBuilder::from_bytes(icon.clone()).build().unwrap();
+/// assert!(!image.resized);
+/// assert_eq!(image.block, ContentBlock::image_from_bytes(&icon).unwrap());
+///
+/// let error = ImageBlockBuilder::from_bytes(b"not an image".to_vec()).build();
+/// assert!(error.is_err());
+/// ```
+#[cfg(feature = "image")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageBlockBuilder {
+    bytes: Vec<u8>,
+    max_dimension: u32,
+    jpeg_quality: u8,
+}
+
+/// Image block built by [`ImageBlockBuilder`]
+#[cfg(feature = "image")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedImage {
+    /// The base64 image block
+    pub block: ContentBlock,
+    /// Width of the image sent, in pixels
+    pub width: u32,
+    /// Height of the image sent, in pixels
+    pub height: u32,
+    /// Whether the image was scaled down or re-encoded
+    pub resized: bool,
+    /// Estimated tokens of the image, see [`estimate_image_tokens`]
+    pub estimated_tokens: u32,
+}
+
+#[cfg(feature = "image")]
+impl ImageBlockBuilder {
+    /// Builder for the image encoded in `bytes`
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            bytes: bytes.into(),
+            max_dimension: MAX_IMAGE_EDGE,
+            jpeg_quality: DEFAULT_JPEG_QUALITY,
+        }
+    }
+
+    /// Longest edge of the image sent, in pixels
+    pub fn max_dimension(mut self, max_dimension: u32) -> Self {
+        self.max_dimension = max_dimension.max(1);
+        self
+    }
+
+    /// Quality from 1 to 100 of re-encoded JPEG images
+    pub fn jpeg_quality(mut self, jpeg_quality: u8) -> Self {
+        self.jpeg_quality = jpeg_quality.clamp(1, 100);
+        self
+    }
+
+    /// Decode, scale and re-encode the image as needed and build the block
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ImageError` if:
+    /// - The image is not a PNG, JPEG, GIF or WebP image
+    /// - The image cannot be decoded or encoded
+    /// - The encoded image still exceeds [`MAX_IMAGE_SIZE`]
+    pub fn build(self) -> Result<PreparedImage, ImageError> {
+        use image::{DynamicImage, ImageFormat, imageops::FilterType};
+
+        let media_type = detect_media_type(&self.bytes).ok_or(ImageError::UnsupportedFormat)?;
+        let within_size = self.bytes.len().div_ceil(3) * 4 <= MAX_IMAGE_SIZE;
+        if let Some((width, height)) = image_dimensions(&self.bytes) {
+            if within_size && width.max(height) <= self.max_dimension {
+                return Ok(PreparedImage {
+                    block: ContentBlock::image_from_bytes_with_media_type(&self.bytes, media_type)?,
+                    width,
+                    height,
+                    resized: false,
+                    estimated_tokens: estimate_image_tokens(width, height),
+                });
+            }
+        }
+
+        let mut image = image::load_from_memory(&self.bytes).map_err(ImageError::Decode)?;
+        if image.width().max(image.height()) > self.max_dimension {
+            image = image.resize(self.max_dimension, self.max_dimension, FilterType::Lanczos3);
+        } else if within_size {
+            // The header could not be read, but the image is within the limits
+            return Ok(PreparedImage {
+                block: ContentBlock::image_from_bytes_with_media_type(&self.bytes, media_type)?,
+                width: image.width(),
+                height: image.height(),
+                resized: false,
+                estimated_tokens: estimate_image_tokens(image.width(), image.height()),
+            });
+        }
+        let (width, height) = (image.width(), image.height());
+        let mut encoded = std::io::Cursor::new(Vec::new());
+        let media_type = if image.color().has_alpha() {
+            image
+                .write_to(&mut encoded, ImageFormat::Png)
+                .map_err(ImageError::Encode)?;
+            ImageMediaType::Png
+        } else {
+            let encoder =
+                image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded, self.jpeg_quality);
+            DynamicImage::ImageRgb8(image.to_rgb8())
+                .write_with_encoder(encoder)
+                .map_err(ImageError::Encode)?;
+            ImageMediaType::Jpeg
+        };
+        Ok(PreparedImage {
+            block: ContentBlock::image_from_bytes_with_media_type(encoded.get_ref(), media_type)?,
+            width,
+            height,
+            resized: true,
+            estimated_tokens: estimate_image_tokens(width, height),
+        })
+    }
+}
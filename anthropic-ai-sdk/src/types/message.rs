@@ -1,14 +1,99 @@
 use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::time::Duration;
 use thiserror::Error;
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+use crate::error::{ApiErrorBody, ApiErrorResponse};
+use crate::types::auto_cache::AutoCacheStrategy;
+use crate::types::beta::{Beta, merge_betas};
+use crate::types::concurrent::MapConcurrent;
+use crate::types::continuation::{ContinuationError, ContinuedResponse};
+use crate::types::extract::JsonError;
+#[cfg(feature = "schemars")]
+use crate::types::extract::{ExtractError, Extraction};
+use crate::types::fallback::{ChainError, ChainResponse, ModelChain};
+use crate::types::image::{ImageMediaType, MAX_IMAGE_SIZE};
+use crate::types::model::{Model, ModelSpec};
+use crate::types::pagination::RateLimitError;
+use crate::types::tool::{ToolInputError, ToolResultOptions, ToolUseRef};
 
 /// Error types for the Messages API
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum MessageError {
     #[error("API request failed: {0}")]
     RequestFailed(String),
     #[error("API error: {0}")]
     ApiError(String),
+    #[error("Invalid request parameters: {0}")]
+    Validation(#[from] ValidationError),
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// Delay requested by the API before retrying
+        retry_after: Option<Duration>,
+    },
+    /// The budget of a [`UsageTracker`](crate::types::budget::UsageTracker) is used up
+    #[error("Usage budget exceeded: {0}")]
+    BudgetExceeded(String),
+}
+
+/// Client-side validation errors for message parameters
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ValidationError {
+    #[error("Duplicate MCP server name: {0}")]
+    DuplicateMcpServerName(String),
+    #[error(transparent)]
+    Roles(#[from] RoleError),
+    #[error(transparent)]
+    Metadata(#[from] MetadataError),
+    #[error("Tool result in message {index} answers unknown tool use {tool_use_id}")]
+    UnmatchedToolResult { index: usize, tool_use_id: String },
+    #[error(
+        "Unsupported image media type {0}, expected image/jpeg, image/png, image/gif or image/webp"
+    )]
+    UnsupportedImageMediaType(String),
+    #[error("Assistant prefill must not end with trailing whitespace")]
+    PrefillTrailingWhitespace,
+    #[error("max_tokens {max_tokens} exceeds the limit of {limit} for model {model}")]
+    MaxTokensExceeded {
+        model: String,
+        max_tokens: u32,
+        limit: u32,
+    },
+    #[error("Request contains {count} images, more than the limit of {limit}")]
+    TooManyImages { count: usize, limit: usize },
+    #[error("Image in message {index}, block {block} is {size} bytes, over the limit of {limit}")]
+    ImageTooLarge {
+        index: usize,
+        block: usize,
+        size: usize,
+        limit: usize,
+    },
+    #[error("PDF in message {index}, block {block} has {pages} pages, over the limit of {limit}")]
+    TooManyPdfPages {
+        index: usize,
+        block: usize,
+        pages: usize,
+        limit: usize,
+    },
+    #[error("Base64 data in message {index}, block {block} contains whitespace")]
+    Base64Whitespace { index: usize, block: usize },
+    #[error("Request body is {size} bytes, over the limit of {limit}")]
+    RequestTooLarge { size: usize, limit: usize },
+}
+
+/// Errors in the role sequence of a conversation
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RoleError {
+    #[error("Message {index} has the same role ({role:?}) as the message before it")]
+    ConsecutiveRole { index: usize, role: Role },
 }
 
 impl From<String> for MessageError {
@@ -17,6 +102,77 @@ impl From<String> for MessageError {
     }
 }
 
+impl From<ApiErrorResponse> for MessageError {
+    fn from(error: ApiErrorResponse) -> Self {
+        if error.is_rate_limited() {
+            MessageError::RateLimited {
+                message: error.message.unwrap_or(error.body),
+                retry_after: error.retry_after,
+            }
+        } else {
+            MessageError::ApiError(error.body)
+        }
+    }
+}
+
+impl MessageError {
+    /// Whether the API rejected the request for exceeding the model's context window
+    ///
+    /// Matches `invalid_request_error`s whose message says that the prompt is
+    /// too long or mentions the context limit. Since the wording is not part
+    /// of the API, compare the input with the
+    /// [context window](CreateMessageParams::model_spec) beforehand as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::MessageError;
+    ///
+    /// let error = |type_: &str, message: &str| {
+    ///     MessageError::ApiError(
+    ///         serde_json::json!({"type": "error", "error": {"type": type_, "message": message}})
+    ///             .to_string(),
+    ///     )
+    /// };
+    /// let too_long = "prompt is too long: 210000 tokens > 200000 maximum";
+    /// assert!(error("invalid_request_error", too_long).is_context_length_exceeded());
+    /// assert!(!error("invalid_request_error", "max_tokens: Field required").is_context_length_exceeded());
+    /// assert!(!error("overloaded_error", "Overloaded").is_context_length_exceeded());
+    /// ```
+    pub fn is_context_length_exceeded(&self) -> bool {
+        let MessageError::ApiError(body) = self else {
+            return false;
+        };
+        let Ok(ApiErrorBody { error }) = serde_json::from_str(body) else {
+            return false;
+        };
+        let message = error.message.to_lowercase();
+        error.type_ == "invalid_request_error"
+            && [
+                "too long",
+                "too many tokens",
+                "context limit",
+                "context window",
+                "context length",
+            ]
+            .iter()
+            .any(|phrase| message.contains(phrase))
+    }
+}
+
+impl RateLimitError for MessageError {
+    fn is_rate_limited(&self) -> bool {
+        matches!(self, MessageError::RateLimited { .. })
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            MessageError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
 #[async_trait]
 pub trait MessageClient {
     async fn create_message<'a>(
@@ -36,17 +192,212 @@ pub trait MessageClient {
         impl futures_util::Stream<Item = Result<StreamEvent, MessageError>> + 'a,
         MessageError,
     >;
+
+    /// Make the model answer with JSON and parse it
+    ///
+    /// Unless `params` already end with an assistant prefill, the reply is
+    /// prefilled with `{`. The prefill is put back in front of the reply and
+    /// Markdown code fences are removed before parsing. Configure stop
+    /// sequences in `params` to cut the reply off after the JSON.
+    async fn create_json<'a>(
+        &'a self,
+        params: &'a CreateMessageParams,
+    ) -> Result<(serde_json::Value, CreateMessageResponse), JsonError>
+    where
+        Self: Sync,
+    {
+        crate::types::extract::create_json(self, params).await
+    }
+
+    /// Make the model answer with a value of type `T`
+    ///
+    /// The tools of `params` are replaced by a single tool whose input schema
+    /// is generated from `T`, and the model is required to call it. Extended
+    /// thinking is turned off, as the API does not allow it with a forced tool.
+    #[cfg(feature = "schemars")]
+    async fn extract<'a, T>(
+        &'a self,
+        params: &'a CreateMessageParams,
+    ) -> Result<Extraction<T>, ExtractError>
+    where
+        Self: Sync,
+        T: schemars::JsonSchema + serde::de::DeserializeOwned,
+    {
+        crate::types::extract::extract(self, params, false).await
+    }
+
+    /// Like [`extract`](Self::extract), but let the model correct output
+    /// that does not match the schema once
+    #[cfg(feature = "schemars")]
+    async fn extract_with_retry<'a, T>(
+        &'a self,
+        params: &'a CreateMessageParams,
+    ) -> Result<Extraction<T>, ExtractError>
+    where
+        Self: Sync,
+        T: schemars::JsonSchema + serde::de::DeserializeOwned,
+    {
+        crate::types::extract::extract(self, params, true).await
+    }
+
+    /// Send a request for each of `params`, with at most `concurrency` in flight
+    ///
+    /// The returned [`MapConcurrent`] streams each result with the index of
+    /// its parameters as the requests complete. See its documentation for
+    /// retries, progress reporting and stopping after repeated failures.
+    fn map_concurrent<'a, I>(&'a self, params: I, concurrency: usize) -> MapConcurrent<'a, Self>
+    where
+        Self: Sync,
+        I: IntoIterator<Item = CreateMessageParams>,
+        I::IntoIter: Send + 'a,
+    {
+        MapConcurrent::new(self, params, concurrency)
+    }
+
+    /// Send `params` to the models of `chain` in turn until one succeeds
+    ///
+    /// The response or final error comes with a log of every attempt. See
+    /// [`ModelChain`] for how attempts are retried and stages advanced.
+    async fn create_message_with_chain<'a>(
+        &'a self,
+        params: &'a CreateMessageParams,
+        chain: &'a ModelChain,
+    ) -> Result<ChainResponse, ChainError>
+    where
+        Self: Sync,
+    {
+        crate::types::fallback::create_message_with_chain(self, params, chain).await
+    }
+
+    /// Count the input tokens of the create-message request `params`
+    ///
+    /// The count request is built with
+    /// [`CountMessageTokensParams::from`], so it counts exactly what would be
+    /// sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::MockMessageClient;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CountMessageTokensParams, CreateMessageParams, Message, MessageClient,
+    ///     RequiredMessageParams, Role,
+    /// };
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MockMessageClient::new().token_count(42);
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-5".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hello")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_system("You are terse");
+    ///
+    /// assert_eq!(client.count_for(&params).await?, 42);
+    /// assert_eq!(client.count_requests(), [CountMessageTokensParams::from(&params)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn count_for<'a>(&'a self, params: &'a CreateMessageParams) -> Result<u32, MessageError>
+    where
+        Self: Sync,
+    {
+        let params = CountMessageTokensParams::from(params);
+        Ok(self.count_tokens(Some(&params)).await?.input_tokens)
+    }
+
+    /// Send `params` and continue the reply while it is cut off by `max_tokens`
+    ///
+    /// At most `max_continues` follow-up requests are sent. See
+    /// [`ContinuedResponse`] for how the replies are stitched together.
+    async fn create_with_continuation<'a>(
+        &'a self,
+        params: &'a CreateMessageParams,
+        max_continues: u32,
+    ) -> Result<ContinuedResponse, ContinuationError>
+    where
+        Self: Sync,
+    {
+        crate::types::continuation::create_with_continuation(self, params, max_continues).await
+    }
 }
 
-#[derive(Debug)]
+/// `max_tokens` used when it is filled in for a model with unknown limits
+pub const DEFAULT_MAX_TOKENS: u32 = 4_096;
+
+/// How `max_tokens` is filled in automatically
+///
+/// See [`CreateMessageParams::with_max_tokens_auto`] and
+/// [`AnthropicClientBuilder::with_default_max_tokens`](crate::client::AnthropicClientBuilder::with_default_max_tokens).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaxTokens {
+    /// The largest value the model accepts, or [`DEFAULT_MAX_TOKENS`] for unknown models
+    ModelMaximum,
+    /// A fixed value, clamped to what the model accepts
+    Fixed(u32),
+}
+
+/// Required parameters of [`CreateMessageParams`]
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{Message, RequiredMessageParams, Role};
+/// use anthropic_ai_sdk::types::model::Model;
+///
+/// let messages = vec![Message::new_text(Role::User, "Hello, Claude")];
+/// let typed = RequiredMessageParams::new(Model::Claude37Sonnet20250219, messages.clone(), 1024);
+/// let named = RequiredMessageParams::new("claude-3-7-sonnet-20250219", messages, 1024);
+/// assert_eq!(typed, named);
+/// assert_eq!(typed.model, "claude-3-7-sonnet-20250219");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 pub struct RequiredMessageParams {
     pub model: String,
     pub messages: Vec<Message>,
     pub max_tokens: u32,
 }
 
+impl RequiredMessageParams {
+    /// Create required parameters for a [`Model`] or any model identifier string
+    pub fn new(model: impl Into<Model>, messages: Vec<Message>, max_tokens: u32) -> Self {
+        Self {
+            model: model.into().into(),
+            messages,
+            max_tokens,
+        }
+    }
+}
+
 /// Parameters for creating a message
-#[derive(Debug, Serialize, Default)]
+///
+/// Parameters serialize to the exact request body sent to the API and can be
+/// deserialized from it again, e.g. to store them as job payloads.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, RequiredMessageParams, Role,
+/// };
+/// use anthropic_ai_sdk::types::model::Model;
+///
+/// let params = CreateMessageParams::new(RequiredMessageParams::new(
+///     Model::Claude35Sonnet20240620,
+///     vec![Message::new_text(Role::User, "Hello, Claude")],
+///     1024,
+/// ))
+/// .with_system("You are a helpful assistant");
+///
+/// let json = serde_json::to_string(&params).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"max_tokens":1024,"messages":[{"role":"user","content":"Hello, Claude"}],"model":"claude-3-5-sonnet-20240620","system":"You are a helpful assistant"}"#
+/// );
+/// assert_eq!(serde_json::from_str::<CreateMessageParams>(&json).unwrap(), params);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 pub struct CreateMessageParams {
     /// Maximum number of tokens to generate
     pub max_tokens: u32,
@@ -56,7 +407,7 @@ pub struct CreateMessageParams {
     pub model: String,
     /// System prompt
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub system: Option<String>,
+    pub system: Option<SystemPrompt>,
     /// Temperature for response generation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
@@ -74,13 +425,235 @@ pub struct CreateMessageParams {
     pub top_p: Option<f32>,
     /// Tools that the model may use
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tools: Option<Vec<Tool>>,
+    pub tools: Option<Vec<ToolUnion>>,
     /// How the model should use tools
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_choice: Option<ToolChoice>,
     /// Request metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Metadata>,
+    /// Container identifier for reuse across requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// Remote MCP servers the model may use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<Vec<McpServer>>,
+    /// Extended thinking configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<Thinking>,
+    /// Automatic clearing of old context
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_management: Option<ContextManagement>,
+    /// Beta feature flags to send in addition to the detected ones
+    #[serde(skip)]
+    pub betas: Vec<Beta>,
+    /// Whether to send only the explicit `betas`, without detecting required ones
+    #[serde(skip)]
+    pub suppress_auto_betas: bool,
+    /// Whether [`validate`](Self::validate) checks that roles alternate
+    #[serde(skip)]
+    pub role_validation: bool,
+    /// Whether [`validate`](Self::validate) checks that tool results answer
+    /// tool uses, see [`validate_tool_results`]
+    #[serde(skip)]
+    pub tool_result_validation: bool,
+    /// Limits of the model, overriding the SDK's built-in table
+    #[serde(skip)]
+    pub model_spec: Option<ModelSpec>,
+    /// Whether the client's response cache is skipped for this request
+    #[serde(skip)]
+    pub bypass_response_cache: bool,
+    /// Strategy placing cache breakpoints when the client sends the request
+    #[serde(skip)]
+    pub auto_cache: Option<AutoCacheStrategy>,
+    /// Additional top-level fields, e.g. for API features not yet supported
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Extended thinking configuration
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Thinking {
+    /// Let the model think before answering, using up to `budget_tokens` tokens
+    Enabled {
+        /// Tokens the model may use for thinking, must be less than `max_tokens`
+        budget_tokens: u32,
+    },
+    /// Disable extended thinking
+    Disabled,
+}
+
+/// Context management configuration
+///
+/// Lets the API clear old context, such as tool results, once a conversation grows.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{ClearToolUsesEdit, ContextManagement};
+///
+/// let context_management = ContextManagement::new([ClearToolUsesEdit::new()
+///     .with_trigger_input_tokens(30000)
+///     .with_keep_tool_uses(3)
+///     .with_exclude_tools(["web_search"])]);
+///
+/// assert_eq!(
+///     serde_json::to_value(&context_management).unwrap(),
+///     serde_json::json!({
+///         "edits": [{
+///             "type": "clear_tool_uses_20250919",
+///             "trigger": {"type": "input_tokens", "value": 30000},
+///             "keep": {"type": "tool_uses", "value": 3},
+///             "exclude_tools": ["web_search"]
+///         }]
+///     })
+/// );
+/// ```
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ContextManagement {
+    /// Edits applied to the context, in order
+    pub edits: Vec<ContextEdit>,
+}
+
+impl ContextManagement {
+    /// Beta flag required to use context management
+    pub const BETA: Beta = Beta::ContextManagement;
+
+    /// Create a context management configuration with the given edits
+    pub fn new(edits: impl IntoIterator<Item = impl Into<ContextEdit>>) -> Self {
+        Self {
+            edits: edits.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Strategy for editing the context
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type")]
+#[non_exhaustive]
+pub enum ContextEdit {
+    /// Clear the oldest tool results
+    #[serde(rename = "clear_tool_uses_20250919")]
+    ClearToolUses(ClearToolUsesEdit),
+}
+
+impl From<ClearToolUsesEdit> for ContextEdit {
+    fn from(edit: ClearToolUsesEdit) -> Self {
+        Self::ClearToolUses(edit)
+    }
+}
+
+/// Clear the oldest tool results once the context grows too large
+///
+/// Unset knobs use the API defaults.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ClearToolUsesEdit {
+    /// When clearing starts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<ContextTrigger>,
+    /// Most recent tool uses to keep
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep: Option<ContextKeep>,
+    /// Minimum amount to clear each time clearing happens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clear_at_least: Option<ContextAmount>,
+    /// Tools whose results are never cleared
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_tools: Option<Vec<String>>,
+    /// Whether tool inputs are cleared along with the results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clear_tool_inputs: Option<bool>,
+}
+
+impl ClearToolUsesEdit {
+    /// Create an edit with the API defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start clearing once the input exceeds the given number of tokens
+    pub fn with_trigger_input_tokens(mut self, tokens: u32) -> Self {
+        self.trigger = Some(ContextTrigger::InputTokens { value: tokens });
+        self
+    }
+
+    /// Start clearing once the conversation has the given number of tool uses
+    pub fn with_trigger_tool_uses(mut self, tool_uses: u32) -> Self {
+        self.trigger = Some(ContextTrigger::ToolUses { value: tool_uses });
+        self
+    }
+
+    /// Keep the given number of most recent tool uses
+    pub fn with_keep_tool_uses(mut self, tool_uses: u32) -> Self {
+        self.keep = Some(ContextKeep::ToolUses { value: tool_uses });
+        self
+    }
+
+    /// Clear at least the given number of input tokens
+    pub fn with_clear_at_least_input_tokens(mut self, tokens: u32) -> Self {
+        self.clear_at_least = Some(ContextAmount::InputTokens { value: tokens });
+        self
+    }
+
+    /// Never clear the results of the named tools
+    pub fn with_exclude_tools(
+        mut self,
+        tools: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.exclude_tools = Some(tools.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Also clear the inputs of cleared tool uses
+    pub fn with_clear_tool_inputs(mut self, clear: bool) -> Self {
+        self.clear_tool_inputs = Some(clear);
+        self
+    }
+}
+
+/// Condition that starts a context edit
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContextTrigger {
+    InputTokens { value: u32 },
+    ToolUses { value: u32 },
+}
+
+/// Context kept by a context edit
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContextKeep {
+    ToolUses { value: u32 },
+}
+
+/// Amount of context cleared by a context edit
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContextAmount {
+    InputTokens { value: u32 },
+}
+
+/// Context edits the API applied to a request
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ContextManagementResponse {
+    /// Edits that were applied
+    #[serde(default)]
+    pub applied_edits: Vec<AppliedContextEdit>,
+}
+
+/// Context edit applied by the API
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct AppliedContextEdit {
+    /// Type of the edit (e.g. "clear_tool_uses_20250919")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Number of tool uses that were cleared
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cleared_tool_uses: Option<u32>,
+    /// Number of input tokens that were cleared
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cleared_input_tokens: Option<u32>,
 }
 
 impl From<RequiredMessageParams> for CreateMessageParams {
@@ -101,7 +674,7 @@ impl CreateMessageParams {
     }
 
     // Builder methods for optional parameters
-    pub fn with_system(mut self, system: impl Into<String>) -> Self {
+    pub fn with_system(mut self, system: impl Into<SystemPrompt>) -> Self {
         self.system = Some(system.into());
         self
     }
@@ -116,6 +689,14 @@ impl CreateMessageParams {
         self
     }
 
+    /// Add a custom stop sequence after the existing ones
+    pub fn with_stop_sequence(mut self, stop_sequence: impl Into<String>) -> Self {
+        self.stop_sequences
+            .get_or_insert_with(Vec::new)
+            .push(stop_sequence.into());
+        self
+    }
+
     pub fn with_stream(mut self, stream: bool) -> Self {
         self.stream = Some(stream);
         self
@@ -132,7 +713,13 @@ impl CreateMessageParams {
     }
 
     pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
-        self.tools = Some(tools);
+        self.tools = Some(tools.into_iter().map(ToolUnion::Custom).collect());
+        self
+    }
+
+    /// Append a single tool (custom or server tool) to the request
+    pub fn with_tool(mut self, tool: impl Into<ToolUnion>) -> Self {
+        self.tools.get_or_insert_with(Vec::new).push(tool.into());
         self
     }
 
@@ -145,20 +732,553 @@ impl CreateMessageParams {
         self.metadata = Some(metadata);
         self
     }
+
+    /// Set the model to use
+    ///
+    /// Accepts a [`Model`] or any model identifier string.
+    pub fn with_model(mut self, model: impl Into<Model>) -> Self {
+        self.model = model.into().into();
+        self
+    }
+
+    /// Set the end user identifier in the request metadata
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.metadata.get_or_insert_with(Default::default).user_id = Some(user_id.into());
+        self
+    }
+
+    /// Reuse the container from a previous response
+    pub fn with_container(mut self, container_id: impl Into<String>) -> Self {
+        self.container = Some(container_id.into());
+        self
+    }
+
+    /// Enable extended thinking with the given token budget
+    pub fn with_thinking(mut self, budget_tokens: u32) -> Self {
+        self.thinking = Some(Thinking::Enabled { budget_tokens });
+        self
+    }
+
+    /// Connect the request to remote MCP servers
+    pub fn with_mcp_servers(mut self, mcp_servers: Vec<McpServer>) -> Self {
+        self.mcp_servers = Some(mcp_servers);
+        self
+    }
+
+    /// Let the API clear old context as the conversation grows
+    pub fn with_context_management(mut self, context_management: ContextManagement) -> Self {
+        self.context_management = Some(context_management);
+        self
+    }
+
+    /// Enable a beta feature for the request
+    pub fn with_beta(mut self, beta: impl Into<Beta>) -> Self {
+        self.betas.push(beta.into());
+        self
+    }
+
+    /// Enable several beta features for the request
+    pub fn with_betas(mut self, betas: impl IntoIterator<Item = impl Into<Beta>>) -> Self {
+        self.betas.extend(betas.into_iter().map(Into::into));
+        self
+    }
+
+    /// Send only the explicitly enabled betas
+    ///
+    /// Disables the automatic detection of [`required_betas`](Self::required_betas).
+    pub fn with_suppress_auto_betas(mut self, suppress: bool) -> Self {
+        self.suppress_auto_betas = suppress;
+        self
+    }
+
+    /// Text of the assistant prefill, if the last message is from the assistant
+    pub fn prefill(&self) -> Option<String> {
+        let last = self.messages.last()?;
+        (last.role == Role::Assistant).then(|| {
+            last.blocks()
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text, .. } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect()
+        })
+    }
+
+    /// Use these limits for the model instead of the SDK's built-in table
+    ///
+    /// Useful for custom or gateway models the SDK does not know about.
+    pub fn with_model_spec(mut self, model_spec: ModelSpec) -> Self {
+        self.model_spec = Some(model_spec);
+        self
+    }
+
+    /// Send this request even if the client's response cache holds its response
+    ///
+    /// The response is not stored, so the cached one stays in place.
+    pub fn with_bypass_response_cache(mut self, bypass: bool) -> Self {
+        self.bypass_response_cache = bypass;
+        self
+    }
+
+    /// Place cache breakpoints according to `strategy` when the request is sent
+    ///
+    /// The breakpoints are added to a copy of the parameters by the client,
+    /// these parameters are not changed; see
+    /// [`place_breakpoints`](crate::types::auto_cache::place_breakpoints).
+    pub fn with_auto_cache(mut self, strategy: AutoCacheStrategy) -> Self {
+        self.auto_cache = Some(strategy);
+        self
+    }
+
+    /// Send an additional top-level field with the request
+    ///
+    /// Use this for request fields the SDK does not support yet. Fields the
+    /// SDK does support must be set through their own setters, as they would
+    /// otherwise be sent twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-5".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hello")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_extra_field("inference_geo", serde_json::json!("eu"));
+    ///
+    /// let json = serde_json::to_value(&params).unwrap();
+    /// assert_eq!(json["inference_geo"], "eu");
+    /// assert_eq!(serde_json::from_value::<CreateMessageParams>(json).unwrap(), params);
+    /// ```
+    pub fn with_extra_field(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.extra.insert(key.into(), value);
+        self
+    }
+
+    /// Limits of the requested model, if known
+    pub fn model_spec(&self) -> Option<ModelSpec> {
+        self.model_spec.or_else(|| Model::from(&self.model).spec())
+    }
+
+    /// Set `max_tokens`, clamped to what the model supports
+    ///
+    /// Values above the model's base limit enable the 128k output beta on models
+    /// that support it. Unknown models take the value unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::beta::Beta;
+    /// use anthropic_ai_sdk::types::message::{CreateMessageParams, RequiredMessageParams};
+    ///
+    /// let params = |model: &str| {
+    ///     CreateMessageParams::new(RequiredMessageParams {
+    ///         model: model.to_string(),
+    ///         messages: vec![],
+    ///         max_tokens: 1024,
+    ///     })
+    /// };
+    ///
+    /// let haiku = params("claude-3-5-haiku-latest").with_max_output_tokens_for_model(64_000);
+    /// assert_eq!(haiku.max_tokens, 8_192);
+    ///
+    /// let sonnet = params("claude-3-7-sonnet-latest").with_max_output_tokens_for_model(100_000);
+    /// assert_eq!(sonnet.max_tokens, 100_000);
+    /// assert!(sonnet.betas().contains(&Beta::Output128k));
+    /// assert!(sonnet.validate().is_ok());
+    /// ```
+    pub fn with_max_output_tokens_for_model(mut self, max_tokens: u32) -> Self {
+        let Some(spec) = self.model_spec() else {
+            self.max_tokens = max_tokens;
+            return self;
+        };
+        if max_tokens > spec.max_output_tokens && spec.extended_max_output_tokens.is_some() {
+            if !self.betas.contains(&Beta::Output128k) {
+                self.betas.push(Beta::Output128k);
+            }
+            self.max_tokens = max_tokens.min(spec.output_limit(true));
+        } else {
+            self.max_tokens = max_tokens.min(spec.max_output_tokens);
+        }
+        self
+    }
+
+    /// Set `max_tokens` to the largest value the model accepts
+    ///
+    /// The 128k output beta is only used if it is already enabled. Unknown
+    /// models get [`DEFAULT_MAX_TOKENS`]. With extended thinking enabled,
+    /// `max_tokens` is raised above the thinking budget where the model
+    /// allows it, leaving [`DEFAULT_MAX_TOKENS`] for the answer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, DEFAULT_MAX_TOKENS, RequiredMessageParams,
+    /// };
+    ///
+    /// let params = |model: &str| {
+    ///     CreateMessageParams::new(RequiredMessageParams {
+    ///         model: model.to_string(),
+    ///         messages: vec![],
+    ///         max_tokens: 0,
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(params("claude-3-5-haiku-latest").with_max_tokens_auto().max_tokens, 8_192);
+    /// assert_eq!(
+    ///     params("claude-unreleased").with_max_tokens_auto().max_tokens,
+    ///     DEFAULT_MAX_TOKENS
+    /// );
+    ///
+    /// let thinking = params("claude-unreleased").with_thinking(10_000).with_max_tokens_auto();
+    /// assert_eq!(thinking.max_tokens, 10_000 + DEFAULT_MAX_TOKENS);
+    /// ```
+    pub fn with_max_tokens_auto(mut self) -> Self {
+        let spec = self.model_spec();
+        self.fill_max_tokens(MaxTokens::ModelMaximum, spec);
+        self
+    }
+
+    /// Set `max_tokens` as chosen by `choice`, within the model limits `spec`
+    pub(crate) fn fill_max_tokens(&mut self, choice: MaxTokens, spec: Option<ModelSpec>) {
+        let limit = spec.map(|spec| spec.output_limit(self.betas.contains(&Beta::Output128k)));
+        let clamp = |max_tokens: u32| limit.map_or(max_tokens, |limit| max_tokens.min(limit));
+        let mut max_tokens = match choice {
+            MaxTokens::ModelMaximum => limit.unwrap_or(DEFAULT_MAX_TOKENS),
+            MaxTokens::Fixed(max_tokens) => clamp(max_tokens),
+        };
+        if let Some(Thinking::Enabled { budget_tokens }) = &self.thinking {
+            if max_tokens <= *budget_tokens {
+                max_tokens = clamp(budget_tokens.saturating_add(DEFAULT_MAX_TOKENS));
+            }
+        }
+        self.max_tokens = max_tokens;
+    }
+
+    /// Prefill the start of the assistant's reply
+    ///
+    /// Trailing whitespace, which the API rejects, is trimmed. The response only
+    /// contains the continuation; use
+    /// [`CreateMessageResponse::text_with_prefill`] to get the full reply.
+    pub fn with_prefill(mut self, text: impl Into<String>) -> Self {
+        self.messages.push(Message::assistant_prefill(text));
+        self
+    }
+
+    /// Check that message roles alternate before sending the request
+    pub fn with_role_validation(mut self, enabled: bool) -> Self {
+        self.role_validation = enabled;
+        self
+    }
+
+    /// Check that every tool result answers a tool use of the preceding
+    /// assistant message before sending the request
+    ///
+    /// Off by default, since the API accepts some histories this check
+    /// rejects, e.g. ones edited by context management.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     ContentBlock, CreateMessageParams, Message, RequiredMessageParams, Role, ValidationError,
+    /// };
+    ///
+    /// let orphan = ContentBlock::ToolResult {
+    ///     tool_use_id: "toolu_01".to_string(),
+    ///     is_error: false,
+    ///     content: "18°C".into(),
+    /// };
+    /// let params = CreateMessageParams::new(RequiredMessageParams::new(
+    ///     "claude-sonnet-4-5",
+    ///     vec![Message::new_blocks(Role::User, vec![orphan])],
+    ///     1024,
+    /// ));
+    /// assert!(params.validate().is_ok());
+    /// assert!(matches!(
+    ///     params.with_tool_result_validation(true).validate(),
+    ///     Err(ValidationError::UnmatchedToolResult { index: 0, .. })
+    /// ));
+    /// ```
+    pub fn with_tool_result_validation(mut self, enabled: bool) -> Self {
+        self.tool_result_validation = enabled;
+        self
+    }
+
+    /// Validate the parameters before they are sent
+    ///
+    /// This catches errors the API would reject without a network round trip.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.validate_with_spec(self.model_spec())
+    }
+
+    /// Validate the parameters, checking `max_tokens` against `spec`
+    ///
+    /// Like [`validate`](Self::validate), with the model limits supplied by
+    /// the caller, e.g. from the Models API, instead of the built-in table.
+    pub fn validate_with_spec(&self, spec: Option<ModelSpec>) -> Result<(), ValidationError> {
+        self.validate_contents(spec)?;
+        check_request_size(serialized_size(self))
+    }
+
+    /// The checks of [`validate_with_spec`](Self::validate_with_spec) except
+    /// the request size, which the client checks on the body it sends
+    pub(crate) fn validate_contents(&self, spec: Option<ModelSpec>) -> Result<(), ValidationError> {
+        let mut names = std::collections::HashSet::new();
+        for server in self.mcp_servers.iter().flatten() {
+            if !names.insert(server.name.as_str()) {
+                return Err(ValidationError::DuplicateMcpServerName(server.name.clone()));
+            }
+        }
+        if self.role_validation {
+            validate_roles(&self.messages)?;
+        }
+        if self.tool_result_validation {
+            validate_tool_results(&self.messages)?;
+        }
+        if let Some(metadata) = &self.metadata {
+            metadata.validate()?;
+        }
+        for message in &self.messages {
+            for block in message.blocks().iter() {
+                if let ContentBlock::Image {
+                    source: ImageSource::Base64 { media_type, .. },
+                } = block
+                {
+                    if !media_type.is_supported() {
+                        return Err(ValidationError::UnsupportedImageMediaType(
+                            media_type.to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+        validate_payload(&self.messages)?;
+        if let Some(prefill) = self.prefill() {
+            if prefill.ends_with(char::is_whitespace) {
+                return Err(ValidationError::PrefillTrailingWhitespace);
+            }
+        }
+        if let Some(spec) = spec {
+            let limit = spec.output_limit(self.betas.contains(&Beta::Output128k));
+            if self.max_tokens > limit {
+                return Err(ValidationError::MaxTokensExceeded {
+                    model: self.model.clone(),
+                    max_tokens: self.max_tokens,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The JSON request body, checked against [`MAX_REQUEST_BYTES`]
+    ///
+    /// The parameters are serialized once, for both the check and the request.
+    pub(crate) fn request_body(&self) -> Result<Vec<u8>, MessageError> {
+        let body = serde_json::to_vec(self)
+            .map_err(|e| MessageError::RequestFailed(format!("Failed to serialize body: {}", e)))?;
+        check_request_size(body.len())?;
+        Ok(body)
+    }
+
+    /// Beta feature flags required by the features used in these parameters
+    ///
+    /// The client sends these in the `anthropic-beta` header. Blocks returned
+    /// in tool results count as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::beta::Beta;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     ContentBlock, CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// let result = ContentBlock::tool_result(
+    ///     "toolu_01",
+    ///     vec![ContentBlock::search_result("atlas.txt", "Atlas", ["Paris is the capital."])],
+    /// );
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-0".to_string(),
+    ///     messages: vec![Message::new_blocks(Role::User, vec![result])],
+    ///     max_tokens: 1024,
+    /// });
+    /// assert_eq!(params.required_betas(), [Beta::SearchResults]);
+    /// ```
+    pub fn required_betas(&self) -> Vec<Beta> {
+        let mut betas = tool_betas(self.tools.as_deref());
+        if self.mcp_servers.as_ref().is_some_and(|s| !s.is_empty()) {
+            betas.push(McpServer::BETA);
+        }
+        if self.context_management.is_some() {
+            betas.push(ContextManagement::BETA);
+        }
+        betas.extend(message_betas(self.system.as_ref(), &self.messages));
+        betas
+    }
+
+    /// Beta feature flags sent in the `anthropic-beta` header
+    ///
+    /// These are the explicitly enabled betas followed by the
+    /// [`required_betas`](Self::required_betas), without duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::beta::Beta;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     ContentBlock, CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// let message = Message::new_blocks(
+    ///     Role::User,
+    ///     vec![
+    ///         ContentBlock::document_file("file_011CNha8iCJcU1wXNR6q4V8w"),
+    ///         ContentBlock::text("Summarize this document"),
+    ///     ],
+    /// );
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-0".to_string(),
+    ///     messages: vec![message],
+    ///     max_tokens: 1024,
+    /// });
+    /// assert_eq!(params.betas(), [Beta::FilesApi]);
+    ///
+    /// let params = params.with_betas([Beta::FilesApi, Beta::from("context-1m-2025-08-07")]);
+    /// assert_eq!(params.betas().len(), 2);
+    ///
+    /// let params = params.with_suppress_auto_betas(true);
+    /// assert_eq!(params.betas()[1].as_str(), "context-1m-2025-08-07");
+    /// ```
+    pub fn betas(&self) -> Vec<Beta> {
+        resolve_betas(&self.betas, self.suppress_auto_betas, || {
+            self.required_betas()
+        })
+    }
+}
+
+/// Explicit beta flags followed by the detected ones unless suppressed
+fn resolve_betas(
+    explicit: &[Beta],
+    suppress_auto_betas: bool,
+    required: impl FnOnce() -> Vec<Beta>,
+) -> Vec<Beta> {
+    let detected = if suppress_auto_betas {
+        Vec::new()
+    } else {
+        required()
+    };
+    merge_betas(explicit, detected)
+}
+
+/// Beta feature flags required by content blocks in the system prompt and messages
+fn message_betas(system: Option<&SystemPrompt>, messages: &[Message]) -> Vec<Beta> {
+    let mut betas = Vec::new();
+    if messages_contain(messages, |block| {
+        matches!(block, ContentBlock::SearchResult { .. })
+    }) {
+        betas.push(ContentBlock::SEARCH_RESULTS_BETA);
+    }
+    if messages_contain(messages, ContentBlock::has_file_source) {
+        betas.push(ContentBlock::FILES_API_BETA);
+    }
+    let one_hour =
+        |block: &ContentBlock| block.cache_control().is_some_and(CacheControl::is_one_hour);
+    if messages_contain(messages, one_hour)
+        || system.is_some_and(|system| system.blocks().iter().any(one_hour))
+    {
+        betas.push(Beta::PromptCaching1hTtl);
+    }
+    betas
+}
+
+/// Generate a unique id in the format of the API's tool use ids
+fn generate_tool_use_id() -> String {
+    use std::hash::{BuildHasher, Hasher};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    const ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let state = std::collections::hash_map::RandomState::new();
+    let mut id = String::from("toolu_01");
+    for part in 0..2u8 {
+        let mut hasher = state.build_hasher();
+        hasher.write_u64(count);
+        hasher.write_u128(nanos);
+        hasher.write_u8(part);
+        let mut value = hasher.finish();
+        for _ in 0..11 {
+            id.push(ALPHABET[(value % 62) as usize] as char);
+            value /= 62;
+        }
+    }
+    id
+}
+
+/// Whether any content block of the messages, or of their tool results, matches the predicate
+fn messages_contain(messages: &[Message], predicate: impl Fn(&ContentBlock) -> bool) -> bool {
+    messages.iter().any(|message| match &message.content {
+        MessageContent::Blocks { content } => content
+            .iter()
+            .any(|block| block_contains(block, &predicate)),
+        MessageContent::Text { .. } => false,
+    })
+}
+
+/// Whether `block` or a block of its tool result content matches the predicate
+fn block_contains(block: &ContentBlock, predicate: &impl Fn(&ContentBlock) -> bool) -> bool {
+    if predicate(block) {
+        return true;
+    }
+    match block {
+        ContentBlock::ToolResult {
+            content: ToolResultContent::Blocks(blocks),
+            ..
+        }
+        | ContentBlock::McpToolResult {
+            content: ToolResultContent::Blocks(blocks),
+            ..
+        } => blocks.iter().any(|block| block_contains(block, predicate)),
+        _ => false,
+    }
+}
+
+/// Beta feature flags required by server tools in a `tools` parameter
+fn tool_betas(tools: Option<&[ToolUnion]>) -> Vec<Beta> {
+    let mut betas = Vec::new();
+    let uses_code_execution = tools
+        .into_iter()
+        .flatten()
+        .any(|tool| matches!(tool, ToolUnion::CodeExecution(_)));
+    if uses_code_execution {
+        betas.push(CodeExecutionTool::BETA);
+    }
+    betas
 }
 
 /// Message in a conversation
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Message {
     /// Role of the message sender
     pub role: Role,
     /// Content of the message (either string or array of content blocks)
-    #[serde(flatten)]
     pub content: MessageContent,
 }
 
 /// Role of a message sender
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Role {
     User,
@@ -166,8 +1286,41 @@ pub enum Role {
 }
 
 /// Content of a message
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(untagged)]
+///
+/// Serialized as a JSON string for text content and as an array for blocks.
+/// Deserialization picks the variant from the JSON type, so an empty array is
+/// always empty blocks.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{ContentBlock, Message, MessageContent, Role};
+///
+/// let messages = [
+///     Message::new_text(Role::User, "Hello"),
+///     Message::new_blocks(Role::User, vec![]),
+///     Message::new_blocks(Role::User, vec![ContentBlock::text("Hello")]),
+///     Message::new_blocks(
+///         Role::Assistant,
+///         vec![
+///             ContentBlock::text("Let me check."),
+///             ContentBlock::ToolUse {
+///                 id: "toolu_01".to_string(),
+///                 name: "get_weather".to_string(),
+///                 input: serde_json::json!({"city": "Paris"}),
+///             },
+///         ],
+///     ),
+/// ];
+/// for message in messages {
+///     let json = serde_json::to_string(&message).unwrap();
+///     assert_eq!(serde_json::from_str::<Message>(&json).unwrap(), message);
+/// }
+///
+/// let message: Message = serde_json::from_str(r#"{"role": "user", "content": []}"#).unwrap();
+/// assert_eq!(message.content, MessageContent::Blocks { content: vec![] });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 pub enum MessageContent {
     /// Simple text content
     Text { content: String },
@@ -175,16 +1328,122 @@ pub enum MessageContent {
     Blocks { content: Vec<ContentBlock> },
 }
 
+impl From<String> for MessageContent {
+    fn from(content: String) -> Self {
+        MessageContent::Text { content }
+    }
+}
+
+impl From<&str> for MessageContent {
+    fn from(content: &str) -> Self {
+        MessageContent::Text {
+            content: content.to_string(),
+        }
+    }
+}
+
+impl From<Vec<ContentBlock>> for MessageContent {
+    fn from(content: Vec<ContentBlock>) -> Self {
+        MessageContent::Blocks { content }
+    }
+}
+
+impl<const N: usize> From<[ContentBlock; N]> for MessageContent {
+    fn from(content: [ContentBlock; N]) -> Self {
+        MessageContent::Blocks {
+            content: content.into(),
+        }
+    }
+}
+
+impl Serialize for MessageContent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MessageContent::Text { content } => content.serialize(serializer),
+            MessageContent::Blocks { content } => content.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageContent {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(content) => Ok(MessageContent::Text { content }),
+            content @ serde_json::Value::Array(_) => Ok(MessageContent::Blocks {
+                content: serde_json::from_value(content).map_err(D::Error::custom)?,
+            }),
+            _ => Err(D::Error::custom(
+                "message content must be a string or an array",
+            )),
+        }
+    }
+}
+
 /// Content block in a message
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(tag = "type")]
+///
+/// Block types not known to this SDK are captured as [`ContentBlock::Unknown`]
+/// and serialize back to their original JSON, so histories containing them can
+/// be replayed.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::ContentBlock;
+///
+/// let json = r#"{"type":"hologram","frames":[1,2,3]}"#;
+/// let block: ContentBlock = serde_json::from_str(json).unwrap();
+/// assert!(matches!(&block, ContentBlock::Unknown { block_type, .. } if block_type == "hologram"));
+/// assert_eq!(
+///     serde_json::to_value(&block).unwrap(),
+///     serde_json::from_str::<serde_json::Value>(json).unwrap()
+/// );
+///
+/// // A known block type with invalid fields is an error
+/// assert!(serde_json::from_str::<ContentBlock>(r#"{"type":"text"}"#).is_err());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "type")]
+#[non_exhaustive]
 pub enum ContentBlock {
     /// Text content
     #[serde(rename = "text")]
-    Text { text: String },
+    Text {
+        text: String,
+        /// Cache breakpoint after this block
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+        /// Sources the model cited for this text
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        citations: Option<Vec<Citation>>,
+    },
+    /// Extended thinking content
+    #[serde(rename = "thinking")]
+    Thinking { thinking: String, signature: String },
+    /// Thinking content redacted by the safety systems
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
     /// Image content
     #[serde(rename = "image")]
     Image { source: ImageSource },
+    /// Document content, such as a PDF or plain text
+    #[serde(rename = "document")]
+    Document {
+        source: DocumentSource,
+        /// Title of the document
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Context about the document that is not cited from
+        #[serde(skip_serializing_if = "Option::is_none")]
+        context: Option<String>,
+        /// Citation configuration for the document
+        #[serde(skip_serializing_if = "Option::is_none")]
+        citations: Option<CitationsConfig>,
+        /// Cache breakpoint on this block
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
     /// Tool use content
     #[serde(rename = "tool_use")]
     ToolUse {
@@ -196,24 +1455,593 @@ pub enum ContentBlock {
     #[serde(rename = "tool_result")]
     ToolResult {
         tool_use_id: String,
-        content: String,
+        /// Whether the tool failed, with `content` describing the error
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        is_error: bool,
+        content: ToolResultContent,
+    },
+    /// Server tool use content (executed by Anthropic)
+    #[serde(rename = "server_tool_use")]
+    ServerToolUse {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+    /// Code execution tool result content
+    #[serde(rename = "code_execution_tool_result")]
+    CodeExecutionToolResult {
+        tool_use_id: String,
+        content: CodeExecutionToolResultContent,
+    },
+    /// MCP tool use content (executed on a remote MCP server)
+    #[serde(rename = "mcp_tool_use")]
+    McpToolUse {
+        id: String,
+        name: String,
+        server_name: String,
+        input: serde_json::Value,
+    },
+    /// MCP tool result content
+    #[serde(rename = "mcp_tool_result")]
+    McpToolResult {
+        tool_use_id: String,
+        /// Whether the tool failed, with `content` describing the error
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        is_error: bool,
+        content: ToolResultContent,
+    },
+    /// Search result with provenance, citable by the model
+    #[serde(rename = "search_result")]
+    SearchResult {
+        /// Source of the result (e.g. a URL or document identifier)
+        source: String,
+        /// Title of the result
+        title: String,
+        /// Text blocks of the result
+        content: Vec<ContentBlock>,
+        /// Citation configuration for the result
+        #[serde(skip_serializing_if = "Option::is_none")]
+        citations: Option<CitationsConfig>,
+        /// Cache breakpoint on this block
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
+    /// Content block of a type not known to this SDK
+    #[serde(skip)]
+    Unknown {
+        /// Value of the block's `type` field
+        block_type: String,
+        /// The complete block as received, including the `type` field
+        data: serde_json::Value,
     },
 }
 
-/// Source of an image
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ImageSource {
-    /// Type of image source
+impl ContentBlock {
+    /// Types of the known blocks
+    const TYPES: &[&str] = &[
+        "text",
+        "thinking",
+        "redacted_thinking",
+        "image",
+        "document",
+        "tool_use",
+        "tool_result",
+        "server_tool_use",
+        "code_execution_tool_result",
+        "mcp_tool_use",
+        "mcp_tool_result",
+        "search_result",
+    ];
+}
+
+impl Serialize for ContentBlock {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ContentBlock::Unknown { data, .. } => data.serialize(serializer),
+            known => ContentBlock::serialize(known, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentBlock {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let data = serde_json::Value::deserialize(deserializer)?;
+        let block_type = data
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| D::Error::missing_field("type"))?;
+        if ContentBlock::TYPES.contains(&block_type) {
+            return ContentBlock::deserialize(&data).map_err(D::Error::custom);
+        }
+        Ok(ContentBlock::Unknown {
+            block_type: block_type.to_string(),
+            data,
+        })
+    }
+}
+
+/// Cache breakpoint for prompt caching
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CacheControl {
+    /// Cache the prompt prefix up to this block
+    Ephemeral {
+        /// Lifetime of the cache entry, defaults to 5 minutes
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ttl: Option<CacheTtl>,
+    },
+}
+
+/// Lifetime of a prompt cache entry
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheTtl {
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+}
+
+impl CacheControl {
+    /// Cache breakpoint with the default 5 minute lifetime
+    pub fn ephemeral() -> Self {
+        CacheControl::Ephemeral { ttl: None }
+    }
+
+    /// Cache breakpoint with the given lifetime
+    pub fn ephemeral_with_ttl(ttl: CacheTtl) -> Self {
+        CacheControl::Ephemeral { ttl: Some(ttl) }
+    }
+
+    /// Whether the cache entry lives for 1 hour, which requires a beta
+    pub fn is_one_hour(&self) -> bool {
+        matches!(
+            self,
+            CacheControl::Ephemeral {
+                ttl: Some(CacheTtl::OneHour)
+            }
+        )
+    }
+}
+
+/// Citation configuration for a content block
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct CitationsConfig {
+    /// Whether the model may cite this block
+    pub enabled: bool,
+}
+
+/// Source cited by a text block of a response
+///
+/// Citations of a type this version does not know are kept as
+/// [`Citation::Unknown`].
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{Citation, ContentBlock};
+///
+/// let block: ContentBlock = serde_json::from_str(
+///     r#"{
+///         "type": "text",
+///         "text": "Paris is the capital.",
+///         "citations": [{
+///             "type": "search_result_location",
+///             "cited_text": "Paris is the capital of France.",
+///             "source": "atlas.txt",
+///             "title": "Atlas",
+///             "search_result_index": 0,
+///             "start_block_index": 0,
+///             "end_block_index": 1
+///         }]
+///     }"#,
+/// )
+/// .unwrap();
+/// let ContentBlock::Text { citations: Some(citations), .. } = block else {
+///     unreachable!()
+/// };
+/// assert!(matches!(
+///     &citations[0],
+///     Citation::SearchResultLocation { source, .. } if source == "atlas.txt"
+/// ));
+/// ```
+///
+/// Streamed citations arrive as `citations_delta` events, which
+/// [`MessageAccumulator`] appends to the text block:
+///
+/// ```
+/// use anthropic_ai_sdk::testing::StreamScenario;
+/// use anthropic_ai_sdk::types::message::{
+///     Citation, ContentBlock, MessageAccumulator, StopReason, StreamEvent,
+/// };
+///
+/// let delta: StreamEvent = serde_json::from_str(
+///     r#"{
+///         "type": "content_block_delta",
+///         "index": 0,
+///         "delta": {
+///             "type": "citations_delta",
+///             "citation": {
+///                 "type": "char_location",
+///                 "cited_text": "Paris is the capital of France.",
+///                 "document_index": 0,
+///                 "start_char_index": 0,
+///                 "end_char_index": 31
+///             }
+///         }
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// let mut accumulator = MessageAccumulator::new();
+/// for event in StreamScenario::new().text("Paris").finish(StopReason::EndTurn) {
+///     if matches!(event, StreamEvent::ContentBlockStop { .. }) {
+///         accumulator.push(&delta).unwrap();
+///     }
+///     accumulator.push(&event).unwrap();
+/// }
+/// let response = accumulator.finish().unwrap();
+/// let ContentBlock::Text { citations: Some(citations), .. } = &response.content[0] else {
+///     unreachable!()
+/// };
+/// assert_eq!(citations[0].cited_text(), Some("Paris is the capital of France."));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Citation {
+    /// Character range of a plain text document
+    CharLocation {
+        cited_text: String,
+        document_index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        document_title: Option<String>,
+        start_char_index: usize,
+        end_char_index: usize,
+    },
+    /// Page range of a PDF document
+    PageLocation {
+        cited_text: String,
+        document_index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        document_title: Option<String>,
+        start_page_number: usize,
+        end_page_number: usize,
+    },
+    /// Block range of a custom content document
+    ContentBlockLocation {
+        cited_text: String,
+        document_index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        document_title: Option<String>,
+        start_block_index: usize,
+        end_block_index: usize,
+    },
+    /// Block range of a `search_result` block
+    SearchResultLocation {
+        cited_text: String,
+        source: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        search_result_index: usize,
+        start_block_index: usize,
+        end_block_index: usize,
+    },
+    /// Result of the web search tool
+    WebSearchResultLocation {
+        cited_text: String,
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        encrypted_index: String,
+    },
+    /// Citation of a type this version does not know, kept as raw JSON
+    #[serde(skip)]
+    Unknown {
+        citation_type: String,
+        data: serde_json::Value,
+    },
+}
+
+impl Citation {
+    /// Types of the known citations
+    const TYPES: &[&str] = &[
+        "char_location",
+        "page_location",
+        "content_block_location",
+        "search_result_location",
+        "web_search_result_location",
+    ];
+
+    /// Text the citation quotes
+    pub fn cited_text(&self) -> Option<&str> {
+        match self {
+            Citation::CharLocation { cited_text, .. }
+            | Citation::PageLocation { cited_text, .. }
+            | Citation::ContentBlockLocation { cited_text, .. }
+            | Citation::SearchResultLocation { cited_text, .. }
+            | Citation::WebSearchResultLocation { cited_text, .. } => Some(cited_text),
+            Citation::Unknown { data, .. } => data.get("cited_text")?.as_str(),
+        }
+    }
+}
+
+impl Serialize for Citation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Citation::Unknown { data, .. } => data.serialize(serializer),
+            known => Citation::serialize(known, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Citation {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let data = serde_json::Value::deserialize(deserializer)?;
+        let citation_type = data
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| D::Error::missing_field("type"))?;
+        if Citation::TYPES.contains(&citation_type) {
+            return Citation::deserialize(&data).map_err(D::Error::custom);
+        }
+        Ok(Citation::Unknown {
+            citation_type: citation_type.to_string(),
+            data,
+        })
+    }
+}
+
+/// System prompt, either plain text or text blocks
+///
+/// Blocks allow cache breakpoints within the system prompt; see
+/// [`SystemPromptBuilder`](crate::types::prompt::SystemPromptBuilder).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SystemPrompt {
+    /// Plain text prompt
+    Text(String),
+    /// Prompt blocks
+    Blocks(Vec<ContentBlock>),
+}
+
+impl SystemPrompt {
+    /// Text of the prompt, with the text of blocks concatenated
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            SystemPrompt::Text(text) => Cow::Borrowed(text),
+            SystemPrompt::Blocks(blocks) => Cow::Owned(
+                blocks
+                    .iter()
+                    .filter_map(|block| match block {
+                        ContentBlock::Text { text, .. } => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Blocks of the prompt; a plain text prompt has none
+    pub fn blocks(&self) -> &[ContentBlock] {
+        match self {
+            SystemPrompt::Text(_) => &[],
+            SystemPrompt::Blocks(blocks) => blocks,
+        }
+    }
+}
+
+impl From<String> for SystemPrompt {
+    fn from(text: String) -> Self {
+        SystemPrompt::Text(text)
+    }
+}
+
+impl From<&str> for SystemPrompt {
+    fn from(text: &str) -> Self {
+        SystemPrompt::Text(text.to_string())
+    }
+}
+
+impl From<Vec<ContentBlock>> for SystemPrompt {
+    fn from(blocks: Vec<ContentBlock>) -> Self {
+        SystemPrompt::Blocks(blocks)
+    }
+}
+
+/// Content of a tool result, either plain text or content blocks
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ToolResultContent {
+    /// Plain text result
+    Text(String),
+    /// Structured result blocks
+    Blocks(Vec<ContentBlock>),
+}
+
+impl From<String> for ToolResultContent {
+    fn from(text: String) -> Self {
+        ToolResultContent::Text(text)
+    }
+}
+
+impl From<&str> for ToolResultContent {
+    fn from(text: &str) -> Self {
+        ToolResultContent::Text(text.to_string())
+    }
+}
+
+impl From<Vec<ContentBlock>> for ToolResultContent {
+    fn from(blocks: Vec<ContentBlock>) -> Self {
+        ToolResultContent::Blocks(blocks)
+    }
+}
+
+/// Result of a code execution server tool call
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum CodeExecutionToolResultContent {
+    /// The code ran to completion
+    #[serde(rename = "code_execution_result")]
+    Result {
+        /// Standard output of the execution
+        stdout: String,
+        /// Standard error of the execution
+        stderr: String,
+        /// Exit code of the execution
+        return_code: i32,
+        /// Files produced by the execution
+        #[serde(default)]
+        content: Vec<CodeExecutionOutput>,
+    },
+    /// The code could not be executed
+    #[serde(rename = "code_execution_tool_result_error")]
+    Error {
+        /// Error code (e.g. "unavailable", "execution_time_exceeded")
+        error_code: String,
+    },
+}
+
+/// File produced by code execution
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CodeExecutionOutput {
+    /// Type of the output (always "code_execution_output")
     #[serde(rename = "type")]
     pub type_: String,
-    /// Media type of the image
-    pub media_type: String,
-    /// Base64-encoded image data
-    pub data: String,
+    /// ID of the file, downloadable through the Files API
+    pub file_id: String,
+}
+
+/// Source of an image
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ImageSource {
+    /// Image data embedded in the request
+    #[serde(rename = "base64")]
+    Base64 {
+        /// Media type of the image
+        media_type: ImageMediaType,
+        /// Base64-encoded image data
+        data: String,
+    },
+    /// Publicly accessible image URL
+    #[serde(rename = "url")]
+    Url {
+        /// URL of the image
+        url: String,
+    },
+    /// Image uploaded through the Files API
+    #[serde(rename = "file")]
+    File {
+        /// ID of the uploaded file
+        file_id: String,
+    },
+}
+
+impl ImageSource {
+    /// Create a base64 image source
+    pub fn base64(media_type: impl Into<ImageMediaType>, data: impl Into<String>) -> Self {
+        Self::Base64 {
+            media_type: media_type.into(),
+            data: data.into(),
+        }
+    }
+
+    /// Create a URL image source
+    pub fn url(url: impl Into<String>) -> Self {
+        Self::Url { url: url.into() }
+    }
+
+    /// Create an image source referencing an uploaded file
+    pub fn file(file_id: impl Into<String>) -> Self {
+        Self::File {
+            file_id: file_id.into(),
+        }
+    }
+}
+
+/// Source of a document block
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum DocumentSource {
+    /// Document data embedded in the request
+    #[serde(rename = "base64")]
+    Base64 {
+        /// Media type of the document (e.g. "application/pdf")
+        media_type: String,
+        /// Base64-encoded document data
+        data: String,
+    },
+    /// Plain text document
+    #[serde(rename = "text")]
+    Text {
+        /// Media type of the text, "text/plain"
+        media_type: String,
+        /// Text of the document
+        data: String,
+    },
+    /// Publicly accessible PDF URL
+    #[serde(rename = "url")]
+    Url {
+        /// URL of the document
+        url: String,
+    },
+    /// Document uploaded through the Files API
+    #[serde(rename = "file")]
+    File {
+        /// ID of the uploaded file
+        file_id: String,
+    },
+    /// Document made of content blocks, cited block by block
+    #[serde(rename = "content")]
+    Content {
+        /// Text and image blocks of the document
+        content: Vec<ContentBlock>,
+    },
+}
+
+impl DocumentSource {
+    /// Create a base64 PDF document source
+    pub fn pdf(data: impl Into<String>) -> Self {
+        Self::Base64 {
+            media_type: "application/pdf".to_string(),
+            data: data.into(),
+        }
+    }
+
+    /// Create a plain text document source
+    pub fn text(data: impl Into<String>) -> Self {
+        Self::Text {
+            media_type: "text/plain".to_string(),
+            data: data.into(),
+        }
+    }
+
+    /// Create a URL document source
+    pub fn url(url: impl Into<String>) -> Self {
+        Self::Url { url: url.into() }
+    }
+
+    /// Create a document source referencing an uploaded file
+    pub fn file(file_id: impl Into<String>) -> Self {
+        Self::File {
+            file_id: file_id.into(),
+        }
+    }
+
+    /// Create a document source made of content blocks
+    pub fn content(content: impl IntoIterator<Item = ContentBlock>) -> Self {
+        Self::Content {
+            content: content.into_iter().collect(),
+        }
+    }
 }
 
 /// Tool definition
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Tool {
     /// Name of the tool
     pub name: String,
@@ -222,11 +2050,252 @@ pub struct Tool {
     pub description: Option<String>,
     /// JSON schema for tool input
     pub input_schema: serde_json::Value,
+    /// Cache breakpoint after this tool definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+/// Code execution server tool
+///
+/// Runs Python code in a sandboxed container on Anthropic's side.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CodeExecutionTool {
+    /// Type of the tool
+    #[serde(rename = "type")]
+    pub type_: CodeExecutionToolType,
+    /// Name of the tool (always "code_execution")
+    pub name: String,
+}
+
+/// Versions of the code execution server tool
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub enum CodeExecutionToolType {
+    #[serde(rename = "code_execution_20250522")]
+    CodeExecution20250522,
+}
+
+impl CodeExecutionTool {
+    /// Beta flag required to use the code execution tool
+    pub const BETA: Beta = Beta::CodeExecution;
+
+    /// Create a new code execution tool definition
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::beta::Beta;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CodeExecutionTool, CodeExecutionToolResultContent, ContentBlock, CreateMessageParams,
+    ///     CreateMessageResponse, Message, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-20250514".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "What is 2 ** 100?")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_tool(CodeExecutionTool::new())
+    /// .with_container("container_011");
+    ///
+    /// let json = serde_json::to_value(&params).unwrap();
+    /// assert_eq!(
+    ///     json["tools"],
+    ///     serde_json::json!([{"type": "code_execution_20250522", "name": "code_execution"}])
+    /// );
+    /// assert_eq!(json["container"], "container_011");
+    /// assert_eq!(params.required_betas(), [Beta::CodeExecution]);
+    /// let restored: CreateMessageParams = serde_json::from_value(json).unwrap();
+    /// assert_eq!(restored.container.as_deref(), Some("container_011"));
+    /// assert_eq!(restored.tools, params.tools);
+    ///
+    /// // The response reports the container to reuse with the next request
+    /// let response = serde_json::json!({
+    ///     "id": "msg_01",
+    ///     "type": "message",
+    ///     "role": "assistant",
+    ///     "model": "claude-sonnet-4-20250514",
+    ///     "content": [
+    ///         {"type": "server_tool_use", "id": "srvtoolu_01", "name": "code_execution",
+    ///          "input": {"code": "print(2 ** 100)"}},
+    ///         {"type": "code_execution_tool_result", "tool_use_id": "srvtoolu_01",
+    ///          "content": {"type": "code_execution_result", "stdout": "1267650600228229401496703205376\n",
+    ///                      "stderr": "", "return_code": 0, "content": []}}
+    ///     ],
+    ///     "stop_reason": "end_turn",
+    ///     "stop_sequence": null,
+    ///     "usage": {"input_tokens": 12, "output_tokens": 30},
+    ///     "container": {"id": "container_011", "expires_at": "2025-05-23T21:13:31.749448Z"}
+    /// });
+    /// let response: CreateMessageResponse = serde_json::from_value(response).unwrap();
+    /// let ContentBlock::CodeExecutionToolResult {
+    ///     content: CodeExecutionToolResultContent::Result { return_code, .. },
+    ///     ..
+    /// } = &response.content[1]
+    /// else {
+    ///     panic!("expected a code execution result");
+    /// };
+    /// assert_eq!(*return_code, 0);
+    ///
+    /// let json = serde_json::to_value(&response).unwrap();
+    /// assert_eq!(json["container"]["id"], "container_011");
+    /// let restored: CreateMessageResponse = serde_json::from_value(json).unwrap();
+    /// assert_eq!(restored, response);
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            type_: CodeExecutionToolType::CodeExecution20250522,
+            name: "code_execution".to_string(),
+        }
+    }
+}
+
+impl Default for CodeExecutionTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Any tool that can be passed in the `tools` request parameter
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ToolUnion {
+    /// Client tool defined by the caller
+    Custom(Tool),
+    /// Code execution server tool
+    CodeExecution(CodeExecutionTool),
+}
+
+impl From<Tool> for ToolUnion {
+    fn from(tool: Tool) -> Self {
+        ToolUnion::Custom(tool)
+    }
+}
+
+impl From<CodeExecutionTool> for ToolUnion {
+    fn from(tool: CodeExecutionTool) -> Self {
+        ToolUnion::CodeExecution(tool)
+    }
+}
+
+/// Remote MCP server definition
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct McpServer {
+    /// Type of the server connection
+    #[serde(rename = "type")]
+    pub type_: McpServerType,
+    /// URL of the MCP server
+    pub url: String,
+    /// Name of the MCP server, must be unique within a request
+    pub name: String,
+    /// OAuth token for servers requiring authentication
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_token: Option<String>,
+    /// Tool configuration for the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_configuration: Option<McpToolConfiguration>,
+}
+
+/// Connection types for MCP servers
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum McpServerType {
+    Url,
+}
+
+/// Tool configuration for an MCP server
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct McpToolConfiguration {
+    /// Whether the server's tools are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Restrict the model to these tools of the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+impl McpServer {
+    /// Beta flag required to use the MCP connector
+    pub const BETA: Beta = Beta::Mcp;
+
+    /// Create a new URL-based MCP server definition
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::beta::Beta;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, McpServer, Message, RequiredMessageParams, Role, ValidationError,
+    /// };
+    ///
+    /// let server = McpServer::new("example-mcp", "https://example-server.modelcontextprotocol.io/sse")
+    ///     .with_authorization_token("token")
+    ///     .with_allowed_tools(vec!["echo".to_string()]);
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-20250514".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Echo hi")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_mcp_servers(vec![server.clone()]);
+    ///
+    /// let json = serde_json::to_value(&params).unwrap();
+    /// assert_eq!(
+    ///     json["mcp_servers"],
+    ///     serde_json::json!([{
+    ///         "type": "url",
+    ///         "url": "https://example-server.modelcontextprotocol.io/sse",
+    ///         "name": "example-mcp",
+    ///         "authorization_token": "token",
+    ///         "tool_configuration": {"allowed_tools": ["echo"]}
+    ///     }])
+    /// );
+    /// assert_eq!(params.required_betas(), [Beta::Mcp]);
+    /// let restored: CreateMessageParams = serde_json::from_value(json).unwrap();
+    /// assert_eq!(restored.mcp_servers, params.mcp_servers);
+    ///
+    /// // Server names must be unique
+    /// let params = params.with_mcp_servers(vec![server.clone(), server]);
+    /// assert!(matches!(
+    ///     params.validate(),
+    ///     Err(ValidationError::DuplicateMcpServerName(name)) if name == "example-mcp"
+    /// ));
+    /// ```
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            type_: McpServerType::Url,
+            url: url.into(),
+            name: name.into(),
+            authorization_token: None,
+            tool_configuration: None,
+        }
+    }
+
+    /// Set the OAuth token used to authenticate with the server
+    pub fn with_authorization_token(mut self, token: impl Into<String>) -> Self {
+        self.authorization_token = Some(token.into());
+        self
+    }
+
+    /// Enable or disable all tools of the server
+    pub fn with_tools_enabled(mut self, enabled: bool) -> Self {
+        self.tool_configuration
+            .get_or_insert_with(Default::default)
+            .enabled = Some(enabled);
+        self
+    }
+
+    /// Only allow the given tools of the server
+    pub fn with_allowed_tools(mut self, allowed_tools: Vec<String>) -> Self {
+        self.tool_configuration
+            .get_or_insert_with(Default::default)
+            .allowed_tools = Some(allowed_tools);
+        self
+    }
 }
 
 /// Tool choice configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(tag = "type")]
+#[non_exhaustive]
 pub enum ToolChoice {
     /// Let model choose whether to use tools
     #[serde(rename = "auto")]
@@ -239,16 +2308,115 @@ pub enum ToolChoice {
     Tool { name: String },
 }
 
+impl ToolChoice {
+    /// Let the model choose whether to use tools
+    pub fn auto() -> Self {
+        Self::Auto
+    }
+
+    /// Require the model to use one of the provided tools
+    pub fn any() -> Self {
+        Self::Any
+    }
+
+    /// Require the model to use the named tool
+    pub fn tool(name: impl Into<String>) -> Self {
+        Self::Tool { name: name.into() }
+    }
+}
+
 /// Message metadata
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 pub struct Metadata {
-    /// Custom metadata fields
+    /// External identifier for the end user, used for abuse detection
+    ///
+    /// Use an opaque value such as a hash or UUID, never names or email addresses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Additional metadata fields
     #[serde(flatten)]
-    pub fields: std::collections::HashMap<String, String>,
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+impl Metadata {
+    /// Maximum length of `user_id`
+    pub const MAX_USER_ID_LENGTH: usize = 256;
+    /// Maximum number of additional fields
+    pub const MAX_FIELDS: usize = 16;
+    /// Maximum length of an additional field name
+    pub const MAX_KEY_LENGTH: usize = 64;
+    /// Maximum size of the serialized metadata in bytes
+    pub const MAX_SERIALIZED_SIZE: usize = 4096;
+
+    /// Create metadata identifying the end user of the request
+    pub fn user(user_id: impl Into<String>) -> Self {
+        Self {
+            user_id: Some(user_id.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Add a field; strings, numbers and booleans convert directly
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::Metadata;
+    ///
+    /// let metadata = Metadata::user("user-7d3f")
+    ///     .with_field("trace_id", "4bf92f35")
+    ///     .with_field("attempt", 2)
+    ///     .with_field("sampled", true);
+    /// assert!(metadata.validate().is_ok());
+    ///
+    /// let oversized = Metadata::default().with_field("blob", "x".repeat(8192));
+    /// assert!(oversized.validate().is_err());
+    /// ```
+    pub fn with_field(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+
+    /// Check the metadata against the size limits
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        if let Some(user_id) = &self.user_id {
+            if user_id.len() > Self::MAX_USER_ID_LENGTH {
+                return Err(MetadataError::UserIdTooLong(user_id.len()));
+            }
+        }
+        if self.extra.len() > Self::MAX_FIELDS {
+            return Err(MetadataError::TooManyFields(self.extra.len()));
+        }
+        if let Some(key) = self.extra.keys().find(|k| k.len() > Self::MAX_KEY_LENGTH) {
+            return Err(MetadataError::KeyTooLong(key.clone()));
+        }
+        let size = serde_json::to_vec(self).map_or(0, |json| json.len());
+        if size > Self::MAX_SERIALIZED_SIZE {
+            return Err(MetadataError::TooLarge(size));
+        }
+        Ok(())
+    }
+}
+
+/// Errors for metadata exceeding the size limits
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum MetadataError {
+    #[error("Metadata user_id is {0} bytes long, the limit is {max}", max = Metadata::MAX_USER_ID_LENGTH)]
+    UserIdTooLong(usize),
+    #[error("Metadata has {0} fields, the limit is {max}", max = Metadata::MAX_FIELDS)]
+    TooManyFields(usize),
+    #[error("Metadata field name \"{0}\" exceeds {max} bytes", max = Metadata::MAX_KEY_LENGTH)]
+    KeyTooLong(String),
+    #[error("Metadata is {0} bytes when serialized, the limit is {max}", max = Metadata::MAX_SERIALIZED_SIZE)]
+    TooLarge(usize),
 }
 
 /// Response from creating a message
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct CreateMessageResponse {
     /// Content blocks in the response
     pub content: Vec<ContentBlock>,
@@ -267,37 +2435,542 @@ pub struct CreateMessageResponse {
     pub type_: String,
     /// Usage statistics
     pub usage: Usage,
+    /// Container used by server tools, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<Container>,
+    /// Context edits applied to the request, if context management was used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_management: Option<ContextManagementResponse>,
+}
+
+impl CreateMessageResponse {
+    /// Concatenated text of all text blocks, in order
+    ///
+    /// Thinking, tool use and other non-text blocks are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::CreateMessageResponse;
+    ///
+    /// let response: CreateMessageResponse = serde_json::from_str(r#"{
+    ///     "id": "msg_01",
+    ///     "type": "message",
+    ///     "role": "assistant",
+    ///     "model": "claude-sonnet-4-20250514",
+    ///     "content": [
+    ///         {"type": "text", "text": "Let me check the weather. "},
+    ///         {"type": "tool_use", "id": "toolu_01", "name": "get_weather",
+    ///          "input": {"location": "Paris"}},
+    ///         {"type": "text", "text": "One moment."}
+    ///     ],
+    ///     "stop_reason": "tool_use",
+    ///     "stop_sequence": null,
+    ///     "usage": {"input_tokens": 12, "output_tokens": 30}
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(response.text(), "Let me check the weather. One moment.");
+    /// assert_eq!(response.first_text(), Some("Let me check the weather. "));
+    /// assert!(response.is_tool_use());
+    /// ```
+    pub fn text(&self) -> String {
+        let texts = || {
+            self.content.iter().filter_map(|block| match block {
+                ContentBlock::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+        };
+        let mut text = String::with_capacity(texts().map(str::len).sum());
+        texts().for_each(|t| text.push_str(t));
+        text
+    }
+
+    /// Full text of the reply, including the prefill of the request
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, CreateMessageResponse, Message, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-0".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Describe a cat as JSON")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_prefill("{");
+    ///
+    /// let response: CreateMessageResponse = serde_json::from_str(r#"{
+    ///     "id": "msg_01",
+    ///     "type": "message",
+    ///     "role": "assistant",
+    ///     "model": "claude-sonnet-4-20250514",
+    ///     "content": [{"type": "text", "text": "\"name\": \"Tom\"}"}],
+    ///     "stop_reason": "end_turn",
+    ///     "stop_sequence": null,
+    ///     "usage": {"input_tokens": 12, "output_tokens": 8}
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(response.text_with_prefill(&params), r#"{"name": "Tom"}"#);
+    /// ```
+    pub fn text_with_prefill(&self, params: &CreateMessageParams) -> String {
+        let mut text = params.prefill().unwrap_or_default();
+        text.push_str(&self.text());
+        text
+    }
+
+    /// Text of the reply and the custom stop sequence that ended it, if any
+    ///
+    /// The API does not include the matched stop sequence in the text, so
+    /// the text is everything generated before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::CreateMessageResponse;
+    ///
+    /// let response: CreateMessageResponse = serde_json::from_str(r#"{
+    ///     "id": "msg_01",
+    ///     "type": "message",
+    ///     "role": "assistant",
+    ///     "model": "claude-sonnet-4-20250514",
+    ///     "content": [{"type": "text", "text": "<answer>42"}],
+    ///     "stop_reason": "stop_sequence",
+    ///     "stop_sequence": "</answer>",
+    ///     "usage": {"input_tokens": 12, "output_tokens": 4}
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     response.text_before_stop(),
+    ///     ("<answer>42".to_string(), Some("</answer>"))
+    /// );
+    /// ```
+    pub fn text_before_stop(&self) -> (String, Option<&str>) {
+        (self.text(), self.stop_sequence.as_deref())
+    }
+
+    /// Text of the first text block, if any
+    pub fn first_text(&self) -> Option<&str> {
+        self.content.iter().find_map(|block| match block {
+            ContentBlock::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Whether the model stopped to use a tool
+    pub fn is_tool_use(&self) -> bool {
+        self.stop_reason == Some(StopReason::ToolUse)
+    }
+
+    /// All `tool_use` blocks of the response, in order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::CreateMessageResponse;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct WeatherInput {
+    ///     location: String,
+    /// }
+    ///
+    /// let response: CreateMessageResponse = serde_json::from_str(r#"{
+    ///     "id": "msg_01",
+    ///     "type": "message",
+    ///     "role": "assistant",
+    ///     "model": "claude-sonnet-4-20250514",
+    ///     "content": [
+    ///         {"type": "tool_use", "id": "toolu_01", "name": "get_weather",
+    ///          "input": {"location": "Paris"}}
+    ///     ],
+    ///     "stop_reason": "tool_use",
+    ///     "stop_sequence": null,
+    ///     "usage": {"input_tokens": 12, "output_tokens": 30}
+    /// }"#).unwrap();
+    ///
+    /// let tool_use = response.find_tool_use("get_weather").unwrap();
+    /// let input: WeatherInput = tool_use.parse_input().unwrap();
+    /// assert_eq!(input.location, "Paris");
+    ///
+    /// let results: Vec<_> = response
+    ///     .tool_uses()
+    ///     .map(|tool_use| tool_use.result("18°C and sunny"))
+    ///     .collect();
+    /// assert_eq!(results.len(), 1);
+    /// ```
+    pub fn tool_uses(&self) -> impl Iterator<Item = ToolUseRef<'_>> {
+        self.content.iter().filter_map(ToolUseRef::from_block)
+    }
+
+    /// First `tool_use` block calling the named tool
+    pub fn find_tool_use(&self, name: &str) -> Option<ToolUseRef<'_>> {
+        self.tool_uses().find(|tool_use| tool_use.name == name)
+    }
+}
+
+/// Container used by the code execution tool
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Container {
+    /// Container identifier, pass it back via `with_container` to reuse the container
+    pub id: String,
+    /// Time when the container will expire
+    #[serde(with = "rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+impl Container {
+    /// Whether the container has expired and can no longer be reused
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::CreateMessageResponse;
+    ///
+    /// let response: CreateMessageResponse = serde_json::from_str(r#"{
+    ///     "id": "msg_01",
+    ///     "type": "message",
+    ///     "role": "assistant",
+    ///     "model": "claude-sonnet-4-20250514",
+    ///     "content": [],
+    ///     "stop_reason": "end_turn",
+    ///     "stop_sequence": null,
+    ///     "usage": {"input_tokens": 12, "output_tokens": 30},
+    ///     "container": {"id": "container_011CPR5CNjB747bTd36fQLFk",
+    ///                   "expires_at": "2025-05-23T21:13:31.749448Z"}
+    /// }"#).unwrap();
+    ///
+    /// let container = response.container.unwrap();
+    /// assert!(container.is_expired());
+    /// ```
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(OffsetDateTime::now_utc())
+    }
+
+    /// Whether the container has expired at the given time
+    pub fn is_expired_at(&self, now: OffsetDateTime) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Reason for stopping message generation
+///
+/// Values not known to this SDK are preserved in [`StopReason::Other`].
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::StopReason;
+///
+/// let reason: StopReason = serde_json::from_str("\"some_future_reason\"").unwrap();
+/// assert_eq!(reason.as_str(), "some_future_reason");
+/// assert_eq!(serde_json::to_string(&reason).unwrap(), "\"some_future_reason\"");
+///
+/// // A paused turn is continued, a refusal is not
+/// let paused: StopReason = serde_json::from_str("\"pause_turn\"").unwrap();
+/// assert_eq!(paused, StopReason::PauseTurn);
+/// assert!(paused.is_continuable());
+/// let refused: StopReason = serde_json::from_str("\"refusal\"").unwrap();
+/// assert_eq!(refused, StopReason::Refusal);
+/// assert!(!refused.is_continuable());
+/// assert_eq!(serde_json::to_string(&refused).unwrap(), "\"refusal\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StopReason {
+    EndTurn,
+    MaxTokens,
+    StopSequence,
+    ToolUse,
+    /// A long-running server tool turn was paused
+    ///
+    /// Continue the turn by sending the response content back as-is in an
+    /// assistant message, without adding a new user message.
+    PauseTurn,
+    /// The model declined to respond
+    Refusal,
+    /// A stop reason not known to this SDK
+    Other(String),
+}
+
+impl StopReason {
+    /// Whether the turn is unfinished and should be continued by sending the
+    /// response content back unchanged
+    pub fn is_continuable(&self) -> bool {
+        matches!(self, StopReason::PauseTurn)
+    }
+
+    /// The wire representation of the stop reason
+    pub fn as_str(&self) -> &str {
+        match self {
+            StopReason::EndTurn => "end_turn",
+            StopReason::MaxTokens => "max_tokens",
+            StopReason::StopSequence => "stop_sequence",
+            StopReason::ToolUse => "tool_use",
+            StopReason::PauseTurn => "pause_turn",
+            StopReason::Refusal => "refusal",
+            StopReason::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for StopReason {
+    fn from(value: &str) -> Self {
+        match value {
+            "end_turn" => StopReason::EndTurn,
+            "max_tokens" => StopReason::MaxTokens,
+            "stop_sequence" => StopReason::StopSequence,
+            "tool_use" => StopReason::ToolUse,
+            "pause_turn" => StopReason::PauseTurn,
+            "refusal" => StopReason::Refusal,
+            other => StopReason::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for StopReason {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for StopReason {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(StopReason::from(value.as_str()))
+    }
+}
+
+/// Service tier used to process a request
+///
+/// Values not known to this SDK are preserved in [`ServiceTier::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ServiceTier {
+    Standard,
+    Priority,
+    Batch,
+    /// A service tier not known to this SDK
+    Other(String),
+}
+
+impl ServiceTier {
+    /// The wire representation of the service tier
+    pub fn as_str(&self) -> &str {
+        match self {
+            ServiceTier::Standard => "standard",
+            ServiceTier::Priority => "priority",
+            ServiceTier::Batch => "batch",
+            ServiceTier::Other(other) => other,
+        }
+    }
 }
 
-/// Reason for stopping message generation
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum StopReason {
-    EndTurn,
-    MaxTokens,
-    StopSequence,
-    ToolUse,
+impl From<&str> for ServiceTier {
+    fn from(value: &str) -> Self {
+        match value {
+            "standard" => ServiceTier::Standard,
+            "priority" => ServiceTier::Priority,
+            "batch" => ServiceTier::Batch,
+            other => ServiceTier::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for ServiceTier {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ServiceTier {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(ServiceTier::from(value.as_str()))
+    }
 }
 
 /// Token usage statistics
-#[derive(Debug, Deserialize)]
+///
+/// Usage fields not known to this SDK are kept in `extra`.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::Usage;
+///
+/// let mut total = Usage::default();
+/// for json in [
+///     r#"{"input_tokens": 10, "output_tokens": 5,
+///         "server_tool_use": {"web_search_requests": 2}}"#,
+///     r#"{"input_tokens": 20, "output_tokens": 7, "cache_read_input_tokens": 3}"#,
+/// ] {
+///     total += serde_json::from_str::<Usage>(json).unwrap();
+/// }
+///
+/// assert_eq!(total.input_tokens, 30);
+/// assert_eq!(total.output_tokens, 12);
+/// assert_eq!(total.server_tool_use.as_ref().unwrap().web_search_requests, Some(2));
+/// assert_eq!(total.extra["cache_read_input_tokens"], 3);
+///
+/// // Totals saturate rather than overflow
+/// total += Usage {
+///     input_tokens: u32::MAX,
+///     ..Default::default()
+/// };
+/// assert_eq!(total.input_tokens, u32::MAX);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 pub struct Usage {
     /// Input tokens used
     pub input_tokens: u32,
     /// Output tokens used
     pub output_tokens: u32,
+    /// Service tier the request was processed with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+    /// Server tool requests made while handling the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_tool_use: Option<ServerToolUsage>,
+    /// Additional usage fields
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Server tool requests counted in [`Usage`]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ServerToolUsage {
+    /// Number of web search requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_search_requests: Option<u32>,
+}
+
+/// Total the usage of several requests
+///
+/// Missing counts are treated as zero. Unknown integer fields in `extra` are
+/// summed as well, other unknown fields keep their latest value. Sums
+/// saturate at the largest count instead of overflowing.
+impl std::ops::AddAssign for Usage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        if other.service_tier.is_some() {
+            self.service_tier = other.service_tier;
+        }
+        if let Some(other) = other.server_tool_use {
+            *self.server_tool_use.get_or_insert_with(Default::default) += other;
+        }
+        for (key, value) in other.extra {
+            match (
+                self.extra.get(&key).and_then(|v| v.as_u64()),
+                value.as_u64(),
+            ) {
+                (Some(a), Some(b)) => {
+                    self.extra.insert(key, a.saturating_add(b).into());
+                }
+                _ => {
+                    self.extra.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+impl Usage {
+    /// Input tokens written to the prompt cache, from `extra`
+    pub fn cache_creation_input_tokens(&self) -> u32 {
+        self.extra_tokens("cache_creation_input_tokens")
+    }
+
+    /// Input tokens read from the prompt cache, from `extra`
+    pub fn cache_read_input_tokens(&self) -> u32 {
+        self.extra_tokens("cache_read_input_tokens")
+    }
+
+    /// Update the usage of a streamed response with the totals of a `message_delta` event
+    ///
+    /// Fields the event does not report keep their value; an input token
+    /// count of 0 is treated as not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{StreamUsage, Usage};
+    ///
+    /// let mut usage: Usage = serde_json::from_str(
+    ///     r#"{"input_tokens": 10, "output_tokens": 1, "cache_read_input_tokens": 0}"#,
+    /// )
+    /// .unwrap();
+    /// let delta: StreamUsage = serde_json::from_str(
+    ///     r#"{"input_tokens": 12, "output_tokens": 30, "cache_read_input_tokens": 5,
+    ///         "server_tool_use": {"web_search_requests": 1}}"#,
+    /// )
+    /// .unwrap();
+    /// usage.apply_delta(&delta);
+    ///
+    /// assert_eq!(usage.input_tokens, 12);
+    /// assert_eq!(usage.output_tokens, 30);
+    /// assert_eq!(usage.cache_read_input_tokens(), 5);
+    /// assert_eq!(usage.server_tool_use.unwrap().web_search_requests, Some(1));
+    /// ```
+    pub fn apply_delta(&mut self, delta: &StreamUsage) {
+        if delta.input_tokens > 0 {
+            self.input_tokens = delta.input_tokens;
+        }
+        self.output_tokens = delta.output_tokens;
+        if delta.server_tool_use.is_some() {
+            self.server_tool_use = delta.server_tool_use.clone();
+        }
+        for (key, value) in &delta.extra {
+            self.extra.insert(key.clone(), value.clone());
+        }
+    }
+
+    fn extra_tokens(&self, key: &str) -> u32 {
+        self.extra
+            .get(key)
+            .and_then(|value| value.as_u64())
+            .map_or(0, |tokens| u32::try_from(tokens).unwrap_or(u32::MAX))
+    }
+}
+
+impl std::ops::AddAssign for ServerToolUsage {
+    fn add_assign(&mut self, other: Self) {
+        if let Some(requests) = other.web_search_requests {
+            let total = self.web_search_requests.get_or_insert(0);
+            *total = total.saturating_add(requests);
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
+/// Usage reported by a `message_delta` event
+///
+/// Counts are totals for the request so far, not increments.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 pub struct StreamUsage {
     /// Input tokens used (may be missing in some events)
     #[serde(default)]
     pub input_tokens: u32,
     /// Output tokens used
     pub output_tokens: u32,
+    /// Server tool requests made so far
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_tool_use: Option<ServerToolUsage>,
+    /// Additional usage fields, such as the cache token counts
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Message {
+    /// Create a new message with text or content blocks
+    pub fn new(role: Role, content: impl Into<MessageContent>) -> Self {
+        Self {
+            role,
+            content: content.into(),
+        }
+    }
+
     /// Create a new message with simple text content
     pub fn new_text(role: Role, text: impl Into<String>) -> Self {
         Self {
@@ -315,44 +2988,925 @@ impl Message {
             content: MessageContent::Blocks { content: blocks },
         }
     }
+
+    /// Create an assistant message prefilling the start of the reply
+    ///
+    /// Trailing whitespace, which the API rejects, is trimmed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::Message;
+    ///
+    /// let prefill = Message::assistant_prefill("{\n");
+    /// assert_eq!(prefill.text(), Some("{"));
+    /// assert!(Message::try_assistant_prefill("{\n").is_err());
+    /// ```
+    pub fn assistant_prefill(text: impl Into<String>) -> Self {
+        let mut text = text.into();
+        text.truncate(text.trim_end().len());
+        Self::new_text(Role::Assistant, text)
+    }
+
+    /// Create an assistant prefill, rejecting text with trailing whitespace
+    pub fn try_assistant_prefill(text: impl Into<String>) -> Result<Self, ValidationError> {
+        let text = text.into();
+        if text.ends_with(char::is_whitespace) {
+            return Err(ValidationError::PrefillTrailingWhitespace);
+        }
+        Ok(Self::new_text(Role::Assistant, text))
+    }
+
+    /// Text of a message consisting only of text
+    ///
+    /// Returns `None` if the message contains anything but a single piece of text.
+    pub fn text(&self) -> Option<&str> {
+        match &self.content {
+            MessageContent::Text { content } => Some(content),
+            MessageContent::Blocks { content } => match content.as_slice() {
+                [ContentBlock::Text { text, .. }] => Some(text),
+                _ => None,
+            },
+        }
+    }
+
+    /// Content blocks of the message
+    ///
+    /// Simple text content is viewed as a single text block.
+    pub fn blocks(&self) -> Cow<'_, [ContentBlock]> {
+        match &self.content {
+            MessageContent::Text { content } => Cow::Owned(vec![ContentBlock::text(content)]),
+            MessageContent::Blocks { content } => Cow::Borrowed(content),
+        }
+    }
+
+    /// Append a content block to the message
+    ///
+    /// Simple text content is upgraded to blocks, keeping the text as the first block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{ContentBlock, Message, Role};
+    ///
+    /// let mut message = Message::new_text(Role::User, "What is in this image?");
+    /// message.push_block(ContentBlock::image_url("https://example.com/cat.png"));
+    ///
+    /// let blocks = message.blocks();
+    /// assert_eq!(blocks.len(), 2);
+    /// assert_eq!(blocks[0], ContentBlock::text("What is in this image?"));
+    /// assert_eq!(message.text(), None);
+    /// ```
+    pub fn push_block(&mut self, block: ContentBlock) {
+        if let MessageContent::Text { content } = &mut self.content {
+            let text = std::mem::take(content);
+            self.content = MessageContent::Blocks {
+                content: vec![ContentBlock::text(text)],
+            };
+        }
+        if let MessageContent::Blocks { content } = &mut self.content {
+            content.push(block);
+        }
+    }
+}
+
+impl MessageContent {
+    /// Convert the content into blocks
+    ///
+    /// Simple text content becomes a single text block.
+    pub fn into_blocks(self) -> Vec<ContentBlock> {
+        match self {
+            MessageContent::Text { content } => vec![ContentBlock::text(content)],
+            MessageContent::Blocks { content } => content,
+        }
+    }
+}
+
+impl IntoIterator for MessageContent {
+    type Item = ContentBlock;
+    type IntoIter = std::vec::IntoIter<ContentBlock>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_blocks().into_iter()
+    }
+}
+
+impl IntoIterator for Message {
+    type Item = ContentBlock;
+    type IntoIter = std::vec::IntoIter<ContentBlock>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.content.into_iter()
+    }
+}
+
+/// Check that every tool result answers a tool use of the preceding assistant message
+pub fn validate_tool_results(messages: &[Message]) -> Result<(), ValidationError> {
+    for (index, message) in messages.iter().enumerate() {
+        let MessageContent::Blocks { content } = &message.content else {
+            continue;
+        };
+        let previous = index
+            .checked_sub(1)
+            .map(|i| &messages[i])
+            .filter(|m| m.role == Role::Assistant);
+        for block in content {
+            let ContentBlock::ToolResult { tool_use_id, .. } = block else {
+                continue;
+            };
+            let answered = previous.is_some_and(|m| {
+                m.blocks()
+                    .iter()
+                    .filter_map(ToolUseRef::from_block)
+                    .any(|tool_use| tool_use.id == tool_use_id)
+            });
+            if !answered {
+                return Err(ValidationError::UnmatchedToolResult {
+                    index,
+                    tool_use_id: tool_use_id.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Maximum number of images in a request
+pub const MAX_IMAGES_PER_REQUEST: usize = 100;
+
+/// Maximum number of pages of a PDF document
+pub const MAX_PDF_PAGES: usize = 100;
+
+/// Maximum size of a request body, in bytes
+pub const MAX_REQUEST_BYTES: usize = 32 * 1024 * 1024;
+
+/// Check the images and documents of a conversation against the API limits
+///
+/// Catches more than [`MAX_IMAGES_PER_REQUEST`] images, base64 images over
+/// [`MAX_IMAGE_SIZE`], base64 data containing whitespace, and PDFs with
+/// more than [`MAX_PDF_PAGES`] pages when the page count is in the header of
+/// the file, as in linearized PDFs. Only the first [`PDF_HEADER_BYTES`] of a
+/// PDF are decoded, so documents are never decoded in full. Blocks nested in tool results are
+/// reported with the index of the enclosing tool result block.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{
+///     ContentBlock, DocumentSource, ImageSource, Message, Role, ValidationError, validate_payload,
+/// };
+/// use base64::Engine;
+/// use base64::engine::general_purpose::STANDARD;
+///
+/// let image = ContentBlock::Image {
+///     source: ImageSource::base64("image/png", "iVBORw0K\nGgo="),
+/// };
+/// let messages = vec![Message::new_blocks(
+///     Role::User,
+///     vec![ContentBlock::text("What is this?"), image],
+/// )];
+/// assert!(matches!(
+///     validate_payload(&messages),
+///     Err(ValidationError::Base64Whitespace { index: 0, block: 1 })
+/// ));
+///
+/// let pdf = "%PDF-1.7\n1 0 obj\n<< /Linearized 1 /L 80000 /N 101 /T 79000 >>\nendobj\n";
+/// let pdf = format!("{}{}", pdf, "2 0 obj << /Type /Page >> endobj\n".repeat(101));
+/// let document = ContentBlock::document(DocumentSource::pdf(STANDARD.encode(pdf)));
+/// let messages = vec![Message::new_blocks(Role::User, vec![document])];
+/// assert!(matches!(
+///     validate_payload(&messages),
+///     Err(ValidationError::TooManyPdfPages { pages: 101, .. })
+/// ));
+/// ```
+pub fn validate_payload(messages: &[Message]) -> Result<(), ValidationError> {
+    let mut images = 0;
+    for (index, message) in messages.iter().enumerate() {
+        let MessageContent::Blocks { content } = &message.content else {
+            continue;
+        };
+        for (block, content) in content.iter().enumerate() {
+            let nested = match content {
+                ContentBlock::ToolResult {
+                    content: ToolResultContent::Blocks(blocks),
+                    ..
+                } => blocks.as_slice(),
+                other => std::slice::from_ref(other),
+            };
+            for content in nested {
+                match content {
+                    ContentBlock::Image { source } => {
+                        images += 1;
+                        if let ImageSource::Base64 { data, .. } = source {
+                            check_base64(data, index, block)?;
+                            if data.len() > MAX_IMAGE_SIZE {
+                                return Err(ValidationError::ImageTooLarge {
+                                    index,
+                                    block,
+                                    size: data.len(),
+                                    limit: MAX_IMAGE_SIZE,
+                                });
+                            }
+                        }
+                    }
+                    ContentBlock::Document {
+                        source: DocumentSource::Base64 { media_type, data },
+                        ..
+                    } => {
+                        check_base64(data, index, block)?;
+                        if media_type != "application/pdf" {
+                            continue;
+                        }
+                        let pages = pdf_header(data).and_then(|pdf| pdf_page_count(&pdf));
+                        if let Some(pages) = pages.filter(|&pages| pages > MAX_PDF_PAGES) {
+                            return Err(ValidationError::TooManyPdfPages {
+                                index,
+                                block,
+                                pages,
+                                limit: MAX_PDF_PAGES,
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    if images > MAX_IMAGES_PER_REQUEST {
+        return Err(ValidationError::TooManyImages {
+            count: images,
+            limit: MAX_IMAGES_PER_REQUEST,
+        });
+    }
+    Ok(())
+}
+
+fn check_base64(data: &str, index: usize, block: usize) -> Result<(), ValidationError> {
+    if data.bytes().any(|b| b.is_ascii_whitespace()) {
+        return Err(ValidationError::Base64Whitespace { index, block });
+    }
+    Ok(())
+}
+
+/// Number of bytes at the start of a PDF searched for its page count
+///
+/// The PDF specification requires the linearization dictionary, which holds
+/// the page count, to be within the first 1024 bytes of the file.
+pub const PDF_HEADER_BYTES: usize = 1024;
+
+/// The first [`PDF_HEADER_BYTES`] of the base64-encoded PDF `data`, decoded
+fn pdf_header(data: &str) -> Option<Vec<u8>> {
+    // Four base64 characters encode three bytes
+    let chars = PDF_HEADER_BYTES.div_ceil(3) * 4;
+    let prefix = data.get(..chars.min(data.len()))?;
+    STANDARD.decode(prefix).ok()
+}
+
+/// The page count of a linearized PDF, read from the start of the file
+///
+/// Returns `None` for PDFs that are not linearized, whose page count is only
+/// known after reading the whole file.
+fn pdf_page_count(header: &[u8]) -> Option<usize> {
+    if !header.starts_with(b"%PDF-") {
+        return None;
+    }
+    let start = header.windows(11).position(|w| w == b"/Linearized")?;
+    let mut rest = &header[start..];
+    if let Some(end) = rest.windows(2).position(|w| w == b">>") {
+        rest = &rest[..end];
+    }
+    while let Some(key) = rest.windows(2).position(|w| w == b"/N") {
+        rest = &rest[key + 2..];
+        // `/N` followed by anything but whitespace is a longer name
+        let skip = rest.iter().take_while(|b| b.is_ascii_whitespace()).count();
+        if skip == 0 {
+            continue;
+        }
+        let digits = rest[skip..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        return std::str::from_utf8(&rest[skip..skip + digits])
+            .ok()?
+            .parse()
+            .ok();
+    }
+    None
+}
+
+/// Check a request body of `size` bytes against [`MAX_REQUEST_BYTES`]
+fn check_request_size(size: usize) -> Result<(), ValidationError> {
+    if size > MAX_REQUEST_BYTES {
+        return Err(ValidationError::RequestTooLarge {
+            size,
+            limit: MAX_REQUEST_BYTES,
+        });
+    }
+    Ok(())
+}
+
+/// Size of the JSON serialization of `value`, without allocating it
+fn serialized_size(value: &impl Serialize) -> usize {
+    struct Counter(usize);
+
+    impl std::io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    serde_json::to_writer(&mut counter, value).map_or(0, |()| counter.0)
+}
+
+/// Check that no two adjacent messages have the same role
+///
+/// The API rejects such conversations; [`normalize_messages`] merges them instead.
+pub fn validate_roles(messages: &[Message]) -> Result<(), RoleError> {
+    for (index, pair) in messages.windows(2).enumerate() {
+        if pair[0].role == pair[1].role {
+            return Err(RoleError::ConsecutiveRole {
+                index: index + 1,
+                role: pair[1].role.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Merge consecutive messages with the same role
+///
+/// The content blocks of merged messages are concatenated in order. A
+/// message with tool results is not merged into a message with other
+/// content, as the API requires tool results to come first; see
+/// [`normalize_messages_with_report`] to find out what was merged.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{
+///     ContentBlock, Message, Role, normalize_messages, validate_roles,
+/// };
+///
+/// let messages = vec![
+///     Message::new_blocks(Role::User, vec![ContentBlock::tool_result("toolu_01", "18°C")]),
+///     Message::new_text(Role::User, "And tomorrow?"),
+/// ];
+/// assert!(validate_roles(&messages).is_err());
+///
+/// let messages = normalize_messages(messages);
+/// assert_eq!(messages.len(), 1);
+/// assert_eq!(messages[0].blocks().len(), 2);
+/// assert!(validate_roles(&messages).is_ok());
+/// ```
+pub fn normalize_messages(messages: Vec<Message>) -> Vec<Message> {
+    normalize_messages_with_report(messages).0
+}
+
+/// Consecutive messages merged into one by [`normalize_messages_with_report`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleMerge {
+    /// Role of the merged messages
+    pub role: Role,
+    /// Indices of the merged messages in the original history
+    pub messages: std::ops::Range<usize>,
+}
+
+/// Merge consecutive messages with the same role and report each merge
+///
+/// Like [`normalize_messages`], with a [`RoleMerge`] for each message of
+/// the result that was merged from several messages.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{
+///     ContentBlock, Message, Role, RoleMerge, normalize_messages_with_report,
+/// };
+///
+/// let messages = vec![
+///     Message::new_text(Role::User, "Hello"),
+///     Message::new_text(Role::User, "Are you there?"),
+///     Message::new_text(Role::Assistant, "Yes"),
+///     Message::new_text(Role::User, "What is the weather?"),
+///     Message::new_blocks(Role::User, vec![ContentBlock::tool_result("toolu_01", "18°C")]),
+/// ];
+/// let (messages, merges) = normalize_messages_with_report(messages);
+/// assert_eq!(merges, [RoleMerge { role: Role::User, messages: 0..2 }]);
+/// // The tool result would not come first in a merged message
+/// assert_eq!(messages.len(), 4);
+/// ```
+pub fn normalize_messages_with_report(messages: Vec<Message>) -> (Vec<Message>, Vec<RoleMerge>) {
+    let is_result = |block: &ContentBlock| matches!(block, ContentBlock::ToolResult { .. });
+    let mut normalized: Vec<Message> = Vec::with_capacity(messages.len());
+    let mut merges: Vec<RoleMerge> = Vec::new();
+    let mut start = 0;
+    for (index, message) in messages.into_iter().enumerate() {
+        let last = normalized.last_mut().filter(|last| {
+            last.role == message.role
+                && (last.blocks().iter().all(is_result) || !message.blocks().iter().any(is_result))
+        });
+        let Some(last) = last else {
+            start = index;
+            normalized.push(message);
+            continue;
+        };
+        let role = last.role.clone();
+        for block in message {
+            last.push_block(block);
+        }
+        match merges.last_mut() {
+            Some(merge) if merge.messages.start == start => merge.messages.end = index + 1,
+            _ => merges.push(RoleMerge {
+                role,
+                messages: start..index + 1,
+            }),
+        }
+    }
+    (normalized, merges)
+}
+
+/// Convert a response into an assistant message for the conversation history
+///
+/// All content blocks are carried over verbatim, including server tool and
+/// MCP tool blocks which the API requires to be replayed unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{CreateMessageResponse, Message};
+///
+/// let response: CreateMessageResponse = serde_json::from_str(r#"{
+///     "id": "msg_01",
+///     "type": "message",
+///     "role": "assistant",
+///     "model": "claude-sonnet-4-20250514",
+///     "content": [
+///         {"type": "mcp_tool_use", "id": "mcptoolu_01", "name": "echo",
+///          "server_name": "example-mcp", "input": {"text": "hi"}},
+///         {"type": "mcp_tool_result", "tool_use_id": "mcptoolu_01", "is_error": false,
+///          "content": [{"type": "text", "text": "hi"}]},
+///         {"type": "text", "text": "The tool echoed: hi"}
+///     ],
+///     "stop_reason": "end_turn",
+///     "stop_sequence": null,
+///     "usage": {"input_tokens": 10, "output_tokens": 20}
+/// }"#).unwrap();
+///
+/// let message = Message::from(response);
+/// let json = serde_json::to_value(&message).unwrap();
+/// assert_eq!(
+///     json["content"][1],
+///     serde_json::json!({"type": "mcp_tool_result", "tool_use_id": "mcptoolu_01",
+///         "content": [{"type": "text", "text": "hi"}]})
+/// );
+/// ```
+impl From<CreateMessageResponse> for Message {
+    fn from(response: CreateMessageResponse) -> Self {
+        Message::new_blocks(Role::Assistant, response.content)
+    }
 }
 
 // Helper methods for content blocks
 impl ContentBlock {
     /// Create a new text block
     pub fn text(text: impl Into<String>) -> Self {
-        Self::Text { text: text.into() }
+        Self::Text {
+            text: text.into(),
+            cache_control: None,
+            citations: None,
+        }
     }
 
-    /// Create a new image block
+    /// Create a new base64 image block
+    ///
+    /// `type_` is ignored, the source is always of type "base64".
+    #[deprecated(note = "use `ContentBlock::image_source(ImageSource::base64(..))` instead")]
     pub fn image(
-        type_: impl Into<String>,
+        _type_: impl Into<String>,
         media_type: impl Into<String>,
         data: impl Into<String>,
     ) -> Self {
-        Self::Image {
-            source: ImageSource {
-                type_: type_.into(),
-                media_type: media_type.into(),
-                data: data.into(),
-            },
+        let media_type: String = media_type.into();
+        Self::image_source(ImageSource::base64(media_type, data))
+    }
+
+    /// Create a new tool use block with a freshly generated id
+    ///
+    /// Useful for building synthetic conversation histories. Returns the block
+    /// together with its id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     ContentBlock, Message, Role, validate_tool_results,
+    /// };
+    ///
+    /// let (tool_use, id) = ContentBlock::tool_use("get_weather", serde_json::json!({"city": "Paris"}));
+    /// assert!(id.starts_with("toolu_"));
+    ///
+    /// let result = ContentBlock::tool_result_for(&tool_use, "18°C").unwrap();
+    /// let messages = vec![
+    ///     Message::new_text(Role::User, "Weather in Paris?"),
+    ///     Message::new_blocks(Role::Assistant, vec![tool_use]),
+    ///     Message::new_blocks(Role::User, vec![result]),
+    /// ];
+    /// assert!(validate_tool_results(&messages).is_ok());
+    /// ```
+    pub fn tool_use(name: impl Into<String>, input: serde_json::Value) -> (Self, String) {
+        let id = generate_tool_use_id();
+        let block = Self::ToolUse {
+            id: id.clone(),
+            name: name.into(),
+            input,
+        };
+        (block, id)
+    }
+
+    /// Create a tool result block answering the given tool use block
+    pub fn tool_result_for(
+        tool_use: &ContentBlock,
+        content: impl Into<ToolResultContent>,
+    ) -> Result<Self, ToolInputError> {
+        let tool_use = ToolUseRef::from_block(tool_use).ok_or(ToolInputError::NotToolUse)?;
+        Ok(tool_use.result(content))
+    }
+
+    /// Create a new tool result block answering the given tool use
+    pub fn tool_result(
+        tool_use_id: impl Into<String>,
+        content: impl Into<ToolResultContent>,
+    ) -> Self {
+        Self::ToolResult {
+            tool_use_id: tool_use_id.into(),
+            is_error: false,
+            content: content.into(),
+        }
+    }
+
+    /// Create a new tool result block reporting that the given tool use failed
+    pub fn tool_error(
+        tool_use_id: impl Into<String>,
+        content: impl Into<ToolResultContent>,
+    ) -> Self {
+        Self::ToolResult {
+            tool_use_id: tool_use_id.into(),
+            is_error: true,
+            content: content.into(),
+        }
+    }
+
+    /// Create a tool result block from the result of a tool
+    ///
+    /// A success is serialized as compact JSON, or used as it is if it
+    /// serializes to a string. A failure becomes an `is_error` result with
+    /// the error message, as does a success that fails to serialize. Use
+    /// [`tool_result_from_with`](Self::tool_result_from_with) for pretty JSON
+    /// or to truncate long results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{ContentBlock, ToolResultContent};
+    ///
+    /// let ok: Result<_, String> = Ok(serde_json::json!({"temperature": 18}));
+    /// assert_eq!(
+    ///     ContentBlock::tool_result_from("toolu_01", ok),
+    ///     ContentBlock::tool_result("toolu_01", r#"{"temperature":18}"#)
+    /// );
+    ///
+    /// let failed: Result<(), _> = Err("City not found");
+    /// assert_eq!(
+    ///     ContentBlock::tool_result_from("toolu_01", failed),
+    ///     ContentBlock::tool_error("toolu_01", "City not found")
+    /// );
+    /// ```
+    pub fn tool_result_from<T, E>(tool_use_id: impl Into<String>, result: Result<T, E>) -> Self
+    where
+        T: Serialize,
+        E: std::fmt::Display,
+    {
+        Self::tool_result_from_with(tool_use_id, result, &ToolResultOptions::default())
+    }
+
+    /// Create a tool result block from the result of a tool, rendered with `options`
+    ///
+    /// Results longer than [`max_len`](ToolResultOptions::max_len) are cut
+    /// at a character boundary and end with a line saying how many bytes
+    /// were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{ContentBlock, ToolResultContent};
+    /// use anthropic_ai_sdk::types::tool::ToolResultOptions;
+    ///
+    /// let options = ToolResultOptions::new().pretty(true).max_len(12);
+    /// let ok: Result<_, String> = Ok(serde_json::json!({"temperature": 18}));
+    /// let ContentBlock::ToolResult { content, is_error, .. } =
+    ///     ContentBlock::tool_result_from_with("toolu_01", ok, &options)
+    /// else {
+    ///     unreachable!()
+    /// };
+    /// assert!(!is_error);
+    /// assert_eq!(
+    ///     content,
+    ///     ToolResultContent::Text("{\n  \"tempera\n[truncated 11 of 23 bytes]".to_string())
+    /// );
+    /// ```
+    pub fn tool_result_from_with<T, E>(
+        tool_use_id: impl Into<String>,
+        result: Result<T, E>,
+        options: &ToolResultOptions,
+    ) -> Self
+    where
+        T: Serialize,
+        E: std::fmt::Display,
+    {
+        match result
+            .map_err(|e| e.to_string())
+            .and_then(|output| options.render_output(&output))
+        {
+            Ok(text) => Self::tool_result(tool_use_id, options.truncate(text)),
+            Err(error) => Self::tool_error(tool_use_id, options.truncate(error)),
+        }
+    }
+
+    /// Create a new image block from an image source
+    pub fn image_source(source: ImageSource) -> Self {
+        Self::Image { source }
+    }
+
+    /// Create a new image block referencing a publicly accessible URL
+    pub fn image_url(url: impl Into<String>) -> Self {
+        Self::image_source(ImageSource::url(url))
+    }
+
+    /// Create a new image block referencing an uploaded file
+    pub fn image_file(file_id: impl Into<String>) -> Self {
+        Self::image_source(ImageSource::file(file_id))
+    }
+
+    /// Create a new document block from a document source
+    pub fn document(source: DocumentSource) -> Self {
+        Self::Document {
+            source,
+            title: None,
+            context: None,
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    /// Create a new document block referencing an uploaded file
+    pub fn document_file(file_id: impl Into<String>) -> Self {
+        Self::document(DocumentSource::file(file_id))
+    }
+
+    /// Beta flag required to reference files uploaded through the Files API
+    pub const FILES_API_BETA: Beta = Beta::FilesApi;
+
+    /// Whether this block references a file uploaded through the Files API
+    pub fn has_file_source(&self) -> bool {
+        matches!(
+            self,
+            Self::Image {
+                source: ImageSource::File { .. }
+            } | Self::Document {
+                source: DocumentSource::File { .. },
+                ..
+            }
+        )
+    }
+
+    /// Beta flag required to send search result blocks
+    pub const SEARCH_RESULTS_BETA: Beta = Beta::SearchResults;
+
+    /// Create a new search result block with citations enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{CacheControl, ContentBlock};
+    ///
+    /// let block = ContentBlock::search_result(
+    ///     "https://docs.example.com/install",
+    ///     "Installation guide",
+    ///     ["Run the installer.", "Restart your machine."],
+    /// )
+    /// .with_cache_control(CacheControl::ephemeral());
+    /// ```
+    pub fn search_result(
+        source: impl Into<String>,
+        title: impl Into<String>,
+        texts: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self::SearchResult {
+            source: source.into(),
+            title: title.into(),
+            content: texts.into_iter().map(Self::text).collect(),
+            citations: Some(CitationsConfig { enabled: true }),
+            cache_control: None,
+        }
+    }
+
+    /// Cache breakpoint set on this block, if any
+    pub fn cache_control(&self) -> Option<&CacheControl> {
+        match self {
+            Self::Text { cache_control, .. }
+            | Self::SearchResult { cache_control, .. }
+            | Self::Document { cache_control, .. } => cache_control.as_ref(),
+            _ => None,
         }
     }
+
+    /// Set a cache breakpoint on this block
+    ///
+    /// Blocks that do not support cache control are returned unchanged.
+    pub fn with_cache_control(mut self, cache_control: CacheControl) -> Self {
+        match &mut self {
+            Self::Text {
+                cache_control: existing,
+                ..
+            }
+            | Self::SearchResult {
+                cache_control: existing,
+                ..
+            }
+            | Self::Document {
+                cache_control: existing,
+                ..
+            } => *existing = Some(cache_control),
+            _ => {}
+        }
+        self
+    }
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 pub struct CountMessageTokensParams {
     pub model: String,
     pub messages: Vec<Message>,
+    /// System prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemPrompt>,
+    /// Tools that the model may use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolUnion>>,
+    /// How the model should use tools
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    /// Extended thinking configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<Thinking>,
+    /// Beta feature flags to send in addition to the detected ones
+    #[serde(skip)]
+    pub betas: Vec<Beta>,
+    /// Whether to send only the explicit `betas`, without detecting required ones
+    #[serde(skip)]
+    pub suppress_auto_betas: bool,
+}
+
+impl CountMessageTokensParams {
+    /// Create new parameters with only required fields
+    pub fn new(model: impl Into<Model>, messages: Vec<Message>) -> Self {
+        Self {
+            model: model.into().into(),
+            messages,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_system(mut self, system: impl Into<SystemPrompt>) -> Self {
+        self.system = Some(system.into());
+        self
+    }
+
+    pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
+        self.tools = Some(tools.into_iter().map(ToolUnion::Custom).collect());
+        self
+    }
+
+    /// Append a single tool (custom or server tool) to the request
+    pub fn with_tool(mut self, tool: impl Into<ToolUnion>) -> Self {
+        self.tools.get_or_insert_with(Vec::new).push(tool.into());
+        self
+    }
+
+    pub fn with_tool_choice(mut self, tool_choice: ToolChoice) -> Self {
+        self.tool_choice = Some(tool_choice);
+        self
+    }
+
+    /// Enable extended thinking with the given token budget
+    pub fn with_thinking(mut self, budget_tokens: u32) -> Self {
+        self.thinking = Some(Thinking::Enabled { budget_tokens });
+        self
+    }
+
+    /// Enable a beta feature for the request
+    pub fn with_beta(mut self, beta: impl Into<Beta>) -> Self {
+        self.betas.push(beta.into());
+        self
+    }
+
+    /// Enable several beta features for the request
+    pub fn with_betas(mut self, betas: impl IntoIterator<Item = impl Into<Beta>>) -> Self {
+        self.betas.extend(betas.into_iter().map(Into::into));
+        self
+    }
+
+    /// Send only the explicitly enabled betas
+    pub fn with_suppress_auto_betas(mut self, suppress: bool) -> Self {
+        self.suppress_auto_betas = suppress;
+        self
+    }
+
+    /// Beta feature flags required by the features used in these parameters
+    pub fn required_betas(&self) -> Vec<Beta> {
+        let mut betas = tool_betas(self.tools.as_deref());
+        betas.extend(message_betas(self.system.as_ref(), &self.messages));
+        betas
+    }
+
+    /// Beta feature flags sent in the `anthropic-beta` header
+    ///
+    /// These are the explicitly enabled betas followed by the
+    /// [`required_betas`](Self::required_betas), without duplicates.
+    pub fn betas(&self) -> Vec<Beta> {
+        resolve_betas(&self.betas, self.suppress_auto_betas, || {
+            self.required_betas()
+        })
+    }
+}
+
+/// Count exactly what a create-message request would send
+///
+/// The model, messages, system prompt, tools, tool choice and thinking
+/// configuration are copied, so every field of the count request
+/// serializes exactly like the same field of the create request.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{
+///     CountMessageTokensParams, CreateMessageParams, Message, RequiredMessageParams, Role, Tool,
+///     ToolChoice,
+/// };
+///
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-3-7-sonnet-latest".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Hello, Claude")],
+///     max_tokens: 2048,
+/// })
+/// .with_system("You are a helpful assistant")
+/// .with_tools(vec![Tool {
+///     name: "get_weather".to_string(),
+///     description: Some("Get the weather in a city".to_string()),
+///     input_schema: serde_json::json!({"type": "object"}),
+///     cache_control: None,
+/// }])
+/// .with_tool_choice(ToolChoice::Auto)
+/// .with_thinking(1024);
+///
+/// let count = CountMessageTokensParams::from(&params);
+/// let create = serde_json::to_value(&params).unwrap();
+/// let count = serde_json::to_value(&count).unwrap();
+/// let fields = count.as_object().unwrap();
+/// assert_eq!(fields.len(), 6);
+/// for (field, value) in fields {
+///     assert_eq!(&create[field], value, "{field} differs");
+/// }
+/// ```
+impl From<&CreateMessageParams> for CountMessageTokensParams {
+    fn from(params: &CreateMessageParams) -> Self {
+        Self {
+            model: params.model.clone(),
+            messages: params.messages.clone(),
+            system: params.system.clone(),
+            tools: params.tools.clone(),
+            tool_choice: params.tool_choice.clone(),
+            thinking: params.thinking.clone(),
+            betas: params.betas.clone(),
+            suppress_auto_betas: params.suppress_auto_betas,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct CountMessageTokensResponse {
     pub input_tokens: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(tag = "type")]
+#[non_exhaustive]
 pub enum StreamEvent {
     #[serde(rename = "message_start")]
     MessageStart { message: MessageStartContent },
@@ -381,7 +3935,7 @@ pub enum StreamEvent {
     Error { error: StreamError },
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct MessageStartContent {
     pub id: String,
     #[serde(rename = "type")]
@@ -392,10 +3946,35 @@ pub struct MessageStartContent {
     pub stop_reason: Option<StopReason>,
     pub stop_sequence: Option<String>,
     pub usage: Usage,
+    /// Container used by server tools, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<Container>,
+    /// Context edits applied to the request, if context management was used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_management: Option<ContextManagementResponse>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(tag = "type")]
+/// Change to a content block in a stream
+///
+/// Deltas of types this version does not know are kept as
+/// [`Unknown`](Self::Unknown) and skipped by [`MessageAccumulator`], so new
+/// delta types do not end the stream.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::ContentBlockDelta;
+///
+/// let json = r#"{"type": "hologram_delta", "frames": [1, 2]}"#;
+/// let delta: ContentBlockDelta = serde_json::from_str(json).unwrap();
+/// assert!(matches!(&delta, ContentBlockDelta::Unknown { delta_type, .. } if delta_type == "hologram_delta"));
+/// assert_eq!(
+///     serde_json::to_value(&delta).unwrap(),
+///     serde_json::from_str::<serde_json::Value>(json).unwrap()
+/// );
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "type")]
 pub enum ContentBlockDelta {
     #[serde(rename = "text_delta")]
     TextDelta { text: String },
@@ -405,17 +3984,282 @@ pub enum ContentBlockDelta {
     ThinkingDelta { thinking: String },
     #[serde(rename = "signature_delta")]
     SignatureDelta { signature: String },
+    /// Citation appended to the text block
+    #[serde(rename = "citations_delta")]
+    CitationsDelta { citation: Citation },
+    /// Delta of a type this version does not know, kept as raw JSON
+    #[serde(skip)]
+    Unknown {
+        delta_type: String,
+        data: serde_json::Value,
+    },
+}
+
+impl ContentBlockDelta {
+    /// Types of the known deltas
+    const TYPES: &[&str] = &[
+        "text_delta",
+        "input_json_delta",
+        "thinking_delta",
+        "signature_delta",
+        "citations_delta",
+    ];
+}
+
+impl Serialize for ContentBlockDelta {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ContentBlockDelta::Unknown { data, .. } => data.serialize(serializer),
+            known => ContentBlockDelta::serialize(known, serializer),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
+impl<'de> Deserialize<'de> for ContentBlockDelta {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let data = serde_json::Value::deserialize(deserializer)?;
+        let delta_type = data
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| D::Error::missing_field("type"))?;
+        if ContentBlockDelta::TYPES.contains(&delta_type) {
+            return ContentBlockDelta::deserialize(&data).map_err(D::Error::custom);
+        }
+        Ok(ContentBlockDelta::Unknown {
+            delta_type: delta_type.to_string(),
+            data,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct MessageDeltaContent {
     pub stop_reason: Option<StopReason>,
     pub stop_sequence: Option<String>,
+    /// Container used by server tools, replacing the one of `message_start`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<Container>,
+    /// Context edits applied to the request, replacing those of `message_start`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_management: Option<ContextManagementResponse>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct StreamError {
     #[serde(rename = "type")]
     pub type_: String,
     pub message: String,
 }
+
+/// Accumulates streaming events into a complete message response
+///
+/// Feed every event received from `create_message_streaming` into
+/// [`MessageAccumulator::push`] and call [`MessageAccumulator::finish`] once the
+/// stream has ended.
+#[derive(Debug, Default)]
+pub struct MessageAccumulator {
+    response: Option<CreateMessageResponse>,
+    partial_json: std::collections::HashMap<usize, String>,
+}
+
+impl MessageAccumulator {
+    /// Create a new, empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a single streaming event
+    ///
+    /// Returns an error if the event is a stream error or arrives out of order.
+    ///
+    /// The container of `message_start` is kept unless `message_delta`
+    /// reports a different one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::StreamScenario;
+    /// use anthropic_ai_sdk::types::message::{MessageAccumulator, StopReason, StreamEvent};
+    ///
+    /// let mut accumulator = MessageAccumulator::new();
+    /// for event in StreamScenario::new().text("Done").finish(StopReason::EndTurn) {
+    ///     let event = match event {
+    ///         StreamEvent::MessageDelta { mut delta, usage } => {
+    ///             delta.container = Some(serde_json::from_str(
+    ///                 r#"{"id": "container_011", "expires_at": "2030-01-01T00:00:00Z"}"#,
+    ///             )?);
+    ///             StreamEvent::MessageDelta { delta, usage }
+    ///         }
+    ///         event => event,
+    ///     };
+    ///     accumulator.push(&event)?;
+    /// }
+    /// let response = accumulator.finish()?;
+    /// assert_eq!(response.container.unwrap().id, "container_011");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn push(&mut self, event: &StreamEvent) -> Result<(), MessageError> {
+        match event {
+            StreamEvent::MessageStart { message } => {
+                self.response = Some(CreateMessageResponse {
+                    content: message.content.clone(),
+                    id: message.id.clone(),
+                    model: message.model.clone(),
+                    role: message.role.clone(),
+                    stop_reason: None,
+                    stop_sequence: None,
+                    type_: message.type_.clone(),
+                    usage: message.usage.clone(),
+                    container: message.container.clone(),
+                    context_management: message.context_management.clone(),
+                });
+            }
+            StreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                let response = self.response_mut()?;
+                if *index != response.content.len() {
+                    return Err(MessageError::ApiError(format!(
+                        "Unexpected content block index {} in stream",
+                        index
+                    )));
+                }
+                response.content.push(content_block.clone());
+            }
+            StreamEvent::ContentBlockDelta { index, delta } => match delta {
+                ContentBlockDelta::TextDelta { text } => {
+                    if let ContentBlock::Text { text: existing, .. } = self.block_mut(*index)? {
+                        existing.push_str(text);
+                    }
+                }
+                ContentBlockDelta::InputJsonDelta { partial_json } => {
+                    self.partial_json
+                        .entry(*index)
+                        .or_default()
+                        .push_str(partial_json);
+                }
+                ContentBlockDelta::ThinkingDelta { thinking } => {
+                    if let ContentBlock::Thinking {
+                        thinking: existing, ..
+                    } = self.block_mut(*index)?
+                    {
+                        existing.push_str(thinking);
+                    }
+                }
+                ContentBlockDelta::SignatureDelta { signature } => {
+                    if let ContentBlock::Thinking {
+                        signature: existing,
+                        ..
+                    } = self.block_mut(*index)?
+                    {
+                        existing.push_str(signature);
+                    }
+                }
+                ContentBlockDelta::CitationsDelta { citation } => {
+                    if let ContentBlock::Text { citations, .. } = self.block_mut(*index)? {
+                        citations
+                            .get_or_insert_with(Vec::new)
+                            .push(citation.clone());
+                    }
+                }
+                ContentBlockDelta::Unknown { .. } => {}
+            },
+            StreamEvent::ContentBlockStop { index } => {
+                if let Some(json) = self.partial_json.remove(index) {
+                    let parsed: serde_json::Value = if json.is_empty() {
+                        serde_json::Value::Object(Default::default())
+                    } else {
+                        serde_json::from_str(&json).map_err(|e| {
+                            MessageError::ApiError(format!(
+                                "Failed to parse tool input: {}. Input: {}",
+                                e, json
+                            ))
+                        })?
+                    };
+                    match self.block_mut(*index)? {
+                        ContentBlock::ToolUse { input, .. }
+                        | ContentBlock::ServerToolUse { input, .. }
+                        | ContentBlock::McpToolUse { input, .. } => *input = parsed,
+                        _ => {}
+                    }
+                }
+            }
+            StreamEvent::MessageDelta { delta, usage } => {
+                let response = self.response_mut()?;
+                response.stop_reason = delta.stop_reason.clone();
+                response.stop_sequence = delta.stop_sequence.clone();
+                if let Some(container) = &delta.container {
+                    response.container = Some(container.clone());
+                }
+                if let Some(context_management) = &delta.context_management {
+                    response.context_management = Some(context_management.clone());
+                }
+                if let Some(usage) = usage {
+                    response.usage.apply_delta(usage);
+                }
+            }
+            StreamEvent::MessageStop | StreamEvent::Ping => {}
+            StreamEvent::Error { error } => {
+                return Err(MessageError::ApiError(format!(
+                    "{}: {}",
+                    error.type_, error.message
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The response accumulated so far, if the stream has started
+    pub fn response(&self) -> Option<&CreateMessageResponse> {
+        self.response.as_ref()
+    }
+
+    /// The custom stop sequence that ended the reply, once `message_delta` reported it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::testing::StreamScenario;
+    /// use anthropic_ai_sdk::types::message::{MessageAccumulator, StopReason, StreamEvent};
+    ///
+    /// let mut accumulator = MessageAccumulator::new();
+    /// for event in StreamScenario::new().text("<answer>42").finish(StopReason::StopSequence) {
+    ///     let event = match event {
+    ///         StreamEvent::MessageDelta { mut delta, usage } => {
+    ///             delta.stop_sequence = Some("</answer>".to_string());
+    ///             StreamEvent::MessageDelta { delta, usage }
+    ///         }
+    ///         event => event,
+    ///     };
+    ///     accumulator.push(&event).unwrap();
+    ///     if accumulator.stop_sequence().is_some() {
+    ///         break;
+    ///     }
+    /// }
+    /// assert_eq!(accumulator.stop_sequence(), Some("</answer>"));
+    /// ```
+    pub fn stop_sequence(&self) -> Option<&str> {
+        self.response.as_ref()?.stop_sequence.as_deref()
+    }
+
+    /// Consume the accumulator and return the complete response
+    pub fn finish(self) -> Result<CreateMessageResponse, MessageError> {
+        self.response
+            .ok_or_else(|| MessageError::ApiError("Stream ended before message_start".to_string()))
+    }
+
+    fn response_mut(&mut self) -> Result<&mut CreateMessageResponse, MessageError> {
+        self.response.as_mut().ok_or_else(|| {
+            MessageError::ApiError("Received stream event before message_start".to_string())
+        })
+    }
+
+    fn block_mut(&mut self, index: usize) -> Result<&mut ContentBlock, MessageError> {
+        self.response_mut()?.content.get_mut(index).ok_or_else(|| {
+            MessageError::ApiError(format!("Unknown content block index {} in stream", index))
+        })
+    }
+}
@@ -2,23 +2,94 @@
 //!
 //! This module contains the types and functions for the Anthropic Message Batches API.
 //!
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_trait::async_trait;
+use futures_util::{Stream, StreamExt, TryStreamExt};
+#[cfg(feature = "schemars")]
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use time::OffsetDateTime;
 use time::serde::rfc3339;
 
+use crate::error::{ApiErrorBody, ApiErrorResponse};
+use crate::types::beta::{Beta, merge_betas};
+#[cfg(feature = "schemars")]
+use crate::types::extract::{ExtractError, parse_extraction};
+use crate::types::message::{
+    CreateMessageParams, CreateMessageResponse, SystemPrompt, ValidationError,
+};
+use crate::types::pagination::{CursorParams, Page, RateLimitError};
+
 /// Error types for the Message Batches API
+///
+/// # Examples
+///
+/// Cancelling a batch that has already ended is reported as a conflict, which
+/// retry logic can treat as success:
+///
+/// ```
+/// use anthropic_ai_sdk::error::ApiErrorResponse;
+/// use anthropic_ai_sdk::types::message_batches::MessageBatchError;
+///
+/// let body = r#"{"type":"error","error":{"type":"invalid_request_error","message":"Batch has already ended"}}"#;
+/// let error = MessageBatchError::from(ApiErrorResponse::new(409, body));
+/// assert!(matches!(&error, MessageBatchError::Conflict(message) if message == "Batch has already ended"));
+/// ```
 #[derive(Debug, Error)]
 pub enum MessageBatchError {
     #[error("Batch size exceeds maximum limit of 100,000 requests")]
     BatchTooLarge,
     #[error("Batch total size exceeds 256MB")]
     BatchSizeExceeded,
+    #[error("Request {0} in the batch has no custom_id")]
+    MissingCustomId(usize),
+    #[error("Duplicate custom_id in the batch: {0}")]
+    DuplicateCustomId(String),
+    #[error("Invalid custom_id {0:?}, expected 1-64 letters, digits, hyphens or underscores")]
+    InvalidCustomId(String),
+    #[error("Invalid parameters for request {custom_id}: {source}")]
+    InvalidRequest {
+        custom_id: String,
+        #[source]
+        source: ValidationError,
+    },
     #[error("API request failed: {0}")]
     RequestFailed(String),
     #[error("API error: {0}")]
     ApiError(String),
+    /// The batch is not in a state that allows the operation, e.g. cancelling
+    /// a batch that has already ended or deleting one that is still processing
+    #[error("Batch state conflict: {0}")]
+    Conflict(String),
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// Delay requested by the API before retrying
+        retry_after: Option<Duration>,
+    },
+    /// Creating one of the batches of a split submission failed after the
+    /// batches before it were created
+    #[error("Failed to create batch {} of a split submission: {source}", submitted.len() + 1)]
+    PartialSubmission {
+        /// The batches that were created and are being processed
+        submitted: Vec<SubmittedBatch>,
+        #[source]
+        source: Box<MessageBatchError>,
+    },
+    /// The batch did not end before the polling deadline
+    #[error("Timed out waiting for the message batch to end")]
+    Timeout {
+        /// The last state of the batch observed while polling
+        last: Option<Box<MessageBatch>>,
+    },
+    /// The results to resume have no entry with the custom_id of the checkpoint
+    #[error("Batch results have no entry with the checkpoint's custom_id {0}")]
+    CheckpointNotFound(String),
 }
 
 impl From<String> for MessageBatchError {
@@ -27,6 +98,34 @@ impl From<String> for MessageBatchError {
     }
 }
 
+impl From<ApiErrorResponse> for MessageBatchError {
+    fn from(error: ApiErrorResponse) -> Self {
+        if error.is_conflict() {
+            MessageBatchError::Conflict(error.message.unwrap_or(error.body))
+        } else if error.is_rate_limited() {
+            MessageBatchError::RateLimited {
+                message: error.message.unwrap_or(error.body),
+                retry_after: error.retry_after,
+            }
+        } else {
+            MessageBatchError::ApiError(error.body)
+        }
+    }
+}
+
+impl RateLimitError for MessageBatchError {
+    fn is_rate_limited(&self) -> bool {
+        matches!(self, MessageBatchError::RateLimited { .. })
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            MessageBatchError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
 #[async_trait]
 pub trait MessageBatchClient {
     /// Create a new message batch
@@ -35,12 +134,26 @@ pub trait MessageBatchClient {
         params: &'a CreateMessageBatchParams,
     ) -> Result<MessageBatch, MessageBatchError>;
 
+    /// Create a new message batch that is canceled unless it is kept
+    async fn create_message_batch_guarded<'a>(
+        &'a self,
+        params: &'a CreateMessageBatchParams,
+    ) -> Result<BatchGuard<Self>, MessageBatchError>
+    where
+        Self: Clone + Sized + Send + Sync + 'static;
+
     /// List message batches
     async fn list_message_batches<'a>(
         &'a self,
         params: Option<&'a ListMessageBatchesParams>,
     ) -> Result<ListMessageBatchesResponse, MessageBatchError>;
 
+    /// List message batches one by one, following pagination to the end
+    fn list_message_batches_stream<'a>(
+        &'a self,
+        params: Option<&'a ListMessageBatchesParams>,
+    ) -> impl futures_util::Stream<Item = Result<MessageBatch, MessageBatchError>> + Send + 'a;
+
     /// Retrieve a message batch
     async fn retrieve_message_batch<'a>(
         &'a self,
@@ -52,12 +165,48 @@ pub trait MessageBatchClient {
         params: &'a RetrieveMessageBatchResultsParams,
     ) -> Result<RetrieveMessageBatchResultsResponse, MessageBatchError>;
 
+    /// Stream the results of a message batch, one entry per request
+    async fn stream_message_batch_results<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchResultsParams,
+    ) -> Result<
+        impl futures_util::Stream<Item = Result<BatchResultEntry, MessageBatchError>> + Send + 'a,
+        MessageBatchError,
+    >;
+
+    /// Stream the results of a message batch after `checkpoint`, each entry
+    /// with the checkpoint to resume after it
+    async fn stream_message_batch_results_from<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchResultsParams,
+        checkpoint: &'a ResultsCheckpoint,
+    ) -> Result<
+        impl futures_util::Stream<Item = Result<CheckpointedEntry, MessageBatchError>> + Send + 'a,
+        MessageBatchError,
+    >;
+
+    /// Poll a message batch until its processing has ended
+    async fn wait_for_message_batch<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchParams,
+        options: &'a PollOptions,
+    ) -> Result<MessageBatch, MessageBatchError>;
+
     /// Cancel a message batch
     async fn cancel_message_batch<'a>(
         &'a self,
         params: &'a CancelMessageBatchParams,
     ) -> Result<CancelResponse, MessageBatchError>;
 
+    /// Create a new batch with the requests of a batch that failed with a retryable error
+    async fn resubmit_errors<'a, S>(
+        &'a self,
+        message_batch_id: &'a str,
+        requests: &'a S,
+    ) -> Result<Resubmission, MessageBatchError>
+    where
+        S: RequestSource + ?Sized;
+
     /// Delete a message batch.
     ///
     /// Message batches can only be deleted once they’ve finished processing.
@@ -69,16 +218,55 @@ pub trait MessageBatchClient {
 }
 
 /// Processing status of a Message Batch
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+///
+/// Values not known to this SDK are preserved in [`ProcessingStatus::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ProcessingStatus {
     InProgress,
     Canceling,
     Ended,
+    /// A processing status not known to this SDK
+    Other(String),
+}
+
+impl ProcessingStatus {
+    /// The wire representation of the processing status
+    pub fn as_str(&self) -> &str {
+        match self {
+            ProcessingStatus::InProgress => "in_progress",
+            ProcessingStatus::Canceling => "canceling",
+            ProcessingStatus::Ended => "ended",
+            ProcessingStatus::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for ProcessingStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "in_progress" => ProcessingStatus::InProgress,
+            "canceling" => ProcessingStatus::Canceling,
+            "ended" => ProcessingStatus::Ended,
+            other => ProcessingStatus::Other(other.to_string()),
+        }
+    }
+}
+
+impl Serialize for ProcessingStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProcessingStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(ProcessingStatus::from(value.as_str()))
+    }
 }
 
 /// Request counts for different statuses
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, Hash)]
 pub struct RequestCounts {
     /// Number of requests currently processing
     pub processing: u32,
@@ -92,8 +280,11 @@ pub struct RequestCounts {
     pub expired: u32,
 }
 
-/// Response structure for Message Batch creation
-#[derive(Debug, Serialize, Deserialize)]
+/// Message Batch as returned by the Message Batches API
+///
+/// Fields not known to this SDK are ignored, and timestamps that are not set
+/// yet may be missing or null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct MessageBatch {
     /// Unique identifier for the batch
     pub id: String,
@@ -107,40 +298,43 @@ pub struct MessageBatch {
     #[serde(with = "rfc3339")]
     pub expires_at: OffsetDateTime,
     /// Time when the batch was archived (if applicable)
-    #[serde(with = "rfc3339::option")]
+    #[serde(default, with = "rfc3339::option")]
     pub archived_at: Option<OffsetDateTime>,
     /// Time when cancellation was initiated (if applicable)
-    #[serde(with = "rfc3339::option")]
+    #[serde(default, with = "rfc3339::option")]
     pub cancel_initiated_at: Option<OffsetDateTime>,
     /// Time when processing ended (if applicable)
-    #[serde(with = "rfc3339::option")]
+    #[serde(default, with = "rfc3339::option")]
     pub ended_at: Option<OffsetDateTime>,
     /// Current processing status
     pub processing_status: ProcessingStatus,
     /// Counts of requests in different states
     pub request_counts: RequestCounts,
     /// URL to download results (once processing ends)
+    #[serde(default)]
     pub results_url: Option<String>,
 }
 
 /// Parameters for creating a message batch
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct CreateMessageBatchParams {
     /// List of message creation requests
     pub requests: Vec<MessageRequest>,
 }
 
 /// Individual message request within a batch
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct MessageRequest {
-    /// Custom identifier for tracking this request
+    /// Custom identifier for tracking this request, required and unique within the batch
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_id: Option<String>,
     /// Request parameters
-    pub params: MessageRequestParams,
+    pub params: CreateMessageParams,
 }
 
-/// Parameters for an individual message request
+/// Simplified parameters for a text-only message request
+///
+/// Converts into [`CreateMessageParams`]; use those directly for the full feature set.
 #[derive(Debug, Serialize)]
 pub struct MessageRequestParams {
     /// Model to use for this message
@@ -149,7 +343,7 @@ pub struct MessageRequestParams {
     pub max_tokens: u32,
     /// System prompt for the message
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub system: Option<String>,
+    pub system: Option<SystemPrompt>,
     /// User message content
     pub messages: Vec<Message>,
 }
@@ -164,21 +358,97 @@ pub struct Message {
 }
 
 impl CreateMessageBatchParams {
+    /// Maximum number of requests in a batch
+    pub const MAX_REQUESTS: usize = 100_000;
+    /// Maximum size of the serialized batch in bytes (256MB)
+    pub const MAX_SIZE: usize = 256 * 1024 * 1024;
+    /// Maximum length of a custom_id
+    pub const MAX_CUSTOM_ID_LENGTH: usize = 64;
+
     /// Create a new CreateMessageBatchParams with the given requests
     pub fn new(requests: Vec<MessageRequest>) -> Self {
-        if requests.len() > 100_000 {
-            panic!("Batch size exceeds maximum limit of 100,000 requests");
-        }
         Self { requests }
     }
+
+    /// Validate the batch before it is uploaded
+    ///
+    /// Checks the batch limits, that every request has a unique and well-formed
+    /// custom_id, and the parameters of each request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     CreateMessageBatchParams, MessageBatchError, MessageRequest,
+    /// };
+    ///
+    /// let request = |custom_id: &str| {
+    ///     let params = CreateMessageParams::new(RequiredMessageParams {
+    ///         model: "claude-3-5-haiku-latest".to_string(),
+    ///         messages: vec![Message::new_text(Role::User, "Summarize this document")],
+    ///         max_tokens: 1024,
+    ///     });
+    ///     MessageRequest::new(params).with_custom_id(custom_id)
+    /// };
+    ///
+    /// let batch = CreateMessageBatchParams::new(vec![request("doc-1"), request("doc-2")]);
+    /// assert!(batch.validate().is_ok());
+    ///
+    /// let batch = CreateMessageBatchParams::new(vec![request("doc-1"), request("doc-1")]);
+    /// assert!(matches!(batch.validate(), Err(MessageBatchError::DuplicateCustomId(_))));
+    /// ```
+    pub fn validate(&self) -> Result<(), MessageBatchError> {
+        if self.requests.len() > Self::MAX_REQUESTS {
+            return Err(MessageBatchError::BatchTooLarge);
+        }
+        let mut custom_ids = HashSet::new();
+        for (index, request) in self.requests.iter().enumerate() {
+            let custom_id = request
+                .custom_id
+                .as_deref()
+                .ok_or(MessageBatchError::MissingCustomId(index))?;
+            let well_formed = (1..=Self::MAX_CUSTOM_ID_LENGTH).contains(&custom_id.len())
+                && custom_id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !well_formed {
+                return Err(MessageBatchError::InvalidCustomId(custom_id.to_string()));
+            }
+            if !custom_ids.insert(custom_id) {
+                return Err(MessageBatchError::DuplicateCustomId(custom_id.to_string()));
+            }
+            request
+                .params
+                .validate()
+                .map_err(|source| MessageBatchError::InvalidRequest {
+                    custom_id: custom_id.to_string(),
+                    source,
+                })?;
+        }
+        let size = serde_json::to_vec(self).map_or(0, |json| json.len());
+        if size > Self::MAX_SIZE {
+            return Err(MessageBatchError::BatchSizeExceeded);
+        }
+        Ok(())
+    }
+
+    /// Beta feature flags required by any request in the batch
+    pub fn betas(&self) -> Vec<Beta> {
+        self.requests.iter().fold(Vec::new(), |betas, request| {
+            merge_betas(&betas, request.params.betas())
+        })
+    }
 }
 
 impl MessageRequest {
     /// Create a new MessageRequest
-    pub fn new(params: MessageRequestParams) -> Self {
+    pub fn new(params: impl Into<CreateMessageParams>) -> Self {
         Self {
             custom_id: None,
-            params,
+            params: params.into(),
         }
     }
 
@@ -189,6 +459,269 @@ impl MessageRequest {
     }
 }
 
+/// Builds message batches from plain request parameters
+///
+/// Requests added without a custom_id get a stable one derived from their
+/// position, `req-<index>`. When the requests exceed the count or size limit
+/// of a batch, they are split into several batches; every batch keeps the
+/// input index of each of its requests, so results can be mapped back to the
+/// original order.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, RequiredMessageParams, Role,
+/// };
+/// use anthropic_ai_sdk::types::message_batches::BatchBuilder;
+///
+/// let params = |text: &str| {
+///     CreateMessageParams::new(RequiredMessageParams {
+///         model: "claude-3-5-haiku-latest".to_string(),
+///         messages: vec![Message::new_text(Role::User, text)],
+///         max_tokens: 1024,
+///     })
+/// };
+///
+/// let chunks = BatchBuilder::new()
+///     .max_requests(2)
+///     .push(params("first"))
+///     .push_with_id("summary", params("second"))
+///     .push(params("third"))
+///     .build()
+///     .unwrap();
+/// assert_eq!(chunks.len(), 2);
+/// assert_eq!(chunks[0].params.requests[1].custom_id.as_deref(), Some("summary"));
+/// assert_eq!(chunks[1].params.requests[0].custom_id.as_deref(), Some("req-2"));
+/// assert_eq!(chunks[1].input_index("req-2"), Some(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchBuilder {
+    requests: Vec<MessageRequest>,
+    max_requests: usize,
+    max_size: usize,
+}
+
+/// One batch of a [`BatchBuilder`], ready to be created
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchChunk {
+    /// The batch to create
+    pub params: CreateMessageBatchParams,
+    /// Input index of each request of the batch, by custom_id
+    pub indices: HashMap<String, usize>,
+}
+
+/// A created batch of a [`BatchBuilder`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmittedBatch {
+    /// The created batch
+    pub batch: MessageBatch,
+    /// Input index of each request of the batch, by custom_id
+    pub indices: HashMap<String, usize>,
+}
+
+impl BatchBuilder {
+    /// Create an empty BatchBuilder with the API limits
+    pub fn new() -> Self {
+        Self {
+            requests: Vec::new(),
+            max_requests: CreateMessageBatchParams::MAX_REQUESTS,
+            max_size: CreateMessageBatchParams::MAX_SIZE,
+        }
+    }
+
+    /// Add a request with a generated custom_id
+    pub fn push(self, params: impl Into<CreateMessageParams>) -> Self {
+        let custom_id = format!("req-{}", self.requests.len());
+        self.push_with_id(custom_id, params)
+    }
+
+    /// Add a request with the given custom_id
+    pub fn push_with_id(
+        mut self,
+        custom_id: impl Into<String>,
+        params: impl Into<CreateMessageParams>,
+    ) -> Self {
+        self.requests
+            .push(MessageRequest::new(params).with_custom_id(custom_id));
+        self
+    }
+
+    /// Add requests with generated custom_ids
+    pub fn extend<P: Into<CreateMessageParams>>(self, params: impl IntoIterator<Item = P>) -> Self {
+        params.into_iter().fold(self, Self::push)
+    }
+
+    /// Add `(custom_id, params)` pairs
+    pub fn extend_with_ids<K, P>(self, requests: impl IntoIterator<Item = (K, P)>) -> Self
+    where
+        K: Into<String>,
+        P: Into<CreateMessageParams>,
+    {
+        requests
+            .into_iter()
+            .fold(self, |builder, (custom_id, params)| {
+                builder.push_with_id(custom_id, params)
+            })
+    }
+
+    /// Limit the number of requests per batch, at most the API limit
+    pub fn max_requests(mut self, max_requests: usize) -> Self {
+        self.max_requests = max_requests.clamp(1, CreateMessageBatchParams::MAX_REQUESTS);
+        self
+    }
+
+    /// Limit the serialized size of each batch in bytes, at most the API limit
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size.min(CreateMessageBatchParams::MAX_SIZE);
+        self
+    }
+
+    /// Number of requests added so far
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Whether no requests have been added
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Split the requests into validated batches
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if a custom_id is used twice or is
+    /// malformed, a request is invalid, or a single request is larger than
+    /// the size limit.
+    pub fn build(self) -> Result<Vec<BatchChunk>, MessageBatchError> {
+        // Serialized as {"requests":[...]} with a comma between requests
+        const ENVELOPE_SIZE: usize = r#"{"requests":[]}"#.len();
+
+        let mut custom_ids = HashSet::new();
+        for request in &self.requests {
+            let custom_id = request.custom_id.as_deref().unwrap_or_default();
+            if !custom_ids.insert(custom_id) {
+                return Err(MessageBatchError::DuplicateCustomId(custom_id.to_string()));
+            }
+        }
+
+        let mut chunks = Vec::new();
+        let mut chunk = BatchChunk::default();
+        let mut size = ENVELOPE_SIZE;
+        for (index, request) in self.requests.into_iter().enumerate() {
+            let request_size = serde_json::to_vec(&request).map_or(0, |json| json.len()) + 1;
+            if ENVELOPE_SIZE + request_size > self.max_size {
+                return Err(MessageBatchError::BatchSizeExceeded);
+            }
+            let full = chunk.params.requests.len() >= self.max_requests
+                || size + request_size > self.max_size;
+            if full {
+                chunks.push(std::mem::take(&mut chunk));
+                size = ENVELOPE_SIZE;
+            }
+            size += request_size;
+            chunk
+                .indices
+                .insert(request.custom_id.clone().unwrap_or_default(), index);
+            chunk.params.requests.push(request);
+        }
+        if !chunk.params.requests.is_empty() {
+            chunks.push(chunk);
+        }
+        for chunk in &chunks {
+            chunk.params.validate()?;
+        }
+        Ok(chunks)
+    }
+
+    /// Split the requests into batches and create them
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`build`](BatchBuilder::build) before creating
+    /// any batch. If creating a batch fails after earlier batches were
+    /// created, returns `MessageBatchError::PartialSubmission` with the
+    /// created batches.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    /// use anthropic_ai_sdk::types::message_batches::{BatchBuilder, MessageBatchError};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
+    /// let documents = ["first document", "second document"];
+    /// let params = documents.iter().map(|document| {
+    ///     CreateMessageParams::new(RequiredMessageParams {
+    ///         model: "claude-3-5-haiku-latest".to_string(),
+    ///         messages: vec![Message::new_text(Role::User, format!("Summarize: {document}"))],
+    ///         max_tokens: 1024,
+    ///     })
+    /// });
+    /// for submitted in BatchBuilder::new().extend(params).submit(&client).await? {
+    ///     println!("{}: {} requests", submitted.batch.id, submitted.indices.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn submit<C>(self, client: &C) -> Result<Vec<SubmittedBatch>, MessageBatchError>
+    where
+        C: MessageBatchClient + Sync + ?Sized,
+    {
+        let mut submitted = Vec::new();
+        for chunk in self.build()? {
+            match client.create_message_batch(&chunk.params).await {
+                Ok(batch) => submitted.push(SubmittedBatch {
+                    batch,
+                    indices: chunk.indices,
+                }),
+                Err(error) if submitted.is_empty() => return Err(error),
+                Err(error) => {
+                    return Err(MessageBatchError::PartialSubmission {
+                        submitted,
+                        source: Box::new(error),
+                    });
+                }
+            }
+        }
+        Ok(submitted)
+    }
+}
+
+impl Default for BatchBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for BatchChunk {
+    fn default() -> Self {
+        Self {
+            params: CreateMessageBatchParams::new(Vec::new()),
+            indices: HashMap::new(),
+        }
+    }
+}
+
+impl BatchChunk {
+    /// Input index of the request with the given custom_id
+    pub fn input_index(&self, custom_id: &str) -> Option<usize> {
+        self.indices.get(custom_id).copied()
+    }
+}
+
+impl SubmittedBatch {
+    /// Input index of the request with the given custom_id
+    pub fn input_index(&self, custom_id: &str) -> Option<usize> {
+        self.indices.get(custom_id).copied()
+    }
+}
+
 impl MessageRequestParams {
     /// Create a new MessageRequestParams
     pub fn new(model: impl Into<String>, messages: Vec<Message>, max_tokens: u32) -> Self {
@@ -201,7 +734,7 @@ impl MessageRequestParams {
     }
 
     /// Set a system prompt for this request
-    pub fn with_system(mut self, system: impl Into<String>) -> Self {
+    pub fn with_system(mut self, system: impl Into<SystemPrompt>) -> Self {
         self.system = Some(system.into());
         self
     }
@@ -217,21 +750,36 @@ impl Message {
     }
 }
 
-/// Response for listing message batches
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ListMessageBatchesResponse {
-    /// List of message batches
-    pub data: Vec<MessageBatch>,
-    /// First ID in the data list
-    pub first_id: Option<String>,
-    /// Last ID in the data list
-    pub last_id: Option<String>,
-    /// Indicates if there are more results
-    pub has_more: bool,
+impl From<MessageRequestParams> for CreateMessageParams {
+    fn from(params: MessageRequestParams) -> Self {
+        use crate::types::message::{Message as ChatMessage, RequiredMessageParams, Role};
+
+        let messages = params
+            .messages
+            .into_iter()
+            .map(|message| {
+                let role = match message.role.as_str() {
+                    "assistant" => Role::Assistant,
+                    _ => Role::User,
+                };
+                ChatMessage::new_text(role, message.content)
+            })
+            .collect();
+        let mut create = CreateMessageParams::new(RequiredMessageParams {
+            model: params.model,
+            messages,
+            max_tokens: params.max_tokens,
+        });
+        create.system = params.system;
+        create
+    }
 }
 
+/// Response for listing message batches
+pub type ListMessageBatchesResponse = Page<MessageBatch>;
+
 /// Parameters for listing message batches
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, Clone)]
 pub struct ListMessageBatchesParams {
     /// Cursor for pagination (before)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -269,6 +817,20 @@ impl ListMessageBatchesParams {
     }
 }
 
+impl CursorParams for ListMessageBatchesParams {
+    fn before_cursor(&self) -> Option<&str> {
+        self.before_id.as_deref()
+    }
+
+    fn set_before_cursor(&mut self, id: String) {
+        self.before_id = Some(id);
+    }
+
+    fn set_after_cursor(&mut self, id: String) {
+        self.after_id = Some(id);
+    }
+}
+
 /// Parameters for retrieving a message batch
 #[derive(Debug, Serialize)]
 pub struct RetrieveMessageBatchParams {
@@ -285,6 +847,104 @@ impl RetrieveMessageBatchParams {
     }
 }
 
+/// Callback invoked with the latest request counts while polling a batch
+pub type ProgressCallback = Arc<dyn Fn(&RequestCounts) + Send + Sync>;
+
+/// Options for [`MessageBatchClient::wait_for_message_batch`]
+///
+/// The delay between polls starts at `interval` and grows by half after each
+/// poll, up to `max_interval`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use anthropic_ai_sdk::types::message_batches::PollOptions;
+///
+/// let options = PollOptions::new()
+///     .with_interval(Duration::from_secs(5))
+///     .with_max_wait(Duration::from_secs(3600))
+///     .with_on_progress(|counts| println!("{} succeeded", counts.succeeded));
+/// assert_eq!(options.max_wait, Some(Duration::from_secs(3600)));
+/// ```
+#[derive(Clone)]
+pub struct PollOptions {
+    /// Delay before the second poll
+    pub interval: Duration,
+    /// Upper bound for the delay between polls
+    pub max_interval: Duration,
+    /// Give up once this much time has passed; unlimited if `None`
+    pub max_wait: Option<Duration>,
+    /// Called with the request counts after every poll
+    pub on_progress: Option<ProgressCallback>,
+}
+
+impl PollOptions {
+    /// Default delay before the second poll
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(10);
+    /// Default upper bound for the delay between polls
+    pub const DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(60);
+
+    /// Create poll options with the default intervals and no deadline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the delay before the second poll
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the upper bound for the delay between polls
+    pub fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Set how long to wait for the batch to end
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    /// Set a callback invoked with the request counts after every poll
+    pub fn with_on_progress(
+        mut self,
+        on_progress: impl Fn(&RequestCounts) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_progress = Some(Arc::new(on_progress));
+        self
+    }
+
+    /// The delay to use after `interval`
+    pub(crate) fn next_interval(&self, interval: Duration) -> Duration {
+        (interval + interval / 2).min(self.max_interval)
+    }
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            interval: Self::DEFAULT_INTERVAL,
+            max_interval: Self::DEFAULT_MAX_INTERVAL,
+            max_wait: None,
+            on_progress: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for PollOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PollOptions")
+            .field("interval", &self.interval)
+            .field("max_interval", &self.max_interval)
+            .field("max_wait", &self.max_wait)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct RetrieveMessageBatchResultsParams {
     /// ID of the message batch to retrieve
@@ -303,64 +963,649 @@ impl RetrieveMessageBatchResultsParams {
 /// Response type for retrieving a message batch
 pub type RetrieveMessageBatchResponse = MessageBatch;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MessageBatchResult {
+/// One line of the message batch results file
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message_batches::{BatchResult, BatchResultEntry};
+///
+/// let entry: BatchResultEntry = serde_json::from_str(r#"{
+///     "custom_id": "req-2",
+///     "result": {
+///         "type": "errored",
+///         "error": {"type": "error", "error": {"type": "invalid_request_error", "message": "bad"}}
+///     }
+/// }"#).unwrap();
+/// assert!(entry.message().is_none());
+/// assert!(matches!(&entry.result, BatchResult::Errored { error } if error.error.message == "bad"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchResultEntry {
     /// Custom identifier provided in the original request
     pub custom_id: String,
-    /// Result of the message request
-    pub result: BatchRequestResult,
+    /// Outcome of the request
+    pub result: BatchResult,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BatchRequestResult {
-    /// Type of result (e.g., "succeeded")
-    #[serde(rename = "type")]
-    pub type_: String,
-    /// The resulting message if successful
-    pub message: MessageResponse,
+impl BatchResultEntry {
+    /// The message of a succeeded request
+    pub fn message(&self) -> Option<&CreateMessageResponse> {
+        match &self.result {
+            BatchResult::Succeeded { message } => Some(message),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MessageResponse {
-    /// Unique identifier for the message
-    pub id: String,
-    /// Type of the response (always "message")
-    #[serde(rename = "type")]
-    pub type_: String,
-    /// Role of the message (e.g., "assistant")
-    pub role: String,
-    /// Model used for generation
-    pub model: String,
-    /// Content of the message
-    pub content: Vec<MessageContent>,
-    /// Reason for stopping generation
-    pub stop_reason: String,
-    /// Sequence that caused the stop
-    pub stop_sequence: Option<String>,
-    /// Token usage statistics
-    pub usage: TokenUsage,
+/// Outcome of a single request in a message batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum BatchResult {
+    /// The request completed with a message
+    Succeeded { message: Box<CreateMessageResponse> },
+    /// The request failed with an API error
+    Errored { error: ApiErrorBody },
+    /// The batch was canceled before the request was processed
+    Canceled,
+    /// The batch expired before the request was processed
+    Expired,
+    /// A result type not known to this SDK
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MessageContent {
-    /// Type of content (e.g., "text")
-    #[serde(rename = "type")]
-    pub type_: String,
-    /// The actual text content
-    pub text: String,
+/// Outcome of a batch request, joined back to its input
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum BatchOutcome {
+    /// The request completed with a message
+    Succeeded(Box<CreateMessageResponse>),
+    /// The request failed with an API error
+    Errored(ApiErrorBody),
+    /// The batch was canceled before the request was processed
+    Canceled,
+    /// The batch expired before the request was processed
+    Expired,
+    /// The results contain no entry for the custom_id
+    Missing,
+    /// A result type not known to this SDK
+    Unknown,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TokenUsage {
-    /// Number of tokens in the input
-    pub input_tokens: u32,
-    /// Number of tokens in the output
-    pub output_tokens: u32,
+impl BatchOutcome {
+    /// The message of a succeeded request
+    pub fn message(&self) -> Option<&CreateMessageResponse> {
+        match self {
+            BatchOutcome::Succeeded(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// The error of a failed request
+    pub fn error(&self) -> Option<&ApiErrorBody> {
+        match self {
+            BatchOutcome::Errored(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Whether the request completed with a message
+    pub fn is_succeeded(&self) -> bool {
+        matches!(self, BatchOutcome::Succeeded(_))
+    }
+}
+
+impl From<BatchResult> for BatchOutcome {
+    fn from(result: BatchResult) -> Self {
+        match result {
+            BatchResult::Succeeded { message } => BatchOutcome::Succeeded(message),
+            BatchResult::Errored { error } => BatchOutcome::Errored(error),
+            BatchResult::Canceled => BatchOutcome::Canceled,
+            BatchResult::Expired => BatchOutcome::Expired,
+            BatchResult::Unknown => BatchOutcome::Unknown,
+        }
+    }
+}
+
+/// An input of a batch paired with its outcome
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoinedResult<T> {
+    /// Custom identifier of the request
+    pub custom_id: String,
+    /// The input the request was built from
+    pub input: T,
+    /// Outcome of the request
+    pub outcome: BatchOutcome,
+}
+
+/// The results of a message batch
+///
+/// Collect a results stream into it with `try_collect`, or convert the
+/// entries returned by `retrieve_message_batch_results`.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message_batches::{BatchOutcome, BatchResults};
+///
+/// let lines = [
+///     r#"{"custom_id":"doc-1","result":{"type":"expired"}}"#,
+///     r#"{"custom_id":"doc-2","result":{"type":"errored","error":{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}}}"#,
+/// ];
+/// let results: BatchResults = lines
+///     .iter()
+///     .map(|line| serde_json::from_str(line).unwrap())
+///     .collect();
+///
+/// let inputs = vec![
+///     ("doc-1".to_string(), "first"),
+///     ("doc-2".to_string(), "second"),
+///     ("doc-3".to_string(), "third"),
+/// ];
+/// let joined = results.join_with(inputs);
+/// assert_eq!(joined[0].outcome, BatchOutcome::Expired);
+/// assert_eq!(joined[1].outcome.error().unwrap().error.type_, "overloaded_error");
+/// assert_eq!((joined[2].input, &joined[2].outcome), ("third", &BatchOutcome::Missing));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BatchResults {
+    /// The result entries, in the order of the results file
+    pub entries: Vec<BatchResultEntry>,
+}
+
+impl BatchResults {
+    /// Map each custom_id to its outcome
+    pub fn collect_map(self) -> HashMap<String, BatchOutcome> {
+        self.entries
+            .into_iter()
+            .map(|entry| (entry.custom_id, entry.result.into()))
+            .collect()
+    }
+
+    /// Pair each `(custom_id, input)` with its outcome, in input order
+    ///
+    /// Inputs without a result entry get [`BatchOutcome::Missing`]. Entries
+    /// whose custom_id is not among the inputs are ignored.
+    pub fn join_with<T>(self, inputs: Vec<(String, T)>) -> Vec<JoinedResult<T>> {
+        let mut outcomes = self.collect_map();
+        inputs
+            .into_iter()
+            .map(|(custom_id, input)| JoinedResult {
+                outcome: outcomes.remove(&custom_id).unwrap_or(BatchOutcome::Missing),
+                custom_id,
+                input,
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<BatchResultEntry>> for BatchResults {
+    fn from(entries: Vec<BatchResultEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+impl FromIterator<BatchResultEntry> for BatchResults {
+    fn from_iter<I: IntoIterator<Item = BatchResultEntry>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<BatchResultEntry> for BatchResults {
+    fn extend<I: IntoIterator<Item = BatchResultEntry>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+    }
+}
+
+/// Looks up the original request parameters of a batch by custom_id
+///
+/// Implemented for request lists, maps from custom_id to parameters, and
+/// closures, so [`MessageBatchClient::resubmit_errors`] works both from
+/// requests kept in memory and from requests stored elsewhere.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::CreateMessageParams;
+/// use anthropic_ai_sdk::types::message_batches::{MessageRequest, RequestSource};
+///
+/// let requests = vec![MessageRequest::new(CreateMessageParams::default()).with_custom_id("doc-1")];
+/// assert!(requests.request("doc-1").is_some());
+///
+/// let lookup = |custom_id: &str| (custom_id == "doc-2").then(CreateMessageParams::default);
+/// assert!(lookup.request("doc-2").is_some());
+/// assert!(lookup.request("doc-3").is_none());
+/// ```
+pub trait RequestSource: Send + Sync {
+    /// The parameters of the request with the given custom_id
+    fn request(&self, custom_id: &str) -> Option<CreateMessageParams>;
+}
+
+impl<F> RequestSource for F
+where
+    F: Fn(&str) -> Option<CreateMessageParams> + Send + Sync,
+{
+    fn request(&self, custom_id: &str) -> Option<CreateMessageParams> {
+        self(custom_id)
+    }
+}
+
+impl RequestSource for [MessageRequest] {
+    fn request(&self, custom_id: &str) -> Option<CreateMessageParams> {
+        self.iter()
+            .find(|request| request.custom_id.as_deref() == Some(custom_id))
+            .map(|request| request.params.clone())
+    }
+}
+
+impl RequestSource for Vec<MessageRequest> {
+    fn request(&self, custom_id: &str) -> Option<CreateMessageParams> {
+        self.as_slice().request(custom_id)
+    }
+}
+
+impl RequestSource for CreateMessageBatchParams {
+    fn request(&self, custom_id: &str) -> Option<CreateMessageParams> {
+        self.requests.request(custom_id)
+    }
+}
+
+impl RequestSource for HashMap<String, CreateMessageParams> {
+    fn request(&self, custom_id: &str) -> Option<CreateMessageParams> {
+        self.get(custom_id).cloned()
+    }
+}
+
+/// Outcome of [`MessageBatchClient::resubmit_errors`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resubmission {
+    /// The new batch, or `None` when no request needed to be retried
+    pub batch: Option<MessageBatch>,
+    /// custom_ids of the requests in the new batch
+    pub resubmitted: Vec<String>,
+    /// Errored entries that were not retried because their error is not retryable
+    pub skipped: Vec<BatchResultEntry>,
+    /// custom_ids of retryable entries whose request was not found
+    pub missing: Vec<String>,
+}
+
+/// Decode a stream of raw JSONL chunks into batch result entries
+///
+/// Lines may be split across chunks and the last line does not need a
+/// trailing newline. Blank lines are skipped. The stream ends after the first
+/// transport error.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message_batches::{BatchResult, decode_batch_results};
+/// use futures_util::TryStreamExt;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let chunks = futures_util::stream::iter([
+///     Ok::<_, std::io::Error>(&br#"{"custom_id":"a","result":{"ty"#[..]),
+///     Ok(&br#"pe":"canceled"}}"#[..]),
+///     Ok(&b"\n\n{\"custom_id\":\"b\",\"result\":{\"type\":\"expired\"}}"[..]),
+/// ]);
+/// let entries: Vec<_> = decode_batch_results(chunks).try_collect().await?;
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[0].custom_id, "a");
+/// assert_eq!(entries[0].result, BatchResult::Canceled);
+/// assert_eq!(entries[1].result, BatchResult::Expired);
+/// # Ok(())
+/// # }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+/// ```
+pub fn decode_batch_results<S, B, E>(
+    chunks: S,
+) -> impl futures_util::Stream<Item = Result<BatchResultEntry, MessageBatchError>>
+where
+    S: futures_util::Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: std::fmt::Display,
+{
+    decode_results_from(chunks, 0, false).map_ok(|entry| entry.entry)
+}
+
+/// Decode JSONL chunks starting at byte `offset` of the results file
+///
+/// With `partial`, the chunks start within a line, which is skipped.
+pub(crate) fn decode_results_from<S, B, E>(
+    chunks: S,
+    offset: u64,
+    partial: bool,
+) -> impl Stream<Item = Result<CheckpointedEntry, MessageBatchError>>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: std::fmt::Display,
+{
+    struct Decoder<S> {
+        chunks: std::pin::Pin<Box<S>>,
+        buffer: Vec<u8>,
+        done: bool,
+        offset: u64,
+        partial: bool,
+    }
+
+    let state = Decoder {
+        chunks: Box::pin(chunks),
+        buffer: Vec::new(),
+        done: false,
+        offset,
+        partial,
+    };
+    futures_util::stream::unfold(state, |mut state| async move {
+        loop {
+            let end = state.buffer.iter().position(|&b| b == b'\n');
+            let line: Vec<u8> = match end {
+                Some(end) => state.buffer.drain(..=end).collect(),
+                None if state.done => std::mem::take(&mut state.buffer),
+                None => {
+                    match state.chunks.next().await {
+                        Some(Ok(chunk)) => state.buffer.extend_from_slice(chunk.as_ref()),
+                        Some(Err(e)) => {
+                            state.buffer.clear();
+                            state.done = true;
+                            let error = MessageBatchError::RequestFailed(e.to_string());
+                            return Some((Err(error), state));
+                        }
+                        None => state.done = true,
+                    }
+                    continue;
+                }
+            };
+            if line.is_empty() {
+                return None;
+            }
+            state.offset += line.len() as u64;
+            if std::mem::take(&mut state.partial) {
+                continue;
+            }
+            let checkpoint = |entry: BatchResultEntry| CheckpointedEntry {
+                checkpoint: ResultsCheckpoint {
+                    offset: state.offset,
+                    last_custom_id: Some(entry.custom_id.clone()),
+                },
+                entry,
+            };
+            if let Some(entry) = parse_result_line(&line) {
+                return Some((entry.map(checkpoint), state));
+            }
+        }
+    })
+}
+
+/// Skip the entries of `entries` up to and including the one of `checkpoint`
+pub(crate) fn resume_results<S>(
+    entries: S,
+    checkpoint: ResultsCheckpoint,
+) -> impl Stream<Item = Result<CheckpointedEntry, MessageBatchError>>
+where
+    S: Stream<Item = Result<CheckpointedEntry, MessageBatchError>>,
+{
+    let by_custom_id = match &checkpoint.last_custom_id {
+        Some(custom_id) if checkpoint.offset == 0 => Some(custom_id.clone()),
+        _ => None,
+    };
+    let state = (Box::pin(entries), by_custom_id);
+    futures_util::stream::unfold(state, move |(mut entries, mut by_custom_id)| {
+        let offset = checkpoint.offset;
+        async move {
+            loop {
+                let Some(item) = entries.next().await else {
+                    let custom_id = by_custom_id.take()?;
+                    let error = MessageBatchError::CheckpointNotFound(custom_id);
+                    return Some((Err(error), (entries, None)));
+                };
+                match (&item, &by_custom_id) {
+                    (Ok(entry), Some(custom_id)) => {
+                        if entry.entry.custom_id == *custom_id {
+                            by_custom_id = None;
+                        }
+                    }
+                    (Ok(entry), None) if entry.checkpoint.offset <= offset => {}
+                    _ => return Some((item, (entries, by_custom_id))),
+                }
+            }
+        }
+    })
+}
+
+/// Position in a batch results file, to resume reading after an entry
+///
+/// Each entry of
+/// [`stream_message_batch_results_from`](MessageBatchClient::stream_message_batch_results_from)
+/// comes with the checkpoint after it. Persist the checkpoint of the last
+/// entry that was fully processed; after a crash, pass it back to continue
+/// with the next entry.
+///
+/// With an `offset`, only the rest of the file is downloaded if the server
+/// supports range requests; otherwise the file is downloaded again and the
+/// entries up to the offset are skipped. A checkpoint made with
+/// [`after_custom_id`](Self::after_custom_id) downloads the whole file and
+/// skips the entries up to and including the one with that custom_id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct ResultsCheckpoint {
+    /// Bytes of the results file up to the end of the entry's line
+    pub offset: u64,
+    /// custom_id of the entry
+    pub last_custom_id: Option<String>,
+}
+
+impl ResultsCheckpoint {
+    /// Start of the results file
+    pub fn start() -> Self {
+        Self::default()
+    }
+
+    /// Resume after the entry with `custom_id`, without a byte offset
+    pub fn after_custom_id(custom_id: impl Into<String>) -> Self {
+        Self {
+            offset: 0,
+            last_custom_id: Some(custom_id.into()),
+        }
+    }
+}
+
+/// A batch result entry with the checkpoint to resume after it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckpointedEntry {
+    /// The result entry
+    pub entry: BatchResultEntry,
+    /// Checkpoint after the entry
+    pub checkpoint: ResultsCheckpoint,
+}
+
+impl From<CheckpointedEntry> for BatchResultEntry {
+    fn from(entry: CheckpointedEntry) -> Self {
+        entry.entry
+    }
+}
+
+/// Items of a batch results stream that carry a [`ResultsCheckpoint`]
+pub trait Checkpointed {
+    /// Checkpoint after the item
+    fn checkpoint(&self) -> &ResultsCheckpoint;
+}
+
+impl Checkpointed for CheckpointedEntry {
+    fn checkpoint(&self) -> &ResultsCheckpoint {
+        &self.checkpoint
+    }
+}
+
+/// Outcome of a batch request made with
+/// [`extraction_request`](crate::types::extract::extraction_request), with
+/// the value extracted from its message
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message_batches::{
+///     BatchResultEntry, BatchResultStreamExt, CheckpointedEntry, ResultsCheckpoint,
+/// };
+/// use futures_util::TryStreamExt;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Sentiment {
+///     score: f32,
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let line = r#"{"custom_id": "review-1", "result": {"type": "succeeded", "message": {
+///     "id": "msg_01", "type": "message", "role": "assistant", "model": "claude-sonnet-4-5",
+///     "content": [{"type": "tool_use", "id": "toolu_01", "name": "structured_output",
+///                  "input": {"score": 0.5}}],
+///     "stop_reason": "tool_use", "usage": {"input_tokens": 20, "output_tokens": 10}}}}"#;
+/// let entry = CheckpointedEntry {
+///     entry: serde_json::from_str::<BatchResultEntry>(line)?,
+///     checkpoint: ResultsCheckpoint::after_custom_id("review-1"),
+/// };
+///
+/// let outcomes: Vec<_> = futures_util::stream::iter([Ok(entry)])
+///     .map_outcomes::<Sentiment>()
+///     .try_collect()
+///     .await?;
+/// assert!(outcomes[0].outcome.is_succeeded());
+/// assert_eq!(outcomes[0].extraction.as_ref().unwrap().as_ref().unwrap().score, 0.5);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "schemars")]
+#[derive(Debug)]
+pub struct TypedOutcome<T> {
+    /// Custom identifier of the request
+    pub custom_id: String,
+    /// Outcome of the request
+    pub outcome: BatchOutcome,
+    /// The value extracted from the message of a succeeded request
+    pub extraction: Option<Result<T, ExtractError>>,
+    /// Checkpoint after the entry
+    pub checkpoint: ResultsCheckpoint,
+}
+
+#[cfg(feature = "schemars")]
+impl<T: DeserializeOwned> From<CheckpointedEntry> for TypedOutcome<T> {
+    fn from(entry: CheckpointedEntry) -> Self {
+        let outcome = BatchOutcome::from(entry.entry.result);
+        let extraction = outcome.message().map(|message| {
+            parse_extraction::<T>(message.clone()).map(|extraction| extraction.value)
+        });
+        Self {
+            custom_id: entry.entry.custom_id,
+            outcome,
+            extraction,
+            checkpoint: entry.checkpoint,
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T> Checkpointed for TypedOutcome<T> {
+    fn checkpoint(&self) -> &ResultsCheckpoint {
+        &self.checkpoint
+    }
+}
+
+/// Combinators for streams of batch results
+///
+/// Implemented for every stream of results, e.g. the one returned by
+/// [`stream_message_batch_results_from`](MessageBatchClient::stream_message_batch_results_from).
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message_batches::{
+///     BatchResultEntry, BatchResultStreamExt, CheckpointedEntry, ResultsCheckpoint,
+/// };
+/// use futures_util::TryStreamExt;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let entries = ["doc-1", "doc-2", "doc-3"].map(|custom_id| {
+///     let line = format!(r#"{{"custom_id":"{}","result":{{"type":"expired"}}}}"#, custom_id);
+///     Ok(CheckpointedEntry {
+///         entry: serde_json::from_str::<BatchResultEntry>(&line).unwrap(),
+///         checkpoint: ResultsCheckpoint::after_custom_id(custom_id),
+///     })
+/// });
+///
+/// // Write two entries to the sink at a time, checkpointing in order
+/// let mut written = futures_util::stream::iter(entries).process_buffered(2, |entry| async move {
+///     format!("{} expired", entry.entry.custom_id)
+/// });
+/// let mut last = ResultsCheckpoint::start();
+/// while let Some((line, checkpoint)) = written.try_next().await? {
+///     assert!(line.ends_with("expired"));
+///     last = checkpoint;
+/// }
+/// assert_eq!(last.last_custom_id.as_deref(), Some("doc-3"));
+/// # Ok(())
+/// # }
+/// ```
+pub trait BatchResultStreamExt<I>: Stream<Item = Result<I, MessageBatchError>> + Sized {
+    /// Pair each outcome with the value of type `T` extracted from its message
+    ///
+    /// For results of requests built with
+    /// [`extraction_request`](crate::types::extract::extraction_request).
+    #[cfg(feature = "schemars")]
+    fn map_outcomes<T>(self) -> impl Stream<Item = Result<TypedOutcome<T>, MessageBatchError>>
+    where
+        I: Into<CheckpointedEntry>,
+        T: DeserializeOwned,
+    {
+        self.map_ok(|entry| TypedOutcome::from(entry.into()))
+    }
+
+    /// Process up to `concurrency` items at a time with `f`
+    ///
+    /// The outputs are yielded in the order of the results, each with the
+    /// checkpoint after its item, so persisting the checkpoint of an output
+    /// never skips an item that is still being processed.
+    fn process_buffered<F, Fut, R>(
+        self,
+        concurrency: usize,
+        mut f: F,
+    ) -> impl Stream<Item = Result<(R, ResultsCheckpoint), MessageBatchError>>
+    where
+        I: Checkpointed,
+        F: FnMut(I) -> Fut,
+        Fut: Future<Output = R>,
+    {
+        self.map_ok(move |item| {
+            let checkpoint = item.checkpoint().clone();
+            let output = f(item);
+            async move { Ok((output.await, checkpoint)) }
+        })
+        .try_buffered(concurrency.max(1))
+    }
+}
+
+impl<S, I> BatchResultStreamExt<I> for S where S: Stream<Item = Result<I, MessageBatchError>> {}
+
+fn parse_result_line(line: &[u8]) -> Option<Result<BatchResultEntry, MessageBatchError>> {
+    let line = line.trim_ascii();
+    if line.is_empty() {
+        return None;
+    }
+    Some(serde_json::from_slice(line).map_err(|e| {
+        MessageBatchError::ApiError(format!(
+            "Failed to parse batch result: {}. Line: {}",
+            e,
+            String::from_utf8_lossy(line)
+        ))
+    }))
 }
 
 /// Response type for retrieving message batch results
-/// This will be a stream of MessageBatchResult objects, one per line
-pub type RetrieveMessageBatchResultsResponse = Vec<MessageBatchResult>;
+pub type RetrieveMessageBatchResultsResponse = Vec<BatchResultEntry>;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CancelMessageBatchParams {
@@ -376,19 +1621,90 @@ impl CancelMessageBatchParams {
 }
 
 /// Response type for cancelling a message batch
-#[derive(Debug, Deserialize)]
-pub struct CancelResponse {
-    pub id: String,
-    #[serde(rename = "type")]
-    pub obj_type: String,
-    pub processing_status: String,
-    pub request_counts: serde_json::Value,
-    pub ended_at: Option<String>,
-    pub created_at: String,
-    pub expires_at: String,
-    pub archived_at: Option<String>,
-    pub cancel_initiated_at: Option<String>,
-    pub results_url: Option<String>,
+pub type CancelResponse = MessageBatch;
+
+/// A created message batch that is canceled when dropped
+///
+/// Returned by [`MessageBatchClient::create_message_batch_guarded`]. Unless
+/// [`keep`](BatchGuard::keep) or [`cancel`](BatchGuard::cancel) is called,
+/// dropping the guard spawns a cancel request onto the Tokio runtime that was
+/// running when the batch was created, so an aborted pipeline does not leave
+/// the batch processing.
+///
+/// The cancel on drop is best effort: it is not awaited, its result is
+/// ignored, and it may never be sent if the guard is dropped while the
+/// runtime shuts down (e.g. at the end of `main`). Call
+/// [`cancel`](BatchGuard::cancel) where delivery matters.
+pub struct BatchGuard<C>
+where
+    C: MessageBatchClient + Send + Sync + 'static,
+{
+    batch: MessageBatch,
+    client: Option<C>,
+    runtime: tokio::runtime::Handle,
+}
+
+impl<C> BatchGuard<C>
+where
+    C: MessageBatchClient + Send + Sync + 'static,
+{
+    /// Guard `batch`, canceling it through `client` when dropped
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn new(client: C, batch: MessageBatch) -> Self {
+        Self {
+            batch,
+            client: Some(client),
+            runtime: tokio::runtime::Handle::current(),
+        }
+    }
+
+    /// The batch as returned when it was created
+    pub fn batch(&self) -> &MessageBatch {
+        &self.batch
+    }
+
+    /// Keep the batch running and release the guard
+    pub fn keep(mut self) -> MessageBatch {
+        self.client = None;
+        self.batch.clone()
+    }
+
+    /// Cancel the batch now and wait for the API to confirm
+    pub async fn cancel(mut self) -> Result<CancelResponse, MessageBatchError> {
+        let client = self.client.take().expect("the client is only taken once");
+        let params = CancelMessageBatchParams::new(&self.batch.id);
+        client.cancel_message_batch(&params).await
+    }
+}
+
+impl<C> Drop for BatchGuard<C>
+where
+    C: MessageBatchClient + Send + Sync + 'static,
+{
+    fn drop(&mut self) {
+        let Some(client) = self.client.take() else {
+            return;
+        };
+        let params = CancelMessageBatchParams::new(&self.batch.id);
+        self.runtime.spawn(async move {
+            let _ = client.cancel_message_batch(&params).await;
+        });
+    }
+}
+
+impl<C> std::fmt::Debug for BatchGuard<C>
+where
+    C: MessageBatchClient + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchGuard")
+            .field("batch", &self.batch)
+            .field("armed", &self.client.is_some())
+            .finish()
+    }
 }
 
 /// Parameters for deleting a message batch
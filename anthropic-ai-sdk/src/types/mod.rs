@@ -1,3 +1,27 @@
+pub mod admin;
+pub mod agent;
+pub mod answer;
+pub mod auto_cache;
+pub mod beta;
+pub mod budget;
+pub mod chunk;
+pub mod compaction;
+pub mod concurrent;
+pub mod continuation;
+pub mod conversation;
+pub mod decimal;
+pub mod estimate;
+pub mod extract;
+pub mod fallback;
+pub mod files;
+pub mod image;
 pub mod message;
 pub mod message_batches;
 pub mod model;
+pub mod pagination;
+pub mod pricing;
+pub mod prompt;
+pub mod rate_limit;
+pub mod response_cache;
+pub mod tool;
+pub mod transcript;
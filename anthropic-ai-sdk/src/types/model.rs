@@ -2,14 +2,20 @@
 //!
 //! This module contains the types and functions for the Anthropic Models API.
 //!
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use time::serde::rfc3339;
 use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+use crate::error::ApiErrorResponse;
+use crate::types::pagination::{CursorParams, Page, RateLimitError};
 
 /// Error types for the Models API
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum ModelError {
     #[error("Invalid pagination parameters")]
     InvalidPagination,
@@ -19,6 +25,14 @@ pub enum ModelError {
     RequestFailed(String),
     #[error("API error: {0}")]
     ApiError(String),
+    #[error("Model not found: {0}")]
+    NotFound(String),
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// Delay requested by the API before retrying
+        retry_after: Option<Duration>,
+    },
 }
 
 impl From<String> for ModelError {
@@ -27,6 +41,34 @@ impl From<String> for ModelError {
     }
 }
 
+impl From<ApiErrorResponse> for ModelError {
+    fn from(error: ApiErrorResponse) -> Self {
+        if error.is_not_found() {
+            ModelError::NotFound(error.message.unwrap_or(error.body))
+        } else if error.is_rate_limited() {
+            ModelError::RateLimited {
+                message: error.message.unwrap_or(error.body),
+                retry_after: error.retry_after,
+            }
+        } else {
+            ModelError::ApiError(error.body)
+        }
+    }
+}
+
+impl RateLimitError for ModelError {
+    fn is_rate_limited(&self) -> bool {
+        matches!(self, ModelError::RateLimited { .. })
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ModelError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
 #[async_trait]
 pub trait ModelClient {
     async fn list_models<'a>(
@@ -34,25 +76,35 @@ pub trait ModelClient {
         params: Option<&'a ListModelsParams>,
     ) -> Result<ListModelsResponse, ModelError>;
 
-    async fn get_model<'a>(&'a self, model_id: &'a str) -> Result<Model, ModelError>;
+    async fn get_model<'a>(&'a self, model_id: &'a str) -> Result<ModelInfo, ModelError>;
+
+    fn list_models_pages<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+    ) -> impl futures_util::Stream<Item = Result<ListModelsResponse, ModelError>> + Send + 'a;
+
+    async fn list_all_models<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+    ) -> Result<Vec<ModelInfo>, ModelError>;
+
+    async fn resolve_alias<'a>(&'a self, alias: &'a str) -> Result<String, ModelError>;
+
+    async fn cached_models(&self) -> Result<Arc<Vec<ModelInfo>>, ModelError>;
+
+    async fn get_model_spec<'a>(&'a self, model: &'a str) -> Option<ModelSpec>;
 }
 
 /// Response structure for the List Models API endpoint
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ListModelsResponse {
-    /// List of model objects
-    pub data: Vec<Model>,
-    /// First ID in the data list
-    pub first_id: Option<String>,
-    /// Indicates if there are more results
-    pub has_more: bool,
-    /// Last ID in the data list
-    pub last_id: Option<String>,
-}
-
-/// Represents an Anthropic model
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Model {
+pub type ListModelsResponse = Page<ModelInfo>;
+
+/// Represents an Anthropic model as returned by the Models API
+///
+/// This struct was named `Model` until the [`Model`] enum of model
+/// identifiers took that name. Code matching on the old struct only needs
+/// the new name: its fields and serialization are unchanged.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModelInfo {
     /// Type of the resource (always "model")
     #[serde(rename = "type")]
     pub type_: String,
@@ -68,10 +120,31 @@ pub struct Model {
     #[serde(rename = "created_at")]
     #[serde(with = "rfc3339")]
     pub created_at: OffsetDateTime,
+
+    /// Maximum number of input tokens, when reported by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_input_tokens: Option<u32>,
+
+    /// Maximum value accepted for `max_tokens`, when reported by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
+impl ModelInfo {
+    /// Limits of the model as reported by the API
+    ///
+    /// Returns `None` unless the API reported both limits.
+    pub fn spec(&self) -> Option<ModelSpec> {
+        Some(ModelSpec {
+            context_window: self.max_input_tokens?,
+            max_output_tokens: self.max_tokens?,
+            extended_max_output_tokens: None,
+        })
+    }
 }
 
 /// Parameters for listing models
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, Clone)]
 pub struct ListModelsParams {
     /// Cursor for pagination (before)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -108,3 +181,303 @@ impl ListModelsParams {
         self
     }
 }
+
+impl CursorParams for ListModelsParams {
+    fn before_cursor(&self) -> Option<&str> {
+        self.before_id.as_deref()
+    }
+
+    fn set_before_cursor(&mut self, id: String) {
+        self.before_id = Some(id);
+    }
+
+    fn set_after_cursor(&mut self, id: String) {
+        self.after_id = Some(id);
+    }
+}
+
+pub const CLAUDE_3_HAIKU_20240307: &str = "claude-3-haiku-20240307";
+pub const CLAUDE_3_OPUS_20240229: &str = "claude-3-opus-20240229";
+pub const CLAUDE_3_OPUS_LATEST: &str = "claude-3-opus-latest";
+pub const CLAUDE_3_5_HAIKU_20241022: &str = "claude-3-5-haiku-20241022";
+pub const CLAUDE_3_5_HAIKU_LATEST: &str = "claude-3-5-haiku-latest";
+pub const CLAUDE_3_5_SONNET_20240620: &str = "claude-3-5-sonnet-20240620";
+pub const CLAUDE_3_5_SONNET_20241022: &str = "claude-3-5-sonnet-20241022";
+pub const CLAUDE_3_5_SONNET_LATEST: &str = "claude-3-5-sonnet-latest";
+pub const CLAUDE_3_7_SONNET_20250219: &str = "claude-3-7-sonnet-20250219";
+pub const CLAUDE_3_7_SONNET_LATEST: &str = "claude-3-7-sonnet-latest";
+pub const CLAUDE_SONNET_4_20250514: &str = "claude-sonnet-4-20250514";
+pub const CLAUDE_SONNET_4_0: &str = "claude-sonnet-4-0";
+pub const CLAUDE_OPUS_4_20250514: &str = "claude-opus-4-20250514";
+pub const CLAUDE_OPUS_4_0: &str = "claude-opus-4-0";
+pub const CLAUDE_OPUS_4_1_20250805: &str = "claude-opus-4-1-20250805";
+pub const CLAUDE_OPUS_4_1: &str = "claude-opus-4-1";
+pub const CLAUDE_SONNET_4_5_20250929: &str = "claude-sonnet-4-5-20250929";
+pub const CLAUDE_SONNET_4_5: &str = "claude-sonnet-4-5";
+pub const CLAUDE_HAIKU_4_5_20251001: &str = "claude-haiku-4-5-20251001";
+pub const CLAUDE_HAIKU_4_5: &str = "claude-haiku-4-5";
+pub const CLAUDE_OPUS_4_5_20251101: &str = "claude-opus-4-5-20251101";
+pub const CLAUDE_OPUS_4_5: &str = "claude-opus-4-5";
+
+/// Identifier of a Claude model
+///
+/// Known models have their own variant; any other identifier (newer models,
+/// gateway-specific names) is kept in [`Model::Custom`]. Strings convert into
+/// a `Model` and back, so APIs taking `impl Into<Model>` also accept `&str`.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::model::Model;
+///
+/// let model: Model = "claude-3-7-sonnet-latest".parse().unwrap();
+/// assert_eq!(model, Model::Claude37SonnetLatest);
+/// assert_eq!(model.to_string(), "claude-3-7-sonnet-latest");
+///
+/// let custom = Model::from("my-gateway/claude");
+/// assert_eq!(custom, Model::Custom("my-gateway/claude".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Model {
+    Claude3Haiku20240307,
+    Claude3Opus20240229,
+    Claude3OpusLatest,
+    Claude35Haiku20241022,
+    Claude35HaikuLatest,
+    Claude35Sonnet20240620,
+    Claude35Sonnet20241022,
+    Claude35SonnetLatest,
+    Claude37Sonnet20250219,
+    Claude37SonnetLatest,
+    ClaudeSonnet4_20250514,
+    ClaudeSonnet4_0,
+    ClaudeOpus4_20250514,
+    ClaudeOpus4_0,
+    ClaudeOpus4_1_20250805,
+    ClaudeOpus4_1,
+    ClaudeSonnet4_5_20250929,
+    ClaudeSonnet4_5,
+    ClaudeHaiku4_5_20251001,
+    ClaudeHaiku4_5,
+    ClaudeOpus4_5_20251101,
+    ClaudeOpus4_5,
+    /// Any model identifier not known to this SDK
+    Custom(String),
+}
+
+/// Static metadata about a model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModelSpec {
+    /// Maximum number of input tokens (context window)
+    pub context_window: u32,
+    /// Maximum value accepted for `max_tokens`
+    pub max_output_tokens: u32,
+    /// Maximum value accepted for `max_tokens` with the 128k output beta, if supported
+    pub extended_max_output_tokens: Option<u32>,
+}
+
+impl ModelSpec {
+    /// Maximum value accepted for `max_tokens`, with or without the 128k output beta
+    pub fn output_limit(&self, output_128k: bool) -> u32 {
+        match self.extended_max_output_tokens {
+            Some(extended) if output_128k => extended,
+            _ => self.max_output_tokens,
+        }
+    }
+}
+
+impl Model {
+    /// All models known to this SDK
+    pub const KNOWN: &'static [Model] = &[
+        Model::Claude3Haiku20240307,
+        Model::Claude3Opus20240229,
+        Model::Claude3OpusLatest,
+        Model::Claude35Haiku20241022,
+        Model::Claude35HaikuLatest,
+        Model::Claude35Sonnet20240620,
+        Model::Claude35Sonnet20241022,
+        Model::Claude35SonnetLatest,
+        Model::Claude37Sonnet20250219,
+        Model::Claude37SonnetLatest,
+        Model::ClaudeSonnet4_20250514,
+        Model::ClaudeSonnet4_0,
+        Model::ClaudeOpus4_20250514,
+        Model::ClaudeOpus4_0,
+        Model::ClaudeOpus4_1_20250805,
+        Model::ClaudeOpus4_1,
+        Model::ClaudeSonnet4_5_20250929,
+        Model::ClaudeSonnet4_5,
+        Model::ClaudeHaiku4_5_20251001,
+        Model::ClaudeHaiku4_5,
+        Model::ClaudeOpus4_5_20251101,
+        Model::ClaudeOpus4_5,
+    ];
+
+    /// The model identifier as sent to the API
+    pub fn as_str(&self) -> &str {
+        match self {
+            Model::Claude3Haiku20240307 => CLAUDE_3_HAIKU_20240307,
+            Model::Claude3Opus20240229 => CLAUDE_3_OPUS_20240229,
+            Model::Claude3OpusLatest => CLAUDE_3_OPUS_LATEST,
+            Model::Claude35Haiku20241022 => CLAUDE_3_5_HAIKU_20241022,
+            Model::Claude35HaikuLatest => CLAUDE_3_5_HAIKU_LATEST,
+            Model::Claude35Sonnet20240620 => CLAUDE_3_5_SONNET_20240620,
+            Model::Claude35Sonnet20241022 => CLAUDE_3_5_SONNET_20241022,
+            Model::Claude35SonnetLatest => CLAUDE_3_5_SONNET_LATEST,
+            Model::Claude37Sonnet20250219 => CLAUDE_3_7_SONNET_20250219,
+            Model::Claude37SonnetLatest => CLAUDE_3_7_SONNET_LATEST,
+            Model::ClaudeSonnet4_20250514 => CLAUDE_SONNET_4_20250514,
+            Model::ClaudeSonnet4_0 => CLAUDE_SONNET_4_0,
+            Model::ClaudeOpus4_20250514 => CLAUDE_OPUS_4_20250514,
+            Model::ClaudeOpus4_0 => CLAUDE_OPUS_4_0,
+            Model::ClaudeOpus4_1_20250805 => CLAUDE_OPUS_4_1_20250805,
+            Model::ClaudeOpus4_1 => CLAUDE_OPUS_4_1,
+            Model::ClaudeSonnet4_5_20250929 => CLAUDE_SONNET_4_5_20250929,
+            Model::ClaudeSonnet4_5 => CLAUDE_SONNET_4_5,
+            Model::ClaudeHaiku4_5_20251001 => CLAUDE_HAIKU_4_5_20251001,
+            Model::ClaudeHaiku4_5 => CLAUDE_HAIKU_4_5,
+            Model::ClaudeOpus4_5_20251101 => CLAUDE_OPUS_4_5_20251101,
+            Model::ClaudeOpus4_5 => CLAUDE_OPUS_4_5,
+            Model::Custom(id) => id,
+        }
+    }
+
+    /// Context window and output limits of the model
+    ///
+    /// Returns `None` for custom models.
+    pub fn spec(&self) -> Option<ModelSpec> {
+        let (context_window, max_output_tokens) = match self {
+            // The first Claude 3.5 Sonnet only writes 8192 tokens with a
+            // legacy beta header; `with_model_spec` allows them
+            Model::Claude3Haiku20240307
+            | Model::Claude3Opus20240229
+            | Model::Claude3OpusLatest
+            | Model::Claude35Sonnet20240620 => (200_000, 4_096),
+            Model::Claude35Haiku20241022
+            | Model::Claude35HaikuLatest
+            | Model::Claude35Sonnet20241022
+            | Model::Claude35SonnetLatest => (200_000, 8_192),
+            Model::ClaudeOpus4_20250514
+            | Model::ClaudeOpus4_0
+            | Model::ClaudeOpus4_1_20250805
+            | Model::ClaudeOpus4_1 => (200_000, 32_000),
+            Model::Claude37Sonnet20250219
+            | Model::Claude37SonnetLatest
+            | Model::ClaudeSonnet4_20250514
+            | Model::ClaudeSonnet4_0
+            | Model::ClaudeSonnet4_5_20250929
+            | Model::ClaudeSonnet4_5
+            | Model::ClaudeHaiku4_5_20251001
+            | Model::ClaudeHaiku4_5
+            | Model::ClaudeOpus4_5_20251101
+            | Model::ClaudeOpus4_5 => (200_000, 64_000),
+            Model::Custom(_) => return None,
+        };
+        let extended_max_output_tokens = match self {
+            Model::Claude37Sonnet20250219 | Model::Claude37SonnetLatest => Some(128_000),
+            _ => None,
+        };
+        Some(ModelSpec {
+            context_window,
+            max_output_tokens,
+            extended_max_output_tokens,
+        })
+    }
+
+    /// Shortest prompt prefix, in tokens, that the model caches
+    ///
+    /// Cache breakpoints on shorter prefixes are ignored by the API. Returns
+    /// `None` for custom models.
+    pub fn min_cacheable_tokens(&self) -> Option<u32> {
+        let tokens = match self {
+            Model::Claude3Opus20240229
+            | Model::Claude3OpusLatest
+            | Model::Claude35Sonnet20240620
+            | Model::Claude35Sonnet20241022
+            | Model::Claude35SonnetLatest
+            | Model::Claude37Sonnet20250219
+            | Model::Claude37SonnetLatest
+            | Model::ClaudeSonnet4_20250514
+            | Model::ClaudeSonnet4_0
+            | Model::ClaudeOpus4_20250514
+            | Model::ClaudeOpus4_0
+            | Model::ClaudeOpus4_1_20250805
+            | Model::ClaudeOpus4_1
+            | Model::ClaudeSonnet4_5_20250929
+            | Model::ClaudeSonnet4_5 => 1_024,
+            Model::Claude3Haiku20240307
+            | Model::Claude35Haiku20241022
+            | Model::Claude35HaikuLatest => 2_048,
+            Model::ClaudeHaiku4_5_20251001
+            | Model::ClaudeHaiku4_5
+            | Model::ClaudeOpus4_5_20251101
+            | Model::ClaudeOpus4_5 => 4_096,
+            Model::Custom(_) => return None,
+        };
+        Some(tokens)
+    }
+}
+
+impl std::fmt::Display for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for Model {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::str::FromStr for Model {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Model::from(s))
+    }
+}
+
+impl From<&str> for Model {
+    fn from(id: &str) -> Self {
+        Model::KNOWN
+            .iter()
+            .find(|model| model.as_str() == id)
+            .cloned()
+            .unwrap_or_else(|| Model::Custom(id.to_string()))
+    }
+}
+
+impl From<String> for Model {
+    fn from(id: String) -> Self {
+        Model::from(id.as_str())
+    }
+}
+
+impl From<&String> for Model {
+    fn from(id: &String) -> Self {
+        Model::from(id.as_str())
+    }
+}
+
+impl From<Model> for String {
+    fn from(model: Model) -> Self {
+        match model {
+            Model::Custom(id) => id,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl Serialize for Model {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Model {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(Model::from(id))
+    }
+}
@@ -0,0 +1,211 @@
+//! Cursor Pagination
+//!
+//! This module contains the shared pagination types of the list endpoints.
+//!
+//! Models, message batches, files and the Admin API lists all return pages of
+//! `{ data, has_more, first_id, last_id }` and accept `before_id`/`after_id`
+//! cursors. [`Paginator`] follows those cursors page by page, retrying pages
+//! that hit a rate limit.
+//!
+use std::future::Future;
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use futures_util::{Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+
+/// A page of a cursor-paginated list
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Page<T> {
+    /// Items of the page
+    pub data: Vec<T>,
+    /// First ID in the data list
+    pub first_id: Option<String>,
+    /// Indicates if there are more results
+    pub has_more: bool,
+    /// Last ID in the data list
+    pub last_id: Option<String>,
+}
+
+/// List parameters with `before_id`/`after_id` cursors
+pub trait CursorParams: Clone {
+    /// The `before_id` cursor, if set
+    fn before_cursor(&self) -> Option<&str>;
+
+    /// Set the `before_id` cursor
+    fn set_before_cursor(&mut self, id: String);
+
+    /// Set the `after_id` cursor
+    fn set_after_cursor(&mut self, id: String);
+}
+
+/// Errors that may be caused by a rate limit
+pub trait RateLimitError {
+    /// Whether the request was rejected by a rate limit
+    fn is_rate_limited(&self) -> bool;
+
+    /// Delay requested by the API before retrying
+    fn retry_after(&self) -> Option<Duration>;
+}
+
+/// Longest delay of the exponential backoff between retries
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Delay before retry number `attempt` (from 0) after `error`
+///
+/// The delay requested by the API, or an exponential backoff starting at one
+/// second and capped at [`MAX_BACKOFF`].
+pub(crate) fn backoff(error: &impl RateLimitError, attempt: u32) -> Duration {
+    error.retry_after().unwrap_or_else(|| {
+        let secs = 1u64.checked_shl(attempt).unwrap_or(u64::MAX);
+        Duration::from_secs(secs).min(MAX_BACKOFF)
+    })
+}
+
+type FetchPage<'a, T, P, E> =
+    Box<dyn Fn(P) -> BoxFuture<'a, Result<Page<T>, E>> + Send + Sync + 'a>;
+
+/// Follows the cursors of a list endpoint page by page
+///
+/// The first page is the one selected by the initial parameters. When they
+/// set `before_id`, the paginator moves backward through `first_id`;
+/// otherwise it moves forward through `last_id`. It stops when a page has
+/// `has_more: false` or lacks the cursor needed for the next page.
+///
+/// Pages rejected by a rate limit are retried up to
+/// [`max_retries`](Paginator::max_retries) times, waiting for the delay
+/// requested by the API or, without one, for an exponential backoff starting
+/// at one second and growing to at most a minute.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::model::{ListModelsParams, ModelError, ModelInfo};
+/// use anthropic_ai_sdk::types::pagination::{Page, Paginator};
+/// use futures_util::TryStreamExt;
+///
+/// # async fn example() -> Result<(), ModelError> {
+/// // A fake endpoint with three pages of one item each
+/// let fetch = |params: ListModelsParams| async move {
+///     let n: u32 = params.after_id.map_or(0, |id| id.parse().unwrap()) + 1;
+///     Ok::<_, ModelError>(Page::<u32> {
+///         data: vec![n],
+///         first_id: Some(n.to_string()),
+///         has_more: n < 3,
+///         last_id: Some(n.to_string()),
+///     })
+/// };
+/// let items: Vec<u32> = Paginator::new(ListModelsParams::new(), fetch).items().try_collect().await?;
+/// assert_eq!(items, [1, 2, 3]);
+/// # Ok(())
+/// # }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+/// ```
+pub struct Paginator<'a, T, P, E> {
+    fetch: FetchPage<'a, T, P, E>,
+    next: Option<P>,
+    backward: bool,
+    max_retries: u32,
+}
+
+impl<'a, T, P, E> Paginator<'a, T, P, E>
+where
+    T: Send + 'a,
+    P: CursorParams + Send + 'a,
+    E: RateLimitError + Send + 'a,
+{
+    /// Default number of retries of a rate limited page
+    pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+    /// Create a paginator starting at the page selected by `params`
+    ///
+    /// `fetch` loads the page selected by the given parameters, usually by
+    /// calling the list method of the endpoint.
+    pub fn new<F, Fut>(params: P, fetch: F) -> Self
+    where
+        F: Fn(P) -> Fut + Send + Sync + 'a,
+        Fut: Future<Output = Result<Page<T>, E>> + Send + 'a,
+    {
+        Self {
+            backward: params.before_cursor().is_some(),
+            fetch: Box::new(move |params| Box::pin(fetch(params))),
+            next: Some(params),
+            max_retries: Self::DEFAULT_MAX_RETRIES,
+        }
+    }
+
+    /// Set how often a rate limited page is retried
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Whether the paginator moves backward through `before_id`
+    pub fn is_backward(&self) -> bool {
+        self.backward
+    }
+
+    /// Whether all pages have been loaded
+    pub fn is_done(&self) -> bool {
+        self.next.is_none()
+    }
+
+    /// Load the next page
+    ///
+    /// Returns `Ok(None)` once all pages have been loaded. After an error the
+    /// same page is requested again on the next call.
+    pub async fn next_page(&mut self) -> Result<Option<Page<T>>, E> {
+        let Some(mut params) = self.next.take() else {
+            return Ok(None);
+        };
+        let mut attempt = 0;
+        let page = loop {
+            match (self.fetch)(params.clone()).await {
+                Ok(page) => break page,
+                Err(error) if error.is_rate_limited() && attempt < self.max_retries => {
+                    tokio::time::sleep(backoff(&error, attempt)).await;
+                    attempt += 1;
+                }
+                Err(error) => {
+                    self.next = Some(params);
+                    return Err(error);
+                }
+            }
+        };
+        let cursor = if self.backward {
+            &page.first_id
+        } else {
+            &page.last_id
+        };
+        self.next = match cursor {
+            Some(id) if page.has_more => {
+                if self.backward {
+                    params.set_before_cursor(id.clone());
+                } else {
+                    params.set_after_cursor(id.clone());
+                }
+                Some(params)
+            }
+            _ => None,
+        };
+        Ok(Some(page))
+    }
+
+    /// Stream the remaining pages
+    ///
+    /// The stream ends after the last page or the first error.
+    pub fn pages(self) -> impl Stream<Item = Result<Page<T>, E>> + Send + 'a {
+        futures_util::stream::try_unfold(self, |mut paginator| async move {
+            Ok(paginator.next_page().await?.map(|page| (page, paginator)))
+        })
+    }
+
+    /// Stream the items of the remaining pages
+    ///
+    /// The stream ends after the last item or the first error.
+    pub fn items(self) -> impl Stream<Item = Result<T, E>> + Send + 'a {
+        self.pages()
+            .map_ok(|page| futures_util::stream::iter(page.data.into_iter().map(Ok)))
+            .try_flatten()
+    }
+}
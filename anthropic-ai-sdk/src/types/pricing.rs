@@ -0,0 +1,254 @@
+//! Cost estimation
+//!
+//! This module contains [`PricingTable`], the list prices of the models per
+//! million tokens, and [`cost`], which prices the [`Usage`] of a request.
+//!
+//! The built-in prices are the published USD list prices at the time of this
+//! release. They do not include batch discounts or long context surcharges,
+//! and price cache writes at the 5-minute rate. Web searches are priced at
+//! $10 per 1,000 searches for every model. Override them with
+//! [`PricingTable::with_model`] or load a table with
+//! [`PricingTable::from_json`] when prices change.
+//!
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::decimal::Decimal;
+use crate::types::message::Usage;
+
+/// Prices of a model in USD per million tokens, and per 1,000 web searches
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModelPricing {
+    /// Price of uncached input tokens
+    pub input: Decimal,
+    /// Price of output tokens
+    pub output: Decimal,
+    /// Price of input tokens written to the prompt cache
+    pub cache_write: Decimal,
+    /// Price of input tokens read from the prompt cache
+    pub cache_read: Decimal,
+    /// Price of 1,000 web searches, $10 if not given
+    #[serde(default = "ModelPricing::default_web_search")]
+    pub web_search: Decimal,
+}
+
+impl ModelPricing {
+    /// The built-in price of 1,000 web searches
+    pub const WEB_SEARCH: Decimal = Decimal::new(10, 0);
+
+    fn default_web_search() -> Decimal {
+        Self::WEB_SEARCH
+    }
+
+    /// Prices from cents per million tokens, in the order of the fields
+    const fn cents(input: i128, output: i128, cache_write: i128, cache_read: i128) -> Self {
+        const fn price(cents: i128) -> Decimal {
+            Decimal::new(cents, 2)
+        }
+        Self {
+            input: price(input),
+            output: price(output),
+            cache_write: price(cache_write),
+            cache_read: price(cache_read),
+            web_search: Self::WEB_SEARCH,
+        }
+    }
+}
+
+/// Built-in prices, keyed by model ID prefix
+const BUILTIN_PRICES: &[(&str, ModelPricing)] = &[
+    ("claude-opus-4-5", ModelPricing::cents(500, 2500, 625, 50)),
+    (
+        "claude-opus-4-1",
+        ModelPricing::cents(1500, 7500, 1875, 150),
+    ),
+    ("claude-opus-4", ModelPricing::cents(1500, 7500, 1875, 150)),
+    ("claude-sonnet-4-5", ModelPricing::cents(300, 1500, 375, 30)),
+    ("claude-sonnet-4", ModelPricing::cents(300, 1500, 375, 30)),
+    ("claude-haiku-4-5", ModelPricing::cents(100, 500, 125, 10)),
+    ("claude-3-7-sonnet", ModelPricing::cents(300, 1500, 375, 30)),
+    ("claude-3-5-sonnet", ModelPricing::cents(300, 1500, 375, 30)),
+    ("claude-3-5-haiku", ModelPricing::cents(80, 400, 100, 8)),
+    ("claude-3-opus", ModelPricing::cents(1500, 7500, 1875, 150)),
+    ("claude-3-haiku", ModelPricing::cents(25, 125, 30, 3)),
+];
+
+/// Prices of the models, keyed by model ID or model ID prefix
+///
+/// A model is priced by the entry with its exact ID or, failing that, by the
+/// longest entry that is a prefix of the ID followed by `-`. So an entry for
+/// `claude-3-7-sonnet` prices `claude-3-7-sonnet-20250219` and
+/// `claude-3-7-sonnet-latest`. The default table holds the built-in prices.
+///
+/// Tables serialize as a JSON object mapping models to prices. Prices may be
+/// given as strings or numbers:
+///
+/// ```
+/// use anthropic_ai_sdk::types::pricing::PricingTable;
+///
+/// let table = PricingTable::from_json(r#"{
+///     "my-fine-tune": {"input": "2.5", "output": 10, "cache_write": 3.125, "cache_read": 0.25}
+/// }"#).unwrap();
+/// assert_eq!(table.get("my-fine-tune").unwrap().input.to_string(), "2.5");
+/// assert!(table.get("claude-3-7-sonnet-20250219").is_none());
+///
+/// // Extend the built-in prices rather than replacing them
+/// let table = PricingTable::default().merge(table);
+/// assert!(table.get("claude-3-7-sonnet-20250219").is_some());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct PricingTable {
+    models: BTreeMap<String, ModelPricing>,
+}
+
+impl PricingTable {
+    /// A table without any prices
+    pub fn empty() -> Self {
+        Self {
+            models: BTreeMap::new(),
+        }
+    }
+
+    /// A table with the built-in prices
+    pub fn builtin() -> Self {
+        Self {
+            models: BUILTIN_PRICES
+                .iter()
+                .map(|(model, pricing)| (model.to_string(), *pricing))
+                .collect(),
+        }
+    }
+
+    /// Load a table from JSON, see [`PricingTable`] for the format
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Set the prices of a model ID or model ID prefix
+    pub fn with_model(mut self, model: impl Into<String>, pricing: ModelPricing) -> Self {
+        self.models.insert(model.into(), pricing);
+        self
+    }
+
+    /// Add the prices of `other`, replacing prices of the same models
+    pub fn merge(mut self, other: PricingTable) -> Self {
+        self.models.extend(other.models);
+        self
+    }
+
+    /// The prices of a model, if the table has them
+    pub fn get(&self, model: &str) -> Option<&ModelPricing> {
+        if let Some(pricing) = self.models.get(model) {
+            return Some(pricing);
+        }
+        self.models
+            .iter()
+            .filter(|(prefix, _)| {
+                model
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.starts_with('-'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, pricing)| pricing)
+    }
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// Cost of a request in USD, by kind of token
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CostBreakdown {
+    /// Cost of uncached input tokens
+    pub input: Decimal,
+    /// Cost of output tokens
+    pub output: Decimal,
+    /// Cost of input tokens written to the prompt cache
+    pub cache_write: Decimal,
+    /// Cost of input tokens read from the prompt cache
+    pub cache_read: Decimal,
+    /// Cost of the web searches of server tools
+    #[serde(default)]
+    pub web_search: Decimal,
+}
+
+impl CostBreakdown {
+    /// Total cost of the request
+    pub fn total(&self) -> Decimal {
+        self.input + self.output + self.cache_write + self.cache_read + self.web_search
+    }
+}
+
+impl std::ops::AddAssign for CostBreakdown {
+    fn add_assign(&mut self, other: Self) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_write += other.cache_write;
+        self.cache_read += other.cache_read;
+        self.web_search += other.web_search;
+    }
+}
+
+/// Price the usage of a request to `model`
+///
+/// Returns `None` if the table has no prices for the model. The amounts are
+/// exact up to [`Decimal::MAX_SCALE`] decimal places and rounded beyond;
+/// round them for display only. Web searches reported in
+/// [`Usage::server_tool_use`] are priced too.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::Usage;
+/// use anthropic_ai_sdk::types::pricing::{PricingTable, cost};
+///
+/// let usage: Usage = serde_json::from_str(
+///     r#"{"input_tokens": 1200, "output_tokens": 350, "cache_read_input_tokens": 10000,
+///         "server_tool_use": {"web_search_requests": 2}}"#,
+/// )
+/// .unwrap();
+/// let table = PricingTable::default();
+///
+/// let breakdown = cost(&usage, "claude-3-7-sonnet-20250219", &table).unwrap();
+/// assert_eq!(breakdown.input.to_string(), "0.0036");
+/// assert_eq!(breakdown.output.to_string(), "0.00525");
+/// assert_eq!(breakdown.cache_read.to_string(), "0.003");
+/// assert_eq!(breakdown.web_search.to_string(), "0.02");
+/// assert_eq!(breakdown.total().to_string(), "0.03185");
+///
+/// assert!(cost(&usage, "claude-unreleased", &table).is_none());
+///
+/// // Prices with many decimal places are rounded, not rejected
+/// let table = PricingTable::from_json(
+///     r#"{"claude-mock": {"input": "0.0000000000000000000000015", "output": "1",
+///         "cache_write": "1", "cache_read": "1"}}"#,
+/// )
+/// .unwrap();
+/// let breakdown = cost(&usage, "claude-mock", &table).unwrap();
+/// assert_eq!(breakdown.input.to_string(), "0.0000000000000000000000000018");
+/// ```
+pub fn cost(usage: &Usage, model: &str, table: &PricingTable) -> Option<CostBreakdown> {
+    let pricing = table.get(model)?;
+    // Prices are per `10^places` units
+    let priced = |units: u32, price: Decimal, places: u32| {
+        Decimal::new(price.mantissa() * i128::from(units), price.scale()).div_pow10(places)
+    };
+    let price = |tokens: u32, per_mtok: Decimal| priced(tokens, per_mtok, 6);
+    let searches = usage
+        .server_tool_use
+        .as_ref()
+        .and_then(|usage| usage.web_search_requests)
+        .unwrap_or(0);
+    Some(CostBreakdown {
+        input: price(usage.input_tokens, pricing.input),
+        output: price(usage.output_tokens, pricing.output),
+        cache_write: price(usage.cache_creation_input_tokens(), pricing.cache_write),
+        cache_read: price(usage.cache_read_input_tokens(), pricing.cache_read),
+        web_search: priced(searches, pricing.web_search, 3),
+    })
+}
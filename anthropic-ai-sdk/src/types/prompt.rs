@@ -0,0 +1,436 @@
+//! Prompt templates
+//!
+//! This module contains [`PromptTemplate`], which renders prompts with
+//! `{{variable}}` placeholders, and [`SystemPromptBuilder`], which assembles
+//! system prompts from sections with cache breakpoints.
+//!
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::types::estimate::estimate_tokens;
+use crate::types::message::{
+    CacheControl, ContentBlock, CreateMessageParams, Message, Role, SystemPrompt,
+};
+
+/// Error types for prompt templates
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("Placeholder opened at byte {0} is not closed")]
+    UnclosedPlaceholder(usize),
+    #[error("Invalid variable name {name:?} at byte {offset}")]
+    InvalidVariableName { name: String, offset: usize },
+    #[error("Missing value for variable {0}")]
+    MissingVariable(String),
+    #[error("Value given for unknown variable {0}")]
+    UnknownVariable(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Variable(String),
+}
+
+/// A prompt with `{{variable}}` placeholders, parsed once and rendered per request
+///
+/// Variable names consist of ASCII letters, digits, `_`, `-` and `.`, and may
+/// be surrounded by spaces inside the braces. Templates are strict by
+/// default: rendering fails if a value is missing or given for a variable the
+/// template does not use. Lenient templates render missing variables as empty
+/// text and ignore unknown ones.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use anthropic_ai_sdk::types::prompt::{PromptTemplate, TemplateError};
+///
+/// let template: PromptTemplate = "Translate {{ text }} into {{language}}.".parse().unwrap();
+/// assert_eq!(template.variables(), ["text", "language"]);
+///
+/// let values = HashMap::from([("text", "bonjour"), ("language", "English")]);
+/// assert_eq!(template.render(&values).unwrap(), "Translate bonjour into English.");
+///
+/// let values = HashMap::from([("text", "bonjour")]);
+/// assert_eq!(
+///     template.render(&values),
+///     Err(TemplateError::MissingVariable("language".to_string()))
+/// );
+/// assert_eq!(template.strict(false).render(&values).unwrap(), "Translate bonjour into .");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplate {
+    segments: Vec<Segment>,
+    strict: bool,
+}
+
+impl PromptTemplate {
+    /// Parse a template
+    pub fn new(template: &str) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            let offset = template.len() - rest.len() + start;
+            if start > 0 {
+                segments.push(Segment::Text(rest[..start].to_string()));
+            }
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or(TemplateError::UnclosedPlaceholder(offset))?;
+            let name = after[..end].trim();
+            let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+            if name.is_empty() || !name.chars().all(valid) {
+                return Err(TemplateError::InvalidVariableName {
+                    name: name.to_string(),
+                    offset,
+                });
+            }
+            segments.push(Segment::Variable(name.to_string()));
+            rest = &after[end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_string()));
+        }
+        Ok(Self {
+            segments,
+            strict: true,
+        })
+    }
+
+    /// Set whether rendering fails on missing and unknown variables
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Names of the variables of the template, in order of first use
+    pub fn variables(&self) -> Vec<&str> {
+        let mut variables = Vec::new();
+        for segment in &self.segments {
+            if let Segment::Variable(name) = segment {
+                if !variables.contains(&name.as_str()) {
+                    variables.push(name.as_str());
+                }
+            }
+        }
+        variables
+    }
+
+    /// Render the template with the given values
+    pub fn render(&self, values: &HashMap<&str, &str>) -> Result<String, TemplateError> {
+        self.check(values, true)?;
+        Ok(self.render_segments(&self.segments, values))
+    }
+
+    /// Substitute the given values, keeping the other placeholders
+    ///
+    /// Use this to fill in the values shared by many requests once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use anthropic_ai_sdk::types::prompt::PromptTemplate;
+    ///
+    /// let template = PromptTemplate::new("{{document}}\n\nQuestion: {{question}}").unwrap();
+    /// let partial = template.partial(&HashMap::from([("document", "The sky is blue.")])).unwrap();
+    /// assert_eq!(partial.variables(), ["question"]);
+    /// ```
+    pub fn partial(&self, values: &HashMap<&str, &str>) -> Result<Self, TemplateError> {
+        self.check(values, false)?;
+        let mut segments: Vec<Segment> = Vec::new();
+        for segment in &self.segments {
+            let segment = match segment {
+                Segment::Variable(name) => match values.get(name.as_str()) {
+                    Some(value) => Segment::Text(value.to_string()),
+                    None => segment.clone(),
+                },
+                Segment::Text(_) => segment.clone(),
+            };
+            match (segments.last_mut(), segment) {
+                (Some(Segment::Text(last)), Segment::Text(text)) => last.push_str(&text),
+                (_, segment) => segments.push(segment),
+            }
+        }
+        Ok(Self {
+            segments,
+            strict: self.strict,
+        })
+    }
+
+    /// Render the template into content blocks suited for prompt caching
+    ///
+    /// The text before the first placeholder is the same for every request,
+    /// so it becomes a block of its own with a cache breakpoint. The rendered
+    /// rest of the template follows in a second block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use anthropic_ai_sdk::types::message::{CacheControl, ContentBlock};
+    /// use anthropic_ai_sdk::types::prompt::PromptTemplate;
+    ///
+    /// let template = PromptTemplate::new("{{document}}\n\nQuestion: {{question}}")
+    ///     .unwrap()
+    ///     .partial(&HashMap::from([("document", "The sky is blue.")]))
+    ///     .unwrap();
+    /// let blocks = template
+    ///     .render_blocks(&HashMap::from([("question", "What color is the sky?")]))
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     blocks,
+    ///     [
+    ///         ContentBlock::text("The sky is blue.\n\nQuestion: ")
+    ///             .with_cache_control(CacheControl::ephemeral()),
+    ///         ContentBlock::text("What color is the sky?"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn render_blocks(
+        &self,
+        values: &HashMap<&str, &str>,
+    ) -> Result<Vec<ContentBlock>, TemplateError> {
+        self.check(values, true)?;
+        let split = self
+            .segments
+            .iter()
+            .position(|segment| matches!(segment, Segment::Variable(_)))
+            .unwrap_or(self.segments.len());
+        let (prefix, tail) = self.segments.split_at(split);
+        let mut blocks = Vec::new();
+        let prefix = self.render_segments(prefix, values);
+        if !prefix.is_empty() {
+            blocks.push(ContentBlock::text(prefix).with_cache_control(CacheControl::ephemeral()));
+        }
+        let tail = self.render_segments(tail, values);
+        if !tail.is_empty() {
+            blocks.push(ContentBlock::text(tail));
+        }
+        Ok(blocks)
+    }
+
+    /// Render the template as the system prompt of `params`
+    pub fn render_system(
+        &self,
+        params: CreateMessageParams,
+        values: &HashMap<&str, &str>,
+    ) -> Result<CreateMessageParams, TemplateError> {
+        Ok(params.with_system(self.render(values)?))
+    }
+
+    /// Render the template as a user message, split into cacheable blocks
+    /// as with [`render_blocks`](Self::render_blocks)
+    pub fn render_user(&self, values: &HashMap<&str, &str>) -> Result<Message, TemplateError> {
+        Ok(Message::new_blocks(Role::User, self.render_blocks(values)?))
+    }
+
+    /// In strict mode, check for unknown and, if `complete`, missing values
+    fn check(&self, values: &HashMap<&str, &str>, complete: bool) -> Result<(), TemplateError> {
+        if !self.strict {
+            return Ok(());
+        }
+        let variables = self.variables();
+        if complete {
+            if let Some(missing) = variables.iter().find(|name| !values.contains_key(*name)) {
+                return Err(TemplateError::MissingVariable(missing.to_string()));
+            }
+        }
+        let mut unknown: Vec<&str> = values
+            .keys()
+            .copied()
+            .filter(|name| !variables.contains(name))
+            .collect();
+        unknown.sort_unstable();
+        match unknown.first() {
+            Some(name) => Err(TemplateError::UnknownVariable(name.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn render_segments(&self, segments: &[Segment], values: &HashMap<&str, &str>) -> String {
+        segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.as_str(),
+                Segment::Variable(name) => values.get(name.as_str()).copied().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for PromptTemplate {
+    type Err = TemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        Self::new(template)
+    }
+}
+
+/// Number of cache breakpoints the API allows in a request
+pub const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Section {
+    name: String,
+    text: String,
+    cacheable: bool,
+}
+
+/// Size of a section of a [`SystemPromptBuilder`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionSize {
+    /// Name of the section
+    pub name: String,
+    /// Length of the text in bytes
+    pub bytes: usize,
+    /// Offline estimate of the number of tokens of the text
+    pub estimated_tokens: u32,
+    /// Whether the section was marked cacheable
+    pub cacheable: bool,
+}
+
+/// A system prompt assembled from named sections, with cache breakpoints
+///
+/// Each section becomes a text block of the system prompt. Sections marked
+/// [`cacheable`](Self::cacheable) get a cache breakpoint, so the static
+/// parts of the prompt are cached and the dynamic ones are not. To stay
+/// within [`max_breakpoints`](Self::max_breakpoints), adjacent cacheable
+/// sections are merged into one block, from the start of the prompt; if
+/// that is not enough, the earliest breakpoints are dropped, since a later
+/// breakpoint also caches everything before it. Empty sections are left out.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{CacheControl, ContentBlock, SystemPrompt};
+/// use anthropic_ai_sdk::types::prompt::SystemPromptBuilder;
+///
+/// let builder = SystemPromptBuilder::new()
+///     .section("persona", "You are a support agent.")
+///     .cacheable()
+///     .section("policies", "Never share account data.")
+///     .cacheable()
+///     .section("examples", "Q: Where is my order? A: Let me check.")
+///     .cacheable()
+///     .section("user", "The user is on the Pro plan.")
+///     .max_breakpoints(2);
+///
+/// let cached = |text: &str| ContentBlock::text(text).with_cache_control(CacheControl::ephemeral());
+/// assert_eq!(
+///     builder.build(),
+///     SystemPrompt::Blocks(vec![
+///         cached("You are a support agent.\n\nNever share account data."),
+///         cached("Q: Where is my order? A: Let me check."),
+///         ContentBlock::text("The user is on the Pro plan."),
+///     ])
+/// );
+///
+/// let largest = builder.sizes().into_iter().max_by_key(|size| size.bytes).unwrap();
+/// assert_eq!(largest.name, "examples");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemPromptBuilder {
+    sections: Vec<Section>,
+    max_breakpoints: usize,
+}
+
+impl Default for SystemPromptBuilder {
+    fn default() -> Self {
+        Self {
+            sections: Vec::new(),
+            max_breakpoints: MAX_CACHE_BREAKPOINTS,
+        }
+    }
+}
+
+impl SystemPromptBuilder {
+    /// Create a builder without sections
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a section
+    pub fn section(mut self, name: impl Into<String>, text: impl Into<String>) -> Self {
+        self.sections.push(Section {
+            name: name.into(),
+            text: text.into(),
+            cacheable: false,
+        });
+        self
+    }
+
+    /// Mark the last section as cacheable
+    pub fn cacheable(mut self) -> Self {
+        if let Some(section) = self.sections.last_mut() {
+            section.cacheable = true;
+        }
+        self
+    }
+
+    /// Number of cache breakpoints the system prompt may use
+    ///
+    /// Defaults to [`MAX_CACHE_BREAKPOINTS`]; lower it to leave breakpoints
+    /// for tools and messages.
+    pub fn max_breakpoints(mut self, max_breakpoints: usize) -> Self {
+        self.max_breakpoints = max_breakpoints;
+        self
+    }
+
+    /// Size of each section, in order
+    pub fn sizes(&self) -> Vec<SectionSize> {
+        self.sections
+            .iter()
+            .map(|section| SectionSize {
+                name: section.name.clone(),
+                bytes: section.text.len(),
+                estimated_tokens: estimate_tokens(&section.text),
+                cacheable: section.cacheable,
+            })
+            .collect()
+    }
+
+    /// Build the system prompt blocks
+    pub fn build(&self) -> SystemPrompt {
+        let mut blocks: Vec<(String, bool)> = self
+            .sections
+            .iter()
+            .filter(|section| !section.text.is_empty())
+            .map(|section| (section.text.clone(), section.cacheable))
+            .collect();
+        let mut breakpoints = blocks.iter().filter(|(_, cacheable)| *cacheable).count();
+        let mut index = 0;
+        while breakpoints > self.max_breakpoints && index + 1 < blocks.len() {
+            if blocks[index].1 && blocks[index + 1].1 {
+                let (text, _) = blocks.remove(index);
+                blocks[index].0 = format!("{}\n\n{}", text, blocks[index].0);
+                breakpoints -= 1;
+            } else {
+                index += 1;
+            }
+        }
+        for (_, cacheable) in blocks.iter_mut().filter(|(_, cacheable)| *cacheable) {
+            if breakpoints <= self.max_breakpoints {
+                break;
+            }
+            *cacheable = false;
+            breakpoints -= 1;
+        }
+        SystemPrompt::Blocks(
+            blocks
+                .into_iter()
+                .map(|(text, cacheable)| {
+                    let block = ContentBlock::text(text);
+                    if cacheable {
+                        block.with_cache_control(CacheControl::ephemeral())
+                    } else {
+                        block
+                    }
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,424 @@
+//! Client-side rate limiting
+//!
+//! This module contains [`RateLimiter`], which paces message requests to stay
+//! within the requests, input tokens and output tokens per minute allowed by
+//! the API, instead of running into rate limit errors. Set one on the client
+//! with
+//! [`AnthropicClientBuilder::with_rate_limiter`](crate::client::AnthropicClientBuilder::with_rate_limiter).
+//!
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::types::message::{MessageError, Usage};
+
+/// Limits per minute of a model or of all models
+///
+/// Unset limits are not enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimits {
+    /// Requests per minute
+    pub requests_per_minute: Option<u32>,
+    /// Input tokens per minute, including tokens written to the prompt cache
+    pub input_tokens_per_minute: Option<u32>,
+    /// Output tokens per minute
+    pub output_tokens_per_minute: Option<u32>,
+}
+
+impl RateLimits {
+    /// Create limits enforcing nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the requests per minute
+    pub fn requests_per_minute(mut self, limit: u32) -> Self {
+        self.requests_per_minute = Some(limit);
+        self
+    }
+
+    /// Set the input tokens per minute
+    pub fn input_tokens_per_minute(mut self, limit: u32) -> Self {
+        self.input_tokens_per_minute = Some(limit);
+        self
+    }
+
+    /// Set the output tokens per minute
+    pub fn output_tokens_per_minute(mut self, limit: u32) -> Self {
+        self.output_tokens_per_minute = Some(limit);
+        self
+    }
+}
+
+/// How the input tokens of a request are estimated before it is sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputTokenEstimate {
+    /// With the [offline estimator](crate::types::estimate::estimate_request_tokens)
+    #[default]
+    Offline,
+    /// With a `count_tokens` request, falling back to the offline estimator
+    /// if it fails
+    CountTokens,
+}
+
+/// A token bucket refilled continuously up to the limit per minute
+#[derive(Debug, Clone)]
+struct Bucket {
+    capacity: f64,
+    available: f64,
+}
+
+impl Bucket {
+    fn new(limit: u32) -> Self {
+        Self {
+            capacity: f64::from(limit),
+            available: f64::from(limit),
+        }
+    }
+
+    fn refill(&mut self, elapsed: Duration) {
+        let refilled = self.available + elapsed.as_secs_f64() * self.capacity / 60.0;
+        self.available = refilled.min(self.capacity);
+    }
+
+    /// Time until `amount` can be taken
+    ///
+    /// Amounts above the capacity only wait for a full bucket and leave it
+    /// in debt, so that oversized requests are delayed rather than refused.
+    fn wait(&self, amount: u32) -> Duration {
+        let missing = f64::from(amount).min(self.capacity) - self.available;
+        if missing <= 0.0 || self.capacity == 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(missing * 60.0 / self.capacity)
+        }
+    }
+
+    fn adjust(&mut self, amount: f64) {
+        self.available = (self.available + amount).min(self.capacity);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Buckets {
+    requests: Option<Bucket>,
+    input_tokens: Option<Bucket>,
+    output_tokens: Option<Bucket>,
+}
+
+impl Buckets {
+    fn new(limits: RateLimits) -> Self {
+        Self {
+            requests: limits.requests_per_minute.map(Bucket::new),
+            input_tokens: limits.input_tokens_per_minute.map(Bucket::new),
+            output_tokens: limits.output_tokens_per_minute.map(Bucket::new),
+        }
+    }
+
+    fn all_mut(&mut self) -> impl Iterator<Item = &mut Bucket> {
+        [
+            self.requests.as_mut(),
+            self.input_tokens.as_mut(),
+            self.output_tokens.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    fn wait(&self, input_tokens: u32) -> Duration {
+        [
+            self.requests.as_ref().map(|bucket| bucket.wait(1)),
+            self.input_tokens
+                .as_ref()
+                .map(|bucket| bucket.wait(input_tokens)),
+            self.output_tokens.as_ref().map(|bucket| bucket.wait(0)),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or_default()
+    }
+
+    /// Add `requests` and the given tokens back, negative amounts take them
+    fn adjust(&mut self, requests: f64, input_tokens: f64, output_tokens: f64) {
+        if let Some(bucket) = &mut self.requests {
+            bucket.adjust(requests);
+        }
+        if let Some(bucket) = &mut self.input_tokens {
+            bucket.adjust(input_tokens);
+        }
+        if let Some(bucket) = &mut self.output_tokens {
+            bucket.adjust(output_tokens);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    global: Buckets,
+    models: Vec<(String, Buckets)>,
+    updated: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl State {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now - self.updated;
+        self.updated = now;
+        let models = self.models.iter_mut().map(|(_, buckets)| buckets);
+        for buckets in std::iter::once(&mut self.global).chain(models) {
+            buckets.all_mut().for_each(|bucket| bucket.refill(elapsed));
+        }
+    }
+
+    /// Index of the limits of `model`: an exact match, else the longest
+    /// prefix followed by `-`
+    fn model_index(&self, model: &str) -> Option<usize> {
+        if let Some(index) = self.models.iter().position(|(name, _)| name == model) {
+            return Some(index);
+        }
+        self.models
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, _))| {
+                model
+                    .strip_prefix(name.as_str())
+                    .is_some_and(|rest| rest.starts_with('-'))
+            })
+            .max_by_key(|(_, (name, _))| name.len())
+            .map(|(index, _)| index)
+    }
+
+    fn buckets_mut(&mut self, model: Option<usize>) -> impl Iterator<Item = &mut Buckets> {
+        let model = match model {
+            Some(index) => self.models.get_mut(index).map(|(_, buckets)| buckets),
+            None => None,
+        };
+        std::iter::once(&mut self.global).chain(model)
+    }
+
+    fn wait(&self, model: Option<usize>, input_tokens: u32) -> Duration {
+        let paused = self
+            .paused_until
+            .map(|until| until.saturating_duration_since(self.updated))
+            .unwrap_or_default();
+        let model = model.map_or(Duration::ZERO, |index| {
+            self.models[index].1.wait(input_tokens)
+        });
+        paused.max(model).max(self.global.wait(input_tokens))
+    }
+}
+
+/// Capacity taken for a request by [`RateLimiter::acquire`]
+///
+/// Pass it to [`RateLimiter::record`] with the usage of the response, or to
+/// [`RateLimiter::release`] if the API answered with an error. Dropping the
+/// permit instead gives all of its capacity back, for requests that never
+/// reached the API.
+#[derive(Debug)]
+#[must_use]
+pub struct RateLimitPermit<'a> {
+    limiter: &'a RateLimiter,
+    model: Option<usize>,
+    input_tokens: u32,
+}
+
+impl RateLimitPermit<'_> {
+    /// Estimated input tokens taken for the request
+    pub fn input_tokens(&self) -> u32 {
+        self.input_tokens
+    }
+
+    /// The model index and estimated input tokens, without giving them back
+    fn settle(self) -> (Option<usize>, u32) {
+        let permit = std::mem::ManuallyDrop::new(self);
+        (permit.model, permit.input_tokens)
+    }
+}
+
+impl Drop for RateLimitPermit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.lock();
+        let input = f64::from(self.input_tokens);
+        for buckets in state.buckets_mut(self.model) {
+            buckets.adjust(1.0, input, 0.0);
+        }
+    }
+}
+
+/// Token bucket rate limiter for message requests
+///
+/// Each limit is a bucket holding a minute's worth of capacity, refilled
+/// continuously. A request waits until there is a request and its estimated
+/// input tokens left in the global buckets and in those of its model, and
+/// for any output token debt to be paid off. Once the response arrives, the
+/// estimate is replaced by the actual input tokens and the output tokens are
+/// taken.
+///
+/// Requests that never reached the API, because the connection failed or
+/// they were cancelled before being sent, are given back, so a retried
+/// request is only counted once. Requests the API answered with an error,
+/// including overloaded and rate limit errors, stay counted like the API
+/// counts them. A rate limit error with a `retry-after` delay pauses all
+/// requests for that delay.
+///
+/// Model limits apply to models equal to or starting with the given name
+/// followed by `-`, such as `claude-sonnet-4-5` for
+/// `claude-sonnet-4-5-20250929`, the longest match winning.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{MessageError, Usage};
+/// use anthropic_ai_sdk::types::rate_limit::{RateLimiter, RateLimits};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let limiter = RateLimiter::new(RateLimits::new().requests_per_minute(50)).with_model(
+///     "claude-opus-4",
+///     RateLimits::new().input_tokens_per_minute(1200).output_tokens_per_minute(600),
+/// );
+///
+/// // The first request estimated at 1000 input tokens goes through at once
+/// let permit = limiter.acquire("claude-opus-4-1-20250805", 1000).await;
+/// // but only used 800, leaving 400 of the 1200 per minute
+/// limiter.record(
+///     permit,
+///     &Usage {
+///         input_tokens: 800,
+///         output_tokens: 100,
+///         ..Default::default()
+///     },
+/// );
+/// assert!(limiter.delay("claude-opus-4-1-20250805", 400).is_zero());
+/// assert!(!limiter.delay("claude-opus-4-1-20250805", 1000).is_zero());
+///
+/// // Other models are only limited by the global request limit
+/// assert!(limiter.delay("claude-3-5-haiku-20241022", 1000).is_zero());
+///
+/// // A permit dropped before the request was sent gives its capacity back
+/// let permit = limiter.acquire("claude-opus-4-1-20250805", 400).await;
+/// drop(permit);
+/// assert!(limiter.delay("claude-opus-4-1-20250805", 400).is_zero());
+///
+/// // A request the API answered with an error stays counted
+/// let permit = limiter.acquire("claude-opus-4-1-20250805", 400).await;
+/// limiter.release(permit, &MessageError::ApiError("overloaded".to_string()));
+/// assert!(!limiter.delay("claude-opus-4-1-20250805", 400).is_zero());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    state: Mutex<State>,
+    estimate: InputTokenEstimate,
+}
+
+impl RateLimiter {
+    /// Create a limiter applying `limits` to all requests together
+    ///
+    /// Use [`RateLimits::new`] to only limit individual models.
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            state: Mutex::new(State {
+                global: Buckets::new(limits),
+                models: Vec::new(),
+                updated: Instant::now(),
+                paused_until: None,
+            }),
+            estimate: InputTokenEstimate::default(),
+        }
+    }
+
+    /// Also apply `limits` to the requests for `model`
+    pub fn with_model(self, model: impl Into<String>, limits: RateLimits) -> Self {
+        let mut state = self.state.into_inner().unwrap_or_else(|e| e.into_inner());
+        state.models.push((model.into(), Buckets::new(limits)));
+        Self {
+            state: Mutex::new(state),
+            estimate: self.estimate,
+        }
+    }
+
+    /// Set how the input tokens of requests are estimated
+    pub fn with_input_token_estimate(mut self, estimate: InputTokenEstimate) -> Self {
+        self.estimate = estimate;
+        self
+    }
+
+    /// How the input tokens of requests are estimated
+    pub fn input_token_estimate(&self) -> InputTokenEstimate {
+        self.estimate
+    }
+
+    /// Time a request for `model` with `input_tokens` would wait now
+    pub fn delay(&self, model: &str, input_tokens: u32) -> Duration {
+        let mut state = self.lock();
+        state.refill();
+        let index = state.model_index(model);
+        state.wait(index, input_tokens)
+    }
+
+    /// Wait until a request for `model` with `input_tokens` may be sent and take its capacity
+    pub async fn acquire(&self, model: &str, input_tokens: u32) -> RateLimitPermit<'_> {
+        loop {
+            let wait = {
+                let mut state = self.lock();
+                state.refill();
+                let index = state.model_index(model);
+                let wait = state.wait(index, input_tokens);
+                if wait.is_zero() {
+                    let input = f64::from(input_tokens);
+                    for buckets in state.buckets_mut(index) {
+                        buckets.adjust(-1.0, -input, 0.0);
+                    }
+                    return RateLimitPermit {
+                        limiter: self,
+                        model: index,
+                        input_tokens,
+                    };
+                }
+                wait
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Replace the estimate of `permit` with the `usage` of the response
+    pub fn record(&self, permit: RateLimitPermit<'_>, usage: &Usage) {
+        let input = usage.input_tokens + usage.cache_creation_input_tokens();
+        self.record_tokens(permit, input, usage.output_tokens);
+    }
+
+    /// Replace the estimate of `permit` with the given input and output tokens
+    pub(crate) fn record_tokens(&self, permit: RateLimitPermit<'_>, input: u32, output: u32) {
+        let (model, estimate) = permit.settle();
+        let mut state = self.lock();
+        let refund = f64::from(estimate) - f64::from(input);
+        for buckets in state.buckets_mut(model) {
+            buckets.adjust(0.0, refund, -f64::from(output));
+        }
+    }
+
+    /// Settle the permit of a request the API answered with an error
+    ///
+    /// The request and its estimated input tokens stay counted, as the API
+    /// received them. If `error` is a rate limit error with a requested
+    /// delay, all requests are paused for that delay. Drop the permit instead
+    /// if the request never reached the API.
+    pub fn release(&self, permit: RateLimitPermit<'_>, error: &MessageError) {
+        permit.settle();
+        let mut state = self.lock();
+        if let MessageError::RateLimited {
+            retry_after: Some(retry_after),
+            ..
+        } = error
+        {
+            let until = Instant::now() + *retry_after;
+            state.paused_until = Some(state.paused_until.map_or(until, |paused| paused.max(until)));
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
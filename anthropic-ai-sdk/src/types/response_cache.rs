@@ -0,0 +1,437 @@
+//! Response caching
+//!
+//! This module contains [`ResponseCache`], which lets a client answer repeated
+//! identical message requests from a [`ResponseStore`] instead of the API.
+//! Set one with
+//! [`AnthropicClientBuilder::with_response_cache`](crate::client::AnthropicClientBuilder::with_response_cache).
+//! [`MemoryStore`] keeps responses in memory; other stores, e.g. on disk, can
+//! implement the trait.
+//!
+//! Not to be confused with prompt caching, which the API does on its side for
+//! prompts marked with [`CacheControl`](crate::types::message::CacheControl).
+//!
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::types::beta::Beta;
+use crate::types::message::{CreateMessageParams, CreateMessageResponse};
+
+/// Key of a cached response: a hash of the request body and beta headers
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CacheKey(String);
+
+impl CacheKey {
+    /// The key of the request `params` would send
+    ///
+    /// The key is a 128-bit FNV-1a hash of the JSON body and the beta
+    /// headers, in hexadecimal. The body is hashed with the keys of every
+    /// object sorted, since maps such as
+    /// [`Metadata::extra`](crate::types::message::Metadata::extra) serialize
+    /// in an unspecified order, so the key is stable across runs and builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, Metadata, RequiredMessageParams, Role,
+    /// };
+    /// use anthropic_ai_sdk::types::response_cache::CacheKey;
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-7-sonnet-20250219".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hello")],
+    ///     max_tokens: 1024,
+    /// });
+    /// let key = CacheKey::new(&params);
+    /// assert_eq!(key.as_str().len(), 32);
+    /// assert_eq!(CacheKey::new(&params.clone().with_bypass_response_cache(true)), key);
+    /// assert_ne!(CacheKey::new(&params.clone().with_temperature(0.0)), key);
+    ///
+    /// // Metadata fields are hashed in the same order however they were added
+    /// let fields = ["a", "b", "c", "d", "e", "f", "g", "h"];
+    /// let forward = fields.iter().fold(Metadata::default(), |m, f| m.with_field(*f, 1));
+    /// let backward = fields.iter().rev().fold(Metadata::default(), |m, f| m.with_field(*f, 1));
+    /// assert_eq!(
+    ///     CacheKey::new(&params.clone().with_metadata(forward)),
+    ///     CacheKey::new(&params.with_metadata(backward))
+    /// );
+    /// ```
+    pub fn new(params: &CreateMessageParams) -> Self {
+        let mut body = String::new();
+        if let Ok(value) = serde_json::to_value(params) {
+            write_canonical(&value, &mut body);
+        }
+        let betas = params.betas();
+        let betas: Vec<&str> = betas.iter().map(Beta::as_str).collect();
+        let hash = fnv1a_128(&[body.as_bytes(), b"\n", betas.join(",").as_bytes()]);
+        Self(format!("{:032x}", hash))
+    }
+
+    /// The key as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Write `value` as compact JSON with the keys of every object sorted
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+/// 128-bit FNV-1a hash of the concatenated `parts`
+fn fnv1a_128(parts: &[&[u8]]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+    let mut hash = OFFSET_BASIS;
+    for byte in parts.iter().flat_map(|part| part.iter()) {
+        hash ^= u128::from(*byte);
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// A response with the time it was stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// The response
+    pub response: CreateMessageResponse,
+    /// When the response was stored
+    #[serde(with = "time::serde::rfc3339")]
+    pub stored_at: time::OffsetDateTime,
+}
+
+/// Storage of cached responses
+///
+/// Stores keep entries as given; [`ResponseCache`] checks their age.
+#[async_trait]
+pub trait ResponseStore: Send + Sync {
+    /// The entry stored under `key`, if any
+    async fn get(&self, key: &CacheKey) -> Option<CachedResponse>;
+
+    /// Store `entry` under `key`, replacing any previous entry
+    async fn put(&self, key: CacheKey, entry: CachedResponse);
+
+    /// Remove the entry stored under `key`
+    async fn remove(&self, key: &CacheKey);
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    entries: HashMap<CacheKey, (u64, CachedResponse)>,
+    order: BTreeMap<u64, CacheKey>,
+    tick: u64,
+}
+
+impl Lru {
+    fn touch(&mut self, key: &CacheKey) -> Option<CachedResponse> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (used, entry) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        self.order.insert(tick, key.clone());
+        *used = tick;
+        Some(entry.clone())
+    }
+}
+
+/// In-memory [`ResponseStore`] evicting the least recently used entries
+#[derive(Debug)]
+pub struct MemoryStore {
+    capacity: usize,
+    lru: Mutex<Lru>,
+}
+
+impl MemoryStore {
+    /// Create a store holding at most `capacity` responses, at least 1
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            lru: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// Number of stored responses
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether no responses are stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.lru.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl ResponseStore for MemoryStore {
+    async fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+        self.lock().touch(key)
+    }
+
+    async fn put(&self, key: CacheKey, entry: CachedResponse) {
+        let mut lru = self.lock();
+        lru.tick += 1;
+        let tick = lru.tick;
+        if let Some((used, _)) = lru.entries.insert(key.clone(), (tick, entry)) {
+            lru.order.remove(&used);
+        }
+        lru.order.insert(tick, key);
+        while lru.entries.len() > self.capacity {
+            let Some((_, oldest)) = lru.order.pop_first() else {
+                break;
+            };
+            lru.entries.remove(&oldest);
+        }
+    }
+
+    async fn remove(&self, key: &CacheKey) {
+        let mut lru = self.lock();
+        if let Some((used, _)) = lru.entries.remove(key) {
+            lru.order.remove(&used);
+        }
+    }
+}
+
+/// Result of looking up a request in a [`ResponseCache`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheLookup {
+    /// The response was taken from the cache
+    Hit,
+    /// The request was sent, no fresh response was cached
+    Miss,
+    /// The request was sent because it set
+    /// [`bypass_response_cache`](CreateMessageParams::bypass_response_cache)
+    Bypassed,
+}
+
+type LookupHook = Box<dyn Fn(CacheLookup, &CacheKey) + Send + Sync>;
+
+/// Cache of message responses for identical requests
+///
+/// Requests are keyed with [`CacheKey::new`]. Only successful responses to
+/// non-streaming requests are cached; streaming requests always go to the
+/// API. Entries older than the [`ttl`](Self::ttl) are treated as missing.
+///
+/// The cache returns the same response for the same request even where the
+/// model would answer differently, so use it for deterministic requests, e.g.
+/// with a temperature of 0, or where any earlier answer will do.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use anthropic_ai_sdk::client::AnthropicClient;
+/// use anthropic_ai_sdk::testing::vcr::{
+///     Cassette, Interaction, RecordedRequest, RecordedResponse, RequestMatcher, Vcr, VcrMode,
+/// };
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
+/// };
+/// use anthropic_ai_sdk::types::response_cache::{CacheLookup, MemoryStore, ResponseCache};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::env::temp_dir().join(format!("response-cache-{}.json", std::process::id()));
+/// let body = r#"{"content": [{"type": "text", "text": "Hi"}], "id": "msg_01",
+///     "model": "claude-3-7-sonnet-20250219", "role": "assistant", "type": "message",
+///     "stop_reason": "end_turn", "usage": {"input_tokens": 9, "output_tokens": 2}}"#;
+/// let interaction = |body: &str| Interaction {
+///     request: RecordedRequest {
+///         method: "POST".to_string(),
+///         path: "/messages".to_string(),
+///         headers: Vec::new(),
+///         body: String::new(),
+///     },
+///     response: RecordedResponse::json(200, body),
+/// };
+/// Cassette {
+///     interactions: vec![interaction(body), interaction(&body.replace("Hi", "Bye"))],
+/// }
+/// .save(&path)?;
+/// let server = Vcr::new(&path)
+///     .mode(VcrMode::Replay)
+///     .matcher(RequestMatcher::new().body(false))
+///     .start()
+///     .await?;
+///
+/// let hits = Arc::new(AtomicUsize::new(0));
+/// let counter = hits.clone();
+/// let cache = ResponseCache::new(MemoryStore::new(1000)).on_lookup(move |lookup, _key| {
+///     if lookup == CacheLookup::Hit {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///     }
+/// });
+/// let client = AnthropicClient::builder("sk-test", "2023-06-01")
+///     .with_api_base_url(server.url())
+///     .with_response_cache(cache)
+///     .build::<MessageError>()?;
+/// let params = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-3-7-sonnet-20250219".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Hello")],
+///     max_tokens: 1024,
+/// })
+/// .with_temperature(0.0);
+///
+/// let first = client.create_message(Some(&params)).await?;
+/// let second = client.create_message(Some(&params)).await?;
+/// assert_eq!(first, second);
+/// assert_eq!(hits.load(Ordering::Relaxed), 1);
+///
+/// // A bypassing request goes to the API and leaves the cached response alone
+/// let bypassed = params.clone().with_bypass_response_cache(true);
+/// assert_eq!(client.create_message(Some(&bypassed)).await?.text(), "Bye");
+/// assert_eq!(client.create_message(Some(&params)).await?, first);
+///
+/// let stats = client.response_cache().unwrap();
+/// assert_eq!((stats.hits(), stats.misses()), (2, 1));
+/// # server.finish().await?;
+/// # std::fs::remove_file(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResponseCache {
+    store: Box<dyn ResponseStore>,
+    ttl: Option<Duration>,
+    on_lookup: Option<LookupHook>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttl", &self.ttl)
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
+
+impl ResponseCache {
+    /// Create a cache keeping responses in `store` without expiry
+    pub fn new(store: impl ResponseStore + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+            ttl: None,
+            on_lookup: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Treat responses older than `ttl` as missing
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Call `on_lookup` with the result of each lookup
+    pub fn on_lookup(
+        mut self,
+        on_lookup: impl Fn(CacheLookup, &CacheKey) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_lookup = Some(Box::new(on_lookup));
+        self
+    }
+
+    /// Number of requests answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of requests sent because no fresh response was cached
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// The cached response to `params`, if fresh
+    ///
+    /// Returns the key to store the response under if the request is sent.
+    pub(crate) async fn lookup(
+        &self,
+        params: &CreateMessageParams,
+    ) -> Result<CreateMessageResponse, CacheKey> {
+        let key = CacheKey::new(params);
+        if params.bypass_response_cache {
+            self.report(CacheLookup::Bypassed, &key);
+            return Err(key);
+        }
+        if let Some(entry) = self.store.get(&key).await {
+            if self.is_fresh(&entry) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                self.report(CacheLookup::Hit, &key);
+                return Ok(entry.response);
+            }
+            self.store.remove(&key).await;
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        self.report(CacheLookup::Miss, &key);
+        Err(key)
+    }
+
+    /// Store the response to the request of `key`
+    pub(crate) async fn store(&self, key: CacheKey, response: &CreateMessageResponse) {
+        let entry = CachedResponse {
+            response: response.clone(),
+            stored_at: SystemTime::now().into(),
+        };
+        self.store.put(key, entry).await;
+    }
+
+    fn is_fresh(&self, entry: &CachedResponse) -> bool {
+        let Some(ttl) = self.ttl else {
+            return true;
+        };
+        let age = time::OffsetDateTime::now_utc() - entry.stored_at;
+        age < ttl
+    }
+
+    fn report(&self, lookup: CacheLookup, key: &CacheKey) {
+        if let Some(on_lookup) = &self.on_lookup {
+            on_lookup(lookup, key);
+        }
+    }
+}
@@ -0,0 +1,591 @@
+//! Tool helpers
+//!
+//! This module contains helpers for defining tools and their input schemas.
+//!
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+use thiserror::Error;
+
+use crate::types::message::{CacheControl, ContentBlock, Tool, ToolResultContent};
+
+/// Error types for tool definitions
+#[derive(Debug, Error)]
+pub enum ToolSchemaError {
+    #[error("Tool input schema must be a JSON object with \"type\": \"object\"")]
+    NotAnObject,
+    #[error("Tool input schema \"properties\" must be an object")]
+    InvalidProperties,
+    #[error("Tool input schema \"required\" must be an array of strings")]
+    InvalidRequired,
+    #[error("Required property \"{0}\" is not defined in \"properties\"")]
+    UnknownRequiredProperty(String),
+}
+
+/// Error types for parsing tool inputs
+#[derive(Debug, Error)]
+pub enum ToolInputError {
+    #[error("Content block is not a tool use block")]
+    NotToolUse,
+    #[error("Failed to parse tool input: {0}")]
+    Deserialize(#[from] serde_json::Error),
+}
+
+/// JSON Schema type of a tool parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParamType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    Object,
+}
+
+impl ParamType {
+    /// The JSON Schema name of the type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParamType::String => "string",
+            ParamType::Number => "number",
+            ParamType::Integer => "integer",
+            ParamType::Boolean => "boolean",
+            ParamType::Object => "object",
+        }
+    }
+}
+
+/// Builder for [`Tool`] definitions
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::Tool;
+/// use anthropic_ai_sdk::types::tool::ParamType;
+///
+/// let tool = Tool::builder("get_weather")
+///     .description("Get the current weather in a given location")
+///     .param("city", ParamType::String, "City name", true)
+///     .enum_param("unit", &["celsius", "fahrenheit"], "Temperature unit", false)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(tool.input_schema["required"], serde_json::json!(["city"]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ToolBuilder {
+    name: String,
+    description: Option<String>,
+    properties: Map<String, Value>,
+    required: Vec<String>,
+    input_schema: Option<Value>,
+}
+
+impl ToolBuilder {
+    /// Create a new builder for a tool with the given name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            properties: Map::new(),
+            required: Vec::new(),
+            input_schema: None,
+        }
+    }
+
+    /// Set the description of the tool
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a parameter of a simple type
+    pub fn param(
+        self,
+        name: impl Into<String>,
+        param_type: ParamType,
+        description: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        let schema = json!({
+            "type": param_type.as_str(),
+            "description": description.into(),
+        });
+        self.property(name, schema, required)
+    }
+
+    /// Add an array parameter whose items are of the given type
+    pub fn array_param(
+        self,
+        name: impl Into<String>,
+        item_type: ParamType,
+        description: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        let schema = json!({
+            "type": "array",
+            "items": { "type": item_type.as_str() },
+            "description": description.into(),
+        });
+        self.property(name, schema, required)
+    }
+
+    /// Add a string parameter restricted to the given values
+    pub fn enum_param(
+        self,
+        name: impl Into<String>,
+        values: &[&str],
+        description: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        let schema = json!({
+            "type": "string",
+            "enum": values,
+            "description": description.into(),
+        });
+        self.property(name, schema, required)
+    }
+
+    /// Add a parameter with a hand-written JSON Schema
+    pub fn property(mut self, name: impl Into<String>, schema: Value, required: bool) -> Self {
+        let name = name.into();
+        if required && !self.required.contains(&name) {
+            self.required.push(name.clone());
+        }
+        self.properties.insert(name, schema);
+        self
+    }
+
+    /// Use a complete hand-written input schema instead of the added parameters
+    pub fn input_schema(mut self, input_schema: Value) -> Self {
+        self.input_schema = Some(input_schema);
+        self
+    }
+
+    /// Build and validate the tool definition
+    pub fn build(self) -> Result<Tool, ToolSchemaError> {
+        let input_schema = match self.input_schema {
+            Some(input_schema) => input_schema,
+            None => {
+                let mut schema = json!({
+                    "type": "object",
+                    "properties": self.properties,
+                });
+                if !self.required.is_empty() {
+                    schema["required"] = json!(self.required);
+                }
+                schema
+            }
+        };
+        validate_input_schema(&input_schema)?;
+        Ok(Tool {
+            name: self.name,
+            description: self.description,
+            input_schema,
+            cache_control: None,
+        })
+    }
+}
+
+/// Validate that a tool input schema is well-formed
+///
+/// Checks that the schema is an object schema and that every required
+/// property is defined in `properties`.
+pub fn validate_input_schema(schema: &Value) -> Result<(), ToolSchemaError> {
+    if schema.get("type").and_then(Value::as_str) != Some("object") {
+        return Err(ToolSchemaError::NotAnObject);
+    }
+    let properties = match schema.get("properties") {
+        None => None,
+        Some(Value::Object(properties)) => Some(properties),
+        Some(_) => return Err(ToolSchemaError::InvalidProperties),
+    };
+    let required = match schema.get("required") {
+        None => return Ok(()),
+        Some(Value::Array(required)) => required,
+        Some(_) => return Err(ToolSchemaError::InvalidRequired),
+    };
+    for name in required {
+        let name = name.as_str().ok_or(ToolSchemaError::InvalidRequired)?;
+        if !properties.is_some_and(|p| p.contains_key(name)) {
+            return Err(ToolSchemaError::UnknownRequiredProperty(name.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Deserialize the input of a `tool_use` block into a concrete type
+pub fn parse_tool_input<T: DeserializeOwned>(block: &ContentBlock) -> Result<T, ToolInputError> {
+    match block {
+        ContentBlock::ToolUse { input, .. } => Ok(T::deserialize(input)?),
+        _ => Err(ToolInputError::NotToolUse),
+    }
+}
+
+/// Borrowed view of a `tool_use` block
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToolUseRef<'a> {
+    /// ID of the tool use, referenced by the tool result
+    pub id: &'a str,
+    /// Name of the tool
+    pub name: &'a str,
+    /// Input for the tool
+    pub input: &'a Value,
+}
+
+impl<'a> ToolUseRef<'a> {
+    /// View a content block as a tool use, if it is one
+    pub fn from_block(block: &'a ContentBlock) -> Option<Self> {
+        match block {
+            ContentBlock::ToolUse { id, name, input } => Some(Self { id, name, input }),
+            _ => None,
+        }
+    }
+
+    /// Deserialize the input into a concrete type
+    pub fn parse_input<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(self.input)
+    }
+
+    /// Create the `tool_result` block answering this tool use
+    pub fn result(&self, content: impl Into<ToolResultContent>) -> ContentBlock {
+        ContentBlock::tool_result(self.id, content)
+    }
+
+    /// Create the `tool_result` block reporting that this tool use failed
+    pub fn error(&self, content: impl Into<ToolResultContent>) -> ContentBlock {
+        ContentBlock::tool_error(self.id, content)
+    }
+}
+
+/// How [`ContentBlock::tool_result_from_with`] renders the result of a tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ToolResultOptions {
+    /// Serialize successful results as indented JSON
+    pub pretty: bool,
+    /// Maximum length of the result text in bytes, before the truncation marker
+    pub max_len: Option<usize>,
+}
+
+impl ToolResultOptions {
+    /// Compact JSON without a length limit
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serialize successful results as indented JSON
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Truncate result texts longer than `max_len` bytes
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Render a successful result, which is used as it is if it serializes to a JSON string
+    pub(crate) fn render_output<T: Serialize>(&self, output: &T) -> Result<String, String> {
+        let value = serde_json::to_value(output)
+            .map_err(|e| format!("Failed to serialize tool output: {}", e))?;
+        let text = match value {
+            Value::String(text) => text,
+            value if self.pretty => serde_json::to_string_pretty(&value).unwrap_or_default(),
+            value => value.to_string(),
+        };
+        Ok(text)
+    }
+
+    /// Cut `text` to `max_len` bytes at a character boundary and mark the cut
+    pub(crate) fn truncate(&self, mut text: String) -> String {
+        let Some(max_len) = self.max_len.filter(|&max_len| text.len() > max_len) else {
+            return text;
+        };
+        let total = text.len();
+        let cut = (0..=max_len)
+            .rev()
+            .find(|&cut| text.is_char_boundary(cut))
+            .unwrap_or(0);
+        text.truncate(cut);
+        text.push_str(&format!("\n[truncated {} of {} bytes]", total - cut, total));
+        text
+    }
+}
+
+/// A type usable as a parameter of a tool defined with `#[anthropic_tool]`
+///
+/// Implement it for your own types to use them as parameters, for example
+/// with [`input_schema_for`] when the `schemars` feature is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::tool::ToolParam;
+/// use serde::Deserialize;
+/// use serde_json::{Value, json};
+///
+/// #[derive(Deserialize)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Unit {
+///     Celsius,
+///     Fahrenheit,
+/// }
+///
+/// impl ToolParam for Unit {
+///     fn schema() -> Value {
+///         json!({"type": "string", "enum": ["celsius", "fahrenheit"]})
+///     }
+/// }
+///
+/// assert_eq!(<Option<Unit>>::schema()["enum"][0], "celsius");
+/// assert!(!<Option<Unit>>::required());
+/// assert_eq!(<Vec<u32>>::schema(), json!({"type": "array", "items": {"type": "integer"}}));
+/// ```
+pub trait ToolParam: DeserializeOwned {
+    /// JSON Schema of the parameter
+    fn schema() -> Value;
+
+    /// Whether the parameter must be given
+    fn required() -> bool {
+        true
+    }
+}
+
+macro_rules! impl_tool_param {
+    ($type_:literal: $($t:ty),*) => {
+        $(
+            impl ToolParam for $t {
+                fn schema() -> Value {
+                    json!({"type": $type_})
+                }
+            }
+        )*
+    };
+}
+
+impl_tool_param!("string": String, char);
+impl_tool_param!("boolean": bool);
+impl_tool_param!("integer": i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_tool_param!("number": f32, f64);
+
+impl<T: ToolParam> ToolParam for Option<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+impl<T: ToolParam> ToolParam for Vec<T> {
+    fn schema() -> Value {
+        json!({"type": "array", "items": T::schema()})
+    }
+}
+
+impl ToolParam for Value {
+    fn schema() -> Value {
+        json!({})
+    }
+}
+
+/// Generate a tool input schema from a Rust type
+///
+/// Subschemas are inlined since the API does not resolve `$ref`s. Recursive
+/// types cannot be fully inlined and are not supported.
+#[cfg(feature = "schemars")]
+pub fn input_schema_for<T: schemars::JsonSchema>() -> Value {
+    let generator = schemars::generate::SchemaSettings::draft07()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator();
+    let mut schema = generator.into_root_schema_for::<T>().to_value();
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+        object.remove("title");
+        object.remove("definitions");
+    }
+    schema
+}
+
+impl Tool {
+    /// Create a builder for a tool with the given name
+    pub fn builder(name: impl Into<String>) -> ToolBuilder {
+        ToolBuilder::new(name)
+    }
+
+    /// Set a cache breakpoint after this tool definition
+    pub fn with_cache_control(mut self, cache_control: CacheControl) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+
+    /// Create a tool whose input schema is generated from `T`
+    ///
+    /// Pair it with [`parse_tool_input`] to read the model's tool calls back into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::Tool;
+    /// use schemars::JsonSchema;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, JsonSchema)]
+    /// #[serde(rename_all = "lowercase")]
+    /// enum Unit {
+    ///     Celsius,
+    ///     Fahrenheit,
+    /// }
+    ///
+    /// #[derive(Deserialize, JsonSchema)]
+    /// struct WeatherQuery {
+    ///     /// City name
+    ///     city: String,
+    ///     unit: Option<Unit>,
+    /// }
+    ///
+    /// let tool = Tool::from_type::<WeatherQuery>("get_weather", "Get the weather");
+    /// assert_eq!(tool.input_schema["required"], serde_json::json!(["city"]));
+    /// ```
+    #[cfg(feature = "schemars")]
+    pub fn from_type<T: schemars::JsonSchema>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Tool {
+            name: name.into(),
+            description: Some(description.into()),
+            input_schema: input_schema_for::<T>(),
+            cache_control: None,
+        }
+    }
+}
+
+/// Tool definition bound to the Rust type of its input
+///
+/// The input schema is generated from `T`, and tool calls are parsed back into
+/// `T`, so the two cannot drift apart.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{CreateMessageResponse, Tool};
+/// use anthropic_ai_sdk::types::tool::TypedTool;
+/// use schemars::JsonSchema;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, JsonSchema)]
+/// struct WeatherQuery {
+///     /// City name
+///     city: String,
+/// }
+///
+/// let weather = TypedTool::<WeatherQuery>::new("get_weather", "Get the current weather");
+/// let tool: Tool = weather.tool();
+/// assert_eq!(tool.input_schema["required"], serde_json::json!(["city"]));
+///
+/// let response: CreateMessageResponse = serde_json::from_str(r#"{
+///     "id": "msg_01",
+///     "type": "message",
+///     "role": "assistant",
+///     "model": "claude-sonnet-4-20250514",
+///     "content": [
+///         {"type": "text", "text": "Let me check."},
+///         {"type": "tool_use", "id": "toolu_01", "name": "get_weather",
+///          "input": {"city": "Paris"}}
+///     ],
+///     "stop_reason": "tool_use",
+///     "stop_sequence": null,
+///     "usage": {"input_tokens": 12, "output_tokens": 30}
+/// }"#).unwrap();
+///
+/// let queries: Vec<WeatherQuery> = response
+///     .content
+///     .iter()
+///     .filter_map(|block| weather.matches(block))
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(queries.len(), 1);
+/// assert_eq!(queries[0].city, "Paris");
+/// ```
+#[cfg(feature = "schemars")]
+pub struct TypedTool<T> {
+    name: String,
+    description: String,
+    input: std::marker::PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema + DeserializeOwned> TypedTool<T> {
+    /// Create a typed tool with the given name and description
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            input: std::marker::PhantomData,
+        }
+    }
+
+    /// Name of the tool
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Wire definition of the tool, with the input schema generated from `T`
+    pub fn tool(&self) -> Tool {
+        Tool::from_type::<T>(self.name.clone(), self.description.clone())
+    }
+
+    /// Parse the input of a tool use block calling this tool
+    ///
+    /// Returns `None` if the block is not a tool use of this tool.
+    pub fn matches(&self, block: &ContentBlock) -> Option<Result<T, ToolInputError>> {
+        let tool_use = ToolUseRef::from_block(block)?;
+        (tool_use.name == self.name).then(|| Ok(tool_use.parse_input()?))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T> std::fmt::Debug for TypedTool<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypedTool")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T> Clone for TypedTool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            input: std::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema + DeserializeOwned> From<TypedTool<T>> for Tool {
+    fn from(tool: TypedTool<T>) -> Self {
+        tool.tool()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema + DeserializeOwned> From<&TypedTool<T>> for Tool {
+    fn from(tool: &TypedTool<T>) -> Self {
+        tool.tool()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema + DeserializeOwned> From<&TypedTool<T>>
+    for crate::types::message::ToolUnion
+{
+    fn from(tool: &TypedTool<T>) -> Self {
+        Self::Custom(tool.tool())
+    }
+}
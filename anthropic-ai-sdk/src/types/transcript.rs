@@ -0,0 +1,346 @@
+//! Plain text transcripts
+//!
+//! This module contains [`TextReportOptions`], the options of
+//! [`Conversation::to_text_report_with`], which writes a conversation as plain
+//! text for logs and support tickets.
+//!
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::types::conversation::Conversation;
+use crate::types::decimal::Decimal;
+use crate::types::message::{ContentBlock, Message, Role, ToolResultContent, Usage};
+use crate::types::pricing::{PricingTable, cost};
+
+/// Number of characters of a tool payload written by default
+pub const DEFAULT_MAX_PAYLOAD_CHARS: usize = 500;
+
+/// Options of [`Conversation::to_text_report_with`]
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::MockMessageClient;
+/// use anthropic_ai_sdk::types::compaction::{CompactionOptions, ModelCompactor};
+/// use anthropic_ai_sdk::types::conversation::Conversation;
+/// use anthropic_ai_sdk::types::decimal::Decimal;
+/// use anthropic_ai_sdk::types::pricing::{ModelPricing, PricingTable};
+/// use anthropic_ai_sdk::types::transcript::TextReportOptions;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = MockMessageClient::new()
+///     .tool_use_response("lookup_order", serde_json::json!({"order": "A-1234"}))
+///     .text_response("Your order has shipped.")
+///     .text_response("You're welcome.");
+/// let mut conversation =
+///     Conversation::new("claude-sonnet-4-5", 1024).with_system("You are a support agent.");
+/// let reply = conversation.send(&client, "Where is my order?").await?;
+/// let result = reply.tool_uses().next().unwrap().result("Shipped on Monday");
+/// conversation.send_blocks(&client, vec![result]).await?;
+///
+/// // $3 per million tokens of any kind
+/// let price = Decimal::new(300, 2);
+/// let pricing = PricingTable::empty().with_model(
+///     "claude-mock",
+///     ModelPricing {
+///         input: price,
+///         output: price,
+///         cache_write: price,
+///         cache_read: price,
+///         web_search: ModelPricing::WEB_SEARCH,
+///     },
+/// );
+/// let options = TextReportOptions::new().max_payload_chars(10);
+/// let report = conversation.to_text_report_with(Some(&pricing), &options);
+/// assert_eq!(
+///     report,
+///     "\
+/// Model: claude-sonnet-4-5
+/// System:
+///   You are a support agent.
+///
+/// #1 User
+///   Where is my order?
+///
+/// #2 Assistant
+///   Tool use lookup_order: {\"order\":\"... [truncated, 18 chars]
+///   Usage: input 10, output 14, cache write 0, cache read 0, cost $0.000072
+///
+/// #3 User
+///   Tool result lookup_order: Shipped on... [truncated, 17 chars]
+///
+/// #4 Assistant
+///   Your order has shipped.
+///   Usage: input 10, output 6, cache write 0, cache read 0, cost $0.000048
+///
+/// Total: 2 requests, input 20, output 20, cache write 0, cache read 0, cost $0.00012
+/// "
+/// );
+///
+/// // The summary of compacted turns is labeled
+/// conversation.send(&client, "Thanks!").await?;
+/// let summarizer = MockMessageClient::new().text_response("The user asked about order A-1234.");
+/// let compactor = ModelCompactor::new(&summarizer);
+/// let options = CompactionOptions::new(0).keep_turns(1);
+/// conversation.compact(&compactor, &options).await?;
+/// let report = conversation.to_text_report(None);
+/// assert!(report.contains(
+///     "\
+/// #1 User
+///   Summary of compacted turns:
+///     The user asked about order A-1234.
+///   Thanks!
+/// "
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextReportOptions {
+    /// Number of characters of tool inputs and results to write
+    pub max_payload_chars: usize,
+}
+
+impl Default for TextReportOptions {
+    fn default() -> Self {
+        Self {
+            max_payload_chars: DEFAULT_MAX_PAYLOAD_CHARS,
+        }
+    }
+}
+
+impl TextReportOptions {
+    /// Options writing [`DEFAULT_MAX_PAYLOAD_CHARS`] characters of each payload
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cut tool inputs and results after `max_payload_chars` characters
+    pub fn max_payload_chars(mut self, max_payload_chars: usize) -> Self {
+        self.max_payload_chars = max_payload_chars;
+        self
+    }
+}
+
+/// Write `conversation` as a plain text report
+pub(crate) fn text_report(
+    conversation: &Conversation,
+    pricing: Option<&PricingTable>,
+    options: &TextReportOptions,
+) -> String {
+    let mut report = Report {
+        text: String::new(),
+        pricing,
+        options,
+        tool_names: HashMap::new(),
+    };
+    report.line(0, &format!("Model: {}", conversation.params().model));
+    if let Some(system) = &conversation.params().system {
+        report.line(0, "System:");
+        report.lines(1, &system.text());
+    }
+    let requests = conversation.request_usage();
+    let summary = conversation.summary();
+    for (index, message) in conversation.messages().iter().enumerate() {
+        report.text.push('\n');
+        report.message(index, message, summary.filter(|_| index == 0));
+        for request in requests
+            .iter()
+            .filter(|request| request.message == Some(index))
+        {
+            let line = report.usage(&request.usage, &request.model);
+            report.line(1, &line);
+        }
+    }
+
+    report.text.push('\n');
+    let removed = requests
+        .iter()
+        .filter(|request| request.message.is_none())
+        .count();
+    if removed > 0 {
+        report.line(0, &format!("Requests of removed messages: {}", removed));
+    }
+    let mut totals = format!(
+        "Total: {} requests, {}",
+        requests.len(),
+        tokens(conversation.usage())
+    );
+    if let Some(table) = pricing {
+        let mut total = Decimal::ZERO;
+        let mut unpriced = 0;
+        for request in requests {
+            match cost(&request.usage, &request.model, table) {
+                Some(breakdown) => total += breakdown.total(),
+                None => unpriced += 1,
+            }
+        }
+        let _ = write!(totals, ", cost ${}", total);
+        if unpriced > 0 {
+            let _ = write!(totals, " (excluding {} unpriced requests)", unpriced);
+        }
+    }
+    report.line(0, &totals);
+    report.text
+}
+
+struct Report<'a> {
+    text: String,
+    pricing: Option<&'a PricingTable>,
+    options: &'a TextReportOptions,
+    /// Names of the tools used so far, by tool use ID
+    tool_names: HashMap<String, String>,
+}
+
+impl Report<'_> {
+    /// Write a message, whose first block is `summary` if given
+    fn message(&mut self, index: usize, message: &Message, summary: Option<&str>) {
+        let role = match message.role {
+            Role::User => "User",
+            Role::Assistant => "Assistant",
+        };
+        self.line(0, &format!("#{} {}", index + 1, role));
+        let mut blocks = message.blocks().into_owned().into_iter();
+        if let Some(summary) = summary {
+            blocks.next();
+            self.line(1, "Summary of compacted turns:");
+            self.lines(2, summary);
+        }
+        for block in blocks {
+            self.block(&block);
+        }
+    }
+
+    fn block(&mut self, block: &ContentBlock) {
+        match block {
+            ContentBlock::Text { text, .. } => self.lines(1, text),
+            ContentBlock::Thinking { thinking, .. } => {
+                self.line(1, "Thinking:");
+                self.lines(2, thinking);
+            }
+            ContentBlock::RedactedThinking { .. } => self.line(1, "Redacted thinking"),
+            ContentBlock::ToolUse { id, name, input }
+            | ContentBlock::ServerToolUse { id, name, input }
+            | ContentBlock::McpToolUse {
+                id, name, input, ..
+            } => {
+                self.tool_names.insert(id.clone(), name.clone());
+                let input = self.payload(&input.to_string());
+                self.line(1, &format!("Tool use {}: {}", name, input));
+            }
+            ContentBlock::ToolResult {
+                tool_use_id,
+                is_error,
+                content,
+            }
+            | ContentBlock::McpToolResult {
+                tool_use_id,
+                is_error,
+                content,
+            } => {
+                let kind = if *is_error {
+                    "Tool error"
+                } else {
+                    "Tool result"
+                };
+                let name = self.tool_name(tool_use_id);
+                let content = self.payload(&result_text(content));
+                self.line(1, &format!("{} {}: {}", kind, name, content));
+            }
+            ContentBlock::CodeExecutionToolResult {
+                tool_use_id,
+                content,
+            } => {
+                let name = self.tool_name(tool_use_id);
+                let content = serde_json::to_string(content).unwrap_or_default();
+                let content = self.payload(&content);
+                self.line(1, &format!("Tool result {}: {}", name, content));
+            }
+            other => {
+                let json = serde_json::to_value(other).unwrap_or_default();
+                let kind = json["type"].as_str().unwrap_or("unknown");
+                let content = self.payload(&json.to_string());
+                self.line(1, &format!("Block {}: {}", kind, content));
+            }
+        }
+    }
+
+    /// Usage line of a request, priced with the model if a table was given
+    fn usage(&self, usage: &Usage, model: &str) -> String {
+        let mut line = format!("Usage: {}", tokens(usage));
+        if let Some(table) = self.pricing {
+            match cost(usage, model, table) {
+                Some(breakdown) => {
+                    let _ = write!(line, ", cost ${}", breakdown.total());
+                }
+                None => {
+                    let _ = write!(line, ", cost unknown for {}", model);
+                }
+            }
+        }
+        line
+    }
+
+    fn tool_name(&self, tool_use_id: &str) -> String {
+        self.tool_names
+            .get(tool_use_id)
+            .cloned()
+            .unwrap_or_else(|| "(unknown tool)".to_string())
+    }
+
+    /// `payload` on a single line, cut after the maximum number of characters
+    fn payload(&self, payload: &str) -> String {
+        let payload = payload.replace('\n', "\\n");
+        let chars = payload.chars().count();
+        if chars <= self.options.max_payload_chars {
+            return payload;
+        }
+        let kept: String = payload
+            .chars()
+            .take(self.options.max_payload_chars)
+            .collect();
+        format!("{}... [truncated, {} chars]", kept, chars)
+    }
+
+    /// Write each line of `text` indented by `depth` levels
+    fn lines(&mut self, depth: usize, text: &str) {
+        for line in text.lines() {
+            self.line(depth, line);
+        }
+    }
+
+    fn line(&mut self, depth: usize, line: &str) {
+        for _ in 0..depth {
+            self.text.push_str("  ");
+        }
+        self.text.push_str(line.trim_end());
+        self.text.push('\n');
+    }
+}
+
+/// Token counts of `usage`, in a fixed order
+fn tokens(usage: &Usage) -> String {
+    format!(
+        "input {}, output {}, cache write {}, cache read {}",
+        usage.input_tokens,
+        usage.output_tokens,
+        usage.cache_creation_input_tokens(),
+        usage.cache_read_input_tokens()
+    )
+}
+
+/// Text of a tool result, non-text blocks written as JSON
+fn result_text(content: &ToolResultContent) -> String {
+    match content {
+        ToolResultContent::Text(text) => text.clone(),
+        ToolResultContent::Blocks(blocks) => blocks
+            .iter()
+            .map(|block| match block {
+                ContentBlock::Text { text, .. } => text.clone(),
+                other => serde_json::to_string(other).unwrap_or_default(),
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
@@ -0,0 +1,211 @@
+//! Utilities for scripts and command line tools
+//!
+//! This module contains [`print_stream`], which prints a streamed reply as it
+//! arrives.
+//!
+use std::io::{self, IsTerminal, Write};
+use std::pin::pin;
+
+use futures_util::{Stream, StreamExt};
+use thiserror::Error;
+
+use crate::types::message::{
+    ContentBlock, ContentBlockDelta, CreateMessageResponse, MessageAccumulator, MessageError,
+    StreamEvent,
+};
+
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Error types for [`print_stream`]
+#[derive(Debug, Error)]
+pub enum PrintError {
+    #[error(transparent)]
+    Message(#[from] MessageError),
+    #[error("Failed to write the stream: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// What [`print_stream`] prints, and where
+#[derive(Debug, Clone)]
+pub struct PrintOptions<W> {
+    /// Print thinking content, labelled and dimmed
+    pub show_thinking: bool,
+    /// Print each tool call with its pretty-printed input once it is complete
+    pub show_tool_calls: bool,
+    /// Style thinking content with ANSI escape codes
+    pub color: bool,
+    /// Destination of the output
+    pub writer: W,
+}
+
+impl<W: Write> PrintOptions<W> {
+    /// Print text and tool calls to `writer`, without colors
+    pub fn new(writer: W) -> Self {
+        Self {
+            show_thinking: false,
+            show_tool_calls: true,
+            color: false,
+            writer,
+        }
+    }
+
+    /// Print thinking content
+    pub fn show_thinking(mut self, show: bool) -> Self {
+        self.show_thinking = show;
+        self
+    }
+
+    /// Print tool calls
+    pub fn show_tool_calls(mut self, show: bool) -> Self {
+        self.show_tool_calls = show;
+        self
+    }
+
+    /// Style thinking content with ANSI escape codes
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl PrintOptions<io::Stdout> {
+    /// Print to standard output, with colors if it is a terminal
+    pub fn stdout() -> Self {
+        let stdout = io::stdout();
+        let color = stdout.is_terminal();
+        Self::new(stdout).color(color)
+    }
+}
+
+/// Print a streamed reply as it arrives and return the complete response
+///
+/// Text deltas are written as soon as they arrive and the writer is flushed
+/// after each one. Deltas are whole strings, so a multi-byte character is
+/// never split across writes. Thinking and tool calls are printed according
+/// to `options`; the output ends with a newline.
+///
+/// # Errors
+///
+/// Returns a [`PrintError`] if the stream fails or reports an error, or if
+/// writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::testing::StreamScenario;
+/// use anthropic_ai_sdk::types::message::StopReason;
+/// use anthropic_ai_sdk::util::{PrintOptions, print_stream};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let stream = StreamScenario::new()
+///     .thinking("The user wants the weather.")
+///     .text("Let me check. ")
+///     .tool_use("get_weather", serde_json::json!({"city": "Paris"}))
+///     .finish_stream(StopReason::ToolUse);
+///
+/// let mut output = Vec::new();
+/// let options = PrintOptions::new(&mut output).show_thinking(true);
+/// let response = print_stream(stream, options).await?;
+/// assert!(response.is_tool_use());
+/// assert_eq!(
+///     String::from_utf8(output)?,
+///     "Thinking: The user wants the weather.\n\n\
+///      Let me check. \n\
+///      get_weather {\n  \"city\": \"Paris\"\n}\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub async fn print_stream<S, W>(
+    stream: S,
+    options: PrintOptions<W>,
+) -> Result<CreateMessageResponse, PrintError>
+where
+    S: Stream<Item = Result<StreamEvent, MessageError>>,
+    W: Write,
+{
+    let mut stream = pin!(stream);
+    let mut accumulator = MessageAccumulator::new();
+    let mut printer = Printer {
+        options,
+        in_thinking: false,
+        at_line_start: true,
+    };
+    while let Some(event) = stream.next().await {
+        let event = event?;
+        accumulator.push(&event)?;
+        printer.print(&event, &accumulator)?;
+    }
+    printer.end_line()?;
+    Ok(accumulator.finish()?)
+}
+
+struct Printer<W> {
+    options: PrintOptions<W>,
+    in_thinking: bool,
+    at_line_start: bool,
+}
+
+impl<W: Write> Printer<W> {
+    fn print(&mut self, event: &StreamEvent, accumulator: &MessageAccumulator) -> io::Result<()> {
+        match event {
+            StreamEvent::ContentBlockStart {
+                content_block: ContentBlock::Thinking { .. },
+                ..
+            } if self.options.show_thinking => {
+                self.end_line()?;
+                let style = if self.options.color { DIM } else { "" };
+                self.write(&format!("{}Thinking: ", style))?;
+                self.in_thinking = true;
+            }
+            StreamEvent::ContentBlockDelta { delta, .. } => match delta {
+                ContentBlockDelta::TextDelta { text } => self.write(text)?,
+                ContentBlockDelta::ThinkingDelta { thinking } if self.in_thinking => {
+                    self.write(thinking)?
+                }
+                _ => {}
+            },
+            StreamEvent::ContentBlockStop { index } => {
+                if self.in_thinking {
+                    self.in_thinking = false;
+                    let style = if self.options.color { RESET } else { "" };
+                    self.write(&format!("{}\n\n", style))?;
+                }
+                let block = accumulator
+                    .response()
+                    .and_then(|response| response.content.get(*index));
+                if let Some(
+                    ContentBlock::ToolUse { name, input, .. }
+                    | ContentBlock::ServerToolUse { name, input, .. }
+                    | ContentBlock::McpToolUse { name, input, .. },
+                ) = block.filter(|_| self.options.show_tool_calls)
+                {
+                    let input = serde_json::to_string_pretty(input).map_err(io::Error::other)?;
+                    self.end_line()?;
+                    self.write(&format!("{} {}\n", name, input))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn end_line(&mut self) -> io::Result<()> {
+        if self.at_line_start {
+            return Ok(());
+        }
+        self.write("\n")
+    }
+
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.options.writer.write_all(text.as_bytes())?;
+        self.options.writer.flush()?;
+        self.at_line_start = text.ends_with('\n');
+        Ok(())
+    }
+}
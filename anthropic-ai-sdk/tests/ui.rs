@@ -0,0 +1,7 @@
+//! Compile errors of the message macros
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _history = anthropic_ai_sdk::messages![user: "hi", bot: "hello"];
+}
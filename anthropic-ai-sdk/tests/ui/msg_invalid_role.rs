@@ -0,0 +1,3 @@
+fn main() {
+    let _message = anthropic_ai_sdk::msg!(system: "Be brief");
+}
@@ -0,0 +1,327 @@
+//! Procedural macros for anthropic-ai-sdk
+//!
+//! These macros are re-exported by `anthropic-ai-sdk` when its `macros`
+//! feature is enabled; use them from there rather than depending on this
+//! crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Expr, ExprLit, FnArg, Ident, ItemFn, Lit, LitStr, MetaNameValue, Pat, Token, Type,
+    parse_macro_input,
+};
+
+/// Define a tool from an async function
+///
+/// The function is replaced by a unit struct of the same name implementing
+/// `AgentTool`, so it can be passed to `ToolRegistry::register` directly. The
+/// function itself remains callable as `<name>::run`.
+///
+/// The input schema has one property per parameter, described by the doc
+/// comments of the parameter. Parameter types must implement `ToolParam`;
+/// `Option` parameters are not required. The function must return a `Result`
+/// whose value converts into `ToolResultContent` and whose error converts into
+/// `ToolError`.
+///
+/// # Attributes
+///
+/// * `description` - Description of the tool, defaults to the doc comments of
+///   the function
+/// * `name` - Name of the tool, defaults to the name of the function
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::anthropic_tool;
+/// use anthropic_ai_sdk::types::agent::{AgentTool, ToolError, ToolRegistry};
+/// use anthropic_ai_sdk::types::message::ToolResultContent;
+/// use anthropic_ai_sdk::types::tool::ToolParam;
+/// use serde::Deserialize;
+/// use serde_json::{Value, json};
+///
+/// #[derive(Deserialize)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Unit {
+///     Celsius,
+///     Fahrenheit,
+/// }
+///
+/// impl ToolParam for Unit {
+///     fn schema() -> Value {
+///         json!({"type": "string", "enum": ["celsius", "fahrenheit"]})
+///     }
+/// }
+///
+/// #[anthropic_tool(description = "Get the current weather in a given location")]
+/// async fn get_weather(
+///     /// City name
+///     city: String,
+///     /// Temperature unit
+///     unit: Option<Unit>,
+/// ) -> Result<String, ToolError> {
+///     let degrees = match unit {
+///         Some(Unit::Fahrenheit) => "72F",
+///         _ => "22C",
+///     };
+///     Ok(format!("{degrees} and sunny in {city}"))
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let tool = get_weather.definition();
+/// assert_eq!(tool.name, "get_weather");
+/// assert_eq!(tool.input_schema["properties"]["city"]["description"], "City name");
+/// assert_eq!(tool.input_schema["required"], json!(["city"]));
+///
+/// let registry = ToolRegistry::new().register(get_weather);
+/// let output = registry
+///     .get("get_weather")
+///     .unwrap()
+///     .call(json!({"city": "Paris", "unit": "fahrenheit"}))
+///     .await
+///     .unwrap();
+/// assert_eq!(output, ToolResultContent::Text("72F and sunny in Paris".to_string()));
+/// # }
+/// ```
+///
+/// Parameters of unsupported types are rejected at compile time:
+///
+/// ```compile_fail
+/// use anthropic_ai_sdk::anthropic_tool;
+/// use anthropic_ai_sdk::types::agent::ToolError;
+///
+/// struct Location;
+///
+/// #[anthropic_tool]
+/// async fn get_weather(location: Location) -> Result<String, ToolError> {
+///     Ok(String::new())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn anthropic_tool(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as ToolArgs);
+    let function = parse_macro_input!(item as ItemFn);
+    expand_tool(args, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Arguments of `#[anthropic_tool]`
+#[derive(Default)]
+struct ToolArgs {
+    name: Option<LitStr>,
+    description: Option<LitStr>,
+}
+
+impl Parse for ToolArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = ToolArgs::default();
+        for meta in Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)? {
+            let Expr::Lit(ExprLit {
+                lit: Lit::Str(value),
+                ..
+            }) = &meta.value
+            else {
+                return Err(syn::Error::new(
+                    meta.value.span(),
+                    "expected a string literal",
+                ));
+            };
+            if meta.path.is_ident("name") {
+                args.name = Some(value.clone());
+            } else if meta.path.is_ident("description") {
+                args.description = Some(value.clone());
+            } else {
+                return Err(syn::Error::new(
+                    meta.path.span(),
+                    "unknown attribute, expected `name` or `description`",
+                ));
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// A parameter of the tool function
+struct Param {
+    ident: Ident,
+    ty: Type,
+    description: Option<String>,
+}
+
+fn expand_tool(args: ToolArgs, function: ItemFn) -> syn::Result<TokenStream2> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+
+    if sig.asyncness.is_none() {
+        return Err(syn::Error::new(
+            sig.fn_token.span(),
+            "tools must be async functions",
+        ));
+    }
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "tools cannot have generic parameters",
+        ));
+    }
+
+    let mut params = Vec::new();
+    let mut inputs = Vec::new();
+    for input in &sig.inputs {
+        let FnArg::Typed(pat_type) = input else {
+            return Err(syn::Error::new(input.span(), "tools cannot take `self`"));
+        };
+        let Pat::Ident(pat_ident) = &*pat_type.pat else {
+            return Err(syn::Error::new(
+                pat_type.pat.span(),
+                "tool parameters must be plain identifiers",
+            ));
+        };
+        let (docs, attrs): (Vec<_>, Vec<_>) = pat_type
+            .attrs
+            .iter()
+            .cloned()
+            .partition(|attr| attr.path().is_ident("doc"));
+        params.push(Param {
+            ident: pat_ident.ident.clone(),
+            ty: (*pat_type.ty).clone(),
+            description: doc_string(&docs),
+        });
+        let mut input = pat_type.clone();
+        input.attrs = attrs;
+        inputs.push(input);
+    }
+
+    let ident = &sig.ident;
+    let output = &sig.output;
+    let (docs, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path().is_ident("doc"));
+
+    let name = args
+        .name
+        .map(|name| name.value())
+        .unwrap_or_else(|| ident.to_string());
+    let description = match args
+        .description
+        .map(|d| d.value())
+        .or_else(|| doc_string(&docs))
+    {
+        Some(description) => quote!(::std::option::Option::Some(#description.to_string())),
+        None => quote!(::std::option::Option::None),
+    };
+
+    let sdk = quote!(::anthropic_ai_sdk);
+    let json = quote!(#sdk::__private::serde_json);
+    let properties = params.iter().map(|param| {
+        let Param {
+            ident,
+            ty,
+            description,
+        } = param;
+        let property = ident.to_string();
+        let describe = description.as_ref().map(|description| {
+            quote! {
+                if let ::std::option::Option::Some(schema) = schema.as_object_mut() {
+                    schema.insert("description".to_string(), #json::Value::from(#description));
+                }
+            }
+        });
+        // Spanned so that unsupported types are reported at the parameter
+        let param_trait = quote_spanned!(ty.span()=> <#ty as #sdk::types::tool::ToolParam>);
+        quote! {
+            let mut schema = #param_trait::schema();
+            #describe
+            properties.insert(#property.to_string(), schema);
+            if #param_trait::required() {
+                required.push(#json::Value::from(#property));
+            }
+        }
+    });
+    let fields = params
+        .iter()
+        .map(|Param { ident, ty, .. }| quote!(#ident: #ty));
+    let arguments = params
+        .iter()
+        .map(|Param { ident, .. }| quote!(input.#ident));
+
+    Ok(quote! {
+        #(#docs)*
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, Default)]
+        #vis struct #ident;
+
+        impl #ident {
+            #(#attrs)*
+            #vis async fn run(#(#inputs),*) #output #block
+        }
+
+        #[#sdk::__private::async_trait]
+        impl #sdk::types::agent::AgentTool for #ident {
+            fn name(&self) -> &str {
+                #name
+            }
+
+            fn definition(&self) -> #sdk::types::message::Tool {
+                let mut properties = #json::Map::new();
+                let mut required = ::std::vec::Vec::new();
+                #(#properties)*
+                #sdk::types::message::Tool {
+                    name: #name.to_string(),
+                    description: #description,
+                    input_schema: #json::json!({
+                        "type": "object",
+                        "properties": properties,
+                        "required": required,
+                    }),
+                    cache_control: None,
+                }
+            }
+
+            async fn call(
+                &self,
+                input: #json::Value,
+            ) -> ::std::result::Result<
+                #sdk::types::message::ToolResultContent,
+                #sdk::types::agent::ToolError,
+            > {
+                #[derive(#sdk::__private::serde::Deserialize)]
+                #[serde(crate = "::anthropic_ai_sdk::__private::serde")]
+                struct Input {
+                    #(#fields),*
+                }
+
+                let input: Input = #json::from_value(input)?;
+                #ident::run(#(#arguments),*)
+                    .await
+                    .map(::std::convert::Into::into)
+                    .map_err(::std::convert::Into::into)
+            }
+        }
+    })
+}
+
+/// The text of `#[doc]` attributes, one line per attribute
+fn doc_string(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta.require_name_value().ok()?.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(line),
+                ..
+            }) => Some(line.value().trim().to_string()),
+            _ => None,
+        })
+        .collect();
+    let text = lines.join("\n").trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
@@ -0,0 +1,49 @@
+use anthropic_ai_sdk::client::AnthropicClient;
+use anthropic_ai_sdk::types::files::{FileClient, FileError};
+use std::env;
+use tracing::{error, info};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_ansi(true)
+        .with_target(true)
+        .with_thread_ids(true)
+        .with_line_number(true)
+        .with_file(false)
+        .with_level(true)
+        .try_init()
+        .expect("Failed to initialize logger");
+
+    let api_key = env::var("ANTHROPIC_API_KEY").expect("ANTHROPIC_API_KEY is not set");
+    let api_version = env::var("ANTHROPIC_API_VERSION").unwrap_or("2023-06-01".to_string());
+    let path = env::args()
+        .nth(1)
+        .expect("Usage: upload-a-file <path> [mime-type]");
+    let mime_type = env::args()
+        .nth(2)
+        .unwrap_or("application/octet-stream".to_string());
+
+    let client = AnthropicClient::new::<FileError>(api_key, api_version).unwrap();
+
+    let file = tokio::fs::File::open(&path)
+        .await
+        .expect("Failed to open file");
+    let filename = std::path::Path::new(&path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or(path.clone());
+
+    match client.upload_file_stream(&filename, file, &mime_type).await {
+        Ok(file) => {
+            info!("Successfully uploaded file:");
+            info!(
+                "- {} ({}, {} bytes)",
+                file.id, file.filename, file.size_bytes
+            );
+        }
+        Err(e) => {
+            error!("Error: {}", e);
+        }
+    }
+}
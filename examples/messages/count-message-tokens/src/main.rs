@@ -25,10 +25,11 @@ async fn main() {
 
     let client = AnthropicClient::new::<MessageError>(api_key, api_version).unwrap();
 
-    let body = CountMessageTokensParams {
-        model: "claude-3-5-sonnet-20240620".to_string(),
-        messages: vec![Message::new_text(Role::User, "Hello, Claude")],
-    };
+    let body = CountMessageTokensParams::new(
+        "claude-3-5-sonnet-20240620",
+        vec![Message::new_text(Role::User, "Hello, Claude")],
+    )
+    .with_system("You are a helpful assistant");
 
     info!("body: {:?}", body);
 
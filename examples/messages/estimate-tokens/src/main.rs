@@ -0,0 +1,99 @@
+use anthropic_ai_sdk::client::AnthropicClient;
+use anthropic_ai_sdk::types::estimate::estimate_request_tokens;
+use anthropic_ai_sdk::types::message::{
+    CountMessageTokensParams, CreateMessageParams, Message, MessageClient, MessageError, Role,
+};
+use std::env;
+use tracing::{error, info, warn};
+
+/// Largest accepted deviation of the estimate from the real count
+const TOLERANCE: f64 = 0.15;
+
+const SAMPLES: &[&str] = &[
+    "Hello, Claude",
+    "Can you summarize the main arguments of the article below in three bullet points? \
+     The article argues that remote work improves productivity for focused tasks, \
+     but makes onboarding and mentoring of junior employees harder.",
+    "fn main() {\n    let numbers = vec![1, 2, 3];\n    let total: i32 = numbers.iter().sum();\n    \
+     println!(\"{total}\");\n}\n",
+    "{\"id\": 42, \"name\": \"Ada Lovelace\", \"tags\": [\"math\", \"poetry\"], \"active\": true}",
+    "Bonjour, pouvez-vous m'aider à écrire une lettre de motivation pour un poste d'ingénieur ?",
+    "東京の天気を教えてください。明日は雨が降りますか？",
+];
+
+/// Compares the offline token estimate with `count_tokens`
+///
+/// With `--record`, prints the counted samples as entries of the calibration
+/// fixture `anthropic-ai-sdk/fixtures/token_counts.json` instead.
+///
+/// cd anthropic-sdk-rs/examples/messages/estimate-tokens
+/// cargo run
+/// cargo run -- --record
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_ansi(true)
+        .with_target(true)
+        .with_thread_ids(true)
+        .with_line_number(true)
+        .with_file(false)
+        .with_level(true)
+        .try_init()
+        .expect("Failed to initialize logger");
+
+    let api_key = env::var("ANTHROPIC_API_KEY").expect("ANTHROPIC_API_KEY is not set");
+    let api_version = env::var("ANTHROPIC_API_VERSION").unwrap_or("2023-06-01".to_string());
+
+    let client = AnthropicClient::new::<MessageError>(api_key, api_version).unwrap();
+
+    let record = env::args().any(|arg| arg == "--record");
+    let mut recorded = Vec::new();
+    let mut failures = 0;
+    for sample in SAMPLES {
+        let messages = vec![Message::new_text(Role::User, *sample)];
+        let params = CreateMessageParams {
+            model: "claude-3-7-sonnet-20250219".to_string(),
+            messages: messages.clone(),
+            ..Default::default()
+        };
+        let body = CountMessageTokensParams::new(params.model.clone(), messages);
+
+        let counted = match client.count_tokens(Some(&body)).await {
+            Ok(response) => response.input_tokens,
+            Err(e) => {
+                error!("Error: {}", e);
+                return;
+            }
+        };
+        if record {
+            recorded.push(serde_json::json!({
+                "source": "estimate-tokens example",
+                "request": body,
+                "input_tokens": counted,
+            }));
+            continue;
+        }
+        let estimated = estimate_request_tokens(&params);
+        let deviation = (estimated as f64 - counted as f64) / counted as f64 * 100.0;
+        if deviation.abs() > TOLERANCE * 100.0 {
+            failures += 1;
+            warn!("counted {counted}, estimated {estimated} ({deviation:+.1}%): {sample:?}");
+        } else {
+            info!("counted {counted}, estimated {estimated} ({deviation:+.1}%)");
+        }
+    }
+
+    if record {
+        println!("{}", serde_json::to_string_pretty(&recorded).unwrap());
+        return;
+    }
+    if failures > 0 {
+        error!(
+            "{failures} of {} samples are off by more than {}%",
+            SAMPLES.len(),
+            TOLERANCE * 100.0
+        );
+        std::process::exit(1);
+    }
+}
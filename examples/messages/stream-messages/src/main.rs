@@ -2,7 +2,7 @@ use anthropic_ai_sdk::client::AnthropicClient;
 use anthropic_ai_sdk::types::message::{
     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
 };
-use futures_util::StreamExt;
+use anthropic_ai_sdk::util::{PrintOptions, print_stream};
 use std::env;
 use tracing::{error, info};
 
@@ -31,14 +31,10 @@ async fn main() {
     .with_stream(true);
 
     match client.create_message_streaming(&body).await {
-        Ok(mut stream) => {
-            while let Some(result) = stream.next().await {
-                match result {
-                    Ok(event) => info!("Received event: {:?}", event),
-                    Err(e) => error!("Stream error: {}", e),
-                }
-            }
-        }
+        Ok(stream) => match print_stream(stream, PrintOptions::stdout()).await {
+            Ok(response) => info!("Usage: {:?}", response.usage),
+            Err(e) => error!("Stream error: {}", e),
+        },
         Err(e) => {
             error!("Error: {}", e);
         }